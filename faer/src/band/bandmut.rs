@@ -0,0 +1,180 @@
+use super::*;
+use crate::assert;
+
+/// mutable banded matrix view, stored in the lapack band layout
+///
+/// see [`BandRef`] for a description of the storage
+pub struct BandMut<'a, T> {
+	pub(crate) inner: MatMut<'a, T>,
+	pub(crate) nrows: usize,
+	pub(crate) kl: usize,
+	pub(crate) ku: usize,
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for BandMut<'_, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		self.rb().fmt(f)
+	}
+}
+
+impl<'short, T> Reborrow<'short> for BandMut<'_, T> {
+	type Target = BandRef<'short, T>;
+
+	#[inline]
+	fn rb(&'short self) -> Self::Target {
+		BandRef {
+			inner: self.inner.rb(),
+			nrows: self.nrows,
+			kl: self.kl,
+			ku: self.ku,
+		}
+	}
+}
+impl<'short, T> ReborrowMut<'short> for BandMut<'_, T> {
+	type Target = BandMut<'short, T>;
+
+	#[inline]
+	fn rb_mut(&'short mut self) -> Self::Target {
+		BandMut {
+			inner: self.inner.rb_mut(),
+			nrows: self.nrows,
+			kl: self.kl,
+			ku: self.ku,
+		}
+	}
+}
+impl<'a, T> IntoConst for BandMut<'a, T> {
+	type Target = BandRef<'a, T>;
+
+	#[inline]
+	fn into_const(self) -> Self::Target {
+		BandRef {
+			inner: self.inner.into_const(),
+			nrows: self.nrows,
+			kl: self.kl,
+			ku: self.ku,
+		}
+	}
+}
+
+impl<'a, T> BandMut<'a, T> {
+	/// creates a mutable banded matrix view with `nrows` rows from its band storage, which must
+	/// have $k_l + k_u + 1$ rows and one column per column of the matrix
+	#[inline]
+	#[track_caller]
+	pub fn from_band_storage_mut(storage: MatMut<'a, T>, nrows: usize, lower_bandwidth: usize, upper_bandwidth: usize) -> Self {
+		assert!(storage.nrows() == lower_bandwidth + upper_bandwidth + 1);
+		Self {
+			inner: storage,
+			nrows,
+			kl: lower_bandwidth,
+			ku: upper_bandwidth,
+		}
+	}
+
+	/// returns the number of rows of the matrix
+	#[inline]
+	pub fn nrows(&self) -> usize {
+		self.nrows
+	}
+
+	/// returns the number of columns of the matrix
+	#[inline]
+	pub fn ncols(&self) -> usize {
+		self.inner.ncols()
+	}
+
+	/// returns the number of rows and columns of the matrix
+	#[inline]
+	pub fn shape(&self) -> (usize, usize) {
+		(self.nrows(), self.ncols())
+	}
+
+	/// returns the number of subdiagonals $k_l$ of the band
+	#[inline]
+	pub fn lower_bandwidth(&self) -> usize {
+		self.kl
+	}
+
+	/// returns the number of superdiagonals $k_u$ of the band
+	#[inline]
+	pub fn upper_bandwidth(&self) -> usize {
+		self.ku
+	}
+
+	/// returns the band storage of the matrix
+	#[inline]
+	pub fn band_storage(self) -> MatRef<'a, T> {
+		self.inner.into_const()
+	}
+
+	/// returns the band storage of the matrix
+	#[inline]
+	pub fn band_storage_mut(self) -> MatMut<'a, T> {
+		self.inner
+	}
+
+	/// returns a view over `self`
+	#[inline]
+	pub fn as_ref(&self) -> BandRef<'_, T> {
+		self.rb()
+	}
+
+	/// returns a view over `self`
+	#[inline]
+	pub fn as_mut(&mut self) -> BandMut<'_, T> {
+		self.rb_mut()
+	}
+
+	/// returns a reference to the element at position $(i, j)$, or `None` if it lies outside
+	/// the band
+	#[inline]
+	#[track_caller]
+	pub fn get(self, row: usize, col: usize) -> Option<&'a T> {
+		self.into_const().get(row, col)
+	}
+
+	/// returns a mutable reference to the element at position $(i, j)$, or `None` if it lies
+	/// outside the band
+	#[inline]
+	#[track_caller]
+	pub fn get_mut(self, row: usize, col: usize) -> Option<&'a mut T> {
+		assert!(all(row < self.nrows(), col < self.ncols()));
+		if row + self.ku >= col && row <= col + self.kl {
+			Some(self.inner.get_mut(self.ku + row - col, col))
+		} else {
+			None
+		}
+	}
+}
+
+impl<'a, T: ComplexField> BandMut<'a, T> {
+	/// returns a newly allocated dense matrix holding the values of `self`
+	pub fn to_dense(&self) -> Mat<T> {
+		self.rb().to_dense()
+	}
+
+	/// returns a newly allocated banded matrix holding the values of `self`
+	pub fn to_owned(&self) -> BandMat<T> {
+		self.rb().to_owned()
+	}
+
+	/// returns a newly allocated sparse matrix holding the values of `self`
+	///
+	/// see [`BandRef::to_sparse`]
+	pub fn to_sparse<I: Index>(&self) -> Result<SparseColMat<I, T>, FaerError> {
+		self.rb().to_sparse()
+	}
+
+	/// copies the values inside the band of `src` into `self`
+	#[track_caller]
+	pub fn copy_from(&mut self, src: MatRef<'_, T>) {
+		let (m, n) = self.shape();
+		assert!(all(src.nrows() == m, src.ncols() == n));
+		for j in 0..n {
+			for i in band_rows(m, self.kl, self.ku, j) {
+				self.inner[(self.ku + i - j, j)] = src[(i, j)].clone();
+			}
+		}
+	}
+}
@@ -0,0 +1,182 @@
+use super::*;
+
+/// heap allocated banded matrix, stored in the lapack band layout
+///
+/// see [`BandRef`] for a description of the storage
+#[derive(Clone)]
+pub struct BandMat<T> {
+	pub(crate) inner: Mat<T>,
+	pub(crate) nrows: usize,
+	pub(crate) kl: usize,
+	pub(crate) ku: usize,
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for BandMat<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		self.as_ref().fmt(f)
+	}
+}
+
+impl<T> BandMat<T> {
+	/// returns the number of rows of the matrix
+	#[inline]
+	pub fn nrows(&self) -> usize {
+		self.nrows
+	}
+
+	/// returns the number of columns of the matrix
+	#[inline]
+	pub fn ncols(&self) -> usize {
+		self.inner.ncols()
+	}
+
+	/// returns the number of rows and columns of the matrix
+	#[inline]
+	pub fn shape(&self) -> (usize, usize) {
+		(self.nrows(), self.ncols())
+	}
+
+	/// returns the number of subdiagonals $k_l$ of the band
+	#[inline]
+	pub fn lower_bandwidth(&self) -> usize {
+		self.kl
+	}
+
+	/// returns the number of superdiagonals $k_u$ of the band
+	#[inline]
+	pub fn upper_bandwidth(&self) -> usize {
+		self.ku
+	}
+
+	/// returns the band storage of the matrix
+	#[inline]
+	pub fn band_storage(&self) -> MatRef<'_, T> {
+		self.inner.as_ref()
+	}
+
+	/// returns the band storage of the matrix
+	#[inline]
+	pub fn band_storage_mut(&mut self) -> MatMut<'_, T> {
+		self.inner.as_mut()
+	}
+
+	/// returns a view over `self`
+	#[inline]
+	pub fn as_ref(&self) -> BandRef<'_, T> {
+		BandRef {
+			inner: self.inner.as_ref(),
+			nrows: self.nrows,
+			kl: self.kl,
+			ku: self.ku,
+		}
+	}
+
+	/// returns a view over `self`
+	#[inline]
+	pub fn as_mut(&mut self) -> BandMut<'_, T> {
+		BandMut {
+			inner: self.inner.as_mut(),
+			nrows: self.nrows,
+			kl: self.kl,
+			ku: self.ku,
+		}
+	}
+
+	/// returns a reference to the element at position $(i, j)$, or `None` if it lies outside
+	/// the band
+	#[inline]
+	#[track_caller]
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		self.as_ref().get(row, col)
+	}
+
+	/// returns a mutable reference to the element at position $(i, j)$, or `None` if it lies
+	/// outside the band
+	#[inline]
+	#[track_caller]
+	pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+		self.as_mut().get_mut(row, col)
+	}
+}
+
+impl<T: ComplexField> BandMat<T> {
+	/// returns a new $m \times n$ matrix with the given bandwidths, filled with zeros
+	pub fn zeros(nrows: usize, ncols: usize, lower_bandwidth: usize, upper_bandwidth: usize) -> Self {
+		Self {
+			inner: Mat::zeros(lower_bandwidth + upper_bandwidth + 1, ncols),
+			nrows,
+			kl: lower_bandwidth,
+			ku: upper_bandwidth,
+		}
+	}
+
+	/// returns a new $m \times n$ matrix with the given bandwidths, where the entry at position
+	/// $(i, j)$ inside the band is computed by `f(i, j)`
+	pub fn from_fn(nrows: usize, ncols: usize, lower_bandwidth: usize, upper_bandwidth: usize, f: impl FnMut(usize, usize) -> T) -> Self {
+		let mut f = f;
+		let mut out = Self::zeros(nrows, ncols, lower_bandwidth, upper_bandwidth);
+		for j in 0..ncols {
+			for i in band_rows(nrows, lower_bandwidth, upper_bandwidth, j) {
+				out.inner[(upper_bandwidth + i - j, j)] = f(i, j);
+			}
+		}
+		out
+	}
+
+	/// returns a new banded matrix holding the values of `A` inside the band with the given
+	/// bandwidths. the entries outside the band are ignored
+	pub fn from_dense(A: MatRef<'_, T>, lower_bandwidth: usize, upper_bandwidth: usize) -> Self {
+		let mut out = Self::zeros(A.nrows(), A.ncols(), lower_bandwidth, upper_bandwidth);
+		out.as_mut().copy_from(A);
+		out
+	}
+
+	/// returns a new banded matrix holding the values of `A`, with the smallest bandwidths that
+	/// contain all of its stored entries. duplicate entries are summed
+	pub fn from_sparse<I: Index>(A: SparseColMatRef<'_, I, T>) -> Self {
+		let (m, n) = A.shape();
+
+		let mut kl = 0usize;
+		let mut ku = 0usize;
+		for j in 0..n {
+			for i in A.row_idx_of_col(j) {
+				kl = Ord::max(kl, i.saturating_sub(j));
+				ku = Ord::max(ku, j.saturating_sub(i));
+			}
+		}
+
+		let mut out = Self::zeros(m, n, kl, ku);
+		for j in 0..n {
+			for (i, val) in core::iter::zip(A.row_idx_of_col(j), A.val_of_col(j)) {
+				let dst = &mut out.inner[(ku + i - j, j)];
+				*dst = add(dst, val);
+			}
+		}
+		out
+	}
+
+	/// returns a newly allocated dense matrix holding the values of `self`
+	pub fn to_dense(&self) -> Mat<T> {
+		self.as_ref().to_dense()
+	}
+
+	/// returns a newly allocated sparse matrix holding the values of `self`
+	///
+	/// see [`BandRef::to_sparse`]
+	pub fn to_sparse<I: Index>(&self) -> Result<SparseColMat<I, T>, FaerError> {
+		self.as_ref().to_sparse()
+	}
+
+	/// returns the $LU$ decomposition of `self` with partial pivoting
+	#[track_caller]
+	pub fn partial_piv_lu(&self) -> solvers::PartialPivLu<T> {
+		self.as_ref().partial_piv_lu()
+	}
+
+	/// returns the $L L^H$ decomposition of `self`, reading only the band on the given side of
+	/// the diagonal
+	#[track_caller]
+	pub fn llt(&self, side: Side) -> Result<solvers::Llt<T>, LltError> {
+		self.as_ref().llt(side)
+	}
+}
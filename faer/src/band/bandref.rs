@@ -0,0 +1,202 @@
+use super::*;
+use crate::assert;
+
+/// banded matrix view, stored in the lapack band layout
+///
+/// a matrix with lower bandwidth $k_l$ and upper bandwidth $k_u$ satisfies $A_{ij} = 0$ whenever
+/// $i > j + k_l$ or $j > i + k_u$. the entries inside the band are stored column by column in a
+/// dense $(k_l + k_u + 1) \times n$ matrix, with $A_{ij}$ at position $(k_u + i - j, j)$. the
+/// positions of the storage that don't correspond to an entry of the matrix are never accessed
+pub struct BandRef<'a, T> {
+	pub(crate) inner: MatRef<'a, T>,
+	pub(crate) nrows: usize,
+	pub(crate) kl: usize,
+	pub(crate) ku: usize,
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for BandRef<'_, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("BandRef")
+			.field("nrows", &self.nrows)
+			.field("ncols", &self.inner.ncols())
+			.field("lower_bandwidth", &self.kl)
+			.field("upper_bandwidth", &self.ku)
+			.field("band", &self.inner)
+			.finish()
+	}
+}
+
+impl<T> Copy for BandRef<'_, T> {}
+impl<T> Clone for BandRef<'_, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'short, T> Reborrow<'short> for BandRef<'_, T> {
+	type Target = BandRef<'short, T>;
+
+	#[inline]
+	fn rb(&'short self) -> Self::Target {
+		*self
+	}
+}
+impl<'short, T> ReborrowMut<'short> for BandRef<'_, T> {
+	type Target = BandRef<'short, T>;
+
+	#[inline]
+	fn rb_mut(&'short mut self) -> Self::Target {
+		*self
+	}
+}
+impl<'a, T> IntoConst for BandRef<'a, T> {
+	type Target = BandRef<'a, T>;
+
+	#[inline]
+	fn into_const(self) -> Self::Target {
+		self
+	}
+}
+
+impl<'a, T> BandRef<'a, T> {
+	/// creates a banded matrix view with `nrows` rows from its band storage, which must have
+	/// $k_l + k_u + 1$ rows and one column per column of the matrix
+	#[inline]
+	#[track_caller]
+	pub fn from_band_storage(storage: MatRef<'a, T>, nrows: usize, lower_bandwidth: usize, upper_bandwidth: usize) -> Self {
+		assert!(storage.nrows() == lower_bandwidth + upper_bandwidth + 1);
+		Self {
+			inner: storage,
+			nrows,
+			kl: lower_bandwidth,
+			ku: upper_bandwidth,
+		}
+	}
+
+	/// returns the number of rows of the matrix
+	#[inline]
+	pub fn nrows(&self) -> usize {
+		self.nrows
+	}
+
+	/// returns the number of columns of the matrix
+	#[inline]
+	pub fn ncols(&self) -> usize {
+		self.inner.ncols()
+	}
+
+	/// returns the number of rows and columns of the matrix
+	#[inline]
+	pub fn shape(&self) -> (usize, usize) {
+		(self.nrows(), self.ncols())
+	}
+
+	/// returns the number of subdiagonals $k_l$ of the band
+	#[inline]
+	pub fn lower_bandwidth(&self) -> usize {
+		self.kl
+	}
+
+	/// returns the number of superdiagonals $k_u$ of the band
+	#[inline]
+	pub fn upper_bandwidth(&self) -> usize {
+		self.ku
+	}
+
+	/// returns the band storage of the matrix
+	#[inline]
+	pub fn band_storage(self) -> MatRef<'a, T> {
+		self.inner
+	}
+
+	/// returns a view over `self`
+	#[inline]
+	pub fn as_ref(&self) -> BandRef<'_, T> {
+		*self
+	}
+
+	/// returns a reference to the element at position $(i, j)$, or `None` if it lies outside
+	/// the band
+	#[inline]
+	#[track_caller]
+	pub fn get(self, row: usize, col: usize) -> Option<&'a T> {
+		assert!(all(row < self.nrows(), col < self.ncols()));
+		if row + self.ku >= col && row <= col + self.kl {
+			Some(self.inner.get(self.ku + row - col, col))
+		} else {
+			None
+		}
+	}
+}
+
+impl<'a, T: ComplexField> BandRef<'a, T> {
+	/// returns a newly allocated dense matrix holding the values of `self`
+	pub fn to_dense(&self) -> Mat<T> {
+		let (m, n) = self.shape();
+		let mut out = Mat::zeros(m, n);
+		for j in 0..n {
+			for i in band_rows(m, self.kl, self.ku, j) {
+				out[(i, j)] = self.inner[(self.ku + i - j, j)].clone();
+			}
+		}
+		out
+	}
+
+	/// returns a newly allocated banded matrix holding the values of `self`
+	pub fn to_owned(&self) -> BandMat<T> {
+		BandMat {
+			inner: self.inner.to_owned(),
+			nrows: self.nrows,
+			kl: self.kl,
+			ku: self.ku,
+		}
+	}
+
+	/// returns a newly allocated sparse matrix holding the values of `self`
+	///
+	/// every entry inside the band is stored explicitly, even if it is zero
+	pub fn to_sparse<I: Index>(&self) -> Result<SparseColMat<I, T>, FaerError> {
+		let (m, n) = self.shape();
+
+		let mut col_ptr = crate::sparse::try_zeroed::<I>(n + 1)?;
+		let mut nnz = 0usize;
+		for j in 0..n {
+			nnz += band_rows(m, self.kl, self.ku, j).len();
+			col_ptr[j + 1] = I::truncate(nnz);
+		}
+		if Ord::max(Ord::max(m, n), nnz) > I::Signed::MAX.zx() {
+			return Err(FaerError::IndexOverflow);
+		}
+
+		let mut row_idx = alloc::vec::Vec::new();
+		let mut val = alloc::vec::Vec::new();
+		row_idx.try_reserve_exact(nnz).map_err(|_| FaerError::OutOfMemory)?;
+		val.try_reserve_exact(nnz).map_err(|_| FaerError::OutOfMemory)?;
+
+		for j in 0..n {
+			for i in band_rows(m, self.kl, self.ku, j) {
+				row_idx.push(I::truncate(i));
+				val.push(self.inner[(self.ku + i - j, j)].clone());
+			}
+		}
+
+		Ok(SparseColMat::new(
+			unsafe { SymbolicSparseColMat::new_unchecked(m, n, col_ptr, None, row_idx) },
+			val,
+		))
+	}
+
+	/// returns the $LU$ decomposition of `self` with partial pivoting
+	#[track_caller]
+	pub fn partial_piv_lu(&self) -> solvers::PartialPivLu<T> {
+		solvers::PartialPivLu::new(*self)
+	}
+
+	/// returns the $L L^H$ decomposition of `self`, reading only the band on the given side of
+	/// the diagonal
+	#[track_caller]
+	pub fn llt(&self, side: Side) -> Result<solvers::Llt<T>, LltError> {
+		solvers::Llt::new(*self, side)
+	}
+}
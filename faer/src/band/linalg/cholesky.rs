@@ -0,0 +1,87 @@
+use super::*;
+use crate::assert;
+
+/// computes the cholesky factor $L$ of the hermitian positive definite banded matrix $A$, in
+/// place
+///
+/// only the diagonal and the subdiagonals of `L` are accessed. on entry, they hold the lower
+/// band of $A$, and on exit they hold the lower band of $L$, which has the same lower bandwidth
+/// as $A$
+///
+/// returns an error if $A$ is not numerically positive definite
+#[track_caller]
+#[math]
+pub fn cholesky_in_place<T: ComplexField>(L: BandMut<'_, T>) -> Result<(), LltError> {
+	let n = L.nrows();
+	let kd = L.kl;
+	let ku = L.ku;
+	assert!(L.ncols() == n);
+
+	let mut A = L.inner;
+
+	for j in 0..n {
+		let d = real(A[(ku, j)]);
+		// written so that a nan pivot is rejected
+		#[allow(clippy::neg_cmp_op_on_partial_ord)]
+		if !(d > zero()) {
+			return Err(LltError::NonPositivePivot { index: j });
+		}
+		let d = sqrt(d);
+		A[(ku, j)] = from_real(d);
+
+		let kn = Ord::min(kd, n - 1 - j);
+		let inv = recip(d);
+		for t in 1..kn + 1 {
+			A[(ku + t, j)] = mul_real(A[(ku + t, j)], inv);
+		}
+
+		for c in 1..kn + 1 {
+			let l = conj(A[(ku + c, j)]);
+			for t in c..kn + 1 {
+				A[(ku + t - c, j + c)] = A[(ku + t - c, j + c)] - A[(ku + t, j)] * l;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// given the cholesky factor $L$ of a banded matrix $A$ computed by [`cholesky_in_place`],
+/// solves the equation $A x = \text{rhs}$, implicitly conjugating $A$ if needed, and stores the
+/// result in `rhs`
+#[track_caller]
+#[math]
+pub fn solve_in_place_with_conj<T: ComplexField>(L: BandRef<'_, T>, conj_lhs: Conj, rhs: MatMut<'_, T>) {
+	let n = L.nrows();
+	let kd = L.kl;
+	let ku = L.ku;
+	assert!(all(L.ncols() == n, rhs.nrows() == n));
+
+	let A = L.inner;
+	let mut rhs = rhs;
+	let k = rhs.ncols();
+
+	for j in 0..n {
+		let kn = Ord::min(kd, n - 1 - j);
+		let inv = recip(real(A[(ku, j)]));
+		for col in 0..k {
+			let x = mul_real(rhs[(j, col)], inv);
+			for t in 1..kn + 1 {
+				rhs[(j + t, col)] = rhs[(j + t, col)] - conj_lhs.apply_rt(&A[(ku + t, j)]) * x;
+			}
+			rhs[(j, col)] = x;
+		}
+	}
+
+	for j in (0..n).rev() {
+		let kn = Ord::min(kd, n - 1 - j);
+		let inv = recip(real(A[(ku, j)]));
+		for col in 0..k {
+			let mut acc = copy(rhs[(j, col)]);
+			for t in 1..kn + 1 {
+				acc = acc - conj(conj_lhs.apply_rt(&A[(ku + t, j)])) * rhs[(j + t, col)];
+			}
+			rhs[(j, col)] = mul_real(acc, inv);
+		}
+	}
+}
@@ -0,0 +1,166 @@
+use super::*;
+use crate::assert;
+use crate::linalg::lu::partial_pivoting::factor::PartialPivLuInfo;
+
+/// computes the $LU$ decomposition of the square banded matrix $A$ with partial pivoting, in
+/// place
+///
+/// row interchanges can increase the upper bandwidth of the $U$ factor from $k_u$ to
+/// $k_l + k_u$, so `LU` must have lower bandwidth $k_l$ and upper bandwidth $k_l + k_u$. on
+/// entry, its top $k_l$ superdiagonals must be zero and the rest of the band holds $A$. on exit,
+/// the diagonal and the superdiagonals hold $U$, and the subdiagonals hold the multipliers of the
+/// unit lower triangular factors
+///
+/// at step $i$, row $i$ is interchanged with row `pivots[i]` before eliminating the entries of
+/// column $i$ below the diagonal, as in lapack's `gbtrf`
+#[track_caller]
+#[math]
+pub fn lu_in_place<T: ComplexField>(LU: BandMut<'_, T>, pivots: &mut [usize]) -> PartialPivLuInfo {
+	let n = LU.nrows();
+	let kl = LU.kl;
+	let kv = LU.ku;
+	assert!(all(LU.ncols() == n, kv >= kl, pivots.len() == n));
+	let ku = kv - kl;
+
+	let mut A = LU.inner;
+	let mut transposition_count = 0usize;
+	// last column affected by the row interchanges so far
+	let mut ju = 0usize;
+
+	for j in 0..n {
+		let km = Ord::min(kl, n - 1 - j);
+
+		let mut jp = 0usize;
+		let mut max = zero::<T::Real>();
+		for t in 0..km + 1 {
+			let val = abs1(A[(kv + t, j)]);
+			if val > max {
+				max = val;
+				jp = t;
+			}
+		}
+		pivots[j] = j + jp;
+
+		if max == zero::<T::Real>() {
+			continue;
+		}
+
+		ju = Ord::max(ju, Ord::min(j + ku + jp, n - 1));
+
+		if jp != 0 {
+			transposition_count += 1;
+			for c in j..ju + 1 {
+				let tmp = copy(A[(kv + j - c, c)]);
+				A[(kv + j - c, c)] = copy(A[(kv + j + jp - c, c)]);
+				A[(kv + j + jp - c, c)] = tmp;
+			}
+		}
+
+		let inv = recip(A[(kv, j)]);
+		for t in 1..km + 1 {
+			A[(kv + t, j)] = A[(kv + t, j)] * inv;
+		}
+
+		for c in j + 1..ju + 1 {
+			let u = copy(A[(kv + j - c, c)]);
+			for t in 1..km + 1 {
+				A[(kv + j + t - c, c)] = A[(kv + j + t - c, c)] - A[(kv + t, j)] * u;
+			}
+		}
+	}
+
+	PartialPivLuInfo { transposition_count }
+}
+
+/// given the $LU$ decomposition of a banded matrix $A$ computed by [`lu_in_place`], solves the
+/// equation $A x = \text{rhs}$, implicitly conjugating $A$ if needed, and stores the result in
+/// `rhs`
+#[track_caller]
+#[math]
+pub fn solve_in_place_with_conj<T: ComplexField>(LU: BandRef<'_, T>, pivots: &[usize], conj_LU: Conj, rhs: MatMut<'_, T>) {
+	let n = LU.nrows();
+	let kl = LU.kl;
+	let kv = LU.ku;
+	assert!(all(LU.ncols() == n, kv >= kl, pivots.len() == n, rhs.nrows() == n));
+
+	let A = LU.inner;
+	let mut rhs = rhs;
+	let k = rhs.ncols();
+
+	for j in 0..n {
+		let p = pivots[j];
+		if p != j {
+			for col in 0..k {
+				let tmp = copy(rhs[(j, col)]);
+				rhs[(j, col)] = copy(rhs[(p, col)]);
+				rhs[(p, col)] = tmp;
+			}
+		}
+
+		let lm = Ord::min(kl, n - 1 - j);
+		for col in 0..k {
+			let x = copy(rhs[(j, col)]);
+			for t in 1..lm + 1 {
+				rhs[(j + t, col)] = rhs[(j + t, col)] - conj_LU.apply_rt(&A[(kv + t, j)]) * x;
+			}
+		}
+	}
+
+	for j in (0..n).rev() {
+		let inv = recip(conj_LU.apply_rt(&A[(kv, j)]));
+		for col in 0..k {
+			let x = rhs[(j, col)] * inv;
+			for i in j.saturating_sub(kv)..j {
+				rhs[(i, col)] = rhs[(i, col)] - conj_LU.apply_rt(&A[(kv + i - j, j)]) * x;
+			}
+			rhs[(j, col)] = x;
+		}
+	}
+}
+
+/// given the $LU$ decomposition of a banded matrix $A$ computed by [`lu_in_place`], solves the
+/// equation $A^\top x = \text{rhs}$, implicitly conjugating $A$ if needed, and stores the result
+/// in `rhs`
+#[track_caller]
+#[math]
+pub fn solve_transpose_in_place_with_conj<T: ComplexField>(LU: BandRef<'_, T>, pivots: &[usize], conj_LU: Conj, rhs: MatMut<'_, T>) {
+	let n = LU.nrows();
+	let kl = LU.kl;
+	let kv = LU.ku;
+	assert!(all(LU.ncols() == n, kv >= kl, pivots.len() == n, rhs.nrows() == n));
+
+	let A = LU.inner;
+	let mut rhs = rhs;
+	let k = rhs.ncols();
+
+	for j in 0..n {
+		let inv = recip(conj_LU.apply_rt(&A[(kv, j)]));
+		for col in 0..k {
+			let mut acc = copy(rhs[(j, col)]);
+			for i in j.saturating_sub(kv)..j {
+				acc = acc - conj_LU.apply_rt(&A[(kv + i - j, j)]) * rhs[(i, col)];
+			}
+			rhs[(j, col)] = acc * inv;
+		}
+	}
+
+	for j in (0..n).rev() {
+		let lm = Ord::min(kl, n - 1 - j);
+		for col in 0..k {
+			let mut acc = copy(rhs[(j, col)]);
+			for t in 1..lm + 1 {
+				acc = acc - conj_LU.apply_rt(&A[(kv + t, j)]) * rhs[(j + t, col)];
+			}
+			rhs[(j, col)] = acc;
+		}
+
+		let p = pivots[j];
+		if p != j {
+			for col in 0..k {
+				let tmp = copy(rhs[(j, col)]);
+				rhs[(j, col)] = copy(rhs[(p, col)]);
+				rhs[(p, col)] = tmp;
+			}
+		}
+	}
+}
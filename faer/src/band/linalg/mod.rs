@@ -0,0 +1,37 @@
+use super::*;
+use crate::assert;
+
+/// banded cholesky decomposition
+pub mod cholesky;
+/// banded $LU$ decomposition with partial pivoting
+pub mod lu;
+/// tridiagonal decompositions and cyclic reduction
+pub mod tridiag;
+
+/// computes the matrix product `[beta * dst] + alpha * lhs * rhs` and stores the result in
+/// `dst`, where `lhs` is a banded matrix
+///
+/// the cost is $O(n (k_l + k_u + 1))$ per column of `rhs`
+#[track_caller]
+#[math]
+pub fn matmul<T: ComplexField>(dst: MatMut<'_, T>, beta: Accum, lhs: BandRef<'_, T>, rhs: MatRef<'_, T>, alpha: T) {
+	let (m, n) = lhs.shape();
+	assert!(all(dst.nrows() == m, dst.ncols() == rhs.ncols(), rhs.nrows() == n));
+
+	let mut dst = dst;
+	if beta == Accum::Replace {
+		dst.fill(zero());
+	}
+
+	let (kl, ku) = (lhs.kl, lhs.ku);
+	let band = lhs.inner;
+
+	for k in 0..rhs.ncols() {
+		for j in 0..n {
+			let x = alpha * rhs[(j, k)];
+			for i in band_rows(m, kl, ku, j) {
+				dst[(i, k)] = dst[(i, k)] + band[(ku + i - j, j)] * x;
+			}
+		}
+	}
+}
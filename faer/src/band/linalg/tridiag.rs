@@ -0,0 +1,443 @@
+use super::*;
+use crate::assert;
+use crate::linalg::cholesky::ldlt::factor::LdltError;
+use crate::linalg::lu::partial_pivoting::factor::PartialPivLuInfo;
+use crate::utils::thread::{join_raw, parallelism_degree};
+
+/// computes the $LU$ decomposition of the tridiagonal matrix $A$ with partial pivoting, in place,
+/// using the thomas algorithm
+///
+/// `diag`, `subdiag` and `superdiag` hold the diagonal, the subdiagonal and the superdiagonal of $A$. on
+/// exit, `diag`, `superdiag` and `superdiag2` hold the diagonal and the first two superdiagonals of the $U$
+/// factor, and `subdiag` holds the multipliers of the unit lower bidiagonal factors
+///
+/// at step $i$, row $i$ is interchanged with row `pivots[i]`, which is either $i$ or $i + 1$,
+/// as in lapack's `gttrf`
+#[track_caller]
+#[math]
+pub fn lu_in_place<T: ComplexField>(
+	diag: ColMut<'_, T>,
+	subdiag: ColMut<'_, T>,
+	superdiag: ColMut<'_, T>,
+	superdiag2: ColMut<'_, T>,
+	pivots: &mut [usize],
+) -> PartialPivLuInfo {
+	let n = diag.nrows();
+	assert!(all(
+		subdiag.nrows() == n.saturating_sub(1),
+		superdiag.nrows() == n.saturating_sub(1),
+		superdiag2.nrows() == n.saturating_sub(2),
+		pivots.len() == n,
+	));
+
+	let (mut dl, mut d, mut du, mut du2) = (subdiag, diag, superdiag, superdiag2);
+	let mut transposition_count = 0usize;
+
+	for (i, p) in pivots.iter_mut().enumerate() {
+		*p = i;
+	}
+	du2.fill(zero());
+
+	for i in 0..n.saturating_sub(1) {
+		if abs1(d[i]) >= abs1(dl[i]) {
+			if d[i] != zero() {
+				let fact = dl[i] * recip(d[i]);
+				dl[i] = copy(fact);
+				d[i + 1] = d[i + 1] - fact * du[i];
+			}
+		} else {
+			let fact = d[i] * recip(dl[i]);
+			d[i] = copy(dl[i]);
+			dl[i] = copy(fact);
+			let tmp = copy(du[i]);
+			du[i] = copy(d[i + 1]);
+			d[i + 1] = tmp - fact * d[i + 1];
+			if i + 2 < n {
+				du2[i] = copy(du[i + 1]);
+				du[i + 1] = -fact * du[i + 1];
+			}
+			pivots[i] = i + 1;
+			transposition_count += 1;
+		}
+	}
+
+	PartialPivLuInfo { transposition_count }
+}
+
+/// given the $LU$ decomposition of a tridiagonal matrix $A$ computed by [`lu_in_place`], solves
+/// the equation $A x = \text{rhs}$, implicitly conjugating $A$ if needed, and stores the result
+/// in `rhs`
+#[track_caller]
+#[math]
+pub fn lu_solve_in_place_with_conj<T: ComplexField>(
+	diag: ColRef<'_, T>,
+	subdiag: ColRef<'_, T>,
+	superdiag: ColRef<'_, T>,
+	superdiag2: ColRef<'_, T>,
+	pivots: &[usize],
+	conj_lhs: Conj,
+	rhs: MatMut<'_, T>,
+) {
+	let n = diag.nrows();
+	assert!(all(
+		subdiag.nrows() == n.saturating_sub(1),
+		superdiag.nrows() == n.saturating_sub(1),
+		superdiag2.nrows() == n.saturating_sub(2),
+		pivots.len() == n,
+		rhs.nrows() == n,
+	));
+
+	let (dl, d, du, du2) = (subdiag, diag, superdiag, superdiag2);
+	let c = |x: &T| conj_lhs.apply_rt(x);
+	let mut rhs = rhs;
+
+	for i in 0..n.saturating_sub(1) {
+		if pivots[i] != i {
+			crate::perm::swap_rows_idx(rhs.rb_mut(), i, i + 1);
+		}
+		let l = c(&dl[i]);
+		for col in 0..rhs.ncols() {
+			rhs[(i + 1, col)] = rhs[(i + 1, col)] - l * rhs[(i, col)];
+		}
+	}
+
+	for i in (0..n).rev() {
+		let inv = recip(c(&d[i]));
+		for col in 0..rhs.ncols() {
+			let mut acc = copy(rhs[(i, col)]);
+			if i + 1 < n {
+				acc = acc - c(&du[i]) * rhs[(i + 1, col)];
+			}
+			if i + 2 < n {
+				acc = acc - c(&du2[i]) * rhs[(i + 2, col)];
+			}
+			rhs[(i, col)] = acc * inv;
+		}
+	}
+}
+
+/// given the $LU$ decomposition of a tridiagonal matrix $A$ computed by [`lu_in_place`], solves
+/// the equation $A^\top x = \text{rhs}$, implicitly conjugating $A$ if needed, and stores the
+/// result in `rhs`
+#[track_caller]
+#[math]
+pub fn lu_solve_transpose_in_place_with_conj<T: ComplexField>(
+	diag: ColRef<'_, T>,
+	subdiag: ColRef<'_, T>,
+	superdiag: ColRef<'_, T>,
+	superdiag2: ColRef<'_, T>,
+	pivots: &[usize],
+	conj_lhs: Conj,
+	rhs: MatMut<'_, T>,
+) {
+	let n = diag.nrows();
+	assert!(all(
+		subdiag.nrows() == n.saturating_sub(1),
+		superdiag.nrows() == n.saturating_sub(1),
+		superdiag2.nrows() == n.saturating_sub(2),
+		pivots.len() == n,
+		rhs.nrows() == n,
+	));
+
+	let (dl, d, du, du2) = (subdiag, diag, superdiag, superdiag2);
+	let c = |x: &T| conj_lhs.apply_rt(x);
+	let mut rhs = rhs;
+
+	for i in 0..n {
+		let inv = recip(c(&d[i]));
+		for col in 0..rhs.ncols() {
+			let mut acc = copy(rhs[(i, col)]);
+			if i >= 1 {
+				acc = acc - c(&du[i - 1]) * rhs[(i - 1, col)];
+			}
+			if i >= 2 {
+				acc = acc - c(&du2[i - 2]) * rhs[(i - 2, col)];
+			}
+			rhs[(i, col)] = acc * inv;
+		}
+	}
+
+	for i in (0..n.saturating_sub(1)).rev() {
+		let l = c(&dl[i]);
+		for col in 0..rhs.ncols() {
+			rhs[(i, col)] = rhs[(i, col)] - l * rhs[(i + 1, col)];
+		}
+		if pivots[i] != i {
+			crate::perm::swap_rows_idx(rhs.rb_mut(), i, i + 1);
+		}
+	}
+}
+
+/// computes the $L D L^H$ decomposition of the hermitian tridiagonal matrix $A$ without
+/// pivoting, in place
+///
+/// `diag` and `subdiag` hold the diagonal and the subdiagonal of $A$, where only the real part of
+/// the diagonal is read. on exit, `diag` holds $D$ and `subdiag` holds the subdiagonal of the
+/// unit lower bidiagonal factor $L$
+///
+/// returns an error if a zero pivot is encountered
+#[track_caller]
+#[math]
+pub fn ldlt_in_place<T: ComplexField>(diag: ColMut<'_, T>, subdiag: ColMut<'_, T>) -> Result<(), LdltError> {
+	let n = diag.nrows();
+	assert!(subdiag.nrows() == n.saturating_sub(1));
+
+	let (mut d, mut e) = (diag, subdiag);
+
+	let mut prev = zero::<T::Real>();
+	for i in 0..n {
+		let mut di = real(d[i]);
+		if i > 0 {
+			let ei = copy(e[i - 1]);
+			let li = mul_real(ei, recip(prev));
+			di = di - real(li * conj(ei));
+			e[i - 1] = li;
+		}
+		if di == zero::<T::Real>() {
+			return Err(LdltError::ZeroPivot { index: i });
+		}
+		d[i] = from_real(di);
+		prev = di;
+	}
+
+	Ok(())
+}
+
+/// given the $L D L^H$ decomposition of a hermitian tridiagonal matrix $A$ computed by
+/// [`ldlt_in_place`], solves the equation $A x = \text{rhs}$, implicitly conjugating $A$ if
+/// needed, and stores the result in `rhs`
+#[track_caller]
+#[math]
+pub fn ldlt_solve_in_place_with_conj<T: ComplexField>(diag: ColRef<'_, T>, subdiag: ColRef<'_, T>, conj_lhs: Conj, rhs: MatMut<'_, T>) {
+	let n = diag.nrows();
+	assert!(all(subdiag.nrows() == n.saturating_sub(1), rhs.nrows() == n));
+
+	let (d, e) = (diag, subdiag);
+	let mut rhs = rhs;
+
+	for i in 1..n {
+		let l = conj_lhs.apply_rt(&e[i - 1]);
+		for col in 0..rhs.ncols() {
+			rhs[(i, col)] = rhs[(i, col)] - l * rhs[(i - 1, col)];
+		}
+	}
+
+	for i in (0..n).rev() {
+		let inv = recip(real(d[i]));
+		let l = if i + 1 < n { conj(conj_lhs.apply_rt(&e[i])) } else { zero() };
+		for col in 0..rhs.ncols() {
+			let mut acc = mul_real(rhs[(i, col)], inv);
+			if i + 1 < n {
+				acc = acc - l * rhs[(i + 1, col)];
+			}
+			rhs[(i, col)] = acc;
+		}
+	}
+}
+
+/// computes the size and alignment of required workspace for solving a tridiagonal system with
+/// [`cyclic_reduction_solve_in_place`]
+pub fn cyclic_reduction_solve_in_place_scratch<T: ComplexField>(dim: usize, rhs_ncols: usize, par: Par) -> StackReq {
+	_ = par;
+	StackReq::all_of(&[temp_mat_scratch::<T>(dim, 3).array(2), temp_mat_scratch::<T>(dim, rhs_ncols)])
+}
+
+#[math]
+fn cyclic_reduction_step<T: ComplexField>(
+	coeffs_out: MatMut<'_, T>,
+	rhs_out: MatMut<'_, T>,
+	coeffs: MatRef<'_, T>,
+	rhs: MatRef<'_, T>,
+	start: usize,
+	stride: usize,
+	par: Par,
+) {
+	let len = coeffs_out.nrows();
+	if len >= 1024 && parallelism_degree(par) > 1 {
+		let mid = len / 2;
+		let (coeffs_top, coeffs_bot) = coeffs_out.split_at_row_mut(mid);
+		let (rhs_top, rhs_bot) = rhs_out.split_at_row_mut(mid);
+		join_raw(
+			|par| cyclic_reduction_step(coeffs_top, rhs_top, coeffs, rhs, start, stride, par),
+			|par| cyclic_reduction_step(coeffs_bot, rhs_bot, coeffs, rhs, start + mid, stride, par),
+			par,
+		);
+		return;
+	}
+
+	let n = coeffs.nrows();
+	let mut coeffs_out = coeffs_out;
+	let mut rhs_out = rhs_out;
+
+	for idx in 0..len {
+		let i = start + idx;
+
+		let mut a = zero::<T>();
+		let mut b = copy(coeffs[(i, 1)]);
+		let mut c = zero::<T>();
+		for col in 0..rhs.ncols() {
+			rhs_out[(idx, col)] = copy(rhs[(i, col)]);
+		}
+
+		// eliminate the coupling with the unknown `stride` rows above
+		if i >= stride {
+			let p = i - stride;
+			let alpha = -coeffs[(i, 0)] * recip(coeffs[(p, 1)]);
+			a = alpha * coeffs[(p, 0)];
+			b = b + alpha * coeffs[(p, 2)];
+			for col in 0..rhs.ncols() {
+				rhs_out[(idx, col)] = rhs_out[(idx, col)] + alpha * rhs[(p, col)];
+			}
+		}
+		// eliminate the coupling with the unknown `stride` rows below
+		if i + stride < n {
+			let q = i + stride;
+			let gamma = -coeffs[(i, 2)] * recip(coeffs[(q, 1)]);
+			c = gamma * coeffs[(q, 2)];
+			b = b + gamma * coeffs[(q, 0)];
+			for col in 0..rhs.ncols() {
+				rhs_out[(idx, col)] = rhs_out[(idx, col)] + gamma * rhs[(q, col)];
+			}
+		}
+
+		coeffs_out[(idx, 0)] = a;
+		coeffs_out[(idx, 1)] = b;
+		coeffs_out[(idx, 2)] = c;
+	}
+}
+
+/// solves the equation $A x = \text{rhs}$ for the tridiagonal matrix $A$ with parallel cyclic
+/// reduction, and stores the result in `rhs`
+///
+/// `diag`, `subdiag` and `superdiag` hold the diagonal, the subdiagonal and the superdiagonal of $A$. each
+/// of the $\lceil \log_2 n \rceil$ reduction steps decouples every unknown from its neighbors
+/// twice as far away as in the previous step, and the rows of each step are processed in
+/// parallel. the total cost is $O(n \log n)$
+///
+/// no pivoting is performed, so this should only be used for matrices that don't need it, such
+/// as diagonally dominant or hermitian positive definite matrices
+///
+/// this is a low-level solver with no factorization to reuse, so there is no corresponding
+/// high-level type in [`crate::band::solvers`]. use [`TridiagLu`](crate::band::solvers::TridiagLu)
+/// for repeated solves with the same matrix
+#[track_caller]
+#[math]
+pub fn cyclic_reduction_solve_in_place<T: ComplexField>(
+	diag: ColRef<'_, T>,
+	subdiag: ColRef<'_, T>,
+	superdiag: ColRef<'_, T>,
+	rhs: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let n = diag.nrows();
+	let k = rhs.ncols();
+	assert!(all(
+		subdiag.nrows() == n.saturating_sub(1),
+		superdiag.nrows() == n.saturating_sub(1),
+		rhs.nrows() == n
+	));
+
+	let (mut coeffs0, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 3, stack) };
+	let (mut coeffs1, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 3, stack) };
+	let (mut tmp, _) = unsafe { temp_mat_uninit::<T, _, _>(n, k, stack) };
+	let mut coeffs0 = coeffs0.as_mat_mut();
+	let mut coeffs1 = coeffs1.as_mat_mut();
+	let mut tmp = tmp.as_mat_mut();
+	let mut rhs = rhs;
+
+	for i in 0..n {
+		coeffs0[(i, 0)] = if i > 0 { copy(subdiag[i - 1]) } else { zero() };
+		coeffs0[(i, 1)] = copy(diag[i]);
+		coeffs0[(i, 2)] = if i + 1 < n { copy(superdiag[i]) } else { zero() };
+	}
+
+	// the unknowns alternate between (coeffs0, rhs) and (coeffs1, tmp)
+	let mut flipped = false;
+	let mut stride = 1usize;
+	while stride < n {
+		if !flipped {
+			cyclic_reduction_step(coeffs1.rb_mut(), tmp.rb_mut(), coeffs0.rb(), rhs.rb(), 0, stride, par);
+		} else {
+			cyclic_reduction_step(coeffs0.rb_mut(), rhs.rb_mut(), coeffs1.rb(), tmp.rb(), 0, stride, par);
+		}
+		flipped = !flipped;
+		stride *= 2;
+	}
+
+	let coeffs = if flipped {
+		rhs.copy_from(&tmp);
+		coeffs1.rb()
+	} else {
+		coeffs0.rb()
+	};
+
+	for i in 0..n {
+		let inv = recip(coeffs[(i, 1)]);
+		for col in 0..k {
+			rhs[(i, col)] = rhs[(i, col)] * inv;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	#[test]
+	fn test_cyclic_reduction() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0usize, 1, 2, 3, 7, 64, 3000] {
+			let m = n.saturating_sub(1);
+			let rand = |rng: &mut StdRng, n: usize| -> Col<f64> {
+				CwiseColDistribution {
+					nrows: n,
+					dist: StandardNormal,
+				}
+				.rand(rng)
+			};
+			let subdiag = rand(rng, m);
+			let superdiag = rand(rng, m);
+			// diagonally dominant
+			let diag = Col::from_fn(n, |i| 4.0 + i as f64 / n as f64);
+
+			let rhs: Mat<f64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 2,
+				dist: StandardNormal,
+			}
+			.rand(rng);
+
+			let dense = Mat::from_fn(n, n, |i, j| {
+				if i == j {
+					diag[i]
+				} else if i == j + 1 {
+					subdiag[j]
+				} else if j == i + 1 {
+					superdiag[i]
+				} else {
+					0.0
+				}
+			});
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 64.0 * (n as f64 + 1.0));
+			for par in [Par::Seq, Par::rayon(4)] {
+				let mut x = rhs.clone();
+				cyclic_reduction_solve_in_place(
+					diag.as_ref(),
+					subdiag.as_ref(),
+					superdiag.as_ref(),
+					x.as_mut(),
+					par,
+					MemStack::new(&mut MemBuffer::new(cyclic_reduction_solve_in_place_scratch::<f64>(n, 2, par))),
+				);
+				assert!(&dense * &x ~ rhs);
+			}
+		}
+	}
+}
@@ -0,0 +1,137 @@
+use crate::internal_prelude::*;
+use crate::internal_prelude_sp::{FaerError, SparseColMat, SparseColMatRef, SymbolicSparseColMat};
+use crate::linalg::cholesky::llt::factor::LltError;
+
+pub(crate) mod bandmut;
+pub(crate) mod bandown;
+pub(crate) mod bandref;
+
+/// low level banded matrix products and decompositions
+pub mod linalg;
+/// high level banded matrix decompositions
+pub mod solvers;
+
+pub use bandmut::BandMut;
+pub use bandown::BandMat;
+pub use bandref::BandRef;
+
+/// returns the range of rows of column `j` that lie inside the band
+#[inline]
+pub(crate) fn band_rows(nrows: usize, kl: usize, ku: usize, j: usize) -> core::ops::Range<usize> {
+	let start = Ord::min(j.saturating_sub(ku), nrows);
+	let end = Ord::min(j.saturating_add(kl).saturating_add(1), nrows);
+	start..Ord::max(start, end)
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<MatRef<'a, T>> for BandRef<'a, T> {
+	type Output = Mat<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: MatRef<'a, T>) -> Self::Output {
+		let mut out = Mat::zeros(self.nrows(), rhs.ncols());
+		linalg::matmul(out.as_mut(), Accum::Replace, self, rhs, one());
+		out
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<ColRef<'a, T>> for BandRef<'a, T> {
+	type Output = Col<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: ColRef<'a, T>) -> Self::Output {
+		let mut out = Col::zeros(self.nrows());
+		linalg::matmul(out.as_mat_mut(), Accum::Replace, self, rhs.as_mat(), one());
+		out
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<&'a Mat<T>> for &'a BandMat<T> {
+	type Output = Mat<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: &'a Mat<T>) -> Self::Output {
+		self.as_ref() * rhs.as_ref()
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<&'a Col<T>> for &'a BandMat<T> {
+	type Output = Col<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: &'a Col<T>) -> Self::Output {
+		self.as_ref() * rhs.as_ref()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+
+	fn random_band(rng: &mut StdRng, m: usize, n: usize, kl: usize, ku: usize) -> BandMat<f64> {
+		let rand = CwiseMatDistribution {
+			nrows: m,
+			ncols: n,
+			dist: StandardNormal,
+		};
+		let A: Mat<f64> = rand.rand(rng);
+		BandMat::from_dense(A.as_ref(), kl, ku)
+	}
+
+	#[test]
+	fn test_conversions() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (m, n, kl, ku) in [(0, 0, 0, 0), (1, 1, 0, 0), (7, 7, 2, 1), (5, 9, 1, 3), (9, 4, 4, 0), (6, 6, 10, 10)] {
+			let A = random_band(rng, m, n, kl, ku);
+			assert!(all(A.nrows() == m, A.ncols() == n, A.lower_bandwidth() == kl, A.upper_bandwidth() == ku));
+
+			let dense = A.to_dense();
+			for j in 0..n {
+				for i in 0..m {
+					let in_band = i <= j + kl && j <= i + ku;
+					assert!(A.get(i, j).is_some() == in_band);
+					if !in_band {
+						assert!(dense[(i, j)] == 0.0);
+					} else {
+						assert!(dense[(i, j)] == *A.get(i, j).unwrap());
+					}
+				}
+			}
+			assert!(BandMat::from_dense(dense.as_ref(), kl, ku).to_dense() == dense);
+
+			let sparse = A.to_sparse::<usize>().unwrap();
+			assert!(sparse.to_dense() == dense);
+
+			let B = BandMat::from_sparse(sparse.as_ref());
+			assert!(all(B.lower_bandwidth() <= kl, B.upper_bandwidth() <= ku));
+			assert!(B.to_dense() == dense);
+		}
+	}
+
+	#[test]
+	fn test_matmul() {
+		let rng = &mut StdRng::seed_from_u64(1);
+
+		for (m, n, kl, ku) in [(0, 0, 0, 0), (1, 1, 0, 0), (7, 7, 2, 1), (5, 9, 1, 3), (9, 4, 4, 0)] {
+			let A = random_band(rng, m, n, kl, ku);
+			let rhs: Mat<f64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: StandardNormal,
+			}
+			.rand(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 8.0 * (n as f64 + 1.0));
+			let target = A.to_dense() * &rhs;
+			assert!(&A * &rhs ~ target);
+			assert!((&A * &rhs.col(0).to_owned()).as_mat() ~ target.col(0).as_mat());
+
+			let mut dst = target.clone();
+			linalg::matmul(dst.as_mut(), Accum::Add, A.as_ref(), rhs.as_ref(), 2.0);
+			assert!(dst ~ &target * Scale(3.0));
+		}
+	}
+}
@@ -0,0 +1,669 @@
+use super::*;
+use crate::assert;
+use crate::linalg::cholesky::ldlt::factor::LdltError;
+use crate::linalg::matmul::matmul_with_conj;
+use crate::linalg::solvers::{self as dense_solvers, ShapeCore, SolveCore};
+
+/// banded $LU$ decomposition with partial pivoting
+///
+/// the $U$ factor has lower bandwidth $0$ and upper bandwidth $k_l + k_u$, and the unit lower
+/// triangular factor is stored as a sequence of row interchanges and column eliminations, as
+/// described in [`linalg::lu::lu_in_place`]
+#[derive(Clone, Debug)]
+pub struct PartialPivLu<T> {
+	LU: BandMat<T>,
+	pivots: alloc::vec::Vec<usize>,
+}
+
+/// banded $L L^H$ decomposition
+#[derive(Clone, Debug)]
+pub struct Llt<T> {
+	L: BandMat<T>,
+}
+
+/// tridiagonal $LU$ decomposition with partial pivoting
+///
+/// the $U$ factor has two superdiagonals, and the unit lower bidiagonal factor is stored as a
+/// sequence of row interchanges and eliminations, as described in
+/// [`linalg::tridiag::lu_in_place`]
+#[derive(Clone, Debug)]
+pub struct TridiagLu<T> {
+	diag: Col<T>,
+	subdiag: Col<T>,
+	superdiag: Col<T>,
+	superdiag2: Col<T>,
+	pivots: alloc::vec::Vec<usize>,
+}
+
+/// hermitian tridiagonal $L D L^H$ decomposition
+#[derive(Clone, Debug)]
+pub struct SymTridiagLdlt<T> {
+	diag: Col<T>,
+	subdiag: Col<T>,
+}
+
+/// block tridiagonal $LU$ decomposition
+///
+/// the matrix is factored as $A = L U$, where $L$ is block lower bidiagonal with the schur
+/// complements $S_i$ on its diagonal and the subdiagonal blocks of $A$ below it, and $U$ is
+/// block unit upper bidiagonal with the blocks $S_i^{-1} C_i$ above its diagonal. the schur
+/// complements are factored with [`PartialPivLu`](crate::linalg::solvers::PartialPivLu), but no
+/// pivoting is performed across blocks
+#[derive(Clone, Debug)]
+pub struct BlockTridiagLu<T: ComplexField> {
+	offsets: alloc::vec::Vec<usize>,
+	lower: alloc::vec::Vec<Mat<T>>,
+	upper: alloc::vec::Vec<Mat<T>>,
+	schur: alloc::vec::Vec<dense_solvers::PartialPivLu<T>>,
+}
+
+impl<T: ComplexField> PartialPivLu<T> {
+	/// returns the $LU$ decomposition of $A$ with partial pivoting
+	#[track_caller]
+	pub fn new(A: BandRef<'_, T>) -> Self {
+		let n = A.nrows();
+		assert!(A.ncols() == n);
+		let (kl, ku) = (A.kl, A.ku);
+
+		let mut LU = BandMat::zeros(n, n, kl, kl + ku);
+		LU.inner.get_mut(kl.., ..).copy_from(A.inner);
+		let mut pivots = alloc::vec![0usize; n];
+
+		linalg::lu::lu_in_place(LU.as_mut(), &mut pivots);
+
+		Self { LU, pivots }
+	}
+
+	/// returns the factors of the decomposition, in the layout described in
+	/// [`linalg::lu::lu_in_place`]
+	pub fn LU(&self) -> BandRef<'_, T> {
+		self.LU.as_ref()
+	}
+
+	/// returns the row interchanges of the decomposition
+	pub fn pivots(&self) -> &[usize] {
+		&self.pivots
+	}
+}
+
+impl<T: ComplexField> Llt<T> {
+	/// returns the $L L^H$ decomposition of $A$, reading only the band on the given side of the
+	/// diagonal
+	#[track_caller]
+	pub fn new(A: BandRef<'_, T>, side: Side) -> Result<Self, LltError> {
+		let n = A.nrows();
+		assert!(A.ncols() == n);
+
+		let kd = match side {
+			Side::Lower => A.kl,
+			Side::Upper => A.ku,
+		};
+		let ku = A.ku;
+
+		let mut L = BandMat::zeros(n, n, kd, 0);
+		for j in 0..n {
+			for i in band_rows(n, kd, 0, j) {
+				L.inner[(i - j, j)] = match side {
+					Side::Lower => copy(&A.inner[(ku + i - j, j)]),
+					Side::Upper => conj(&A.inner[(ku + j - i, i)]),
+				};
+			}
+		}
+
+		linalg::cholesky::cholesky_in_place(L.as_mut())?;
+
+		Ok(Self { L })
+	}
+
+	/// returns the $L$ factor, with lower bandwidth $k$ and upper bandwidth $0$
+	pub fn L(&self) -> BandRef<'_, T> {
+		self.L.as_ref()
+	}
+}
+
+impl<T: ComplexField> TridiagLu<T> {
+	/// returns the $LU$ decomposition with partial pivoting of the tridiagonal matrix with the
+	/// given diagonal, subdiagonal and superdiagonal
+	#[track_caller]
+	pub fn new(diag: ColRef<'_, T>, subdiag: ColRef<'_, T>, superdiag: ColRef<'_, T>) -> Self {
+		let n = diag.nrows();
+		let mut diag = diag.to_owned();
+		let mut subdiag = subdiag.to_owned();
+		let mut superdiag = superdiag.to_owned();
+		let mut superdiag2 = Col::zeros(n.saturating_sub(2));
+		let mut pivots = alloc::vec![0usize; n];
+
+		linalg::tridiag::lu_in_place(diag.as_mut(), subdiag.as_mut(), superdiag.as_mut(), superdiag2.as_mut(), &mut pivots);
+
+		Self {
+			diag,
+			subdiag,
+			superdiag,
+			superdiag2,
+			pivots,
+		}
+	}
+
+	/// returns the row interchanges of the decomposition
+	pub fn pivots(&self) -> &[usize] {
+		&self.pivots
+	}
+}
+
+impl<T: ComplexField> SymTridiagLdlt<T> {
+	/// returns the $L D L^H$ decomposition of the hermitian tridiagonal matrix with the given
+	/// diagonal and subdiagonal, where only the real part of the diagonal is read
+	#[track_caller]
+	pub fn new(diag: ColRef<'_, T>, subdiag: ColRef<'_, T>) -> Result<Self, LdltError> {
+		let mut diag = diag.to_owned();
+		let mut subdiag = subdiag.to_owned();
+
+		linalg::tridiag::ldlt_in_place(diag.as_mut(), subdiag.as_mut())?;
+
+		Ok(Self { diag, subdiag })
+	}
+
+	/// returns the $D$ factor
+	pub fn D(&self) -> DiagRef<'_, T> {
+		self.diag.as_diagonal()
+	}
+
+	/// returns the subdiagonal of the unit lower bidiagonal $L$ factor
+	pub fn L_subdiagonal(&self) -> ColRef<'_, T> {
+		self.subdiag.as_ref()
+	}
+}
+
+impl<T: ComplexField> BlockTridiagLu<T> {
+	/// returns the block $LU$ decomposition of the block tridiagonal matrix with the given
+	/// diagonal, subdiagonal and superdiagonal blocks
+	///
+	/// the diagonal blocks must be square, and `lower[i]` and `upper[i]` are the blocks at
+	/// positions $(i + 1, i)$ and $(i, i + 1)$ respectively
+	#[track_caller]
+	pub fn new(diag: &[MatRef<'_, T>], lower: &[MatRef<'_, T>], upper: &[MatRef<'_, T>]) -> Self {
+		let par = crate::get_global_parallelism();
+		let nblocks = diag.len();
+		assert!(all(lower.len() == nblocks.saturating_sub(1), upper.len() == nblocks.saturating_sub(1)));
+
+		let mut offsets = alloc::vec![0usize; nblocks + 1];
+		for (i, D) in diag.iter().enumerate() {
+			assert!(D.nrows() == D.ncols());
+			offsets[i + 1] = offsets[i] + D.nrows();
+		}
+		for i in 0..nblocks.saturating_sub(1) {
+			let (n0, n1) = (diag[i].nrows(), diag[i + 1].nrows());
+			assert!(all(
+				lower[i].nrows() == n1,
+				lower[i].ncols() == n0,
+				upper[i].nrows() == n0,
+				upper[i].ncols() == n1
+			));
+		}
+
+		let mut schur = alloc::vec::Vec::with_capacity(nblocks);
+		let mut factors = alloc::vec::Vec::<Mat<T>>::with_capacity(nblocks.saturating_sub(1));
+		for i in 0..nblocks {
+			let mut S = diag[i].to_owned();
+			if i > 0 {
+				crate::linalg::matmul::matmul(S.as_mut(), Accum::Add, lower[i - 1], factors[i - 1].as_ref(), neg(&one::<T>()), par);
+			}
+			let lu = dense_solvers::PartialPivLu::new(S.as_ref());
+			if i + 1 < nblocks {
+				let mut U = upper[i].to_owned();
+				lu.solve_in_place_with_conj(Conj::No, U.as_mut());
+				factors.push(U);
+			}
+			schur.push(lu);
+		}
+
+		Self {
+			offsets,
+			lower: lower.iter().map(|A| A.to_owned()).collect(),
+			upper: factors,
+			schur,
+		}
+	}
+
+	/// returns the number of diagonal blocks
+	pub fn nblocks(&self) -> usize {
+		self.schur.len()
+	}
+}
+
+impl<T: ComplexField> ShapeCore for PartialPivLu<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.LU.nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.LU.ncols()
+	}
+}
+
+impl<T: ComplexField> ShapeCore for Llt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L.nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.L.ncols()
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for PartialPivLu<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::lu::solve_in_place_with_conj(self.LU(), &self.pivots, conj, rhs);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::lu::solve_transpose_in_place_with_conj(self.LU(), &self.pivots, conj, rhs);
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Llt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::cholesky::solve_in_place_with_conj(self.L(), conj, rhs);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::cholesky::solve_in_place_with_conj(self.L(), conj.compose(Conj::Yes), rhs);
+	}
+}
+
+impl<T: ComplexField> ShapeCore for TridiagLu<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.diag.nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.diag.nrows()
+	}
+}
+
+impl<T: ComplexField> ShapeCore for SymTridiagLdlt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.diag.nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.diag.nrows()
+	}
+}
+
+impl<T: ComplexField> ShapeCore for BlockTridiagLu<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		*self.offsets.last().unwrap()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		*self.offsets.last().unwrap()
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for TridiagLu<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::tridiag::lu_solve_in_place_with_conj(
+			self.diag.as_ref(),
+			self.subdiag.as_ref(),
+			self.superdiag.as_ref(),
+			self.superdiag2.as_ref(),
+			&self.pivots,
+			conj,
+			rhs,
+		);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::tridiag::lu_solve_transpose_in_place_with_conj(
+			self.diag.as_ref(),
+			self.subdiag.as_ref(),
+			self.superdiag.as_ref(),
+			self.superdiag2.as_ref(),
+			&self.pivots,
+			conj,
+			rhs,
+		);
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for SymTridiagLdlt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::tridiag::ldlt_solve_in_place_with_conj(self.diag.as_ref(), self.subdiag.as_ref(), conj, rhs);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::tridiag::ldlt_solve_in_place_with_conj(self.diag.as_ref(), self.subdiag.as_ref(), conj.compose(Conj::Yes), rhs);
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for BlockTridiagLu<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = crate::get_global_parallelism();
+		let nblocks = self.nblocks();
+		let off = &*self.offsets;
+		assert!(rhs.nrows() == off[nblocks]);
+
+		let mut rhs = rhs;
+		for i in 0..nblocks {
+			let (top, bot) = rhs.rb_mut().split_at_row_mut(off[i]);
+			let mut cur = bot.subrows_mut(0, off[i + 1] - off[i]);
+			if i > 0 {
+				let prev = top.rb().subrows(off[i - 1], off[i] - off[i - 1]);
+				matmul_with_conj(
+					cur.rb_mut(),
+					Accum::Add,
+					self.lower[i - 1].as_ref(),
+					conj,
+					prev,
+					Conj::No,
+					neg(&one::<T>()),
+					par,
+				);
+			}
+			self.schur[i].solve_in_place_with_conj(conj, cur);
+		}
+
+		for i in (0..nblocks.saturating_sub(1)).rev() {
+			let (top, bot) = rhs.rb_mut().split_at_row_mut(off[i + 1]);
+			let cur = top.subrows_mut(off[i], off[i + 1] - off[i]);
+			let next = bot.rb().subrows(0, off[i + 2] - off[i + 1]);
+			matmul_with_conj(cur, Accum::Add, self.upper[i].as_ref(), conj, next, Conj::No, neg(&one::<T>()), par);
+		}
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = crate::get_global_parallelism();
+		let nblocks = self.nblocks();
+		let off = &*self.offsets;
+		assert!(rhs.nrows() == off[nblocks]);
+
+		let mut rhs = rhs;
+		for i in 1..nblocks {
+			let (top, bot) = rhs.rb_mut().split_at_row_mut(off[i]);
+			let cur = bot.subrows_mut(0, off[i + 1] - off[i]);
+			let prev = top.rb().subrows(off[i - 1], off[i] - off[i - 1]);
+			matmul_with_conj(
+				cur,
+				Accum::Add,
+				self.upper[i - 1].transpose(),
+				conj,
+				prev,
+				Conj::No,
+				neg(&one::<T>()),
+				par,
+			);
+		}
+
+		for i in (0..nblocks).rev() {
+			let (top, bot) = rhs.rb_mut().split_at_row_mut(off[i + 1]);
+			let mut cur = top.subrows_mut(off[i], off[i + 1] - off[i]);
+			if i + 1 < nblocks {
+				let next = bot.rb().subrows(0, off[i + 2] - off[i + 1]);
+				matmul_with_conj(
+					cur.rb_mut(),
+					Accum::Add,
+					self.lower[i].transpose(),
+					conj,
+					next,
+					Conj::No,
+					neg(&one::<T>()),
+					par,
+				);
+			}
+			self.schur[i].solve_transpose_in_place_with_conj(conj, cur);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::linalg::solvers::Solve;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+
+	#[test]
+	fn test_partial_piv_lu() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (n, kl, ku) in [(0, 0, 0), (1, 0, 0), (8, 0, 0), (8, 2, 1), (12, 1, 3), (10, 4, 0), (9, 3, 3), (5, 7, 7)] {
+			let rand = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			};
+			let A: Mat<c64> = rand.rand(rng);
+			let A = BandMat::from_dense(A.as_ref(), kl, ku);
+			let dense = A.to_dense();
+
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+
+			let lu = A.partial_piv_lu();
+			assert!(lu.LU().upper_bandwidth() == kl + ku);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e3 * (n as f64 + 1.0));
+			assert!(&dense * lu.solve(&rhs) ~ rhs);
+			assert!(dense.conjugate() * lu.solve_conjugate(&rhs) ~ rhs);
+			assert!(dense.transpose() * lu.solve_transpose(&rhs) ~ rhs);
+			assert!(dense.adjoint() * lu.solve_adjoint(&rhs) ~ rhs);
+		}
+	}
+
+	#[test]
+	fn test_llt() {
+		let rng = &mut StdRng::seed_from_u64(1);
+
+		for (n, kd) in [(0, 0), (1, 0), (8, 0), (8, 2), (12, 3), (6, 9)] {
+			let rand = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			};
+			let B: Mat<c64> = rand.rand(rng);
+			let B = BandMat::from_dense(B.as_ref(), kd, kd).to_dense();
+			let mut dense = &B + B.adjoint();
+			for i in 0..n {
+				dense[(i, i)] = c64::new(4.0 * (2 * kd + 1) as f64, 0.0);
+			}
+
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e3 * (n as f64 + 1.0));
+			for side in [Side::Lower, Side::Upper] {
+				let (kl, ku) = match side {
+					Side::Lower => (kd, 0),
+					Side::Upper => (0, kd),
+				};
+				let A = BandMat::from_dense(dense.as_ref(), kl, ku);
+				let llt = A.llt(side).unwrap();
+
+				let L = llt.L().to_dense();
+				assert!(&L * L.adjoint() ~ dense);
+				assert!(&dense * llt.solve(&rhs) ~ rhs);
+				assert!(dense.conjugate() * llt.solve_conjugate(&rhs) ~ rhs);
+				assert!(dense.transpose() * llt.solve_transpose(&rhs) ~ rhs);
+			}
+		}
+
+		let A = BandMat::from_fn(3, 3, 1, 1, |i, j| if i == j { -1.0 } else { 0.5 });
+		assert!(matches!(A.llt(Side::Lower), Err(LltError::NonPositivePivot { index: 0 })));
+	}
+
+	fn random_col(rng: &mut StdRng, n: usize) -> Col<c64> {
+		let rand = CwiseColDistribution {
+			nrows: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		};
+		rand.rand(rng)
+	}
+
+	fn tridiag_to_dense(subdiag: ColRef<'_, c64>, diag: ColRef<'_, c64>, superdiag: ColRef<'_, c64>) -> Mat<c64> {
+		let n = diag.nrows();
+		Mat::from_fn(n, n, |i, j| {
+			if i == j {
+				diag[i]
+			} else if i == j + 1 {
+				subdiag[j]
+			} else if j == i + 1 {
+				superdiag[i]
+			} else {
+				c64::new(0.0, 0.0)
+			}
+		})
+	}
+
+	#[test]
+	fn test_tridiag_lu() {
+		let rng = &mut StdRng::seed_from_u64(2);
+
+		for n in [0usize, 1, 2, 3, 10, 33] {
+			let m = n.saturating_sub(1);
+			let subdiag = random_col(rng, m);
+			let diag = random_col(rng, n);
+			let superdiag = random_col(rng, m);
+			let dense = tridiag_to_dense(subdiag.as_ref(), diag.as_ref(), superdiag.as_ref());
+
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+
+			let lu = TridiagLu::new(diag.as_ref(), subdiag.as_ref(), superdiag.as_ref());
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e3 * (n as f64 + 1.0));
+			assert!(&dense * lu.solve(&rhs) ~ rhs);
+			assert!(dense.conjugate() * lu.solve_conjugate(&rhs) ~ rhs);
+			assert!(dense.transpose() * lu.solve_transpose(&rhs) ~ rhs);
+			assert!(dense.adjoint() * lu.solve_adjoint(&rhs) ~ rhs);
+		}
+	}
+
+	#[test]
+	fn test_sym_tridiag_ldlt() {
+		let rng = &mut StdRng::seed_from_u64(3);
+
+		for n in [0usize, 1, 2, 3, 10, 33] {
+			let subdiag = random_col(rng, n.saturating_sub(1));
+			// indefinite, but with nonzero pivots
+			let diag = Col::from_fn(n, |i| c64::new(if i % 2 == 0 { 4.0 } else { -4.0 }, 0.0));
+			let dense = tridiag_to_dense(subdiag.as_ref(), diag.as_ref(), subdiag.conjugate().to_owned().as_ref());
+
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+
+			let ldlt = SymTridiagLdlt::new(diag.as_ref(), subdiag.as_ref()).unwrap();
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e3 * (n as f64 + 1.0));
+			let L = Mat::from_fn(n, n, |i, j| {
+				if i == j {
+					c64::new(1.0, 0.0)
+				} else if i == j + 1 {
+					ldlt.L_subdiagonal()[j]
+				} else {
+					c64::new(0.0, 0.0)
+				}
+			});
+			assert!(&L * ldlt.D() * L.adjoint() ~ dense);
+			assert!(&dense * ldlt.solve(&rhs) ~ rhs);
+			assert!(dense.conjugate() * ldlt.solve_conjugate(&rhs) ~ rhs);
+			assert!(dense.transpose() * ldlt.solve_transpose(&rhs) ~ rhs);
+		}
+
+		let diag = Col::from_fn(2, |_| 1.0);
+		let subdiag = Col::from_fn(1, |_| 1.0);
+		assert!(matches!(
+			SymTridiagLdlt::new(diag.as_ref(), subdiag.as_ref()),
+			Err(LdltError::ZeroPivot { index: 1 })
+		));
+	}
+
+	#[test]
+	fn test_block_tridiag_lu() {
+		let rng = &mut StdRng::seed_from_u64(4);
+
+		for sizes in [&[][..], &[3][..], &[2, 4][..], &[3, 1, 4, 2, 5][..]] {
+			let nblocks = sizes.len();
+			let n: usize = sizes.iter().sum();
+			let mut offsets = vec![0usize];
+			for &size in sizes {
+				offsets.push(offsets.last().unwrap() + size);
+			}
+
+			let rand = |rng: &mut StdRng, m: usize, n: usize| -> Mat<c64> {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+				}
+				.rand(rng)
+			};
+
+			let diag: Vec<Mat<c64>> = sizes.iter().map(|&k| rand(rng, k, k)).collect();
+			let lower: Vec<Mat<c64>> = (1..nblocks).map(|i| rand(rng, sizes[i], sizes[i - 1])).collect();
+			let upper: Vec<Mat<c64>> = (1..nblocks).map(|i| rand(rng, sizes[i - 1], sizes[i])).collect();
+
+			let mut dense = Mat::<c64>::zeros(n, n);
+			for i in 0..nblocks {
+				let (o, k) = (offsets[i], sizes[i]);
+				dense.as_mut().submatrix_mut(o, o, k, k).copy_from(&diag[i]);
+				if i + 1 < nblocks {
+					let (o1, k1) = (offsets[i + 1], sizes[i + 1]);
+					dense.as_mut().submatrix_mut(o1, o, k1, k).copy_from(&lower[i]);
+					dense.as_mut().submatrix_mut(o, o1, k, k1).copy_from(&upper[i]);
+				}
+			}
+
+			let rhs = rand(rng, n, 3);
+
+			let lower: Vec<_> = lower.iter().map(|A| A.as_ref()).collect();
+			let diag: Vec<_> = diag.iter().map(|A| A.as_ref()).collect();
+			let upper: Vec<_> = upper.iter().map(|A| A.as_ref()).collect();
+			let lu = BlockTridiagLu::new(&diag, &lower, &upper);
+			assert!(all(lu.nblocks() == nblocks, lu.nrows() == n));
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e4 * (n as f64 + 1.0));
+			assert!(&dense * lu.solve(&rhs) ~ rhs);
+			assert!(dense.conjugate() * lu.solve_conjugate(&rhs) ~ rhs);
+			assert!(dense.transpose() * lu.solve_transpose(&rhs) ~ rhs);
+			assert!(dense.adjoint() * lu.solve_adjoint(&rhs) ~ rhs);
+		}
+	}
+}
@@ -561,10 +561,14 @@ macro_rules! concat {
 /// helper utilities
 pub mod utils;
 
+/// banded matrix
+pub mod band;
 /// diagonal matrix
 pub mod diag;
 /// rectangular matrix
 pub mod mat;
+/// packed triangular and hermitian matrix
+pub mod packed;
 /// permutation matrix
 pub mod perm;
 
@@ -1,3 +1,4 @@
+use crate::assert;
 use crate::internal_prelude::*;
 use pulp::Simd;
 
@@ -377,7 +378,9 @@ impl<T: ComplexField> RankRUpdate<'_, T> {
 					let alpha_conj_p = *alpha * conj(*p);
 					let new_d = abs2(real(*d)) + real(mul(alpha_conj_p, *p));
 
-					if new_d <= zero() {
+					// written so that a nan pivot is rejected
+					#[allow(clippy::neg_cmp_op_on_partial_ord)]
+					if !(new_d > zero()) {
 						return Err(LltError::NonPositivePivot { index: j });
 					}
 
@@ -430,6 +433,121 @@ pub fn rank_r_update_clobber<T: ComplexField>(cholesky_factors: MatMut<'_, T>, w
 	.run()
 }
 
+/// computes the size and alignment of required workspace for inserting a row and a column into
+/// the factor of a matrix of dimension `dim`
+pub fn insert_row_col_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	_ = dim;
+	_ = par;
+	temp_mat_scratch::<T>(1, 1)
+}
+
+/// computes the size and alignment of required workspace for deleting a row and a column from the
+/// factor of a matrix of dimension `dim`
+pub fn delete_row_col_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	_ = par;
+	StackReq::all_of(&[temp_mat_scratch::<T>(dim, 1), temp_mat_scratch::<T>(1, 1)])
+}
+
+/// updates the cholesky factor of $A$ to that of the matrix obtained by inserting a row and a
+/// column at `index`, with the new column of the matrix given by `inserted_col`
+///
+/// on input, the top left $(n - 1) \times (n - 1)$ block of `cholesky_factors` contains the factor
+/// of $A$. `inserted_col` is used as workspace
+///
+/// returns an error if the new matrix is not numerically positive definite, in which case the
+/// contents of `cholesky_factors` are unspecified
+#[math]
+#[track_caller]
+pub fn insert_row_col_clobber<T: ComplexField>(
+	cholesky_factors: MatMut<'_, T>,
+	index: usize,
+	inserted_col: ColMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), LltError> {
+	let n = cholesky_factors.nrows();
+	let k = index;
+	assert!(all(cholesky_factors.ncols() == n, k < n, inserted_col.nrows() == n));
+
+	let mut L = cholesky_factors;
+	let mut a = inserted_col;
+
+	for j in (k..n - 1).rev() {
+		crate::perm::swap_cols_idx(L.rb_mut(), j, j + 1);
+		crate::perm::swap_rows_idx(L.rb_mut(), j, j + 1);
+	}
+	L.rb_mut().row_mut(k).fill(zero());
+	L.rb_mut().col_mut(k).fill(zero());
+
+	let (mut a1, a) = a.rb_mut().split_at_row_mut(k);
+	let (alpha, mut a2) = a.split_at_row_mut(1);
+
+	// the new row of L is the solution of L11 x = a1
+	linalg::triangular_solve::solve_lower_triangular_in_place(L.rb().get(..k, ..k), a1.rb_mut().as_mat_mut(), par);
+	let d = real(alpha[0]) - a1.squared_norm_l2();
+	// written so that a nan pivot is rejected
+	#[allow(clippy::neg_cmp_op_on_partial_ord)]
+	if !(d > zero()) {
+		return Err(LltError::NonPositivePivot { index: k });
+	}
+	let d = sqrt(d);
+
+	L.rb_mut().row_mut(k).get_mut(..k).copy_from(a1.rb().adjoint());
+	L[(k, k)] = from_real(d);
+
+	linalg::matmul::matmul(
+		a2.rb_mut().as_mat_mut(),
+		Accum::Add,
+		L.rb().get(k + 1.., ..k),
+		a1.rb().as_mat(),
+		-one::<T>(),
+		par,
+	);
+	let d_inv = recip(d);
+	z!(a2.rb_mut()).for_each(|uz!(x)| *x = mul_real(*x, d_inv));
+	L.rb_mut().col_mut(k).get_mut(k + 1..).copy_from(a2.rb());
+
+	let (mut alpha, _) = unsafe { temp_mat_uninit::<T, _, _>(1, 1, stack) };
+	let mut alpha = alpha.as_mat_mut();
+	alpha[(0, 0)] = -one::<T>();
+
+	rank_r_update_clobber(L.get_mut(k + 1.., k + 1..), a2.as_mat_mut(), alpha.col_mut(0).as_diagonal_mut())
+		.map_err(|LltError::NonPositivePivot { index }| LltError::NonPositivePivot { index: index + k + 1 })
+}
+
+/// updates the cholesky factor of $A$ to that of the matrix obtained by deleting the row and the
+/// column at `index`
+///
+/// on output, the top left $(n - 1) \times (n - 1)$ block of `cholesky_factors` contains the new
+/// factor, and its last row and column are set to zero
+#[math]
+#[track_caller]
+pub fn delete_row_col_clobber<T: ComplexField>(cholesky_factors: MatMut<'_, T>, index: usize, par: Par, stack: &mut MemStack) {
+	let n = cholesky_factors.nrows();
+	let k = index;
+	assert!(all(cholesky_factors.ncols() == n, k < n));
+	_ = par;
+
+	let mut L = cholesky_factors;
+
+	let (mut w, stack) = unsafe { temp_mat_uninit::<T, _, _>(n - k - 1, 1, stack) };
+	let (mut alpha, _) = unsafe { temp_mat_uninit::<T, _, _>(1, 1, stack) };
+	let mut w = w.as_mat_mut();
+	let mut alpha = alpha.as_mat_mut();
+	w.copy_from(L.rb().get(k + 1.., k..k + 1));
+	alpha[(0, 0)] = one();
+
+	// the update adds a positive semidefinite term, so it can not fail
+	_ = rank_r_update_clobber(L.rb_mut().get_mut(k + 1.., k + 1..), w, alpha.col_mut(0).as_diagonal_mut());
+
+	for j in k..n - 1 {
+		crate::perm::swap_cols_idx(L.rb_mut(), j, j + 1);
+		crate::perm::swap_rows_idx(L.rb_mut(), j, j + 1);
+	}
+	L.rb_mut().row_mut(n - 1).fill(zero());
+	L.rb_mut().col_mut(n - 1).fill(zero());
+}
+
 #[cfg(test)]
 mod tests {
 	use dyn_stack::MemBuffer;
@@ -509,4 +627,74 @@ mod tests {
 			}
 		}
 	}
+
+	fn factor(A: MatRef<'_, c64>) -> Mat<c64> {
+		let dim = A.nrows();
+		let mut L = A.to_owned();
+		linalg::cholesky::llt::factor::cholesky_in_place(
+			L.as_mut(),
+			default(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(linalg::cholesky::llt::factor::cholesky_in_place_scratch::<c64>(
+				dim,
+				Par::Seq,
+				default(),
+			))),
+			default(),
+		)
+		.unwrap();
+		L
+	}
+
+	fn reconstruct(L: MatRef<'_, c64>) -> Mat<c64> {
+		let dim = L.nrows();
+		let L = Mat::from_fn(dim, dim, |i, j| if i >= j { L[(i, j)] } else { c64::ZERO });
+		&L * L.adjoint()
+	}
+
+	#[test]
+	fn test_insert_delete_row_col() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		let approx_eq = CwiseMat(ApproxEq {
+			abs_tol: 1e-12,
+			rel_tol: 1e-12,
+		});
+
+		for n in [1, 2, 4, 8, 15] {
+			let B = CwiseMatDistribution {
+				nrows: n + 1,
+				ncols: n + 1,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let B = &B * B.adjoint() + Mat::<c64>::identity(n + 1, n + 1);
+
+			for k in 0..=n {
+				let A = Mat::from_fn(n, n, |i, j| B[(i + (i >= k) as usize, j + (j >= k) as usize)]);
+
+				let mut L = Mat::zeros(n + 1, n + 1);
+				L.get_mut(..n, ..n).copy_from(factor(A.as_ref()));
+				let mut col = B.col(k).to_owned();
+				insert_row_col_clobber(
+					L.as_mut(),
+					k,
+					col.as_mut(),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(insert_row_col_scratch::<c64>(n + 1, Par::Seq))),
+				)
+				.unwrap();
+				assert!(reconstruct(L.as_ref()) ~ B);
+
+				let mut L = factor(B.as_ref());
+				delete_row_col_clobber(
+					L.as_mut(),
+					k,
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(delete_row_col_scratch::<c64>(n + 1, Par::Seq))),
+				);
+				assert!(reconstruct(L.get(..n, ..n)) ~ A);
+			}
+		}
+	}
 }
@@ -0,0 +1,370 @@
+//! balancing of a general square matrix, similarly to lapack `gebal` and `gebak`
+//!
+//! balancing computes a similarity transformation $B = D^{-1} P A P^\top D$, where $P$ is a
+//! permutation that isolates eigenvalues whenever possible, and $D$ is a diagonal matrix whose
+//! entries are powers of two, chosen so that the rows and columns of $B$ have comparable norms.
+//! the eigenvalues of $B$ are those of $A$, but they can usually be computed more accurately
+
+use crate::assert;
+use crate::internal_prelude::*;
+
+/// which transformations should be applied when balancing a matrix
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BalanceJob {
+	/// do not balance the matrix
+	None,
+	/// only permute the matrix to isolate eigenvalues
+	Permute,
+	/// only scale the matrix
+	Scale,
+	/// permute the matrix to isolate eigenvalues, then scale the remaining block
+	PermuteAndScale,
+}
+
+/// balancing error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BalanceError {
+	/// the matrix contains a nan
+	Nan,
+}
+
+/// information about the result of the balancing
+#[derive(Copy, Clone, Debug)]
+pub struct BalanceInfo {
+	/// start of the block that was not isolated by the permutation
+	///
+	/// the balanced matrix is upper triangular outside of the diagonal block
+	/// `ilo..ihi`
+	pub ilo: usize,
+	/// end of the block that was not isolated by the permutation
+	pub ihi: usize,
+}
+
+/// balances the matrix $A$ in place, overwriting it with $B = D^{-1} P A P^\top D$
+///
+/// the diagonal entries of $D$ are stored in `scale`, and the permutation $P$ is stored in `perm`
+/// and `perm_inv`, so that $(P A P^\top)_{ij} = A_{\text{perm}_i, \text{perm}_j}$
+///
+/// returns an error if a nan is encountered while scaling, in which case $A$ is left partially
+/// balanced
+#[math]
+#[track_caller]
+pub fn balance_in_place<'out, T: ComplexField>(
+	A: MatMut<'_, T>,
+	job: BalanceJob,
+	scale: DiagMut<'_, T::Real>,
+	perm: &'out mut [usize],
+	perm_inv: &'out mut [usize],
+) -> Result<(BalanceInfo, PermRef<'out, usize>), BalanceError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, scale.dim() == n, perm.len() == n, perm_inv.len() == n));
+
+	let mut A = A;
+	let mut scale = scale.column_vector_mut();
+
+	for i in 0..n {
+		perm[i] = i;
+		scale[i] = one();
+	}
+
+	let mut ilo = 0;
+	let mut ihi = n;
+
+	if job == BalanceJob::Permute || job == BalanceJob::PermuteAndScale {
+		// push the rows isolating an eigenvalue to the bottom
+		let mut j = ihi;
+		while j > 0 {
+			j -= 1;
+
+			let mut isolated = true;
+			for i in ilo..ihi {
+				if i != j && A[(j, i)] != zero() {
+					isolated = false;
+					break;
+				}
+			}
+
+			if isolated {
+				ihi -= 1;
+				swap(A.rb_mut(), perm, j, ihi);
+				if ihi == 0 {
+					break;
+				}
+				j = ihi;
+			}
+		}
+
+		// push the columns isolating an eigenvalue to the left
+		let mut j = ilo;
+		while j < ihi {
+			let mut isolated = true;
+			for i in ilo..ihi {
+				if i != j && A[(i, j)] != zero() {
+					isolated = false;
+					break;
+				}
+			}
+
+			if isolated {
+				swap(A.rb_mut(), perm, j, ilo);
+				ilo += 1;
+				j = ilo;
+			} else {
+				j += 1;
+			}
+		}
+	}
+
+	if job == BalanceJob::Scale || job == BalanceJob::PermuteAndScale {
+		let two = from_f64::<T::Real>(2.0);
+		let half = from_f64::<T::Real>(0.5);
+		let factor = from_f64::<T::Real>(0.95);
+
+		let sfmin1 = min_positive::<T::Real>() / (eps::<T::Real>() * two);
+		let sfmax1 = recip(sfmin1);
+		let sfmin2 = sfmin1 * two;
+		let sfmax2 = recip(sfmin2);
+
+		loop {
+			let mut converged = true;
+
+			for i in ilo..ihi {
+				let mut c = A.rb().col(i).subrows(ilo, ihi - ilo).norm_l2();
+				let mut r = A.rb().row(i).subcols(ilo, ihi - ilo).norm_l2();
+
+				let mut ca = zero::<T::Real>();
+				for k in 0..ihi {
+					ca = max(ca, abs(A[(k, i)]));
+				}
+				let mut ra = zero::<T::Real>();
+				for k in ilo..n {
+					ra = max(ra, abs(A[(i, k)]));
+				}
+
+				// the scaling iterations below would never converge on a nan, so report it as lapack's `gebal` does
+				if is_nan(c + ca + r + ra) {
+					return Err(BalanceError::Nan);
+				}
+
+				if c == zero() || r == zero() {
+					continue;
+				}
+
+				let s = c + r;
+				let mut f = one::<T::Real>();
+
+				let mut g = r * half;
+				while c < g && max(max(f, c), ca) < sfmax2 && min(min(r, g), ra) > sfmin2 {
+					f = f * two;
+					c = c * two;
+					ca = ca * two;
+					r = r * half;
+					g = g * half;
+					ra = ra * half;
+				}
+
+				g = c * half;
+				while g >= r && max(r, ra) < sfmax2 && min(min(f, c), min(g, ca)) > sfmin2 {
+					f = f * half;
+					c = c * half;
+					g = g * half;
+					ca = ca * half;
+					r = r * two;
+					ra = ra * two;
+				}
+
+				// only accept the scaling if it significantly reduces the norm
+				if c + r >= factor * s {
+					continue;
+				}
+				if f < one() && scale[i] < one() && f * scale[i] <= sfmin1 {
+					continue;
+				}
+				if f > one() && scale[i] > one() && scale[i] >= sfmax1 / f {
+					continue;
+				}
+
+				let g = recip(f);
+				scale[i] = scale[i] * f;
+				converged = false;
+
+				for k in 0..n {
+					A[(i, k)] = mul_real(A[(i, k)], g);
+				}
+				for k in 0..n {
+					A[(k, i)] = mul_real(A[(k, i)], f);
+				}
+			}
+
+			if converged {
+				break;
+			}
+		}
+	}
+
+	for i in 0..n {
+		perm_inv[perm[i]] = i;
+	}
+
+	Ok((BalanceInfo { ilo, ihi }, unsafe { PermRef::new_unchecked(perm, perm_inv, n) }))
+}
+
+fn swap<T: ComplexField>(A: MatMut<'_, T>, perm: &mut [usize], i: usize, j: usize) {
+	if i != j {
+		let mut A = A;
+		crate::perm::swap_rows_idx(A.rb_mut(), i, j);
+		crate::perm::swap_cols_idx(A.rb_mut(), i, j);
+		perm.swap(i, j);
+	}
+}
+
+/// computes the size and alignment of the workspace required to transform the eigenvectors of a
+/// balanced matrix back to those of the original matrix
+pub fn undo_balance_in_place_scratch<T: ComplexField>(dim: usize, ncols: usize) -> StackReq {
+	crate::perm::permute_rows_in_place_scratch::<usize, T>(dim, ncols)
+}
+
+/// transforms the right eigenvectors $V$ of the balanced matrix $B = D^{-1} P A P^\top D$ into
+/// those of $A$, overwriting $V$ with $P^\top D V$
+#[math]
+#[track_caller]
+pub fn undo_balance_right_in_place<T: ComplexField>(V: MatMut<'_, T>, scale: DiagRef<'_, T::Real>, perm: PermRef<'_, usize>, stack: &mut MemStack) {
+	let n = V.nrows();
+	assert!(all(scale.dim() == n, perm.len() == n));
+
+	let mut V = V;
+	let scale = scale.column_vector();
+	for j in 0..V.ncols() {
+		for i in 0..n {
+			V[(i, j)] = mul_real(V[(i, j)], scale[i]);
+		}
+	}
+	crate::perm::permute_rows_in_place(V, perm.inverse(), stack);
+}
+
+/// transforms the left eigenvectors $V$ of the balanced matrix $B = D^{-1} P A P^\top D$ into
+/// those of $A$, overwriting $V$ with $P^\top D^{-1} V$
+#[math]
+#[track_caller]
+pub fn undo_balance_left_in_place<T: ComplexField>(V: MatMut<'_, T>, scale: DiagRef<'_, T::Real>, perm: PermRef<'_, usize>, stack: &mut MemStack) {
+	let n = V.nrows();
+	assert!(all(scale.dim() == n, perm.len() == n));
+
+	let mut V = V;
+	let scale = scale.column_vector();
+	for j in 0..V.ncols() {
+		for i in 0..n {
+			V[(i, j)] = mul_real(V[(i, j)], recip(scale[i]));
+		}
+	}
+	crate::perm::permute_rows_in_place(V, perm.inverse(), stack);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	#[test]
+	fn test_balance() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [1, 2, 5, 20, 50] {
+			let mut A = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			// bad scaling, and a few isolated eigenvalues
+			for j in 0..n {
+				for i in 0..n {
+					A[(i, j)] *= 2.0f64.powi(i as i32 * 3 - j as i32 * 2);
+				}
+			}
+			if n > 2 {
+				for j in 0..n {
+					if j != 1 {
+						A[(1, j)] = c64::ZERO;
+					}
+					if j != n - 2 {
+						A[(j, n - 2)] = c64::ZERO;
+					}
+				}
+			}
+
+			let mut B = A.clone();
+			let mut scale = Diag::<f64>::zeros(n);
+			let perm = &mut *vec![0usize; n];
+			let perm_inv = &mut *vec![0usize; n];
+			let (info, P) = balance_in_place(B.as_mut(), BalanceJob::PermuteAndScale, scale.as_mut(), perm, perm_inv).unwrap();
+
+			if n > 2 {
+				assert!(all(info.ilo >= 1, info.ihi <= n - 1));
+			}
+			for j in 0..info.ilo {
+				for i in j + 1..n {
+					assert!(B[(i, j)] == c64::ZERO);
+				}
+			}
+			for i in info.ihi..n {
+				for j in 0..i {
+					assert!(B[(i, j)] == c64::ZERO);
+				}
+			}
+
+			// scaling by powers of two is exact
+			let PA = Mat::from_fn(n, n, |i, j| A[(P.arrays().0[i], P.arrays().0[j])]);
+			let DBD = Mat::from_fn(n, n, |i, j| B[(i, j)] * scale[i] / scale[j]);
+			assert!(DBD == PA);
+
+			let approx_eq = CwiseMat(ApproxEq::eps());
+
+			let mut V = Mat::<c64>::identity(n, n);
+			undo_balance_right_in_place(
+				V.as_mut(),
+				scale.as_ref(),
+				P,
+				MemStack::new(&mut MemBuffer::new(undo_balance_in_place_scratch::<c64>(n, n))),
+			);
+			let mut W = Mat::<c64>::identity(n, n);
+			undo_balance_left_in_place(
+				W.as_mut(),
+				scale.as_ref(),
+				P,
+				MemStack::new(&mut MemBuffer::new(undo_balance_in_place_scratch::<c64>(n, n))),
+			);
+
+			// V = P^T D and W = P^T D^{-1}, so that A V = V B and W^H A = B W^H
+			assert!(&W.adjoint() * &V ~ Mat::<c64>::identity(n, n));
+			assert!(&A * &V ~ &V * &B);
+			assert!(W.adjoint() * &A ~ &B * W.adjoint());
+		}
+	}
+
+	#[test]
+	fn test_balance_nan() {
+		let n = 4;
+		let mut A = Mat::from_fn(n, n, |i, j| if i == j { 2.0 } else { 1.0 / (1.0 + i as f64 + j as f64) });
+		A[(2, 1)] = f64::NAN;
+
+		for job in [BalanceJob::Scale, BalanceJob::PermuteAndScale] {
+			let mut B = A.clone();
+			let mut scale = Diag::<f64>::zeros(n);
+			let perm = &mut *vec![0usize; n];
+			let perm_inv = &mut *vec![0usize; n];
+			assert!(balance_in_place(B.as_mut(), job, scale.as_mut(), perm, perm_inv).unwrap_err() == BalanceError::Nan);
+		}
+
+		// permuting alone does not inspect the values beyond comparing them to zero
+		let mut B = A.clone();
+		let mut scale = Diag::<f64>::zeros(n);
+		let perm = &mut *vec![0usize; n];
+		let perm_inv = &mut *vec![0usize; n];
+		assert!(balance_in_place(B.as_mut(), BalanceJob::Permute, scale.as_mut(), perm, perm_inv).is_ok());
+	}
+}
@@ -11,9 +11,10 @@
 //!
 //! if $A$ is self-adjoint, then $U$ can be made unitary ($U^{-1} = U^H$), and $S$ is real valued
 
+/// matrix balancing
+pub mod balance;
 /// hessenberg decomposition
 pub mod hessenberg;
-#[doc(hidden)]
 pub mod schur;
 
 /// self-adjoint tridiagonalization
@@ -22,6 +23,7 @@ pub(crate) mod tridiag_evd;
 
 use crate::assert;
 use crate::internal_prelude::*;
+use balance::BalanceJob;
 use hessenberg::HessenbergParams;
 use linalg::matmul::triangular::BlockStructure;
 use schur::SchurParams;
@@ -52,6 +54,11 @@ pub struct EvdParams {
 	pub schur: SchurParams,
 	/// eigendecomposition from schur conversion parameters
 	pub evd_from_schur: EvdFromSchurParams,
+	/// balancing applied to the matrix before computing its eigendecomposition
+	///
+	/// the eigenvectors are transformed back to those of the original matrix. if the matrix is
+	/// scaled, the condition numbers are those of the balanced matrix
+	pub balance: BalanceJob,
 
 	#[doc(hidden)]
 	pub non_exhaustive: NonExhaustive,
@@ -75,6 +82,7 @@ impl<T: ComplexField> Auto<T> for EvdParams {
 			hessenberg: auto!(T),
 			schur: auto!(T),
 			evd_from_schur: auto!(T),
+			balance: BalanceJob::None,
 			non_exhaustive: NonExhaustive(()),
 		}
 	}
@@ -107,6 +115,17 @@ pub enum ComputeEigenvectors {
 	Yes,
 }
 
+/// subset of the eigenvalues of a self-adjoint matrix to compute
+#[derive(Clone, Debug, PartialEq)]
+pub enum EigenvalueSelection<T> {
+	/// all the eigenvalues
+	All,
+	/// the eigenvalues with the given indices, when sorted in nondecreasing order
+	Indices(core::ops::Range<usize>),
+	/// the eigenvalues in the half-open interval $[a, b)$
+	Interval(T, T),
+}
+
 /// computes the size and alignment of the workspace required to compute a self-adjoint matrix's
 /// eigendecomposition
 #[math]
@@ -184,37 +203,27 @@ pub fn self_adjoint_evd<T: ComplexField>(
 	let (mut trid, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
 	let mut trid = trid.as_mat_mut();
 
-	trid.copy_from_triangular_lower(A);
-
 	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
 	let (mut householder, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs, n - 1, stack) };
 	let mut householder = householder.as_mat_mut();
 
-	{
-		tridiag::tridiag_in_place(trid.rb_mut(), householder.rb_mut(), par, stack, params.tridiag.into());
-	}
-
-	let trid = trid.rb();
-
 	let (mut diag, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(n, 1, stack) };
 	let (mut offdiag, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(n, 1, stack) };
 
 	let mut diag = diag.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
 	let mut offdiag = offdiag.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
 
-	for i in 0..n {
-		diag[i] = real(trid[(i, i)]);
-
-		if i + 1 < n {
-			if try_const! { T::IS_REAL } {
-				offdiag[i] = real(trid[(i + 1, i)]);
-			} else {
-				offdiag[i] = abs(trid[(i + 1, i)]);
-			}
-		} else {
-			offdiag[i] = zero();
-		}
-	}
+	self_adjoint_to_real_tridiag(
+		A,
+		trid.rb_mut(),
+		householder.rb_mut(),
+		diag.rb_mut(),
+		offdiag.rb_mut(),
+		par,
+		stack,
+		params.config,
+	);
+	let trid = trid.rb();
 
 	let mut s = s;
 	let mut u = match u {
@@ -243,6 +252,69 @@ pub fn self_adjoint_evd<T: ComplexField>(
 		tridiag_evd::divide_and_conquer::<T::Real>(diag.rb_mut(), offdiag.rb_mut(), u_evd.rb_mut(), par, stack, params.recursion_threshold)?;
 	}
 
+	eigenvectors_from_real_tridiag(trid, householder.rb(), u.rb_mut(), u_real.rb(), par, stack);
+
+	for i in 0..n {
+		s[i] = from_real(diag[i]);
+	}
+
+	Ok(())
+}
+
+/// reduces the self-adjoint matrix $A$, of which only the lower triangular half is accessed, to
+/// tridiagonal form, and stores the diagonal and the off-diagonal of a real symmetric tridiagonal
+/// matrix similar to it in `diag` and `offdiag`
+#[math]
+pub(crate) fn self_adjoint_to_real_tridiag<T: ComplexField>(
+	A: MatRef<'_, T>,
+	trid: MatMut<'_, T>,
+	householder: MatMut<'_, T>,
+	diag: ColMut<'_, T::Real, usize, ContiguousFwd>,
+	offdiag: ColMut<'_, T::Real, usize, ContiguousFwd>,
+	par: Par,
+	stack: &mut MemStack,
+	params: SelfAdjointEvdParams,
+) {
+	let n = A.nrows();
+	let mut trid = trid;
+	let mut diag = diag;
+	let mut offdiag = offdiag;
+
+	trid.copy_from_triangular_lower(A);
+	tridiag::tridiag_in_place(trid.rb_mut(), householder, par, stack, params.tridiag.into());
+
+	for i in 0..n {
+		diag[i] = real(trid[(i, i)]);
+
+		if i + 1 < n {
+			if try_const! { T::IS_REAL } {
+				offdiag[i] = real(trid[(i + 1, i)]);
+			} else {
+				offdiag[i] = abs(trid[(i + 1, i)]);
+			}
+		} else {
+			offdiag[i] = zero();
+		}
+	}
+}
+
+/// computes the eigenvectors $U$ of the matrix that was reduced by
+/// [`self_adjoint_to_real_tridiag`], given the eigenvectors of the real tridiagonal matrix
+///
+/// if `T` is real, `u` initially holds the eigenvectors of the tridiagonal matrix, otherwise they
+/// are read from `u_real`
+#[math]
+fn eigenvectors_from_real_tridiag<T: ComplexField>(
+	trid: MatRef<'_, T>,
+	householder: MatRef<'_, T>,
+	u: MatMut<'_, T>,
+	u_real: MatRef<'_, T::Real>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let n = trid.nrows();
+	let mut u = u;
+
 	if try_const! { !T::IS_REAL } {
 		let normalized = |x: T| {
 			if x == zero() { one() } else { mul_real(x, recip(abs(x))) }
@@ -258,8 +330,8 @@ pub fn self_adjoint_evd<T: ComplexField>(
 			x = normalized(trid[(i, i - 1)] * x);
 			scale[i] = copy(x);
 		}
-		for j in 0..n {
-			z!(u.rb_mut().col_mut(j), u_real.rb().col(j), scale.rb()).for_each(|uz!(u, real, scale)| {
+		for j in 0..u.ncols() {
+			z!(u.rb_mut().col_mut(j), u_real.col(j), scale.rb()).for_each(|uz!(u, real, scale)| {
 				*u = mul_real(*scale, *real);
 			});
 		}
@@ -267,17 +339,210 @@ pub fn self_adjoint_evd<T: ComplexField>(
 
 	linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
 		trid.submatrix(1, 0, n - 1, n - 1),
-		householder.rb(),
+		householder,
 		Conj::No,
-		u.rb_mut().subrows_mut(1, n - 1),
+		u.subrows_mut(1, n - 1),
 		par,
 		stack,
 	);
+}
 
-	for i in 0..n {
-		s[i] = from_real(diag[i]);
+/// computes the size and alignment of the workspace required to compute a subset of a
+/// self-adjoint matrix's eigenpairs, where at most `count` eigenpairs are computed
+#[math]
+pub fn self_adjoint_evd_select_scratch<T: ComplexField>(
+	dim: usize,
+	count: usize,
+	compute_u: ComputeEigenvectors,
+	par: Par,
+	params: Spec<SelfAdjointEvdParams, T>,
+) -> StackReq {
+	let n = dim;
+	let k = count;
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+
+	let partial = StackReq::all_of(&[
+		temp_mat_scratch::<T>(n, n),
+		temp_mat_scratch::<T>(bs, n),
+		temp_mat_scratch::<T::Real>(n, 1).array(2),
+		StackReq::any_of(&[
+			tridiag::tridiag_in_place_scratch::<T>(n, par, params.tridiag.into()),
+			evd_select_from_real_tridiag_scratch::<T>(n, k, compute_u),
+		]),
+	]);
+
+	if k == n {
+		StackReq::any_of(&[partial, self_adjoint_evd_scratch::<T>(n, compute_u, par, params)])
+	} else {
+		partial
+	}
+}
+
+/// computes the eigenpairs of the self-adjoint matrix $A$ given by `selection`
+///
+/// the eigenvalues are stored in $S$, and the eigenvectors in the columns of $U$ such that the
+/// eigenvalues are sorted in nondecreasing order. if the selection contains more than `s.dim()`
+/// eigenvalues, only the smallest ones are computed. returns the number of computed eigenpairs $k$,
+/// which are stored in the first $k$ entries of $S$ and the first $k$ columns of $U$
+///
+/// unless all the eigenvalues are selected, the eigenvalues are computed by bisection and the
+/// eigenvectors by inverse iteration on the tridiagonal form of $A$, which is cheaper than the full
+/// decomposition when few eigenpairs are needed. returns [`EvdError::NoConvergence`] if the inverse
+/// iteration fails to converge
+///
+/// only the lower triangular half of $A$ is accessed
+#[math]
+pub fn self_adjoint_evd_select<T: ComplexField>(
+	A: MatRef<'_, T>,
+	selection: EigenvalueSelection<T::Real>,
+	s: DiagMut<'_, T>,
+	u: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<SelfAdjointEvdParams, T>,
+) -> Result<usize, EvdError> {
+	let n = A.nrows();
+	let cap = s.dim();
+	assert!(all(A.nrows() == A.ncols(), cap <= n));
+	if let Some(u) = u.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == cap));
+	}
+	if let EigenvalueSelection::Indices(range) = &selection {
+		assert!(all(range.start <= range.end, range.end <= n));
+	}
+
+	let full = match &selection {
+		EigenvalueSelection::All => true,
+		EigenvalueSelection::Indices(range) => range.start == 0 && range.end == n,
+		EigenvalueSelection::Interval(..) => false,
+	};
+	if full && cap == n {
+		self_adjoint_evd(A, s, u, par, stack, params)?;
+		return Ok(n);
+	}
+
+	let s = s.column_vector_mut();
+	if n == 0 {
+		return Ok(0);
+	}
+
+	let (mut trid, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut trid = trid.as_mat_mut();
+
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+	let (mut householder, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs, n - 1, stack) };
+	let mut householder = householder.as_mat_mut();
+
+	let (mut diag, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(n, 1, stack) };
+	let (mut offdiag, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(n, 1, stack) };
+
+	let mut diag = diag.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+	let mut offdiag = offdiag.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+
+	self_adjoint_to_real_tridiag(
+		A,
+		trid.rb_mut(),
+		householder.rb_mut(),
+		diag.rb_mut(),
+		offdiag.rb_mut(),
+		par,
+		stack,
+		params.config,
+	);
+
+	let (lo, hi) = match selection {
+		EigenvalueSelection::All => (0, n),
+		EigenvalueSelection::Indices(range) => (range.start, range.end),
+		EigenvalueSelection::Interval(a, b) => interval_index_range(diag.rb(), offdiag.rb(), a, b),
+	};
+	let k = Ord::min(hi - lo, cap);
+
+	evd_select_from_real_tridiag(
+		trid.rb(),
+		householder.rb(),
+		diag.rb(),
+		offdiag.rb(),
+		lo,
+		s.subrows_mut(0, k),
+		u.map(|u| u.subcols_mut(0, k)),
+		par,
+		stack,
+	)?;
+
+	Ok(k)
+}
+
+/// returns the range of indices of the eigenvalues of the real tridiagonal matrix that lie in
+/// $[a, b)$
+pub(crate) fn interval_index_range<T: RealField>(diag: ColRef<'_, T, usize, ContiguousFwd>, offdiag: ColRef<'_, T, usize, ContiguousFwd>, a: T, b: T) -> (usize, usize) {
+	let lo = tridiag_evd::eigenvalue_count(diag, offdiag, a);
+	let hi = tridiag_evd::eigenvalue_count(diag, offdiag, b);
+	(lo, Ord::max(lo, hi))
+}
+
+pub(crate) fn evd_select_from_real_tridiag_scratch<T: ComplexField>(dim: usize, count: usize, compute_u: ComputeEigenvectors) -> StackReq {
+	let n = dim;
+	let k = count;
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+
+	let w = temp_mat_scratch::<T::Real>(k, 1);
+	if compute_u == ComputeEigenvectors::No {
+		w
+	} else {
+		StackReq::all_of(&[
+			w,
+			temp_mat_scratch::<T::Real>(n, if try_const! { T::IS_REAL } { 0 } else { k }),
+			StackReq::any_of(&[
+				tridiag_evd::inverse_iteration_scratch::<T::Real>(n),
+				temp_mat_scratch::<T>(n, 1),
+				linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(n - 1, bs, k),
+			]),
+		])
+	}
+}
+
+/// computes the eigenvalues with indices `first..first + s.nrows()` of the matrix that was reduced
+/// by [`self_adjoint_to_real_tridiag`] by bisection, and the corresponding eigenvectors by inverse
+/// iteration if `u` is provided
+#[math]
+pub(crate) fn evd_select_from_real_tridiag<T: ComplexField>(
+	trid: MatRef<'_, T>,
+	householder: MatRef<'_, T>,
+	diag: ColRef<'_, T::Real, usize, ContiguousFwd>,
+	offdiag: ColRef<'_, T::Real, usize, ContiguousFwd>,
+	first: usize,
+	s: ColMut<'_, T>,
+	u: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), EvdError> {
+	let n = diag.nrows();
+	let k = s.nrows();
+	let mut s = s;
+
+	let (mut w, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(k, 1, stack) };
+	let mut w = w.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+	tridiag_evd::bisection(diag, offdiag, first, w.rb_mut());
+
+	for i in 0..k {
+		s[i] = from_real(w[i]);
 	}
 
+	let Some(mut u) = u else {
+		return Ok(());
+	};
+
+	let (mut u_real, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(n, if try_const! { T::IS_REAL } { 0 } else { k }, stack) };
+	let mut u_real = u_real.as_mat_mut();
+	let u_evd = if try_const! { T::IS_REAL } {
+		unsafe { core::mem::transmute(u.rb_mut()) }
+	} else {
+		u_real.rb_mut()
+	};
+
+	tridiag_evd::inverse_iteration(diag, offdiag, w.rb(), u_evd, stack)?;
+	eigenvectors_from_real_tridiag(trid, householder, u.rb_mut(), u_real.rb(), par, stack);
+
 	Ok(())
 }
 
@@ -353,6 +618,109 @@ pub fn pseudoinverse_from_self_adjoint_evd_with_tolerance<T: ComplexField>(
 	}
 }
 
+/// computes the size and alignment of the workspace required to compute a function of a
+/// self-adjoint matrix, given the eigendecomposition
+pub fn self_adjoint_fn_from_evd_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	_ = par;
+	temp_mat_scratch::<T>(dim, dim)
+}
+
+/// computes the function $f(A) = U f(S) U^H$ of a self-adjoint matrix $A$, given its
+/// eigendecomposition factors $S$ and $U$, where the real scalar function $f$ is applied to each
+/// eigenvalue
+///
+/// the result is self-adjoint, and is stored in `out`
+#[math]
+#[track_caller]
+pub fn self_adjoint_fn_from_evd<T: ComplexField>(
+	out: MatMut<'_, T>,
+	s: ColRef<'_, T>,
+	u: MatRef<'_, T>,
+	f: impl FnMut(T::Real) -> T::Real,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let mut out = out;
+	let mut f = f;
+	let n = u.ncols();
+
+	assert!(all(u.nrows() == n, u.ncols() == n, s.nrows() == n, out.nrows() == n, out.ncols() == n));
+
+	let (mut fu, _) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut fu = fu.as_mat_mut();
+
+	for j in 0..n {
+		let fj = f(real(s[j]));
+		z!(fu.rb_mut().col_mut(j), u.col(j)).for_each(|uz!(dst, src)| *dst = mul_real(*src, fj));
+	}
+
+	linalg::matmul::triangular::matmul(
+		out.rb_mut(),
+		BlockStructure::TriangularLower,
+		Accum::Replace,
+		fu.rb(),
+		BlockStructure::Rectangular,
+		u.adjoint(),
+		BlockStructure::Rectangular,
+		one(),
+		par,
+	);
+
+	for j in 0..n {
+		for i in 0..j {
+			out[(i, j)] = conj(out[(j, i)]);
+		}
+	}
+}
+
+/// computes the size and alignment of the workspace required to compute a function of a
+/// self-adjoint matrix
+pub fn self_adjoint_fn_scratch<T: ComplexField>(dim: usize, par: Par, params: Spec<SelfAdjointEvdParams, T>) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(dim, dim),
+		temp_mat_scratch::<T>(dim, 1),
+		StackReq::any_of(&[
+			if dim == 0 {
+				StackReq::EMPTY
+			} else {
+				self_adjoint_evd_scratch::<T>(dim, ComputeEigenvectors::Yes, par, params)
+			},
+			self_adjoint_fn_from_evd_scratch::<T>(dim, par),
+		]),
+	])
+}
+
+/// computes the function $f(A)$ of a self-adjoint matrix $A$, where the real scalar function $f$
+/// is applied to each eigenvalue
+///
+/// only the lower triangular half of $A$ is accessed. the eigendecomposition is computed
+/// internally, and discarded afterwards. [`self_adjoint_fn_from_evd`] can be used instead to
+/// reuse an existing decomposition
+#[track_caller]
+pub fn self_adjoint_fn<T: ComplexField>(
+	out: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	f: impl FnMut(T::Real) -> T::Real,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<SelfAdjointEvdParams, T>,
+) -> Result<(), EvdError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, out.nrows() == n, out.ncols() == n));
+
+	let (mut u, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut s, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let mut u = u.as_mat_mut();
+	let mut s = s.as_mat_mut();
+
+	if n > 0 {
+		self_adjoint_evd(A, s.rb_mut().col_mut(0).as_diagonal_mut(), Some(u.rb_mut()), par, stack, params)?;
+	}
+	self_adjoint_fn_from_evd(out, s.rb().col(0), u.rb(), f, par, stack);
+
+	Ok(())
+}
+
 #[math]
 fn dot2x1<T: RealField>(lhs0: RowRef<'_, T>, lhs1: RowRef<'_, T>, rhs: ColRef<'_, T>) -> (T, T) {
 	let n = rhs.nrows();
@@ -806,81 +1174,268 @@ fn solve_shifted_upper_triangular_system<T: ComplexField>(
 	}
 }
 
-/// computes the size and alignment of the workspace required to compute a matrix's
-/// eigendecomposition
-pub fn evd_scratch<T: ComplexField>(
-	dim: usize,
-	eigen_left: ComputeEigenvectors,
-	eigen_right: ComputeEigenvectors,
-	par: Par,
-	params: Spec<EvdParams, T>,
-) -> StackReq {
-	let n = dim;
+/// converts the standardized real schur form $A$ to a complex upper triangular schur form $T$, by
+/// triangularizing each $2\times 2$ diagonal block with a unitary rotation
+///
+/// the eigenvalue with positive imaginary part of each pair is placed first
+#[math]
+fn real_schur_to_cplx<T: RealField>(A: MatRef<'_, T>, T: MatMut<'_, Complex<T>>) {
+	let n = A.nrows();
+	let mut T = T;
 
-	if n == 0 {
-		return StackReq::EMPTY;
+	for j in 0..n {
+		for i in 0..n {
+			T[(i, j)] = if i <= j + 1 { Complex::new(copy(A[(i, j)]), zero()) } else { zero() };
+		}
 	}
 
-	let compute_eigen = eigen_left == ComputeEigenvectors::Yes || eigen_right == ComputeEigenvectors::Yes;
-	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n - 1, n - 1);
+	let mut k = 0;
+	while k < n {
+		if k + 1 == n || A[(k + 1, k)] == zero() {
+			if k + 1 < n {
+				T[(k + 1, k)] = zero();
+			}
+			k += 1;
+			continue;
+		}
 
-	let H = temp_mat_scratch::<T>(n, n);
-	let X = H;
-	let Z = temp_mat_scratch::<T>(n, if compute_eigen { n } else { 0 });
-	let householder = temp_mat_scratch::<T>(bs, n);
-	let apply = linalg::householder::apply_block_householder_sequence_on_the_right_in_place_scratch::<T>(n - 1, bs, n - 1);
+		let p = copy(A[(k, k)]);
+		let b = copy(A[(k, k + 1)]);
+		let c = copy(A[(k + 1, k)]);
+		let q = sqrt(abs(b)) * sqrt(abs(c));
 
-	StackReq::all_of(&[
-		H,
-		Z,
+		// eigenvector of [p b; c p] associated with p + iq
+		let (v0, v1) = if abs(b) >= abs(c) {
+			(Complex::new(copy(b), zero()), Complex::new(zero(), copy(q)))
+		} else {
+			(Complex::new(zero(), copy(q)), Complex::new(copy(c), zero()))
+		};
+		let norm = recip(hypot(abs(v0), abs(v1)));
+		let u0 = mul_real(v0, norm);
+		let u1 = mul_real(v1, norm);
+
+		// T := G^H T G, with G = [u0 -conj(u1); u1 conj(u0)]
+		for j in k..n {
+			let r0 = copy(T[(k, j)]);
+			let r1 = copy(T[(k + 1, j)]);
+			T[(k, j)] = conj(u0) * r0 + conj(u1) * r1;
+			T[(k + 1, j)] = u0 * r1 - u1 * r0;
+		}
+		for i in 0..k + 2 {
+			let c0 = copy(T[(i, k)]);
+			let c1 = copy(T[(i, k + 1)]);
+			T[(i, k)] = c0 * u0 + c1 * u1;
+			T[(i, k + 1)] = c1 * conj(u0) - c0 * conj(u1);
+		}
+
+		T[(k, k)] = Complex::new(copy(p), copy(q));
+		T[(k + 1, k + 1)] = Complex::new(p, -q);
+		T[(k + 1, k)] = zero();
+
+		k += 2;
+	}
+}
+
+fn condition_from_cplx_schur_scratch<T: ComplexField>(dim: usize) -> StackReq {
+	temp_mat_scratch::<T>(dim, dim)
+		.array(2)
+		.and(linalg::norm_est::norm1_est_scratch::<T>(dim.saturating_sub(1)))
+}
+
+/// computes the reciprocal condition numbers of the eigenvalues and eigenvectors of the upper
+/// triangular matrix $A$, similarly to lapack `trsna`
+///
+/// the condition numbers are invariant under unitary similarity, so they are also those of any
+/// matrix with complex schur form $A$
+#[math]
+fn condition_from_cplx_schur_imp<T: ComplexField>(
+	A: MatRef<'_, T>,
+	s: ColMut<'_, T::Real>,
+	sep: ColMut<'_, T::Real>,
+	par: Par,
+	stack: &mut MemStack,
+	params: EvdFromSchurParams,
+) {
+	let n = A.nrows();
+	if n == 0 {
+		return;
+	}
+
+	let mut s = s;
+	let mut sep = sep;
+
+	let (mut X, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut X = X.as_mat_mut();
+	let mut W = W.as_mat_mut();
+
+	// the right eigenvector x_k is supported on the rows [0, k], and the left eigenvector y_k on the
+	// rows [k, n), and both have a unit entry at row k. so y_k^H x_k = 1 and
+	// s_k = 1 / (|x_k| |y_k|)
+	evd_from_cplx_schur_imp(A, Conj::No, X.rb_mut(), par, params);
+	for k in 0..n {
+		s[k] = X.rb().col(k).subrows(0, k + 1).norm_l2();
+	}
+
+	let mut Y = X.rb_mut().reverse_rows_mut();
+	evd_from_cplx_schur_imp(A.transpose().reverse_rows_and_cols(), Conj::Yes, Y.rb_mut(), par, params);
+	for k in 0..n {
+		let norm = Y.rb().col(k).subrows(0, k + 1).norm_l2();
+		let k = n - 1 - k;
+		s[k] = recip(s[k] * norm);
+	}
+
+	if n == 1 {
+		sep[0] = abs(A[(0, 0)]);
+		return;
+	}
+
+	// sep_k is the smallest singular value of T22 - λ_k I, where T22 is the trailing block of the
+	// schur form after moving λ_k to the top left corner
+	for k in 0..n {
+		for j in 0..n {
+			for i in 0..n {
+				W[(i, j)] = if i <= j { copy(A[(i, j)]) } else { zero() };
+			}
+		}
+
+		let mut ilst = 0;
+		schur::complex_schur::schur_move(W.rb_mut(), None, k, &mut ilst);
+
+		let p = copy(W[(0, 0)]);
+		let T22 = W.rb().submatrix(1, 1, n - 1, n - 1);
+
+		let mut norm = zero::<T::Real>();
+		for j in 0..n - 1 {
+			for i in 0..j + 1 {
+				norm = norm + abs1(T22[(i, j)]);
+			}
+		}
+
+		let est = linalg::norm_est::norm1_est(
+			n - 1,
+			&mut |x: ColMut<'_, T>| {
+				solve_shifted_upper_triangular_system(T22, Conj::No, copy(p), x, copy(norm), par, params);
+			},
+			&mut |x: ColMut<'_, T>| {
+				solve_shifted_upper_triangular_system(
+					T22.transpose().reverse_rows_and_cols(),
+					Conj::Yes,
+					conj(p),
+					x.reverse_rows_mut(),
+					copy(norm),
+					par,
+					params,
+				);
+			},
+			stack,
+		);
+
+		sep[k] = recip(est);
+	}
+}
+
+/// computes the size and alignment of the workspace required to compute a matrix's
+/// eigendecomposition
+pub fn evd_scratch<T: ComplexField>(
+	dim: usize,
+	eigen_left: ComputeEigenvectors,
+	eigen_right: ComputeEigenvectors,
+	par: Par,
+	params: Spec<EvdParams, T>,
+) -> StackReq {
+	evd_scratch_imp::<T>(dim, eigen_left, eigen_right, false, par, params)
+}
+
+/// computes the size and alignment of the workspace required to compute a matrix's
+/// eigendecomposition, along with the condition numbers of its eigenvalues and eigenvectors
+pub fn evd_with_condition_scratch<T: ComplexField>(
+	dim: usize,
+	eigen_left: ComputeEigenvectors,
+	eigen_right: ComputeEigenvectors,
+	par: Par,
+	params: Spec<EvdParams, T>,
+) -> StackReq {
+	evd_scratch_imp::<T>(dim, eigen_left, eigen_right, true, par, params)
+}
+
+fn evd_scratch_imp<T: ComplexField>(
+	dim: usize,
+	eigen_left: ComputeEigenvectors,
+	eigen_right: ComputeEigenvectors,
+	condition: bool,
+	par: Par,
+	params: Spec<EvdParams, T>,
+) -> StackReq {
+	let n = dim;
+
+	if n == 0 {
+		return StackReq::EMPTY;
+	}
+
+	let compute_eigen = eigen_left == ComputeEigenvectors::Yes || eigen_right == ComputeEigenvectors::Yes;
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n - 1, n - 1);
+
+	let H = temp_mat_scratch::<T>(n, n);
+	let X = H;
+	let Z = temp_mat_scratch::<T>(n, if compute_eigen { n } else { 0 });
+	let householder = temp_mat_scratch::<T>(bs, n);
+	let apply = linalg::householder::apply_block_householder_sequence_on_the_right_in_place_scratch::<T>(n - 1, bs, n - 1);
+
+	let cond = if !condition {
+		StackReq::EMPTY
+	} else if try_const! { T::IS_REAL } {
+		temp_mat_scratch::<Complex<T::Real>>(n, n).and(condition_from_cplx_schur_scratch::<Complex<T::Real>>(n))
+	} else {
+		condition_from_cplx_schur_scratch::<T>(n)
+	};
+
+	let balancing = if params.balance == BalanceJob::None {
+		StackReq::EMPTY
+	} else {
+		temp_mat_scratch::<T::Real>(n, 1).and(StackReq::new::<usize>(n).array(2))
+	};
+
+	StackReq::all_of(&[
+		H,
+		Z,
+		balancing,
 		StackReq::any_of(&[
 			householder.and(hessenberg::hessenberg_in_place_scratch::<T>(n, bs, par, params.hessenberg.into()).or(apply)),
-			schur::multishift_qr_scratch::<T>(n, n, compute_eigen, compute_eigen, par, params.schur),
+			schur::multishift_qr_scratch::<T>(n, n, compute_eigen || condition, compute_eigen, par, params.schur),
 			X,
+			balance::undo_balance_in_place_scratch::<T>(n, n),
+			cond,
 		]),
 	])
 }
 
+/// computes the schur decomposition of the matrix stored in $H$, overwriting it with the
+/// (quasi-)triangular factor if `want_t` is set
+///
+/// $Z$ is overwritten with the accumulated unitary factor, if provided
 #[math]
-fn evd_imp<T: ComplexField>(
-	A: MatRef<'_, T>,
+fn schur_imp<T: ComplexField>(
+	H: MatMut<'_, T>,
+	Z: Option<MatMut<'_, T>>,
 	s: ColMut<'_, T>,
 	s_im: Option<ColMut<'_, T>>,
-	u_left: Option<MatMut<'_, T>>,
-	u_right: Option<MatMut<'_, T>>,
+	want_t: bool,
 	par: Par,
 	stack: &mut MemStack,
 	params: EvdParams,
 ) -> Result<(), EvdError> {
-	let n = A.nrows();
-
+	let n = H.nrows();
 	if n == 0 {
 		return Ok(());
 	}
 
-	for j in 0..n {
-		for i in 0..n {
-			if !is_finite(A[(i, j)]) {
-				return Err(EvdError::NoConvergence);
-			}
-		}
-	}
-
-	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n - 1, n - 1);
+	let mut H = H;
+	let mut Z = Z;
 	let mut s = s;
 	let mut s_im = s_im;
 
-	let (mut H, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
-	let (mut Z, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, if u_left.is_some() || u_right.is_some() { n } else { 0 }, stack) };
-
-	let mut H = H.as_mat_mut();
-	let mut Z = if u_left.is_some() || u_right.is_some() {
-		Some(Z.as_mat_mut())
-	} else {
-		None
-	};
-
-	H.copy_from(A);
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n - 1, n - 1);
 
 	{
 		let (mut householder, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs, n - 1, stack) };
@@ -909,9 +1464,9 @@ fn evd_imp<T: ComplexField>(
 		}
 	}
 
-	if try_const! { T::IS_REAL } {
+	let (info, _, _) = if try_const! { T::IS_REAL } {
 		schur::real_schur::multishift_qr::<T::Real>(
-			unsafe { core::mem::transmute(Z.is_some()) },
+			want_t,
 			unsafe { core::mem::transmute(H.rb_mut()) },
 			unsafe { core::mem::transmute(Z.rb_mut()) },
 			unsafe { core::mem::transmute(s.rb_mut()) },
@@ -921,14 +1476,84 @@ fn evd_imp<T: ComplexField>(
 			par,
 			stack,
 			params.schur,
-		);
+		)
 	} else {
-		schur::complex_schur::multishift_qr::<T>(Z.is_some(), H.rb_mut(), Z.rb_mut(), s.rb_mut(), 0, n, par, stack, params.schur);
+		schur::complex_schur::multishift_qr::<T>(want_t, H.rb_mut(), Z.rb_mut(), s.rb_mut(), 0, n, par, stack, params.schur)
+	};
+
+	if info != 0 {
+		return Err(EvdError::NoConvergence);
+	}
+
+	Ok(())
+}
+
+#[math]
+fn evd_imp<T: ComplexField>(
+	A: MatRef<'_, T>,
+	s: ColMut<'_, T>,
+	s_im: Option<ColMut<'_, T>>,
+	u_left: Option<MatMut<'_, T>>,
+	u_right: Option<MatMut<'_, T>>,
+	condition: Option<(ColMut<'_, T::Real>, ColMut<'_, T::Real>)>,
+	par: Par,
+	stack: &mut MemStack,
+	params: EvdParams,
+) -> Result<(), EvdError> {
+	let n = A.nrows();
+
+	if n == 0 {
+		return Ok(());
+	}
+
+	for j in 0..n {
+		for i in 0..n {
+			if !is_finite(A[(i, j)]) {
+				return Err(EvdError::NoConvergence);
+			}
+		}
 	}
 
+	let mut s = s;
+	let mut s_im = s_im;
+
+	let (mut H, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut Z, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, if u_left.is_some() || u_right.is_some() { n } else { 0 }, stack) };
+
+	let mut H = H.as_mat_mut();
+	let mut Z = if u_left.is_some() || u_right.is_some() {
+		Some(Z.as_mat_mut())
+	} else {
+		None
+	};
+
+	H.copy_from(A);
+
+	let balanced = params.balance != BalanceJob::None;
+	let (mut scale, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(if balanced { n } else { 0 }, 1, stack) };
+	let (mut perm, stack) = stack.make_with(if balanced { n } else { 0 }, |_| 0usize);
+	let (mut perm_inv, stack) = stack.make_with(if balanced { n } else { 0 }, |_| 0usize);
+	let mut scale = scale.as_mat_mut().col_mut(0).as_diagonal_mut();
+
+	let perm = if balanced {
+		// the balancing only fails on nans, which were rejected when checking that $A$ is finite
+		let (_, perm) = balance::balance_in_place(H.rb_mut(), params.balance, scale.rb_mut(), &mut perm, &mut perm_inv).unwrap();
+		Some(perm)
+	} else {
+		None
+	};
+	let scale = scale.rb();
+
+	let want_t = Z.is_some() || condition.is_some();
+	// non-convergence of the qr iteration is not reported by the eigendecomposition, which
+	// returns the partially converged result instead
+	let _ = schur_imp(H.rb_mut(), Z.rb_mut(), s.rb_mut(), s_im.rb_mut(), want_t, par, stack, params);
+
 	let H = H.rb();
+	let mut u_left = u_left;
+	let mut u_right = u_right;
 
-	if let (Some(mut u), Some(Z)) = (u_right, Z.rb()) {
+	if let (Some(mut u), Some(Z)) = (u_right.rb_mut(), Z.rb()) {
 		let (mut X, _) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
 		let mut X = X.as_mat_mut();
 
@@ -956,7 +1581,7 @@ fn evd_imp<T: ComplexField>(
 		);
 	}
 
-	if let (Some(mut u), Some(Z)) = (u_left, Z.rb()) {
+	if let (Some(mut u), Some(Z)) = (u_left.rb_mut(), Z.rb()) {
 		let (mut X, _) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
 		let mut X = X.as_mat_mut().reverse_rows_mut();
 
@@ -984,6 +1609,27 @@ fn evd_imp<T: ComplexField>(
 		);
 	}
 
+	if let Some(perm) = perm {
+		if let Some(u) = u_right {
+			balance::undo_balance_right_in_place(u, scale, perm, stack);
+		}
+		if let Some(u) = u_left {
+			balance::undo_balance_left_in_place(u, scale, perm, stack);
+		}
+	}
+
+	if let Some((s_cond, sep)) = condition {
+		if try_const! { T::IS_REAL } {
+			let (mut T, stack) = unsafe { temp_mat_uninit::<Complex<T::Real>, _, _>(n, n, stack) };
+			let mut T = T.as_mat_mut();
+
+			real_schur_to_cplx::<T::Real>(unsafe { core::mem::transmute(H) }, T.rb_mut());
+			condition_from_cplx_schur_imp::<Complex<T::Real>>(T.rb(), s_cond, sep, par, stack, params.evd_from_schur);
+		} else {
+			condition_from_cplx_schur_imp::<T>(H, s_cond, sep, par, stack, params.evd_from_schur);
+		}
+	}
+
 	Ok(())
 }
 
@@ -1010,7 +1656,7 @@ pub fn evd_cplx<T: RealField>(
 		assert!(all(u.nrows() == n, u.ncols() == n));
 	}
 
-	evd_imp(A, s.column_vector_mut(), None, u_left, u_right, par, stack, params.config)
+	evd_imp(A, s.column_vector_mut(), None, u_left, u_right, None, par, stack, params.config)
 }
 
 /// computes the matrix $A$'s eigendecomposition
@@ -1043,12 +1689,231 @@ pub fn evd_real<T: RealField>(
 		Some(s_im.column_vector_mut()),
 		u_left,
 		u_right,
+		None,
 		par,
 		stack,
 		params.config,
 	)
 }
 
+/// computes the matrix $A$'s eigendecomposition, along with the reciprocal condition numbers of
+/// its eigenvalues and eigenvectors, similarly to lapack `geevx`
+///
+/// the eigenvalues are stored in $S$, the left eigenvectors in $U_L$, and the right eigenvectors in
+/// $U_R$. for each eigenvalue $\lambda_i$ with left and right eigenvectors $y_i$ and $x_i$,
+/// `s_cond[i]` is set to $|y_i^H x_i| / (\|y_i\|_2 \|x_i\|_2)$, and `sep[i]` to an estimate of the
+/// separation $\sigma_{\min}(T_{22} - \lambda_i I)$, where $T_{22}$ is the trailing block of a
+/// schur form of $A$ whose leading entry is $\lambda_i$
+///
+/// the error in $\lambda_i$ is approximately $\varepsilon \|A\| / s_i$, and the angle between the
+/// computed and exact eigenvector $x_i$ is approximately $\varepsilon \|A\| / \text{sep}_i$
+#[track_caller]
+pub fn evd_cplx_with_condition<T: RealField>(
+	A: MatRef<'_, Complex<T>>,
+	s: DiagMut<'_, Complex<T>>,
+	u_left: Option<MatMut<'_, Complex<T>>>,
+	u_right: Option<MatMut<'_, Complex<T>>>,
+	s_cond: ColMut<'_, T>,
+	sep: ColMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<EvdParams, Complex<T>>,
+) -> Result<(), EvdError> {
+	let n = A.nrows();
+	assert!(all(A.nrows() == n, A.ncols() == n, s.dim() == n, s_cond.nrows() == n, sep.nrows() == n));
+	if let Some(u) = u_left.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+	if let Some(u) = u_right.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+
+	evd_imp(
+		A,
+		s.column_vector_mut(),
+		None,
+		u_left,
+		u_right,
+		Some((s_cond, sep)),
+		par,
+		stack,
+		params.config,
+	)
+}
+
+/// computes the matrix $A$'s eigendecomposition, along with the reciprocal condition numbers of
+/// its eigenvalues and eigenvectors, similarly to lapack `geevx`
+///
+/// the eigenvalues and eigenvectors are stored as in [`evd_real`], and the condition numbers as in
+/// [`evd_cplx_with_condition`]. both eigenvalues of a complex conjugate pair have the same condition
+/// numbers
+#[track_caller]
+pub fn evd_real_with_condition<T: RealField>(
+	A: MatRef<'_, T>,
+	s_re: DiagMut<'_, T>,
+	s_im: DiagMut<'_, T>,
+	u_left: Option<MatMut<'_, T>>,
+	u_right: Option<MatMut<'_, T>>,
+	s_cond: ColMut<'_, T>,
+	sep: ColMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<EvdParams, T>,
+) -> Result<(), EvdError> {
+	let n = A.nrows();
+	assert!(all(
+		A.nrows() == n,
+		A.ncols() == n,
+		s_re.dim() == n,
+		s_im.dim() == n,
+		s_cond.nrows() == n,
+		sep.nrows() == n,
+	));
+	if let Some(u) = u_left.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+	if let Some(u) = u_right.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+
+	evd_imp(
+		A,
+		s_re.column_vector_mut(),
+		Some(s_im.column_vector_mut()),
+		u_left,
+		u_right,
+		Some((s_cond, sep)),
+		par,
+		stack,
+		params.config,
+	)
+}
+
+/// computes the size and alignment of the workspace required to compute a matrix's schur
+/// decomposition
+pub fn schur_scratch<T: ComplexField>(dim: usize, par: Par, params: Spec<EvdParams, T>) -> StackReq {
+	let n = dim;
+
+	if n == 0 {
+		return StackReq::EMPTY;
+	}
+
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n - 1, n - 1);
+
+	let householder = temp_mat_scratch::<T>(bs, n);
+	let apply = linalg::householder::apply_block_householder_sequence_on_the_right_in_place_scratch::<T>(n - 1, bs, n - 1);
+
+	StackReq::any_of(&[
+		householder.and(hessenberg::hessenberg_in_place_scratch::<T>(n, bs, par, params.hessenberg.into()).or(apply)),
+		schur::multishift_qr_scratch::<T>(n, n, true, true, par, params.schur),
+	])
+}
+
+/// computes the matrix $A$'s complex schur decomposition $A = Z T Z^H$
+///
+/// $T$ is upper triangular and $Z$ is unitary. the eigenvalues are stored in $S$, in the same
+/// order as they appear on the diagonal of $T$
+///
+/// returns [`EvdError::NoConvergence`] if $A$ contains non-finite values, or if the qr iteration
+/// fails to converge
+#[track_caller]
+pub fn schur_cplx<T: ComplexField>(
+	A: MatRef<'_, T>,
+	t: MatMut<'_, T>,
+	z: Option<MatMut<'_, T>>,
+	s: DiagMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<EvdParams, T>,
+) -> Result<(), EvdError> {
+	let n = A.nrows();
+	assert!(all(A.nrows() == n, A.ncols() == n, t.nrows() == n, t.ncols() == n, s.dim() == n));
+	if let Some(z) = z.rb() {
+		assert!(all(z.nrows() == n, z.ncols() == n));
+	}
+
+	let mut t = t;
+	for j in 0..n {
+		for i in 0..n {
+			if !is_finite(&A[(i, j)]) {
+				return Err(EvdError::NoConvergence);
+			}
+		}
+	}
+
+	t.copy_from(A);
+	schur_imp(t.rb_mut(), z, s.column_vector_mut(), None, true, par, stack, params.config)?;
+
+	z!(t.rb_mut()).for_each_triangular_lower(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+	Ok(())
+}
+
+/// computes the matrix $A$'s real schur decomposition $A = Z T Z^\top$
+///
+/// $T$ is upper quasi-triangular with $1\times 1$ and $2\times 2$ diagonal blocks, the latter
+/// corresponding to pairs of complex conjugate eigenvalues, and $Z$ is orthogonal. the real and
+/// imaginary parts of the eigenvalues are stored in $S_{\text{re}}$ and $S_{\text{im}}$, in the
+/// same order as they appear on the diagonal of $T$
+///
+/// returns [`EvdError::NoConvergence`] if $A$ contains non-finite values, or if the qr iteration
+/// fails to converge
+#[track_caller]
+pub fn schur_real<T: RealField>(
+	A: MatRef<'_, T>,
+	t: MatMut<'_, T>,
+	z: Option<MatMut<'_, T>>,
+	s_re: DiagMut<'_, T>,
+	s_im: DiagMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<EvdParams, T>,
+) -> Result<(), EvdError> {
+	let n = A.nrows();
+	assert!(all(
+		A.nrows() == n,
+		A.ncols() == n,
+		t.nrows() == n,
+		t.ncols() == n,
+		s_re.dim() == n,
+		s_im.dim() == n,
+	));
+	if let Some(z) = z.rb() {
+		assert!(all(z.nrows() == n, z.ncols() == n));
+	}
+
+	let mut t = t;
+	let mut s_im = s_im;
+	for j in 0..n {
+		for i in 0..n {
+			if !is_finite(&A[(i, j)]) {
+				return Err(EvdError::NoConvergence);
+			}
+		}
+	}
+
+	t.copy_from(A);
+	schur_imp(
+		t.rb_mut(),
+		z,
+		s_re.column_vector_mut(),
+		Some(s_im.rb_mut().column_vector_mut()),
+		true,
+		par,
+		stack,
+		params.config,
+	)?;
+
+	for j in 0..n {
+		for i in j + 2..n {
+			t[(i, j)] = zero();
+		}
+		if j + 1 < n && s_im[j] == zero() {
+			t[(j + 1, j)] = zero();
+		}
+	}
+	Ok(())
+}
+
 #[cfg(test)]
 mod general_tests {
 	use super::*;
@@ -1205,6 +2070,366 @@ mod general_tests {
 			test_real_evd(mat.as_ref());
 		}
 	}
+
+	/// exact separation of the `k`-th diagonal entry of the upper triangular matrix $T$ from the
+	/// rest of its spectrum
+	fn sep_exact(t: MatRef<'_, c64>, k: usize) -> f64 {
+		let n = t.nrows();
+		let mut t = t.to_owned();
+		let mut select = vec![false; n];
+		select[k] = true;
+		schur::reorder_schur_cplx(t.as_mut(), None, &select);
+
+		let p = t[(0, 0)];
+		let M = Mat::from_fn(n - 1, n - 1, |i, j| if i == j { t[(i + 1, j + 1)] - p } else { t[(i + 1, j + 1)] });
+		M.singular_values().unwrap().into_iter().fold(f64::INFINITY, f64::min)
+	}
+
+	fn test_condition(
+		A: MatRef<'_, c64>,
+		s: ColRef<'_, c64>,
+		ul: MatRef<'_, c64>,
+		ur: MatRef<'_, c64>,
+		s_cond: ColRef<'_, f64>,
+		sep: ColRef<'_, f64>,
+	) {
+		use faer_traits::math_utils::abs;
+
+		let n = A.nrows();
+
+		let mut t = Mat::zeros(n, n);
+		let mut e = Diag::zeros(n);
+		schur_cplx(
+			A,
+			t.as_mut(),
+			None,
+			e.as_mut(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(schur_scratch::<c64>(n, Par::Seq, default()))),
+			default(),
+		)
+		.unwrap();
+
+		for i in 0..n {
+			let x = ur.col(i);
+			let y = ul.col(i);
+			let expected = abs(&(y.adjoint() * x)) / (x.norm_l2() * y.norm_l2());
+			assert!((s_cond[i] - expected).abs() < 1e-8 * expected);
+
+			if n == 1 {
+				assert!(sep[i] == abs(&A[(0, 0)]));
+			} else {
+				let mut k = 0;
+				for j in 0..n {
+					if abs(&(e[j] - s[i])) < abs(&(e[k] - s[i])) {
+						k = j;
+					}
+				}
+
+				// the 1-norm estimate of the inverse is within a small factor of its 2-norm
+				let exact = sep_exact(t.as_ref(), k);
+				let factor = 10.0 * (n as f64).sqrt();
+				assert!(sep[i] >= exact / factor);
+				assert!(sep[i] <= exact * factor);
+			}
+		}
+	}
+
+	// zeroes a row and a column of $M$ so that some of its eigenvalues are isolated, and the
+	// balancing permutes the matrix
+	fn isolate_eigenvalues<T: ComplexField>(M: &mut Mat<T>) {
+		let n = M.nrows();
+		if n > 2 {
+			for j in 0..n {
+				if j != 1 {
+					M[(1, j)] = zero();
+				}
+				if j != n - 2 {
+					M[(j, n - 2)] = zero();
+				}
+			}
+		}
+	}
+
+	// checks that `s`, `ul` and `ur` are the eigenvalues and the left and right eigenvectors of
+	// $A = D^{-1} M D$
+	fn check_balanced_evd(M: MatRef<'_, c64>, d: ColRef<'_, f64>, s: ColRef<'_, c64>, ul: MatRef<'_, c64>, ur: MatRef<'_, c64>) {
+		use faer_traits::math_utils::abs;
+
+		let n = M.nrows();
+
+		// the eigenvectors of M are x_M = D x_A and y_M = D^{-1} y_A
+		let xm = Mat::from_fn(n, n, |i, j| ur[(i, j)] * d[i]);
+		let ym = Mat::from_fn(n, n, |i, j| ul[(i, j)] / d[i]);
+
+		let tol = 1e-10 * M.norm_l2();
+		for j in 0..n {
+			let x = xm.col(j);
+			let y = ym.col(j);
+			assert!((M * x - x * Scale(s[j])).norm_l2() <= tol * x.norm_l2());
+			assert!((y.adjoint() * M - y.adjoint() * Scale(s[j])).norm_l2() <= tol * y.norm_l2());
+		}
+
+		// the eigenvalues match those of M
+		let e = M.eigenvalues().unwrap();
+		for j in 0..n {
+			let dist = e.iter().map(|e| abs(&(e - s[j]))).fold(f64::INFINITY, f64::min);
+			assert!(dist <= tol);
+		}
+	}
+
+	#[test]
+	fn test_balanced_evd() {
+		let rng = &mut StdRng::seed_from_u64(4);
+
+		let params_cplx = Spec::new(EvdParams {
+			balance: balance::BalanceJob::PermuteAndScale,
+			..auto!(c64)
+		});
+		let params_real = Spec::new(EvdParams {
+			balance: balance::BalanceJob::PermuteAndScale,
+			..auto!(f64)
+		});
+
+		for n in [1, 2, 5, 20, 50] {
+			for isolated in [false, true] {
+				// A = D^{-1} M D, with a badly scaled D whose entries are powers of two
+				let d = Col::from_fn(n, |i| 2.0f64.powi(((i * 37) % 61) as i32 - 30));
+
+				let mut M = CwiseMatDistribution {
+					nrows: n,
+					ncols: n,
+					dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+				}
+				.rand::<Mat<c64>>(rng);
+				if isolated {
+					isolate_eigenvalues(&mut M);
+				}
+				let A = Mat::from_fn(n, n, |i, j| M[(i, j)] * (d[j] / d[i]));
+
+				let mut s = Diag::zeros(n);
+				let mut ul = Mat::zeros(n, n);
+				let mut ur = Mat::zeros(n, n);
+				evd_cplx(
+					A.as_ref(),
+					s.as_mut(),
+					Some(ul.as_mut()),
+					Some(ur.as_mut()),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(evd_scratch::<c64>(
+						n,
+						ComputeEigenvectors::Yes,
+						ComputeEigenvectors::Yes,
+						Par::Seq,
+						params_cplx,
+					))),
+					params_cplx,
+				)
+				.unwrap();
+				check_balanced_evd(M.as_ref(), d.as_ref(), s.column_vector(), ul.as_ref(), ur.as_ref());
+
+				let mut M = CwiseMatDistribution {
+					nrows: n,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.rand::<Mat<f64>>(rng);
+				if isolated {
+					isolate_eigenvalues(&mut M);
+				}
+				let A = Mat::from_fn(n, n, |i, j| M[(i, j)] * (d[j] / d[i]));
+
+				let mut s_re = Diag::zeros(n);
+				let mut s_im = Diag::zeros(n);
+				let mut ul = Mat::zeros(n, n);
+				let mut ur = Mat::zeros(n, n);
+				evd_real(
+					A.as_ref(),
+					s_re.as_mut(),
+					s_im.as_mut(),
+					Some(ul.as_mut()),
+					Some(ur.as_mut()),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(evd_scratch::<f64>(
+						n,
+						ComputeEigenvectors::Yes,
+						ComputeEigenvectors::Yes,
+						Par::Seq,
+						params_real,
+					))),
+					params_real,
+				)
+				.unwrap();
+
+				// complex conjugate pairs are stored as the real and imaginary parts of the
+				// eigenvector associated with the first eigenvalue
+				let s = Col::from_fn(n, |j| c64::new(s_re[j], s_im[j]));
+				let to_cplx = |u: &Mat<f64>| {
+					let mut v = Mat::<c64>::zeros(n, n);
+					let mut j = 0;
+					while j < n {
+						if s_im[j] == 0.0 {
+							for i in 0..n {
+								v[(i, j)] = c64::new(u[(i, j)], 0.0);
+							}
+							j += 1;
+						} else {
+							for i in 0..n {
+								v[(i, j)] = c64::new(u[(i, j)], u[(i, j + 1)]);
+								v[(i, j + 1)] = c64::new(u[(i, j)], -u[(i, j + 1)]);
+							}
+							j += 2;
+						}
+					}
+					v
+				};
+				let M = Mat::from_fn(n, n, |i, j| c64::new(M[(i, j)], 0.0));
+				check_balanced_evd(M.as_ref(), d.as_ref(), s.as_ref(), to_cplx(&ul).as_ref(), to_cplx(&ur).as_ref());
+			}
+		}
+	}
+
+	#[test]
+	fn test_cplx_condition() {
+		let rng = &mut StdRng::seed_from_u64(2);
+
+		for n in [1, 2, 5, 20, 40] {
+			let mat = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			let mut s = Diag::zeros(n);
+			let mut ul = Mat::zeros(n, n);
+			let mut ur = Mat::zeros(n, n);
+			let mut s_cond = Col::zeros(n);
+			let mut sep = Col::zeros(n);
+
+			evd_cplx_with_condition(
+				mat.as_ref(),
+				s.as_mut(),
+				Some(ul.as_mut()),
+				Some(ur.as_mut()),
+				s_cond.as_mut(),
+				sep.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(evd_with_condition_scratch::<c64>(
+					n,
+					ComputeEigenvectors::Yes,
+					ComputeEigenvectors::Yes,
+					Par::Seq,
+					default(),
+				))),
+				default(),
+			)
+			.unwrap();
+
+			test_condition(mat.as_ref(), s.column_vector(), ul.as_ref(), ur.as_ref(), s_cond.as_ref(), sep.as_ref());
+		}
+
+		// nearly defective matrix, with ill-conditioned eigenvalues
+		let mat = &mat![[c64::new(1.0, 0.0), c64::new(1e4, 0.0)], [c64::new(0.0, 0.0), c64::new(1.0 + 1e-4, 0.0)]];
+		let mut s = Diag::zeros(2);
+		let mut s_cond = Col::zeros(2);
+		let mut sep = Col::zeros(2);
+		evd_cplx_with_condition(
+			mat.as_ref(),
+			s.as_mut(),
+			None,
+			None,
+			s_cond.as_mut(),
+			sep.as_mut(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(evd_with_condition_scratch::<c64>(
+				2,
+				ComputeEigenvectors::No,
+				ComputeEigenvectors::No,
+				Par::Seq,
+				default(),
+			))),
+			default(),
+		)
+		.unwrap();
+
+		for i in 0..2 {
+			assert!((s_cond[i] - 1e-8).abs() < 1e-12);
+			assert!((sep[i] - 1e-4).abs() < 1e-10);
+		}
+	}
+
+	#[test]
+	fn test_real_condition() {
+		let rng = &mut StdRng::seed_from_u64(3);
+
+		for n in [1, 2, 5, 20, 40] {
+			let mat = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: StandardNormal,
+			}
+			.rand::<Mat<f64>>(rng);
+
+			let mut s_re = Diag::zeros(n);
+			let mut s_im = Diag::zeros(n);
+			let mut ul = Mat::zeros(n, n);
+			let mut ur = Mat::zeros(n, n);
+			let mut s_cond = Col::zeros(n);
+			let mut sep = Col::zeros(n);
+
+			evd_real_with_condition(
+				mat.as_ref(),
+				s_re.as_mut(),
+				s_im.as_mut(),
+				Some(ul.as_mut()),
+				Some(ur.as_mut()),
+				s_cond.as_mut(),
+				sep.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(evd_with_condition_scratch::<f64>(
+					n,
+					ComputeEigenvectors::Yes,
+					ComputeEigenvectors::Yes,
+					Par::Seq,
+					default(),
+				))),
+				default(),
+			)
+			.unwrap();
+
+			let s = Col::from_fn(n, |i| c64::new(s_re[i], s_im[i]));
+			let to_cplx = |u: &Mat<f64>| {
+				let mut v = Mat::<c64>::zeros(n, n);
+				let mut j = 0;
+				while j < n {
+					if s_im[j] == 0.0 {
+						for i in 0..n {
+							v[(i, j)] = c64::new(u[(i, j)], 0.0);
+						}
+						j += 1;
+					} else {
+						for i in 0..n {
+							v[(i, j)] = c64::new(u[(i, j)], u[(i, j + 1)]);
+							v[(i, j + 1)] = c64::new(u[(i, j)], -u[(i, j + 1)]);
+						}
+						j += 2;
+					}
+				}
+				v
+			};
+
+			let mat = Mat::from_fn(n, n, |i, j| c64::from(mat[(i, j)]));
+			test_condition(
+				mat.as_ref(),
+				s.as_ref(),
+				to_cplx(&ul).as_ref(),
+				to_cplx(&ur).as_ref(),
+				s_cond.as_ref(),
+				sep.as_ref(),
+			);
+		}
+	}
 }
 
 #[cfg(test)]
@@ -1303,6 +2528,66 @@ mod self_adjoint_tests {
 		}
 	}
 
+	#[test]
+	fn test_self_adjoint_fn() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 4, 30] {
+			let X = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let A = &X * X.adjoint() + Mat::<c64>::identity(n, n);
+
+			let mut sqrt_A = Mat::zeros(n, n);
+			self_adjoint_fn(
+				sqrt_A.as_mut(),
+				A.as_ref(),
+				|x: f64| x.sqrt(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(self_adjoint_fn_scratch::<c64>(n, Par::Seq, default()))),
+				default(),
+			)
+			.unwrap();
+
+			let mut s = Diag::<c64>::zeros(n);
+			let mut u = Mat::<c64>::zeros(n, n);
+			if n > 0 {
+				self_adjoint_evd(
+					A.as_ref(),
+					s.as_mut(),
+					Some(u.as_mut()),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(self_adjoint_evd_scratch::<c64>(
+						n,
+						ComputeEigenvectors::Yes,
+						Par::Seq,
+						default(),
+					))),
+					default(),
+				)
+				.unwrap();
+			}
+
+			let mut inv_A = Mat::zeros(n, n);
+			self_adjoint_fn_from_evd(
+				inv_A.as_mut(),
+				s.column_vector(),
+				u.as_ref(),
+				|x: f64| x.recip(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(self_adjoint_fn_from_evd_scratch::<c64>(n, Par::Seq))),
+			);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * (n as f64));
+			assert!(&sqrt_A * &sqrt_A ~ A);
+			assert!(sqrt_A.adjoint().to_owned() ~ sqrt_A);
+			assert!(&inv_A * &A ~ Mat::<c64>::identity(n, n));
+		}
+	}
+
 	#[test]
 	fn test_special() {
 		for n in [1, 2, 4, 10, 15, 20, 50, 100, 150] {
@@ -1314,4 +2599,167 @@ mod self_adjoint_tests {
 			test_self_adjoint_evd(Mat::<c64>::identity(n, n).as_ref());
 		}
 	}
+
+	#[track_caller]
+	fn test_self_adjoint_evd_select<T: ComplexField<Real = f64>>(mat: MatRef<'_, T>, selection: EigenvalueSelection<f64>, cap: usize) -> usize {
+		let n = mat.nrows();
+		let params = default();
+
+		let full = mat.self_adjoint_eigenvalues(Side::Lower).unwrap();
+
+		let mut s = Diag::<T>::zeros(cap);
+		let mut u = Mat::<T>::zeros(n, cap);
+		let k = self_adjoint_evd_select(
+			mat,
+			selection.clone(),
+			s.as_mut(),
+			Some(u.as_mut()),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(self_adjoint_evd_select_scratch::<T>(
+				n,
+				cap,
+				ComputeEigenvectors::Yes,
+				Par::Seq,
+				params,
+			))),
+			params,
+		)
+		.unwrap();
+
+		let first = match &selection {
+			EigenvalueSelection::All => 0,
+			EigenvalueSelection::Indices(range) => range.start,
+			EigenvalueSelection::Interval(a, _) => full.iter().filter(|&x| x < a).count(),
+		};
+
+		let norm = Ord::max(1, n) as f64 * full.iter().fold(1.0f64, |acc, x| acc.max(x.abs()));
+		for i in 0..k {
+			assert!((real(&s[i]) - full[first + i]).abs() <= 1e-13 * norm);
+		}
+
+		let u = u.get(.., ..k);
+		let S = s.column_vector().get(..k).as_diagonal();
+		let approx_eq = CwiseMat(ApproxEq::<f64>::eps() * 16.0 * norm);
+		assert!(mat * u ~ u * S);
+		let approx_eq = CwiseMat(ApproxEq::<f64>::eps() * 16.0 * (n as f64));
+		assert!(u.adjoint() * u ~ Mat::<T>::identity(k, k));
+
+		let mut s2 = Diag::<T>::zeros(cap);
+		let k2 = self_adjoint_evd_select(
+			mat,
+			selection,
+			s2.as_mut(),
+			None,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(self_adjoint_evd_select_scratch::<T>(
+				n,
+				cap,
+				ComputeEigenvectors::No,
+				Par::Seq,
+				params,
+			))),
+			params,
+		)
+		.unwrap();
+		assert!(k2 == k);
+		for i in 0..k {
+			assert!(s2[i] == s[i]);
+		}
+
+		k
+	}
+
+	#[test]
+	fn test_select() {
+		let rng = &mut StdRng::seed_from_u64(2);
+
+		for n in [1, 2, 5, 30, 100] {
+			let mat = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: StandardNormal,
+			}
+			.rand::<Mat<f64>>(rng);
+			let mat = &mat + mat.adjoint();
+
+			assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::All, n) == n);
+			assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Indices(0..n.div_ceil(3)), n.div_ceil(3)) == n.div_ceil(3));
+			assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Indices(n / 2..n), n - n / 2) == n - n / 2);
+			assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Interval(-1.0, 2.0), n) <= n);
+
+			let mat = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let mat = &mat + mat.adjoint();
+
+			assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Indices(0..n.div_ceil(4)), n.div_ceil(4)) == n.div_ceil(4));
+			assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Interval(-3.0, 0.0), n) <= n);
+		}
+
+		// capacity smaller than the selection
+		let n = 40;
+		let mat = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
+		let mat = &mat + mat.adjoint();
+		assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::All, 5) == 5);
+		assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Interval(f64::NEG_INFINITY, f64::INFINITY), 7) == 7);
+		assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Interval(1.0, -1.0), 7) == 0);
+	}
+
+	#[test]
+	fn test_select_non_finite() {
+		let n = 10;
+		let mut mat = Mat::<f64>::identity(n, n);
+		mat[(3, 2)] = f64::NAN;
+
+		let mut s = Diag::<f64>::zeros(2);
+		let mut u = Mat::<f64>::zeros(n, 2);
+		let result = self_adjoint_evd_select(
+			mat.as_ref(),
+			EigenvalueSelection::Indices(0..2),
+			s.as_mut(),
+			Some(u.as_mut()),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(self_adjoint_evd_select_scratch::<f64>(
+				n,
+				2,
+				ComputeEigenvectors::Yes,
+				Par::Seq,
+				default(),
+			))),
+			default(),
+		);
+		assert!(result == Err(EvdError::NoConvergence));
+	}
+
+	#[test]
+	fn test_select_clustered() {
+		let rng = &mut StdRng::seed_from_u64(3);
+		let n = 50;
+
+		// eigenvalues with high multiplicity
+		let Q: Mat<c64> = UnitaryMat {
+			dim: n,
+			standard_normal: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.sample(rng);
+		let D = Col::<c64>::from_fn(n, |i| c64::new((i % 3) as f64, 0.0));
+		let mat = &Q * D.as_diagonal() * Q.adjoint();
+		let mat = Scale(c64::new(0.5, 0.0)) * (&mat + mat.adjoint());
+		assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Indices(0..20), 20) == 20);
+		assert!(test_self_adjoint_evd_select(mat.as_ref(), EigenvalueSelection::Interval(0.5, 1.5), n) == (0..n).filter(|i| i % 3 == 1).count());
+
+		for n in [1, 10, 50] {
+			assert!(test_self_adjoint_evd_select(Mat::<f64>::identity(n, n).as_ref(), EigenvalueSelection::Indices(0..n / 2), n / 2) == n / 2);
+			assert!(test_self_adjoint_evd_select(Mat::full(n, n, 0.0).as_ref(), EigenvalueSelection::Indices(0..n / 2), n / 2) == n / 2);
+			assert!(test_self_adjoint_evd_select(Mat::full(n, n, c64::ONE).as_ref(), EigenvalueSelection::Interval(0.5, n as f64 + 1.0), 1) == 1);
+		}
+	}
 }
@@ -491,7 +491,7 @@ fn aggressive_early_deflation<T: ComplexField>(
 	(ns, nd)
 }
 
-fn schur_move<T: ComplexField>(mut a: MatMut<'_, T>, mut q: Option<MatMut<'_, T>>, ifst: usize, ilst: &mut usize) -> isize {
+pub(crate) fn schur_move<T: ComplexField>(mut a: MatMut<'_, T>, mut q: Option<MatMut<'_, T>>, ifst: usize, ilst: &mut usize) -> isize {
 	let n = a.nrows();
 
 	// Quick return
@@ -1,8 +1,21 @@
+//! schur decomposition kernels and reordering of computed schur factorizations
+//!
+//! a real or complex schur decomposition $A = Z T Z^H$ can be reordered with
+//! [`reorder_schur_real`] and [`reorder_schur_cplx`], or their predicate-based counterparts
+//! [`reorder_schur_real_by`] and [`reorder_schur_cplx_by`], so that a selected cluster of
+//! eigenvalues appears in the leading block of $T$. the first columns of the updated $Z$ then span
+//! the corresponding invariant subspace of $A$, and the sensitivity of that cluster can be
+//! measured with [`schur_cluster_condition`]
+
 use super::*;
+use crate::assert;
+use linalg::matmul::matmul;
+use linalg::norm_est::{norm1_est, norm1_est_scratch};
 
 pub(crate) mod complex_schur;
 pub(crate) mod real_schur;
 
+/// schur decomposition tuning parameters
 #[derive(Clone, Copy, Debug)]
 pub struct SchurParams {
 	/// function that returns the number of shifts to use for a given matrix size
@@ -31,6 +44,8 @@ impl<T: ComplexField> Auto<T> for SchurParams {
 	}
 }
 
+/// computes the size and alignment of the workspace required to compute the schur form of a
+/// hessenberg matrix
 pub fn multishift_qr_scratch<T: ComplexField>(n: usize, nh: usize, want_t: bool, want_z: bool, parallelism: Par, params: SchurParams) -> StackReq {
 	let nsr = (params.recommended_shift_count)(n, nh);
 
@@ -95,3 +110,639 @@ fn default_recommended_deflation_window(dim: usize, _active_block_dim: usize) ->
 		384
 	}
 }
+
+/// error that can occur when reordering a real schur decomposition
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SchurReorderError {
+	/// two adjacent diagonal blocks are too close to be swapped stably. the factorization is left
+	/// valid, but only partially reordered
+	IllConditioned,
+}
+
+/// condition estimates for the cluster of eigenvalues in the leading block of a schur
+/// decomposition
+#[derive(Copy, Clone, Debug)]
+pub struct SchurClusterCondition<R> {
+	/// reciprocal condition number of the average of the eigenvalues of the cluster
+	pub s: R,
+	/// estimate of the reciprocal condition number of the right invariant subspace associated with
+	/// the cluster, i.e. of the separation of the leading and trailing blocks
+	pub sep: R,
+}
+
+/// reorders the complex schur decomposition $A = Z T Z^H$ so that the eigenvalues for which
+/// `select` is `true` are moved to the leading diagonal block of $T$, keeping their relative order
+///
+/// $T$ and $Z$ are updated in place. returns the dimension of the leading block
+#[track_caller]
+pub fn reorder_schur_cplx<T: ComplexField>(t: MatMut<'_, T>, z: Option<MatMut<'_, T>>, select: &[bool]) -> usize {
+	assert!(select.len() == t.nrows());
+	reorder_schur_cplx_imp(t, z, |k, _| select[k])
+}
+
+/// reorders the complex schur decomposition $A = Z T Z^H$ so that the eigenvalues for which
+/// `select` returns `true` are moved to the leading diagonal block of $T$, keeping their relative
+/// order
+///
+/// $T$ and $Z$ are updated in place. returns the dimension of the leading block
+#[track_caller]
+pub fn reorder_schur_cplx_by<T: ComplexField>(t: MatMut<'_, T>, z: Option<MatMut<'_, T>>, select: impl FnMut(&T) -> bool) -> usize {
+	let mut select = select;
+	reorder_schur_cplx_imp(t, z, |_, e| select(e))
+}
+
+// the diagonal entries past the current one are not modified by moving earlier blocks, so
+// `select` always sees the original eigenvalue at index `k`
+#[track_caller]
+fn reorder_schur_cplx_imp<T: ComplexField>(t: MatMut<'_, T>, z: Option<MatMut<'_, T>>, mut select: impl FnMut(usize, &T) -> bool) -> usize {
+	let n = t.nrows();
+	assert!(t.ncols() == n);
+	if let Some(z) = z.rb() {
+		assert!(all(z.nrows() == n, z.ncols() == n));
+	}
+
+	let mut t = t;
+	let mut z = z;
+
+	let mut ks = 0;
+	for k in 0..n {
+		if select(k, &t[(k, k)]) {
+			if k != ks {
+				let mut ilst = ks;
+				complex_schur::schur_move(t.rb_mut(), z.rb_mut(), k, &mut ilst);
+			}
+			ks += 1;
+		}
+	}
+	ks
+}
+
+/// reorders the real schur decomposition $A = Z T Z^\top$ so that the eigenvalues for which
+/// `select` is `true` are moved to the leading diagonal block of $T$, keeping their relative order
+///
+/// a pair of complex conjugate eigenvalues is moved if either of them is selected. $T$ and $Z$ are
+/// updated in place. returns the dimension of the leading block
+#[track_caller]
+pub fn reorder_schur_real<T: RealField>(t: MatMut<'_, T>, z: Option<MatMut<'_, T>>, select: &[bool]) -> Result<usize, SchurReorderError> {
+	assert!(select.len() == t.nrows());
+	reorder_schur_real_imp(t, z, |k, pair, _| select[k] || (pair && select[k + 1]))
+}
+
+/// reorders the real schur decomposition $A = Z T Z^\top$ so that the eigenvalues for which
+/// `select` returns `true` are moved to the leading diagonal block of $T$, keeping their relative
+/// order
+///
+/// a pair of complex conjugate eigenvalues is moved if either of them is selected. $T$ and $Z$ are
+/// updated in place. returns the dimension of the leading block
+#[track_caller]
+pub fn reorder_schur_real_by<T: RealField>(
+	t: MatMut<'_, T>,
+	z: Option<MatMut<'_, T>>,
+	select: impl FnMut(&Complex<T>) -> bool,
+) -> Result<usize, SchurReorderError> {
+	let mut select = select;
+	reorder_schur_real_imp(t, z, |_, pair, (e0, e1)| select(&e0) || (pair && select(&e1)))
+}
+
+#[math]
+#[track_caller]
+fn reorder_schur_real_imp<T: RealField>(
+	t: MatMut<'_, T>,
+	z: Option<MatMut<'_, T>>,
+	mut select: impl FnMut(usize, bool, (Complex<T>, Complex<T>)) -> bool,
+) -> Result<usize, SchurReorderError> {
+	let n = t.nrows();
+	assert!(t.ncols() == n);
+	if let Some(z) = z.rb() {
+		assert!(all(z.nrows() == n, z.ncols() == n));
+	}
+
+	let mut t = t;
+	let mut z = z;
+
+	let mut ks = 0;
+	let mut k = 0;
+	while k < n {
+		let pair = k + 1 < n && t[(k + 1, k)] != zero();
+		let nb = if pair { 2 } else { 1 };
+
+		let eig = if pair {
+			let ((re0, im0), (re1, im1)) =
+				real_schur::lahqr_eig22(copy(t[(k, k)]), copy(t[(k, k + 1)]), copy(t[(k + 1, k)]), copy(t[(k + 1, k + 1)]));
+			(Complex::new(re0, im0), Complex::new(re1, im1))
+		} else {
+			(Complex::new(copy(t[(k, k)]), zero()), Complex::new(zero(), zero()))
+		};
+
+		if select(k, pair, eig) {
+			if k != ks {
+				let mut ilst = ks;
+				if real_schur::schur_move(t.rb_mut(), z.rb_mut(), k, &mut ilst) != 0 {
+					return Err(SchurReorderError::IllConditioned);
+				}
+			}
+			ks += nb;
+		}
+		k += nb;
+	}
+	Ok(ks)
+}
+
+/// solves the $p\times q$ sylvester equation $A X + X B = C$ for $p, q \le 2$, overwriting $C$
+/// with $X$, using gaussian elimination with complete pivoting on the kronecker form
+///
+/// small pivots are perturbed, similarly to lapack `lasy2`
+#[math]
+fn solve_small_sylvester<T: ComplexField>(A: MatRef<'_, T>, B: MatRef<'_, T>, X: MatMut<'_, T>) {
+	let p = A.nrows();
+	let q = B.nrows();
+	let pq = p * q;
+
+	stack_mat!(K, pq, pq, 4, 4, T);
+
+	let mut smin = zero::<T::Real>();
+	for c in 0..q {
+		for r in 0..p {
+			for r2 in 0..p {
+				K[(r + p * c, r2 + p * c)] = K[(r + p * c, r2 + p * c)] + A[(r, r2)];
+				smin = max(smin, abs1(A[(r, r2)]));
+			}
+			for c2 in 0..q {
+				K[(r + p * c, r + p * c2)] = K[(r + p * c, r + p * c2)] + B[(c2, c)];
+				smin = max(smin, abs1(B[(c2, c)]));
+			}
+		}
+	}
+
+	solve_small_kronecker(K, X, smin);
+}
+
+/// solves the $p\times q$ stein equation $A X B - X = C$ for $p, q \le 2$, overwriting $C$ with
+/// $X$, using gaussian elimination with complete pivoting on the kronecker form
+#[math]
+fn solve_small_stein<T: ComplexField>(A: MatRef<'_, T>, B: MatRef<'_, T>, X: MatMut<'_, T>) {
+	let p = A.nrows();
+	let q = B.nrows();
+	let pq = p * q;
+
+	stack_mat!(K, pq, pq, 4, 4, T);
+
+	let mut smin = one::<T::Real>();
+	for c in 0..q {
+		for r in 0..p {
+			for c2 in 0..q {
+				for r2 in 0..p {
+					let k = A[(r, r2)] * B[(c2, c)];
+					smin = max(smin, abs1(k));
+					K[(r + p * c, r2 + p * c2)] = k;
+				}
+			}
+			K[(r + p * c, r + p * c)] = K[(r + p * c, r + p * c)] - one::<T>();
+		}
+	}
+
+	solve_small_kronecker(K, X, smin);
+}
+
+/// solves the $pq \times pq$ system $K \operatorname{vec}(X) = \operatorname{vec}(C)$, overwriting
+/// $C$ with $X$, where `smin` is the magnitude of the largest entry of the terms of $K$
+///
+/// small pivots are perturbed, similarly to lapack `lasy2`
+#[math]
+fn solve_small_kronecker<T: ComplexField>(K: MatMut<'_, T>, X: MatMut<'_, T>, smin: T::Real) {
+	let p = X.nrows();
+	let q = X.ncols();
+	let pq = p * q;
+	let mut K = K;
+	let mut X = X;
+
+	let eps = eps::<T::Real>();
+	let smlnum = min_positive::<T::Real>() / eps;
+
+	stack_mat!(rhs, pq, 1, 4, 1, T);
+	for c in 0..q {
+		for r in 0..p {
+			rhs[(r + p * c, 0)] = copy(X[(r, c)]);
+		}
+	}
+	let smin = max(eps * smin, smlnum);
+
+	let mut col_perm = [0usize, 1, 2, 3];
+	for i in 0..pq {
+		let mut ip = i;
+		let mut jp = i;
+		let mut xmax = zero::<T::Real>();
+		for jj in i..pq {
+			for ii in i..pq {
+				if abs1(K[(ii, jj)]) >= xmax {
+					xmax = abs1(K[(ii, jj)]);
+					ip = ii;
+					jp = jj;
+				}
+			}
+		}
+		if ip != i {
+			crate::perm::swap_rows_idx(K.rb_mut(), ip, i);
+			crate::perm::swap_rows_idx(rhs.rb_mut(), ip, i);
+		}
+		if jp != i {
+			crate::perm::swap_cols_idx(K.rb_mut(), jp, i);
+			col_perm.swap(jp, i);
+		}
+		if abs1(K[(i, i)]) < smin {
+			K[(i, i)] = from_real(smin);
+		}
+
+		let inv = recip(K[(i, i)]);
+		for ii in i + 1..pq {
+			let f = K[(ii, i)] * inv;
+			rhs[(ii, 0)] = rhs[(ii, 0)] - f * rhs[(i, 0)];
+			for jj in i + 1..pq {
+				K[(ii, jj)] = K[(ii, jj)] - f * K[(i, jj)];
+			}
+		}
+	}
+
+	for i in (0..pq).rev() {
+		let mut acc = copy(rhs[(i, 0)]);
+		for jj in i + 1..pq {
+			acc = acc - K[(i, jj)] * rhs[(jj, 0)];
+		}
+		rhs[(i, 0)] = acc * recip(K[(i, i)]);
+	}
+
+	for i in 0..pq {
+		let idx = col_perm[i];
+		X[(idx % p, idx / p)] = copy(rhs[(i, 0)]);
+	}
+}
+
+/// solves the sylvester equation $A X + X B = C$, where $A$ and $B$ are upper quasi-triangular
+/// (i.e. in real or complex schur form), overwriting $C$ with the solution $X$
+#[math]
+#[track_caller]
+pub(crate) fn solve_sylvester_quasi_triangular_in_place<T: ComplexField>(A: MatRef<'_, T>, B: MatRef<'_, T>, C: MatMut<'_, T>, par: Par) {
+	let m = A.nrows();
+	let n = B.nrows();
+	assert!(all(A.ncols() == m, B.ncols() == n, C.nrows() == m, C.ncols() == n));
+
+	let mut C = C;
+
+	let mut j = 0;
+	while j < n {
+		let q = if j + 1 < n && B[(j + 1, j)] != zero() { 2 } else { 1 };
+
+		if j > 0 {
+			let (X, mut Cj) = C.rb_mut().split_at_col_mut(j);
+			matmul(
+				Cj.rb_mut().subcols_mut(0, q),
+				Accum::Add,
+				X.rb(),
+				B.submatrix(0, j, j, q),
+				-one::<T>(),
+				par,
+			);
+		}
+
+		let mut i = m;
+		while i > 0 {
+			let p = if i >= 2 && A[(i - 1, i - 2)] != zero() { 2 } else { 1 };
+			i -= p;
+
+			if i + p < m {
+				let (top, bot) = C.rb_mut().subcols_mut(j, q).split_at_row_mut(i + p);
+				matmul(
+					top.subrows_mut(i, p),
+					Accum::Add,
+					A.submatrix(i, i + p, p, m - i - p),
+					bot.rb(),
+					-one::<T>(),
+					par,
+				);
+			}
+			solve_small_sylvester(A.submatrix(i, i, p, p), B.submatrix(j, j, q, q), C.rb_mut().submatrix_mut(i, j, p, q));
+		}
+
+		j += q;
+	}
+}
+
+pub(crate) fn solve_stein_quasi_triangular_in_place_scratch<T: ComplexField>(m: usize, n: usize, par: Par) -> StackReq {
+	_ = (n, par);
+	temp_mat_scratch::<T>(m, 2)
+}
+
+/// solves the stein equation $A X B - X = C$, where $A$ and $B$ are upper quasi-triangular (i.e.
+/// in real or complex schur form), overwriting $C$ with the solution $X$
+#[math]
+#[track_caller]
+pub(crate) fn solve_stein_quasi_triangular_in_place<T: ComplexField>(
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	C: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let m = A.nrows();
+	let n = B.nrows();
+	assert!(all(A.ncols() == m, B.ncols() == n, C.nrows() == m, C.ncols() == n));
+
+	let mut C = C;
+	let (mut W, _) = unsafe { temp_mat_uninit::<T, _, _>(m, 2, stack) };
+	let mut W = W.as_mat_mut();
+
+	let mut j = 0;
+	while j < n {
+		let q = if j + 1 < n && B[(j + 1, j)] != zero() { 2 } else { 1 };
+		let mut W = W.rb_mut().subcols_mut(0, q);
+
+		// C_j -= A (X_{<j} B_{<j, j})
+		if j > 0 {
+			let (X, mut Cj) = C.rb_mut().split_at_col_mut(j);
+			matmul(W.rb_mut(), Accum::Replace, X.rb(), B.submatrix(0, j, j, q), one(), par);
+			matmul(Cj.rb_mut().subcols_mut(0, q), Accum::Add, A, W.rb(), -one::<T>(), par);
+		}
+
+		let mut i = m;
+		while i > 0 {
+			let p = if i >= 2 && A[(i - 1, i - 2)] != zero() { 2 } else { 1 };
+			i -= p;
+
+			// C_ij -= (A_{i, >i} X_{>i, j}) B_jj
+			if i + p < m {
+				stack_mat!(V, p, q, 2, 2, T);
+				matmul(
+					V.rb_mut(),
+					Accum::Replace,
+					A.submatrix(i, i + p, p, m - i - p),
+					C.rb().submatrix(i + p, j, m - i - p, q),
+					one(),
+					par,
+				);
+				matmul(
+					C.rb_mut().submatrix_mut(i, j, p, q),
+					Accum::Add,
+					V.rb(),
+					B.submatrix(j, j, q, q),
+					-one::<T>(),
+					par,
+				);
+			}
+			solve_small_stein(A.submatrix(i, i, p, p), B.submatrix(j, j, q, q), C.rb_mut().submatrix_mut(i, j, p, q));
+		}
+
+		j += q;
+	}
+}
+
+/// computes the size and alignment of the workspace required to compute the condition estimates
+/// of the leading `k`-dimensional cluster of a schur decomposition of dimension `dim`
+pub fn schur_cluster_condition_scratch<T: ComplexField>(dim: usize, k: usize) -> StackReq {
+	assert!(k <= dim);
+	let n1 = k;
+	let n2 = dim - k;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n2, n2),
+		temp_mat_scratch::<T>(n1, n2),
+		temp_mat_scratch::<T>(n2, n1),
+		norm1_est_scratch::<T>(n1 * n2),
+	])
+}
+
+/// computes condition estimates for the cluster of eigenvalues in the leading `k`-dimensional
+/// diagonal block of the (real or complex) schur form $T$, similarly to lapack `trsen`
+///
+/// this is typically used after moving the eigenvalues of interest to the leading block with
+/// [`reorder_schur_real`] or [`reorder_schur_cplx`]. `k` must not split a $2\times 2$ diagonal
+/// block
+#[math]
+#[track_caller]
+pub fn schur_cluster_condition<T: ComplexField>(t: MatRef<'_, T>, k: usize, par: Par, stack: &mut MemStack) -> SchurClusterCondition<T::Real> {
+	let n = t.nrows();
+	assert!(all(t.ncols() == n, k <= n));
+	if k > 0 && k < n {
+		assert!(t[(k, k - 1)] == zero());
+	}
+
+	let n1 = k;
+	let n2 = n - k;
+
+	if n1 == 0 || n2 == 0 {
+		let mut norm = zero::<T::Real>();
+		for j in 0..n {
+			let mut col = zero::<T::Real>();
+			for i in 0..n {
+				col = col + abs(t[(i, j)]);
+			}
+			norm = max(norm, col);
+		}
+		return SchurClusterCondition { s: one(), sep: norm };
+	}
+
+	let (T11, T12, _, T22) = t.split_at(k, k);
+
+	let (mut neg_T22, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut X, stack) = unsafe { temp_mat_uninit::<T, _, _>(n1, n2, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n1, stack) };
+	let mut neg_T22 = neg_T22.as_mat_mut();
+	let mut X = X.as_mat_mut();
+	let mut W = W.as_mat_mut();
+
+	z!(neg_T22.rb_mut(), T22).for_each(|uz!(dst, src)| *dst = -*src);
+	let neg_T22 = neg_T22.rb();
+
+	// T11 X - X T22 = T12
+	X.copy_from(T12);
+	solve_sylvester_quasi_triangular_in_place(T11, neg_T22, X.rb_mut(), par);
+
+	let rnorm = X.norm_l2();
+	let s = if rnorm == zero() {
+		one()
+	} else {
+		recip(sqrt(recip(rnorm) + rnorm) * sqrt(rnorm))
+	};
+
+	// the vector x is the column-major storage of an n1×n2 matrix
+	let est = norm1_est(
+		n1 * n2,
+		&mut |mut x: ColMut<'_, T>| {
+			for j in 0..n2 {
+				for i in 0..n1 {
+					X[(i, j)] = copy(x[i + n1 * j]);
+				}
+			}
+			solve_sylvester_quasi_triangular_in_place(T11, neg_T22, X.rb_mut(), par);
+			for j in 0..n2 {
+				for i in 0..n1 {
+					x[i + n1 * j] = copy(X[(i, j)]);
+				}
+			}
+		},
+		// T11^H Y - Y T22^H = C is equivalent to (-T22) Y^H + Y^H T11 = C^H
+		&mut |mut x: ColMut<'_, T>| {
+			for j in 0..n2 {
+				for i in 0..n1 {
+					W[(j, i)] = conj(x[i + n1 * j]);
+				}
+			}
+			solve_sylvester_quasi_triangular_in_place(neg_T22, T11, W.rb_mut(), par);
+			for j in 0..n2 {
+				for i in 0..n1 {
+					x[i + n1 * j] = conj(W[(j, i)]);
+				}
+			}
+		},
+		stack,
+	);
+
+	SchurClusterCondition { s, sep: recip(est) }
+}
+
+#[cfg(test)]
+mod reorder_tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	#[test]
+	fn test_reorder_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+
+		let schur = A.schur().unwrap();
+		let mut t = schur.T().to_owned();
+		let mut z = schur.Z().to_owned();
+
+		let select = (0..n).map(|i| t[(i, i)].re < 0.0).collect::<Vec<_>>();
+		let count = select.iter().filter(|&&x| x).count();
+
+		let k = reorder_schur_cplx(t.as_mut(), Some(z.as_mut()), &select);
+		assert!(k == count);
+
+		assert!(&z * &t * z.adjoint() ~ A);
+		assert!(z.adjoint() * &z ~ Mat::<c64>::identity(n, n));
+		for j in 0..n {
+			assert!((t[(j, j)].re < 0.0) == (j < k));
+			for i in j + 1..n {
+				assert!(t[(i, j)] == c64::ZERO);
+			}
+		}
+
+		let cond = schur_cluster_condition(
+			t.as_ref(),
+			k,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(schur_cluster_condition_scratch::<c64>(n, k))),
+		);
+		assert!(all(cond.s > 0.0, cond.s <= 1.0, cond.sep > 0.0));
+	}
+
+	#[test]
+	fn test_reorder_real() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+
+		let schur = A.schur_from_real().unwrap();
+		let mut t = schur.T().to_owned();
+		let mut z = schur.Z().to_owned();
+
+		let select = schur.S().column_vector().iter().map(|e| e.re < 0.0).collect::<Vec<_>>();
+		let count = select.iter().filter(|&&x| x).count();
+
+		let k = reorder_schur_real(t.as_mut(), Some(z.as_mut()), &select).unwrap();
+		assert!(k == count);
+
+		assert!(&z * &t * z.transpose() ~ A);
+		assert!(z.transpose() * &z ~ Mat::<f64>::identity(n, n));
+		for j in 0..n {
+			for i in j + 2..n {
+				assert!(t[(i, j)] == 0.0);
+			}
+		}
+
+		let cond = schur_cluster_condition(
+			t.as_ref(),
+			k,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(schur_cluster_condition_scratch::<f64>(n, k))),
+		);
+		assert!(all(cond.s > 0.0, cond.s <= 1.0, cond.sep > 0.0));
+	}
+
+	#[test]
+	fn test_reorder_by() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
+
+		// selecting by value is the same as selecting with the equivalent mask
+		let schur = A.schur_from_real().unwrap();
+		let select = schur.S().column_vector().iter().map(|e| e.im > 0.0).collect::<Vec<_>>();
+
+		let mut t0 = schur.T().to_owned();
+		let mut z0 = schur.Z().to_owned();
+		let k0 = reorder_schur_real(t0.as_mut(), Some(z0.as_mut()), &select).unwrap();
+		let mut t1 = schur.T().to_owned();
+		let mut z1 = schur.Z().to_owned();
+		let k1 = reorder_schur_real_by(t1.as_mut(), Some(z1.as_mut()), |e| e.im > 0.0).unwrap();
+		assert!(all(k0 == k1, t0 == t1, z0 == z1));
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+
+		let schur = A.schur().unwrap();
+		let select = schur.S().column_vector().iter().map(|e| e.im > 0.0).collect::<Vec<_>>();
+
+		let mut t0 = schur.T().to_owned();
+		let mut z0 = schur.Z().to_owned();
+		let k0 = reorder_schur_cplx(t0.as_mut(), Some(z0.as_mut()), &select);
+		let mut t1 = schur.T().to_owned();
+		let mut z1 = schur.Z().to_owned();
+		let k1 = reorder_schur_cplx_by(t1.as_mut(), Some(z1.as_mut()), |e| e.im > 0.0);
+		assert!(all(k0 == k1, t0 == t1, z0 == z1));
+	}
+
+	#[test]
+	fn test_cluster_condition_diag() {
+		// for a normal matrix, s = 1 and sep is the distance between the two spectra
+		let t = Mat::<f64>::from_fn(4, 4, |i, j| if i == j { [1.0, 2.0, 5.0, 7.0][i] } else { 0.0 });
+		let cond = schur_cluster_condition(
+			t.as_ref(),
+			2,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(schur_cluster_condition_scratch::<f64>(4, 2))),
+		);
+		assert!((cond.s - 1.0).abs() < 1e-12);
+		assert!((cond.sep - 3.0).abs() < 1e-12);
+	}
+}
@@ -6,7 +6,7 @@ use linalg::matmul::matmul;
 
 // ret: (a b c d) (eig1_re eig1_im) (eig2_re eig2_im) (cs sn)
 #[math]
-fn lahqr_eig22<T: RealField>(mut a00: T, mut a01: T, mut a10: T, mut a11: T) -> ((T, T), (T, T)) {
+pub(crate) fn lahqr_eig22<T: RealField>(mut a00: T, mut a01: T, mut a10: T, mut a11: T) -> ((T, T), (T, T)) {
 	let half = from_f64::<T>(0.5);
 
 	let s = abs(a00) + abs(a01) + abs(a10) + abs(a11);
@@ -302,7 +302,7 @@ fn lahqr_shiftcolumn<T: RealField>(h: MatRef<'_, T>, mut v: ColMut<'_, T>, s1: (
 }
 
 #[math]
-fn schur_move<T: RealField>(mut a: MatMut<T>, mut q: Option<MatMut<T>>, mut ifst: usize, ilst: &mut usize) -> isize {
+pub(crate) fn schur_move<T: RealField>(mut a: MatMut<T>, mut q: Option<MatMut<T>>, mut ifst: usize, ilst: &mut usize) -> isize {
 	let n = a.nrows();
 	if n == 0 {
 		return 0;
@@ -813,6 +813,335 @@ pub(crate) fn divide_and_conquer_scratch<T: ComplexField>(n: usize, par: Par) ->
 	])
 }
 
+/// returns the minimum pivot magnitude used in the sturm sequences of the symmetric tridiagonal
+/// matrix
+#[math]
+fn min_pivot<T: RealField>(offdiag: ColRef<'_, T, usize, ContiguousFwd>, n: usize) -> T {
+	let mut max_e2 = one::<T>();
+	for i in 0..n.saturating_sub(1) {
+		max_e2 = max(max_e2, abs2(offdiag[i]));
+	}
+	min_positive::<T>() * max_e2
+}
+
+/// returns the number of eigenvalues of the symmetric tridiagonal matrix that are strictly less
+/// than `x`
+#[math]
+fn sturm_count<T: RealField>(diag: ColRef<'_, T, usize, ContiguousFwd>, offdiag: ColRef<'_, T, usize, ContiguousFwd>, x: T, pivmin: T) -> usize {
+	let n = diag.nrows();
+	let mut count = 0;
+	let mut q = zero::<T>();
+
+	for i in 0..n {
+		q = if i == 0 { diag[0] - x } else { diag[i] - x - abs2(offdiag[i - 1]) / q };
+		if abs(q) <= pivmin {
+			q = -pivmin;
+		}
+		if q < zero() {
+			count += 1;
+		}
+	}
+	count
+}
+
+/// returns gershgorin bounds on the eigenvalues of the symmetric tridiagonal matrix, widened
+/// so that they can be used as starting points for bisection
+#[math]
+fn gershgorin_bounds<T: RealField>(diag: ColRef<'_, T, usize, ContiguousFwd>, offdiag: ColRef<'_, T, usize, ContiguousFwd>, pivmin: T) -> (T, T) {
+	let n = diag.nrows();
+	let mut lo = copy(diag[0]);
+	let mut hi = copy(diag[0]);
+
+	for i in 0..n {
+		let mut r = zero::<T>();
+		if i > 0 {
+			r = r + abs(offdiag[i - 1]);
+		}
+		if i + 1 < n {
+			r = r + abs(offdiag[i]);
+		}
+		lo = min(lo, diag[i] - r);
+		hi = max(hi, diag[i] + r);
+	}
+
+	let norm = max(abs(lo), abs(hi));
+	let delta = from_f64::<T>(2.0 * n as f64) * eps::<T>() * norm + from_f64::<T>(2.0) * pivmin;
+	(lo - delta, hi + delta)
+}
+
+/// returns the number of eigenvalues of the symmetric tridiagonal matrix that are strictly less
+/// than `x`
+pub(crate) fn eigenvalue_count<T: RealField>(diag: ColRef<'_, T, usize, ContiguousFwd>, offdiag: ColRef<'_, T, usize, ContiguousFwd>, x: T) -> usize {
+	let n = diag.nrows();
+	if n == 0 {
+		return 0;
+	}
+	sturm_count(diag, offdiag, x, min_pivot(offdiag, n))
+}
+
+/// computes the eigenvalues with indices `first..first + w.nrows()` of the symmetric tridiagonal
+/// matrix by bisection, sorted in nondecreasing order
+#[math]
+pub(crate) fn bisection<T: RealField>(
+	diag: ColRef<'_, T, usize, ContiguousFwd>,
+	offdiag: ColRef<'_, T, usize, ContiguousFwd>,
+	first: usize,
+	w: ColMut<'_, T, usize, ContiguousFwd>,
+) {
+	let n = diag.nrows();
+	let mut w = w;
+	if w.nrows() == 0 {
+		return;
+	}
+
+	let pivmin = min_pivot(offdiag, n);
+	let (gl, gu) = gershgorin_bounds(diag, offdiag, copy(pivmin));
+	let abs_tol = eps::<T>() * max(abs(gl), abs(gu));
+	let rel_tol = from_f64::<T>(2.0) * eps::<T>();
+	let half = from_f64::<T>(0.5);
+
+	for j in 0..w.nrows() {
+		let idx = first + j;
+
+		// invariant: the eigenvalue with index `idx` lies in $[lo, hi)$
+		let mut lo = if j > 0 { max(copy(gl), w[j - 1] - abs_tol) } else { copy(gl) };
+		if sturm_count(diag, offdiag, copy(lo), copy(pivmin)) > idx {
+			lo = copy(gl);
+		}
+		let mut hi = copy(gu);
+
+		// the comparisons are written so that the loop terminates if the matrix contains nans
+		loop {
+			let tol = max(max(copy(abs_tol), copy(pivmin)), rel_tol * max(abs(lo), abs(hi)));
+			#[allow(clippy::neg_cmp_op_on_partial_ord)]
+			if !(hi - lo > tol) {
+				break;
+			}
+			let mid = (lo + hi) * half;
+			if !(mid > lo && mid < hi) {
+				break;
+			}
+
+			if sturm_count(diag, offdiag, copy(mid), copy(pivmin)) > idx {
+				hi = mid;
+			} else {
+				lo = mid;
+			}
+		}
+
+		w[j] = (lo + hi) * half;
+	}
+}
+
+pub(crate) fn inverse_iteration_scratch<T: ComplexField>(n: usize) -> StackReq {
+	StackReq::all_of(&[temp_mat_scratch::<T>(n, 1).array(5), StackReq::new::<bool>(n)])
+}
+
+/// computes the eigenvectors of the symmetric tridiagonal matrix corresponding to the eigenvalues
+/// `w`, sorted in nondecreasing order, by inverse iteration
+///
+/// eigenvectors of close eigenvalues are reorthogonalized against each other
+///
+/// returns [`EvdError::NoConvergence`] if the iteration fails to converge for one of the
+/// eigenvalues, in which case all the eigenvectors are still computed, but the failed ones may be
+/// inaccurate
+#[math]
+pub(crate) fn inverse_iteration<T: RealField>(
+	diag: ColRef<'_, T, usize, ContiguousFwd>,
+	offdiag: ColRef<'_, T, usize, ContiguousFwd>,
+	w: ColRef<'_, T, usize, ContiguousFwd>,
+	u: MatMut<'_, T, usize, usize>,
+	stack: &mut MemStack,
+) -> Result<(), EvdError> {
+	const MAX_ITERS: usize = 5;
+	const EXTRA_ITERS: usize = 2;
+
+	let n = diag.nrows();
+	let k = w.nrows();
+	let mut u = u;
+	if n == 0 || k == 0 {
+		return Ok(());
+	}
+
+	let (mut lower, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut main, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut upper, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut upper2, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut x, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut pivot, _) = stack.make_with(n, |_| false);
+
+	let mut lower = lower.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+	let mut main = main.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+	let mut upper = upper.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+	let mut upper2 = upper2.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+	let mut x = x.as_mat_mut().col_mut(0).try_as_col_major_mut().unwrap();
+
+	let mut norm = zero::<T>();
+	for i in 0..n {
+		let mut r = abs(diag[i]);
+		if i > 0 {
+			r = r + abs(offdiag[i - 1]);
+		}
+		if i + 1 < n {
+			r = r + abs(offdiag[i]);
+		}
+		norm = max(norm, r);
+	}
+	if norm == zero() {
+		norm = one();
+	}
+
+	let eps = eps::<T>();
+	let tiny = eps * norm;
+	let cluster_tol = from_f64::<T>(1e-3) * norm;
+	let growth_tol = sqrt(from_f64::<T>(0.1 / n as f64));
+
+	// deterministic pseudo-random starting vectors, uniform in $(-1, 1)$
+	let mut seed = 0x2545_f491_4f6c_dd1du64;
+	let mut next = || {
+		seed ^= seed << 13;
+		seed ^= seed >> 7;
+		seed ^= seed << 17;
+		from_f64::<T>((seed >> 11) as f64 * (2.0 / (1u64 << 53) as f64) - 1.0)
+	};
+
+	let mut cluster_start = 0;
+	let mut prev = zero::<T>();
+	let mut failed = false;
+
+	for j in 0..k {
+		let mut shift = copy(w[j]);
+		if j > 0 {
+			if shift - prev > cluster_tol {
+				cluster_start = j;
+			}
+			// separate equal eigenvalues slightly, so that the iterations do not converge to the
+			// same vector
+			let sep = from_f64::<T>(10.0) * eps * abs(shift);
+			if shift - prev < sep {
+				shift = prev + sep;
+			}
+		}
+		prev = copy(shift);
+
+		// $LU$ factorization of $T - \lambda I$ with partial pivoting
+		for i in 0..n {
+			main[i] = diag[i] - shift;
+			if i + 1 < n {
+				lower[i] = copy(offdiag[i]);
+				upper[i] = copy(offdiag[i]);
+			}
+			upper2[i] = zero();
+		}
+		for i in 0..n - 1 {
+			if abs(main[i]) >= abs(lower[i]) {
+				pivot[i] = false;
+				if main[i] == zero() {
+					main[i] = copy(tiny);
+				}
+				let f = lower[i] / main[i];
+				lower[i] = copy(f);
+				main[i + 1] = main[i + 1] - f * upper[i];
+			} else {
+				pivot[i] = true;
+				let f = main[i] / lower[i];
+				main[i] = copy(lower[i]);
+				lower[i] = copy(f);
+				let tmp = copy(upper[i]);
+				upper[i] = copy(main[i + 1]);
+				main[i + 1] = tmp - f * main[i + 1];
+				if i + 2 < n {
+					upper2[i] = copy(upper[i + 1]);
+					upper[i + 1] = -f * upper[i + 1];
+				}
+			}
+		}
+		if abs(main[n - 1]) < tiny {
+			main[n - 1] = if main[n - 1] < zero() { -tiny } else { copy(tiny) };
+		}
+		for i in 0..n - 1 {
+			if abs(main[i]) < tiny {
+				main[i] = if main[i] < zero() { -tiny } else { copy(tiny) };
+			}
+		}
+
+		for i in 0..n {
+			x[i] = next();
+		}
+
+		let mut converged = 0;
+		for _ in 0..MAX_ITERS {
+			let mut sum = zero::<T>();
+			for i in 0..n {
+				sum = sum + abs(x[i]);
+			}
+			let scale = from_f64::<T>(n as f64) * norm * max(copy(eps), abs(main[n - 1])) / sum;
+			for i in 0..n {
+				x[i] = x[i] * scale;
+			}
+
+			// solve with $P L U$
+			for i in 0..n - 1 {
+				if pivot[i] {
+					let tmp = copy(x[i]);
+					x[i] = copy(x[i + 1]);
+					x[i + 1] = tmp - lower[i] * x[i];
+				} else {
+					x[i + 1] = x[i + 1] - lower[i] * x[i];
+				}
+			}
+			for i in (0..n).rev() {
+				let mut acc = copy(x[i]);
+				if i + 1 < n {
+					acc = acc - upper[i] * x[i + 1];
+				}
+				if i + 2 < n {
+					acc = acc - upper2[i] * x[i + 2];
+				}
+				x[i] = acc / main[i];
+			}
+
+			for c in cluster_start..j {
+				let d = dot::inner_prod(u.rb().col(c).transpose(), Conj::No, x.rb().as_dyn_stride(), Conj::No);
+				for i in 0..n {
+					x[i] = x[i] - d * u[(i, c)];
+				}
+			}
+
+			let mut nrm = zero::<T>();
+			for i in 0..n {
+				nrm = max(nrm, abs(x[i]));
+			}
+			if nrm >= growth_tol {
+				converged += 1;
+				if converged > EXTRA_ITERS {
+					break;
+				}
+			}
+		}
+		// the growth test is written so that a nan iterate counts as a failure
+		if converged == 0 {
+			failed = true;
+		}
+
+		let mut imax = 0;
+		for i in 0..n {
+			if abs(x[i]) > abs(x[imax]) {
+				imax = i;
+			}
+		}
+		let mut scale = recip(x.rb().norm_l2());
+		if x[imax] < zero() {
+			scale = -scale;
+		}
+		for i in 0..n {
+			u[(i, j)] = x[i] * scale;
+		}
+	}
+
+	if failed { Err(EvdError::NoConvergence) } else { Ok(()) }
+}
+
 #[cfg(test)]
 mod evd_qr_tests {
 	use dyn_stack::MemBuffer;
@@ -0,0 +1,871 @@
+//! low level implementation of the generalized eigenvalue decomposition of a square matrix pencil
+//!
+//! the generalized eigenvalues of a pencil $(A, B)$ of shape $(n, n)$ are the values $\lambda$ for
+//! which $A - \lambda B$ is singular. they are represented as pairs $(\alpha, \beta)$ with
+//! $\lambda = \alpha / \beta$, so that infinite eigenvalues (corresponding to $\beta = 0$) can be
+//! represented as well
+//!
+//! the right eigenvectors $x$ and left eigenvectors $y$ satisfy
+//!
+//! $$\beta A x = \alpha B x, \quad \beta y^H A = \alpha y^H B$$
+//!
+//! they are computed from the generalized schur decomposition
+//!
+//! $$A = Q S Z^H, \quad B = Q P Z^H$$
+//!
+//! where $Q$ and $Z$ are unitary, and $S$ and $P$ are upper triangular, which is obtained by
+//! reducing the pencil to hessenberg-triangular form, then applying the qz algorithm
+//!
+//! if $A$ is self-adjoint and $B$ is self-adjoint positive definite, the problem can instead be
+//! reduced to a standard self-adjoint eigenproblem using the cholesky factorization of $B$, in
+//! which case the eigenvalues are real and the eigenvectors can be chosen to be $B$-orthonormal
+
+use crate::assert;
+use crate::internal_prelude::*;
+use linalg::cholesky::llt::factor::LltError;
+use linalg::evd::{ComputeEigenvectors, SelfAdjointEvdParams};
+use linalg::matmul::triangular::BlockStructure;
+
+pub(crate) mod qz;
+
+/// generalized eigendecomposition error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GevdError {
+	/// reached max iterations
+	NoConvergence,
+}
+
+/// generalized self-adjoint eigendecomposition error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelfAdjointGevdError {
+	/// the matrix $B$ is not positive definite, the leading minor of order `index + 1` being
+	/// non-positive
+	NonPositivePivot {
+		/// index of the first non-positive pivot of the cholesky factorization
+		index: usize,
+	},
+	/// reached max iterations
+	NoConvergence,
+}
+
+/// type of a generalized self-adjoint eigenproblem, with $A$ self-adjoint and $B$ self-adjoint
+/// positive definite
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelfAdjointGevdProblem {
+	/// $A x = \lambda B x$, the eigenvectors are $B$-orthonormal
+	AxLambdaBx,
+	/// $A B x = \lambda x$, the eigenvectors are $B$-orthonormal
+	ABxLambdaX,
+	/// $B A x = \lambda x$, the eigenvectors are $B^{-1}$-orthonormal
+	BAxLambdaX,
+}
+
+/// computes the size and alignment of the workspace required to compute the generalized schur
+/// decomposition of a matrix pencil
+pub fn generalized_schur_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	qz::hessenberg_triangular_scratch::<T>(dim, par)
+}
+
+#[math]
+pub(crate) fn generalized_schur_imp<T: ComplexField>(
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	S: MatMut<'_, T>,
+	P: MatMut<'_, T>,
+	Q: Option<MatMut<'_, T>>,
+	Z: Option<MatMut<'_, T>>,
+	alpha: ColMut<'_, T>,
+	beta: ColMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), GevdError> {
+	let n = A.nrows();
+
+	for j in 0..n {
+		for i in 0..n {
+			if !(is_finite(A[(i, j)]) && is_finite(B[(i, j)])) {
+				return Err(GevdError::NoConvergence);
+			}
+		}
+	}
+
+	let mut S = S;
+	let mut P = P;
+	let mut Q = Q;
+	let mut Z = Z;
+
+	S.copy_from(A);
+	P.copy_from(B);
+
+	qz::hessenberg_triangular_in_place(S.rb_mut(), P.rb_mut(), Q.rb_mut(), Z.rb_mut(), par, stack);
+
+	if qz::qz(S.rb_mut(), P.rb_mut(), Q.rb_mut(), Z.rb_mut(), alpha, beta) != 0 {
+		return Err(GevdError::NoConvergence);
+	}
+
+	z!(S.rb_mut()).for_each_triangular_lower(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+	z!(P.rb_mut()).for_each_triangular_lower(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+
+	Ok(())
+}
+
+/// computes the generalized schur decomposition of the pencil $(A, B)$
+///
+/// $$A = Q S Z^H, \quad B = Q P Z^H$$
+///
+/// $S$ and $P$ are upper triangular, the diagonal of $P$ is real and non-negative, and $Q$ and $Z$
+/// are unitary. the generalized eigenvalues are stored as pairs $(\alpha_i, \beta_i) = (S_{ii},
+/// P_{ii})$
+#[track_caller]
+pub fn generalized_schur_cplx<T: RealField>(
+	A: MatRef<'_, Complex<T>>,
+	B: MatRef<'_, Complex<T>>,
+	s: MatMut<'_, Complex<T>>,
+	p: MatMut<'_, Complex<T>>,
+	q: Option<MatMut<'_, Complex<T>>>,
+	z: Option<MatMut<'_, Complex<T>>>,
+	alpha: DiagMut<'_, Complex<T>>,
+	beta: DiagMut<'_, Complex<T>>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), GevdError> {
+	let n = A.nrows();
+	assert!(all(
+		A.ncols() == n,
+		B.nrows() == n,
+		B.ncols() == n,
+		s.nrows() == n,
+		s.ncols() == n,
+		p.nrows() == n,
+		p.ncols() == n,
+		alpha.dim() == n,
+		beta.dim() == n,
+	));
+	if let Some(q) = q.rb() {
+		assert!(all(q.nrows() == n, q.ncols() == n));
+	}
+	if let Some(z) = z.rb() {
+		assert!(all(z.nrows() == n, z.ncols() == n));
+	}
+
+	generalized_schur_imp(A, B, s, p, q, z, alpha.column_vector_mut(), beta.column_vector_mut(), par, stack)
+}
+
+/// reorders the generalized schur decomposition $A = Q S Z^H$, $B = Q P Z^H$ so that the
+/// generalized eigenvalues for which `select` is `true` are moved to the leading diagonal block of
+/// $(S, P)$, keeping their relative order
+///
+/// the first columns of the updated $Z$ then span the corresponding right deflating subspace of
+/// the pencil. the diagonal of $P$ is kept real and non-negative. returns the dimension of the
+/// leading block
+#[track_caller]
+pub fn reorder_generalized_schur_cplx<T: RealField>(
+	s: MatMut<'_, Complex<T>>,
+	p: MatMut<'_, Complex<T>>,
+	q: Option<MatMut<'_, Complex<T>>>,
+	z: Option<MatMut<'_, Complex<T>>>,
+	select: &[bool],
+) -> usize {
+	reorder_generalized_schur_imp(s, p, q, z, select)
+}
+
+#[math]
+#[track_caller]
+pub(crate) fn reorder_generalized_schur_imp<T: ComplexField>(
+	S: MatMut<'_, T>,
+	P: MatMut<'_, T>,
+	Q: Option<MatMut<'_, T>>,
+	Z: Option<MatMut<'_, T>>,
+	select: &[bool],
+) -> usize {
+	let n = S.nrows();
+	assert!(all(S.ncols() == n, P.nrows() == n, P.ncols() == n, select.len() == n));
+	if let Some(q) = Q.rb() {
+		assert!(all(q.nrows() == n, q.ncols() == n));
+	}
+	if let Some(z) = Z.rb() {
+		assert!(all(z.nrows() == n, z.ncols() == n));
+	}
+
+	let mut S = S;
+	let mut P = P;
+	let mut Q = Q;
+	let mut Z = Z;
+
+	let mut ks = 0;
+	for (k, &selected) in select.iter().enumerate() {
+		if selected {
+			for i in (ks..k).rev() {
+				qz::swap(S.rb_mut(), P.rb_mut(), Q.rb_mut(), Z.rb_mut(), i);
+			}
+			ks += 1;
+		}
+	}
+
+	// scale the rows of the pencil so that the diagonal of $P$ is real and non-negative
+	for k in 0..n {
+		let d = abs(P[(k, k)]);
+		if d > zero() {
+			let phase = mul_real(P[(k, k)], recip(d));
+			let phase_conj = conj(phase);
+			zip!(S.rb_mut().row_mut(k).get_mut(k..)).for_each(|unzip!(x)| *x = phase_conj * *x);
+			zip!(P.rb_mut().row_mut(k).get_mut(k + 1..)).for_each(|unzip!(x)| *x = phase_conj * *x);
+			P[(k, k)] = from_real(d);
+			if let Some(Q) = Q.rb_mut() {
+				zip!(Q.col_mut(k)).for_each(|unzip!(x)| *x = *x * phase);
+			}
+		}
+	}
+
+	ks
+}
+
+/// computes the size and alignment of the workspace required to compute the generalized
+/// eigendecomposition of a matrix pencil
+pub fn gevd_scratch<T: ComplexField>(dim: usize, eigen_left: ComputeEigenvectors, eigen_right: ComputeEigenvectors, par: Par) -> StackReq {
+	let n = dim;
+
+	if n == 0 {
+		return StackReq::EMPTY;
+	}
+
+	let left = eigen_left == ComputeEigenvectors::Yes;
+	let right = eigen_right == ComputeEigenvectors::Yes;
+
+	let SP = temp_mat_scratch::<T>(n, n).array(2);
+	let Q = temp_mat_scratch::<T>(n, if left { n } else { 0 });
+	let Z = temp_mat_scratch::<T>(n, if right { n } else { 0 });
+	let X = temp_mat_scratch::<T>(n, n);
+
+	StackReq::all_of(&[SP, Q, Z, StackReq::any_of(&[generalized_schur_scratch::<T>(n, par), X])])
+}
+
+/// computes the eigenvectors of the upper triangular pencil $(S, P)$, storing them in the upper
+/// triangular part of $X$, similarly to lapack `tgevc`
+///
+/// if `left` is set, the left eigenvectors are computed instead, and stored in the lower
+/// triangular part of $X$
+#[math]
+fn gevd_from_schur_imp<T: ComplexField>(S: MatRef<'_, T>, P: MatRef<'_, T>, X: MatMut<'_, T>, left: bool) {
+	let n = S.nrows();
+	let mut X = X;
+
+	let ulp = eps::<T::Real>();
+	let safmin = min_positive::<T::Real>();
+	let bignum = recip(safmin * from_f64::<T::Real>(n as f64));
+
+	let ascale = recip(max(safmin, S.norm_l2()));
+	let bscale = recip(max(safmin, P.norm_l2()));
+
+	X.fill(zero());
+
+	for k in 0..n {
+		let a = mul_real(S[(k, k)], ascale);
+		let b = mul_real(P[(k, k)], bscale);
+
+		if abs(S[(k, k)]) <= safmin && abs(P[(k, k)]) <= safmin {
+			// singular pencil, return the unit vector
+			X[(k, k)] = one();
+			continue;
+		}
+
+		// the eigenvector lies in the kernel of b A - a B, where A and B are the scaled factors
+		let t = recip(max(max(abs1(a), abs1(b)), safmin));
+		let a = mul_real(a, t * bscale);
+		let b = mul_real(b, t * ascale);
+		let dmin = max(safmin, ulp * (abs1(a) / bscale + abs1(b) / ascale));
+
+		let coef = |i: usize, j: usize| b * S[(i, j)] - a * P[(i, j)];
+
+		X[(k, k)] = one();
+
+		if !left {
+			for j in (0..k).rev() {
+				let mut sum = zero::<T>();
+				for i in j + 1..k + 1 {
+					sum = sum + coef(j, i) * X[(i, k)];
+				}
+				let mut d = coef(j, j);
+				if abs1(d) < dmin {
+					d = from_real(dmin);
+				}
+				X[(j, k)] = -sum * recip(d);
+
+				if abs1(X[(j, k)]) > bignum {
+					let scale = recip(abs1(X[(j, k)]));
+					for i in j..k + 1 {
+						X[(i, k)] = mul_real(X[(i, k)], scale);
+					}
+				}
+			}
+		} else {
+			for j in k + 1..n {
+				let mut sum = zero::<T>();
+				for i in k..j {
+					sum = sum + conj(coef(i, j)) * X[(i, k)];
+				}
+				let mut d = conj(coef(j, j));
+				if abs1(d) < dmin {
+					d = from_real(dmin);
+				}
+				X[(j, k)] = -sum * recip(d);
+
+				if abs1(X[(j, k)]) > bignum {
+					let scale = recip(abs1(X[(j, k)]));
+					for i in k..j + 1 {
+						X[(i, k)] = mul_real(X[(i, k)], scale);
+					}
+				}
+			}
+		}
+	}
+}
+
+#[math]
+fn gevd_imp<T: ComplexField>(
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	alpha: ColMut<'_, T>,
+	beta: ColMut<'_, T>,
+	u_left: Option<MatMut<'_, T>>,
+	u_right: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), GevdError> {
+	let n = A.nrows();
+
+	if n == 0 {
+		return Ok(());
+	}
+
+	let (mut S, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut P, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut Q, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, if u_left.is_some() { n } else { 0 }, stack) };
+	let (mut Z, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, if u_right.is_some() { n } else { 0 }, stack) };
+
+	let mut S = S.as_mat_mut();
+	let mut P = P.as_mat_mut();
+	let mut Q = if u_left.is_some() { Some(Q.as_mat_mut()) } else { None };
+	let mut Z = if u_right.is_some() { Some(Z.as_mat_mut()) } else { None };
+
+	generalized_schur_imp(A, B, S.rb_mut(), P.rb_mut(), Q.rb_mut(), Z.rb_mut(), alpha, beta, par, stack)?;
+
+	let S = S.rb();
+	let P = P.rb();
+
+	for (u, Q, left) in [(u_left, Q.rb(), true), (u_right, Z.rb(), false)] {
+		let (Some(mut u), Some(Q)) = (u, Q) else {
+			continue;
+		};
+
+		let (mut X, _) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let mut X = X.as_mat_mut();
+		gevd_from_schur_imp(S, P, X.rb_mut(), left);
+
+		linalg::matmul::triangular::matmul(
+			u.rb_mut(),
+			BlockStructure::Rectangular,
+			Accum::Replace,
+			Q,
+			BlockStructure::Rectangular,
+			X.rb(),
+			if left {
+				BlockStructure::TriangularLower
+			} else {
+				BlockStructure::TriangularUpper
+			},
+			one(),
+			par,
+		);
+
+		for j in 0..n {
+			let norm = u.rb().col(j).norm_l2();
+			if norm > zero() {
+				let norm_inv = recip(norm);
+				z!(u.rb_mut().col_mut(j)).for_each(|uz!(x)| *x = mul_real(*x, norm_inv));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// computes the generalized eigendecomposition of the pencil $(A, B)$
+///
+/// the eigenvalues are stored as pairs $(\alpha_i, \beta_i)$, the left eigenvectors in $U_L$ and
+/// the right eigenvectors in $U_R$, such that
+///
+/// $$\beta_i A u_{R, i} = \alpha_i B u_{R, i}, \quad \beta_i u_{L, i}^H A = \alpha_i u_{L, i}^H B$$
+///
+/// the eigenvectors are normalized to have unit norm
+#[track_caller]
+pub fn gevd_cplx<T: RealField>(
+	A: MatRef<'_, Complex<T>>,
+	B: MatRef<'_, Complex<T>>,
+	alpha: DiagMut<'_, Complex<T>>,
+	beta: DiagMut<'_, Complex<T>>,
+	u_left: Option<MatMut<'_, Complex<T>>>,
+	u_right: Option<MatMut<'_, Complex<T>>>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), GevdError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, B.nrows() == n, B.ncols() == n, alpha.dim() == n, beta.dim() == n));
+	if let Some(u) = u_left.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+	if let Some(u) = u_right.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+
+	gevd_imp(A, B, alpha.column_vector_mut(), beta.column_vector_mut(), u_left, u_right, par, stack)
+}
+
+/// computes the size and alignment of the workspace required to compute the eigendecomposition of
+/// a generalized self-adjoint definite eigenproblem
+pub fn self_adjoint_gevd_scratch<T: ComplexField>(
+	dim: usize,
+	compute_u: ComputeEigenvectors,
+	par: Par,
+	params: Spec<SelfAdjointEvdParams, T>,
+) -> StackReq {
+	let n = dim;
+
+	if n == 0 {
+		return StackReq::EMPTY;
+	}
+
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n, n).array(3),
+		StackReq::any_of(&[
+			linalg::cholesky::llt::factor::cholesky_in_place_scratch::<T>(n, par, default()),
+			linalg::evd::self_adjoint_evd_scratch::<T>(n, compute_u, par, params),
+		]),
+	])
+}
+
+/// computes the eigendecomposition of the generalized self-adjoint eigenproblem given by `problem`,
+/// where $A$ is self-adjoint and $B$ is self-adjoint positive definite
+///
+/// the problem is reduced to a standard self-adjoint eigenproblem using the cholesky factorization
+/// $B = L L^H$. the eigenvalues are stored in $S$, sorted in nondecreasing order, and the
+/// eigenvectors in $U$, normalized so that $U^H B U = I$ (or $U^H B^{-1} U = I$ for
+/// [`SelfAdjointGevdProblem::BAxLambdaX`])
+///
+/// only the lower triangular halves of $A$ and $B$ are accessed
+#[math]
+#[track_caller]
+pub fn self_adjoint_gevd<T: ComplexField>(
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	problem: SelfAdjointGevdProblem,
+	s: DiagMut<'_, T>,
+	u: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<SelfAdjointEvdParams, T>,
+) -> Result<(), SelfAdjointGevdError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, B.nrows() == n, B.ncols() == n, s.dim() == n));
+	if let Some(u) = u.rb() {
+		assert!(all(u.nrows() == n, u.ncols() == n));
+	}
+
+	if n == 0 {
+		return Ok(());
+	}
+
+	let mut u = u;
+
+	let (mut L, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut C, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut L = L.as_mat_mut();
+	let mut C = C.as_mat_mut();
+	let mut W = W.as_mat_mut();
+
+	L.copy_from_triangular_lower(B);
+	linalg::cholesky::llt::factor::cholesky_in_place(L.rb_mut(), default(), par, stack, default()).map_err(|err| match err {
+		LltError::NonPositivePivot { index } => SelfAdjointGevdError::NonPositivePivot { index },
+	})?;
+	z!(L.rb_mut()).for_each_triangular_upper(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+	let L = L.rb();
+
+	// form the full self-adjoint matrix from its lower half
+	C.copy_from_triangular_lower(A);
+	for j in 0..n {
+		C[(j, j)] = from_real(real(C[(j, j)]));
+		for i in j + 1..n {
+			C[(j, i)] = conj(C[(i, j)]);
+		}
+	}
+
+	match problem {
+		SelfAdjointGevdProblem::AxLambdaBx => {
+			// C = L^{-1} A L^{-H}
+			linalg::triangular_solve::solve_lower_triangular_in_place(L, C.rb_mut(), par);
+			linalg::triangular_solve::solve_lower_triangular_in_place(L.conjugate(), C.rb_mut().transpose_mut(), par);
+		},
+		SelfAdjointGevdProblem::ABxLambdaX | SelfAdjointGevdProblem::BAxLambdaX => {
+			// C = L^H A L
+			linalg::matmul::triangular::matmul(
+				W.rb_mut(),
+				BlockStructure::Rectangular,
+				Accum::Replace,
+				C.rb(),
+				BlockStructure::Rectangular,
+				L,
+				BlockStructure::TriangularLower,
+				one(),
+				par,
+			);
+			linalg::matmul::triangular::matmul(
+				C.rb_mut(),
+				BlockStructure::Rectangular,
+				Accum::Replace,
+				L.adjoint(),
+				BlockStructure::TriangularUpper,
+				W.rb(),
+				BlockStructure::Rectangular,
+				one(),
+				par,
+			);
+		},
+	}
+
+	linalg::evd::self_adjoint_evd(C.rb(), s, u.rb_mut(), par, stack, params).map_err(|_| SelfAdjointGevdError::NoConvergence)?;
+
+	if let Some(mut u) = u {
+		match problem {
+			SelfAdjointGevdProblem::AxLambdaBx | SelfAdjointGevdProblem::ABxLambdaX => {
+				// U = L^{-H} Y
+				linalg::triangular_solve::solve_upper_triangular_in_place(L.adjoint(), u.rb_mut(), par);
+			},
+			SelfAdjointGevdProblem::BAxLambdaX => {
+				// U = L Y
+				W.copy_from(u.rb());
+				linalg::matmul::triangular::matmul(
+					u.rb_mut(),
+					BlockStructure::Rectangular,
+					Accum::Replace,
+					L,
+					BlockStructure::TriangularLower,
+					W.rb(),
+					BlockStructure::Rectangular,
+					one(),
+					par,
+				);
+			},
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::linalg::solvers::Solve;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn random(n: usize, rng: &mut StdRng) -> Mat<c64> {
+		CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng)
+	}
+
+	#[test]
+	fn test_generalized_schur() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 2, 3, 10, 40] {
+			let A = random(n, rng);
+			let B = random(n, rng);
+
+			let mut S = Mat::zeros(n, n);
+			let mut P = Mat::zeros(n, n);
+			let mut Q = Mat::zeros(n, n);
+			let mut Z = Mat::zeros(n, n);
+			let mut alpha = Diag::zeros(n);
+			let mut beta = Diag::zeros(n);
+
+			generalized_schur_cplx(
+				A.as_ref(),
+				B.as_ref(),
+				S.as_mut(),
+				P.as_mut(),
+				Some(Q.as_mut()),
+				Some(Z.as_mut()),
+				alpha.as_mut(),
+				beta.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(generalized_schur_scratch::<c64>(n, Par::Seq))),
+			)
+			.unwrap();
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+			assert!(&Q * &S * Z.adjoint() ~ A);
+			assert!(&Q * &P * Z.adjoint() ~ B);
+			assert!(Q.adjoint() * &Q ~ Mat::<c64>::identity(n, n));
+			assert!(Z.adjoint() * &Z ~ Mat::<c64>::identity(n, n));
+
+			for j in 0..n {
+				assert!(alpha[j] == S[(j, j)]);
+				assert!(beta[j] == P[(j, j)]);
+				assert!(all(beta[j].im == 0.0, beta[j].re >= 0.0));
+				for i in j + 1..n {
+					assert!(all(S[(i, j)] == c64::ZERO, P[(i, j)] == c64::ZERO));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_reorder_generalized_schur() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [1, 2, 3, 10, 40] {
+			let A = random(n, rng);
+			let B = random(n, rng);
+
+			let mut S = Mat::zeros(n, n);
+			let mut P = Mat::zeros(n, n);
+			let mut Q = Mat::zeros(n, n);
+			let mut Z = Mat::zeros(n, n);
+			let mut alpha = Diag::zeros(n);
+			let mut beta = Diag::zeros(n);
+
+			generalized_schur_cplx(
+				A.as_ref(),
+				B.as_ref(),
+				S.as_mut(),
+				P.as_mut(),
+				Some(Q.as_mut()),
+				Some(Z.as_mut()),
+				alpha.as_mut(),
+				beta.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(generalized_schur_scratch::<c64>(n, Par::Seq))),
+			)
+			.unwrap();
+
+			let select = (0..n).map(|j| abs(&alpha[j]) < abs(&beta[j])).collect::<Vec<_>>();
+			let mut expected = (0..n).filter(|&j| select[j]).map(|j| alpha[j] / beta[j]).collect::<Vec<_>>();
+			let k = reorder_generalized_schur_cplx(S.as_mut(), P.as_mut(), Some(Q.as_mut()), Some(Z.as_mut()), &select);
+			assert!(k == expected.len());
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+			assert!(&Q * &S * Z.adjoint() ~ A);
+			assert!(&Q * &P * Z.adjoint() ~ B);
+			assert!(Q.adjoint() * &Q ~ Mat::<c64>::identity(n, n));
+			assert!(Z.adjoint() * &Z ~ Mat::<c64>::identity(n, n));
+
+			for j in 0..n {
+				assert!(all(P[(j, j)].im == 0.0, P[(j, j)].re >= 0.0));
+				for i in j + 1..n {
+					assert!(all(S[(i, j)] == c64::ZERO, P[(i, j)] == c64::ZERO));
+				}
+			}
+
+			for j in 0..k {
+				let lambda = S[(j, j)] / P[(j, j)];
+				assert!(abs(&lambda) < 1.0);
+				let pos = expected.iter().position(|mu| abs(&(lambda - mu)) < 1e-8).unwrap();
+				expected.remove(pos);
+			}
+		}
+	}
+
+	#[test]
+	fn test_gevd() {
+		let rng = &mut StdRng::seed_from_u64(1);
+
+		for n in [1, 2, 3, 10, 40] {
+			let A = random(n, rng);
+			let B = random(n, rng);
+
+			let mut alpha = Diag::zeros(n);
+			let mut beta = Diag::zeros(n);
+			let mut UL = Mat::zeros(n, n);
+			let mut UR = Mat::zeros(n, n);
+
+			gevd_cplx(
+				A.as_ref(),
+				B.as_ref(),
+				alpha.as_mut(),
+				beta.as_mut(),
+				Some(UL.as_mut()),
+				Some(UR.as_mut()),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(gevd_scratch::<c64>(
+					n,
+					ComputeEigenvectors::Yes,
+					ComputeEigenvectors::Yes,
+					Par::Seq,
+				))),
+			)
+			.unwrap();
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+			assert!(&A * &UR * beta.as_ref() ~ &B * &UR * alpha.as_ref());
+			assert!(beta.as_ref() * UL.adjoint() * &A ~ alpha.as_ref() * UL.adjoint() * &B);
+		}
+	}
+
+	#[test]
+	fn test_gevd_infinite() {
+		let rng = &mut StdRng::seed_from_u64(2);
+		let n = 8;
+
+		// B has rank n - 2, so the pencil has two infinite eigenvalues
+		let A = random(n, rng);
+		let mut B = random(n, rng);
+		for j in 0..n {
+			B[(n - 1, j)] = B[(0, j)];
+			B[(n - 2, j)] = B[(1, j)] * c64::new(2.0, -1.0);
+		}
+
+		let mut alpha = Diag::zeros(n);
+		let mut beta = Diag::zeros(n);
+		let mut UR = Mat::zeros(n, n);
+
+		gevd_cplx(
+			A.as_ref(),
+			B.as_ref(),
+			alpha.as_mut(),
+			beta.as_mut(),
+			None,
+			Some(UR.as_mut()),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(gevd_scratch::<c64>(
+				n,
+				ComputeEigenvectors::No,
+				ComputeEigenvectors::Yes,
+				Par::Seq,
+			))),
+		)
+		.unwrap();
+
+		let infinite = (0..n).filter(|&i| beta[i].norm_sqr().sqrt() < 1e-10 * alpha[i].norm_sqr().sqrt()).count();
+		assert!(infinite == 2);
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+		assert!(&A * &UR * beta.as_ref() ~ &B * &UR * alpha.as_ref());
+	}
+
+	fn random_hpd(n: usize, rng: &mut StdRng) -> Mat<c64> {
+		let X = random(n, rng);
+		&X * X.adjoint() + Mat::<c64>::identity(n, n)
+	}
+
+	#[test]
+	fn test_self_adjoint_gevd() {
+		let rng = &mut StdRng::seed_from_u64(3);
+
+		for n in [0, 1, 2, 10, 40] {
+			let X = random(n, rng);
+			let A = &X + X.adjoint();
+			let B = random_hpd(n, rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+
+			for problem in [
+				SelfAdjointGevdProblem::AxLambdaBx,
+				SelfAdjointGevdProblem::ABxLambdaX,
+				SelfAdjointGevdProblem::BAxLambdaX,
+			] {
+				let mut s = Diag::zeros(n);
+				let mut U = Mat::zeros(n, n);
+
+				self_adjoint_gevd(
+					A.as_ref(),
+					B.as_ref(),
+					problem,
+					s.as_mut(),
+					Some(U.as_mut()),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(self_adjoint_gevd_scratch::<c64>(
+						n,
+						ComputeEigenvectors::Yes,
+						Par::Seq,
+						default(),
+					))),
+					default(),
+				)
+				.unwrap();
+
+				for i in 1..n {
+					assert!(s[i - 1].re <= s[i].re);
+				}
+
+				match problem {
+					SelfAdjointGevdProblem::AxLambdaBx => {
+						assert!(&A * &U ~ &B * &U * s.as_ref());
+						assert!(U.adjoint() * &B * &U ~ Mat::<c64>::identity(n, n));
+					},
+					SelfAdjointGevdProblem::ABxLambdaX => {
+						assert!(&A * &B * &U ~ &U * s.as_ref());
+						assert!(U.adjoint() * &B * &U ~ Mat::<c64>::identity(n, n));
+					},
+					SelfAdjointGevdProblem::BAxLambdaX => {
+						assert!(&B * &A * &U ~ &U * s.as_ref());
+						let Binv_U = B.as_ref().llt(Side::Lower).unwrap().solve(&U);
+						assert!(U.adjoint() * &Binv_U ~ Mat::<c64>::identity(n, n));
+					},
+				}
+
+				let mut s2 = Diag::zeros(n);
+				self_adjoint_gevd(
+					A.as_ref(),
+					B.as_ref(),
+					problem,
+					s2.as_mut(),
+					None,
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(self_adjoint_gevd_scratch::<c64>(
+						n,
+						ComputeEigenvectors::No,
+						Par::Seq,
+						default(),
+					))),
+					default(),
+				)
+				.unwrap();
+				assert!(s2.column_vector() ~ s.column_vector());
+			}
+		}
+	}
+
+	#[test]
+	fn test_self_adjoint_gevd_not_positive_definite() {
+		let rng = &mut StdRng::seed_from_u64(4);
+		let n = 5;
+
+		let A = random_hpd(n, rng);
+		let B = -random_hpd(n, rng);
+
+		let mut s = Diag::zeros(n);
+		let err = self_adjoint_gevd(
+			A.as_ref(),
+			B.as_ref(),
+			SelfAdjointGevdProblem::AxLambdaBx,
+			s.as_mut(),
+			None,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(self_adjoint_gevd_scratch::<c64>(
+				n,
+				ComputeEigenvectors::No,
+				Par::Seq,
+				default(),
+			))),
+			default(),
+		);
+		assert!(err == Err(SelfAdjointGevdError::NonPositivePivot { index: 0 }));
+	}
+}
@@ -0,0 +1,439 @@
+use crate::internal_prelude::*;
+use linalg::jacobi::JacobiRotation;
+
+/// returns a plane rotation $G$ and $r$ such that $G \begin{bmatrix} f \\\\ g \end{bmatrix} =
+/// \begin{bmatrix} r \\\\ 0 \end{bmatrix}$
+#[math]
+fn make_rot<T: ComplexField>(f: T, g: T) -> (JacobiRotation<T>, T) {
+	if g == zero() {
+		(JacobiRotation { c: one(), s: zero() }, f)
+	} else {
+		JacobiRotation::rotg(f, g)
+	}
+}
+
+/// applies $G$ to the rows `i0` and `i1` of $M$
+#[inline]
+fn rot_rows<T: ComplexField>(rot: &JacobiRotation<T>, M: MatMut<'_, T>, i0: usize, i1: usize) {
+	rot.adjoint().apply_on_the_left_in_place(M.two_rows_mut(i0, i1));
+}
+
+/// applies $G$ to the columns `j0` and `j1` of $M$, viewed as rows
+#[inline]
+fn rot_cols<T: ComplexField>(rot: &JacobiRotation<T>, M: MatMut<'_, T>, j0: usize, j1: usize) {
+	let (x, y) = M.two_cols_mut(j0, j1);
+	rot.adjoint().apply_on_the_left_in_place((x.transpose_mut(), y.transpose_mut()));
+}
+
+/// multiplies the columns `j0` and `j1` of $M$ by $G^H$ from the right, which accumulates a
+/// rotation applied with [`rot_rows`]
+#[inline]
+fn rot_cols_adjoint<T: ComplexField>(rot: &JacobiRotation<T>, M: MatMut<'_, T>, j0: usize, j1: usize) {
+	rot.apply_on_the_right_in_place(M.two_cols_mut(j0, j1));
+}
+
+pub(crate) fn hessenberg_triangular_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	let n = dim;
+	if n == 0 {
+		return StackReq::EMPTY;
+	}
+
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+
+	temp_mat_scratch::<T>(bs, n).and(StackReq::any_of(&[
+		linalg::qr::no_pivoting::factor::qr_in_place_scratch::<T>(n, n, bs, par, default()),
+		linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_scratch::<T>(n, bs, n),
+		linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(n, bs, n),
+	]))
+}
+
+/// reduces the pencil $(S, P)$ to hessenberg-triangular form, similarly to lapack `gghrd`, after
+/// a qr decomposition of $P$
+///
+/// $Q$ and $Z$ are overwritten with the accumulated left and right unitary factors, if provided
+#[math]
+pub(crate) fn hessenberg_triangular_in_place<T: ComplexField>(
+	S: MatMut<'_, T>,
+	P: MatMut<'_, T>,
+	Q: Option<MatMut<'_, T>>,
+	Z: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let n = S.nrows();
+	if n == 0 {
+		return;
+	}
+
+	let mut S = S;
+	let mut P = P;
+	let mut Q = Q;
+	let mut Z = Z;
+
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+
+	{
+		let (mut householder, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs, n, stack) };
+		let mut householder = householder.as_mat_mut();
+
+		linalg::qr::no_pivoting::factor::qr_in_place(P.rb_mut(), householder.rb_mut(), par, stack, default());
+
+		linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(
+			P.rb(),
+			householder.rb(),
+			Conj::Yes,
+			S.rb_mut(),
+			par,
+			stack,
+		);
+
+		if let Some(mut Q) = Q.rb_mut() {
+			Q.fill(zero());
+			Q.rb_mut().diagonal_mut().fill(one());
+
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+				P.rb(),
+				householder.rb(),
+				Conj::No,
+				Q.rb_mut(),
+				par,
+				stack,
+			);
+		}
+	}
+
+	z!(P.rb_mut()).for_each_triangular_lower(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+
+	if let Some(mut Z) = Z.rb_mut() {
+		Z.fill(zero());
+		Z.rb_mut().diagonal_mut().fill(one());
+	}
+
+	for j in 0..n.saturating_sub(2) {
+		for i in (j + 2..n).rev() {
+			// annihilate S[i, j] with a rotation of the rows i - 1 and i
+			let (rot, r) = make_rot(copy(S[(i - 1, j)]), copy(S[(i, j)]));
+			S[(i - 1, j)] = r;
+			S[(i, j)] = zero();
+
+			rot_rows(&rot, S.rb_mut().subcols_mut(j + 1, n - j - 1), i - 1, i);
+			rot_rows(&rot, P.rb_mut().subcols_mut(i - 1, n - i + 1), i - 1, i);
+			if let Some(Q) = Q.rb_mut() {
+				rot_cols_adjoint(&rot, Q, i - 1, i);
+			}
+
+			// annihilate the fill-in P[i, i - 1] with a rotation of the columns i - 1 and i
+			let (rot, r) = make_rot(copy(P[(i, i)]), copy(P[(i, i - 1)]));
+			P[(i, i)] = r;
+			P[(i, i - 1)] = zero();
+
+			rot_cols(&rot, P.rb_mut().subrows_mut(0, i), i, i - 1);
+			rot_cols(&rot, S.rb_mut(), i, i - 1);
+			if let Some(Z) = Z.rb_mut() {
+				rot_cols(&rot, Z, i, i - 1);
+			}
+		}
+	}
+}
+
+enum Step {
+	Deflate,
+	SplitInfinite,
+	Sweep(usize),
+}
+
+/// computes the generalized schur form of a pencil $(S, P)$ in hessenberg-triangular form using the
+/// single-shift complex qz algorithm, similarly to lapack `hgeqz`
+///
+/// on exit, $S$ and $P$ are upper triangular, and the diagonal of $P$ is real and non-negative.
+/// the generalized eigenvalues are given by the pairs $(\alpha_i, \beta_i)$. $Q$ and $Z$ are
+/// updated with the applied transformations, if provided
+///
+/// returns a nonzero value if the algorithm failed to converge
+#[math]
+pub(crate) fn qz<T: ComplexField>(
+	S: MatMut<'_, T>,
+	P: MatMut<'_, T>,
+	Q: Option<MatMut<'_, T>>,
+	Z: Option<MatMut<'_, T>>,
+	alpha: ColMut<'_, T>,
+	beta: ColMut<'_, T>,
+) -> isize {
+	let n = S.nrows();
+	if n == 0 {
+		return 0;
+	}
+
+	let mut S = S;
+	let mut P = P;
+	let mut Q = Q;
+	let mut Z = Z;
+	let mut alpha = alpha;
+	let mut beta = beta;
+
+	let ulp = eps::<T::Real>();
+	let safmin = min_positive::<T::Real>();
+
+	let anorm = S.norm_l2();
+	let bnorm = P.norm_l2();
+	let atol = max(safmin, ulp * anorm);
+	let btol = max(safmin, ulp * bnorm);
+	let ascale = recip(max(safmin, anorm));
+	let bscale = recip(max(safmin, bnorm));
+
+	let mut ilast = n - 1;
+	let mut iiter = 0usize;
+	let mut eshift = zero::<T>();
+
+	let maxit = 30 * n;
+	let mut jiter = 0usize;
+
+	loop {
+		if jiter >= maxit {
+			return (ilast + 1) as isize;
+		}
+		jiter += 1;
+
+		let step = if ilast == 0 {
+			Step::Deflate
+		} else if abs1(S[(ilast, ilast - 1)]) <= max(safmin, ulp * (abs1(S[(ilast, ilast)]) + abs1(S[(ilast - 1, ilast - 1)]))) {
+			S[(ilast, ilast - 1)] = zero();
+			Step::Deflate
+		} else if abs(P[(ilast, ilast)]) <= btol {
+			P[(ilast, ilast)] = zero();
+			Step::SplitInfinite
+		} else {
+			let mut step = Step::Sweep(0);
+
+			for j in (0..ilast).rev() {
+				let ilazro = if j == 0 {
+					true
+				} else if abs1(S[(j, j - 1)]) <= max(safmin, ulp * (abs1(S[(j, j)]) + abs1(S[(j - 1, j - 1)]))) {
+					S[(j, j - 1)] = zero();
+					true
+				} else {
+					false
+				};
+
+				if abs(P[(j, j)]) < btol {
+					P[(j, j)] = zero();
+
+					// test whether the product of the two subdiagonal entries is negligible
+					let mut ilazr2 = !ilazro && abs1(S[(j, j - 1)]) * (ascale * abs1(S[(j + 1, j)])) <= abs1(S[(j, j)]) * (ascale * atol);
+
+					if ilazro || ilazr2 {
+						// chase the zero on the diagonal of P down, splitting S along the way
+						step = Step::SplitInfinite;
+						for jch in j..ilast {
+							let (rot, r) = make_rot(copy(S[(jch, jch)]), copy(S[(jch + 1, jch)]));
+							S[(jch, jch)] = r;
+							S[(jch + 1, jch)] = zero();
+
+							rot_rows(&rot, S.rb_mut().subcols_mut(jch + 1, n - jch - 1), jch, jch + 1);
+							rot_rows(&rot, P.rb_mut().subcols_mut(jch + 1, n - jch - 1), jch, jch + 1);
+							if let Some(Q) = Q.rb_mut() {
+								rot_cols_adjoint(&rot, Q, jch, jch + 1);
+							}
+							if ilazr2 {
+								S[(jch, jch - 1)] = S[(jch, jch - 1)] * rot.c;
+							}
+							ilazr2 = false;
+
+							if abs1(P[(jch + 1, jch + 1)]) >= btol {
+								step = if jch + 1 >= ilast { Step::Deflate } else { Step::Sweep(jch + 1) };
+								break;
+							}
+							P[(jch + 1, jch + 1)] = zero();
+						}
+					} else {
+						// chase the zero on the diagonal of P down to P[ilast, ilast]
+						for jch in j..ilast {
+							let (rot, r) = make_rot(copy(P[(jch, jch + 1)]), copy(P[(jch + 1, jch + 1)]));
+							P[(jch, jch + 1)] = r;
+							P[(jch + 1, jch + 1)] = zero();
+
+							rot_rows(&rot, P.rb_mut().subcols_mut(jch + 2, n - jch - 2), jch, jch + 1);
+							rot_rows(&rot, S.rb_mut().subcols_mut(jch - 1, n - jch + 1), jch, jch + 1);
+							if let Some(Q) = Q.rb_mut() {
+								rot_cols_adjoint(&rot, Q, jch, jch + 1);
+							}
+
+							let (rot, r) = make_rot(copy(S[(jch + 1, jch)]), copy(S[(jch + 1, jch - 1)]));
+							S[(jch + 1, jch)] = r;
+							S[(jch + 1, jch - 1)] = zero();
+
+							rot_cols(&rot, S.rb_mut().subrows_mut(0, jch + 1), jch, jch - 1);
+							rot_cols(&rot, P.rb_mut().subrows_mut(0, jch), jch, jch - 1);
+							if let Some(Z) = Z.rb_mut() {
+								rot_cols(&rot, Z, jch, jch - 1);
+							}
+						}
+						step = Step::SplitInfinite;
+					}
+					break;
+				} else if ilazro {
+					step = Step::Sweep(j);
+					break;
+				}
+			}
+
+			step
+		};
+
+		let step = if let Step::SplitInfinite = step {
+			// P[ilast, ilast] is zero, annihilate S[ilast, ilast - 1] to split off an infinite
+			// eigenvalue
+			let (rot, r) = make_rot(copy(S[(ilast, ilast)]), copy(S[(ilast, ilast - 1)]));
+			S[(ilast, ilast)] = r;
+			S[(ilast, ilast - 1)] = zero();
+
+			rot_cols(&rot, S.rb_mut().subrows_mut(0, ilast), ilast, ilast - 1);
+			rot_cols(&rot, P.rb_mut().subrows_mut(0, ilast), ilast, ilast - 1);
+			if let Some(Z) = Z.rb_mut() {
+				rot_cols(&rot, Z, ilast, ilast - 1);
+			}
+			Step::Deflate
+		} else {
+			step
+		};
+
+		match step {
+			Step::Deflate => {
+				// normalize so that beta is real and non-negative
+				let absb = abs(P[(ilast, ilast)]);
+				if absb > safmin {
+					let signbc = conj(mul_real(P[(ilast, ilast)], recip(absb)));
+					P[(ilast, ilast)] = from_real(absb);
+
+					z!(P.rb_mut().col_mut(ilast).subrows_mut(0, ilast)).for_each(|uz!(x)| *x = *x * signbc);
+					z!(S.rb_mut().col_mut(ilast).subrows_mut(0, ilast + 1)).for_each(|uz!(x)| *x = *x * signbc);
+					if let Some(Z) = Z.rb_mut() {
+						z!(Z.col_mut(ilast)).for_each(|uz!(x)| *x = *x * signbc);
+					}
+				} else {
+					P[(ilast, ilast)] = zero();
+				}
+
+				alpha[ilast] = copy(S[(ilast, ilast)]);
+				beta[ilast] = copy(P[(ilast, ilast)]);
+
+				if ilast == 0 {
+					return 0;
+				}
+				ilast -= 1;
+				iiter = 0;
+				eshift = zero();
+			},
+			Step::Sweep(ifirst) => {
+				iiter += 1;
+
+				let shift = if iiter % 10 != 0 {
+					// wilkinson-like shift from the trailing 2x2 block of the scaled pencil
+					let u12 = mul_real(P[(ilast - 1, ilast)], bscale) * recip(mul_real(P[(ilast, ilast)], bscale));
+					let ad11 = mul_real(S[(ilast - 1, ilast - 1)], ascale) * recip(mul_real(P[(ilast - 1, ilast - 1)], bscale));
+					let ad21 = mul_real(S[(ilast, ilast - 1)], ascale) * recip(mul_real(P[(ilast - 1, ilast - 1)], bscale));
+					let ad12 = mul_real(S[(ilast - 1, ilast)], ascale) * recip(mul_real(P[(ilast, ilast)], bscale));
+					let ad22 = mul_real(S[(ilast, ilast)], ascale) * recip(mul_real(P[(ilast, ilast)], bscale));
+					let abi22 = ad22 - u12 * ad21;
+					let abi12 = ad12 - u12 * ad11;
+
+					let mut shift = copy(abi22);
+					let ctemp = sqrt(abi12) * sqrt(ad21);
+					if ctemp != zero() {
+						let x = mul_real(ad11 - shift, from_f64::<T::Real>(0.5));
+						let temp2 = abs1(x);
+						let temp = max(abs1(ctemp), temp2);
+						let xs = mul_real(x, recip(temp));
+						let cs = mul_real(ctemp, recip(temp));
+						let mut y = mul_real(sqrt(xs * xs + cs * cs), temp);
+						if temp2 > zero() && real(conj(x) * y) < zero() {
+							y = -y;
+						}
+						shift = shift - ctemp * (ctemp * recip(x + y));
+					}
+					shift
+				} else {
+					// exceptional shift
+					eshift = eshift + mul_real(S[(ilast, ilast - 1)], ascale) * recip(mul_real(P[(ilast - 1, ilast - 1)], bscale));
+					copy(eshift)
+				};
+
+				let istart = ifirst;
+				let f = mul_real(S[(istart, istart)], ascale) - shift * mul_real(P[(istart, istart)], bscale);
+				let g = mul_real(S[(istart + 1, istart)], ascale);
+				let (mut rot, _) = make_rot(f, g);
+
+				for j in istart..ilast {
+					if j > istart {
+						let r;
+						(rot, r) = make_rot(copy(S[(j, j - 1)]), copy(S[(j + 1, j - 1)]));
+						S[(j, j - 1)] = r;
+						S[(j + 1, j - 1)] = zero();
+					}
+
+					rot_rows(&rot, S.rb_mut().subcols_mut(j, n - j), j, j + 1);
+					rot_rows(&rot, P.rb_mut().subcols_mut(j, n - j), j, j + 1);
+					if let Some(Q) = Q.rb_mut() {
+						rot_cols_adjoint(&rot, Q, j, j + 1);
+					}
+
+					let r;
+					(rot, r) = make_rot(copy(P[(j + 1, j + 1)]), copy(P[(j + 1, j)]));
+					P[(j + 1, j + 1)] = r;
+					P[(j + 1, j)] = zero();
+
+					rot_cols(&rot, S.rb_mut().subrows_mut(0, Ord::min(j + 2, ilast) + 1), j + 1, j);
+					rot_cols(&rot, P.rb_mut().subrows_mut(0, j + 1), j + 1, j);
+					if let Some(Z) = Z.rb_mut() {
+						rot_cols(&rot, Z, j + 1, j);
+					}
+				}
+			},
+			Step::SplitInfinite => unreachable!(),
+		}
+	}
+}
+
+/// swaps the adjacent diagonal entries `k` and `k + 1` of the upper triangular pencil $(S, P)$,
+/// similarly to lapack `tgex2`
+///
+/// $Q$ and $Z$ are updated with the applied transformations, if provided
+#[math]
+pub(crate) fn swap<T: ComplexField>(S: MatMut<'_, T>, P: MatMut<'_, T>, Q: Option<MatMut<'_, T>>, Z: Option<MatMut<'_, T>>, k: usize) {
+	let mut S = S;
+	let mut P = P;
+
+	let s11 = copy(S[(k, k)]);
+	let s12 = copy(S[(k, k + 1)]);
+	let s22 = copy(S[(k + 1, k + 1)]);
+	let p11 = copy(P[(k, k)]);
+	let p12 = copy(P[(k, k + 1)]);
+	let p22 = copy(P[(k + 1, k + 1)]);
+
+	// the first column of the right rotation spans the right eigenvector of the trailing
+	// eigenvalue, which is the kernel of $p_{22} S - s_{22} P$
+	let f = p22 * s11 - s22 * p11;
+	let g = p22 * s12 - s22 * p12;
+	let (rot, _) = make_rot(g, -f);
+	rot_cols_adjoint(&rot, S.rb_mut().get_mut(..k + 2, ..), k, k + 1);
+	rot_cols_adjoint(&rot, P.rb_mut().get_mut(..k + 2, ..), k, k + 1);
+	if let Some(Z) = Z {
+		rot_cols_adjoint(&rot, Z, k, k + 1);
+	}
+
+	// the left rotation then restores the triangular structure, using whichever of $S$ and $P$
+	// is better conditioned
+	let (rot, _) = if abs(s22) * abs(p11) >= abs(s11) * abs(p22) {
+		make_rot(copy(S[(k, k)]), copy(S[(k + 1, k)]))
+	} else {
+		make_rot(copy(P[(k, k)]), copy(P[(k + 1, k)]))
+	};
+	rot_rows(&rot, S.rb_mut().get_mut(.., k..), k, k + 1);
+	rot_rows(&rot, P.rb_mut().get_mut(.., k..), k, k + 1);
+	if let Some(Q) = Q {
+		rot_cols_adjoint(&rot, Q, k, k + 1);
+	}
+
+	S[(k + 1, k)] = zero();
+	P[(k + 1, k)] = zero();
+}
@@ -0,0 +1,559 @@
+use super::*;
+use crate::assert;
+use linalg::lu::partial_pivoting::{factor as lu_factor, solve as lu_solve};
+
+// coefficients of the numerator of the $[m/m]$ padé approximant of $e^x$, in increasing order
+const PADE_3: &[f64] = &[120.0, 60.0, 12.0, 1.0];
+const PADE_5: &[f64] = &[30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0];
+const PADE_7: &[f64] = &[17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0];
+const PADE_9: &[f64] = &[
+	17643225600.0,
+	8821612800.0,
+	2075673600.0,
+	302702400.0,
+	30270240.0,
+	2162160.0,
+	110880.0,
+	3960.0,
+	90.0,
+	1.0,
+];
+const PADE_13: &[f64] = &[
+	64764752532480000.0,
+	32382376266240000.0,
+	7771770303897600.0,
+	1187353796428800.0,
+	129060195264000.0,
+	10559470521600.0,
+	670442572800.0,
+	33522128640.0,
+	1323241920.0,
+	40840800.0,
+	960960.0,
+	16380.0,
+	182.0,
+	1.0,
+];
+
+// $(m, \theta_m)$ from al-mohy and higham (2009), a new scaling and squaring algorithm for the
+// matrix exponential
+const THETA: &[(usize, f64)] = &[
+	(3, 1.495585217958292e-2),
+	(5, 2.53939833006323e-1),
+	(7, 9.504178996162932e-1),
+	(9, 2.097847961257068e0),
+];
+const THETA_13: f64 = 4.25;
+
+// $(m, \ell_m)$ from al-mohy and higham (2009), computing the fréchet derivative of the matrix
+// exponential, with an application to condition number estimation
+const ELL_FRECHET: &[(usize, f64)] = &[(3, 1.08e-2), (5, 2.00e-1), (7, 7.83e-1), (9, 1.78e0)];
+const ELL_FRECHET_13: f64 = 4.74;
+
+fn pade_coeffs(m: usize) -> &'static [f64] {
+	match m {
+		3 => PADE_3,
+		5 => PADE_5,
+		7 => PADE_7,
+		9 => PADE_9,
+		_ => PADE_13,
+	}
+}
+
+// $1 / |c_{2m + 1}|$, where $c_{2m + 1}$ is the leading coefficient of the backward error of the
+// $[m/m]$ padé approximant
+fn backward_error_coeff_recip(m: usize) -> f64 {
+	match m {
+		3 => 100800.0,
+		5 => 10059033600.0,
+		7 => 4487938430976000.0,
+		9 => 5914384781877411840000.0,
+		_ => 113250775606021113483283660800000000.0,
+	}
+}
+
+/// computes the size and alignment of the workspace required to compute the exponential of a
+/// matrix of dimension `dim`, and optionally its fréchet derivative
+pub fn expm_scratch<T: ComplexField>(dim: usize, compute_frechet: bool, par: Par) -> StackReq {
+	let n = dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n, n).array(11),
+		temp_mat_scratch::<T>(n, n).array(if compute_frechet { 7 } else { 0 }),
+		StackReq::new::<usize>(n).array(2),
+		StackReq::any_of(&[
+			norm1_est_product_scratch::<T>(n),
+			lu_factor::lu_in_place_scratch::<usize, T>(n, n, par, default()),
+			lu_solve::solve_in_place_scratch::<usize, T>(n, n, par),
+		]),
+	])
+}
+
+// smallest $\ell \geq 0$ such that the backward error bound of the $[m/m]$ padé approximant of
+// $e^{2^{-\ell} A}$ is below the unit roundoff
+#[math]
+fn ell<T: ComplexField>(A: MatRef<'_, T>, abs_A: MatMut<'_, T>, m: usize, stack: &mut MemStack) -> usize {
+	let norm = norm1(A);
+	if norm == zero() {
+		return 0;
+	}
+
+	let mut abs_A = abs_A;
+	z!(abs_A.rb_mut(), A).for_each(|uz!(dst, src)| *dst = from_real(abs(*src)));
+
+	let factors = [abs_A.rb(); 27];
+	let est = norm1_est_product(&factors[..2 * m + 1], stack);
+
+	let u = mul_pow2(eps::<T::Real>(), from_f64::<T::Real>(0.5));
+	let ratio = est / norm / from_f64::<T::Real>(backward_error_coeff_recip(m)) / u;
+
+	let step = from_f64::<T::Real>((1u64 << (2 * m)) as f64);
+	let mut bound = one::<T::Real>();
+	let mut l = 0;
+	while ratio > bound {
+		bound = bound * step;
+		l += 1;
+	}
+	l
+}
+
+#[math]
+fn pow<T: ComplexField>(x: T::Real, k: usize) -> T::Real {
+	let mut out = one::<T::Real>();
+	for _ in 0..k {
+		out = out * x;
+	}
+	out
+}
+
+// dst = sum_k coeffs[k] * mats[k], plus `diag` times the identity
+#[math]
+fn lin_comb<T: ComplexField>(dst: MatMut<'_, T>, coeffs: &[f64], mats: &[MatRef<'_, T>], diag: f64) {
+	let mut dst = dst;
+	dst.fill(zero());
+	for (&c, &M) in coeffs.iter().zip(mats.iter()) {
+		let c = from_f64::<T::Real>(c);
+		z!(dst.rb_mut(), M).for_each(|uz!(dst, src)| *dst = *dst + mul_real(*src, c));
+	}
+	if diag != 0.0 {
+		let diag = from_f64::<T>(diag);
+		z!(dst.rb_mut().diagonal_mut().column_vector_mut()).for_each(|uz!(dst)| *dst = *dst + diag);
+	}
+}
+
+#[math]
+fn scale<T: ComplexField>(M: MatMut<'_, T>, factor: T::Real) {
+	z!(M).for_each(|uz!(x)| *x = mul_real(*x, factor));
+}
+
+/// computes the exponential $e^A$ of the square matrix $A$, and stores the result in `out`
+///
+/// if `frechet` is `Some((L, E))`, the fréchet derivative of the exponential at $A$ in the
+/// direction $E$, i.e., the linear term $L(A, E)$ in the expansion
+///
+/// $$e^{A + E} = e^A + L(A, E) + o(\|E\|)$$
+///
+/// is also computed and stored in $L$
+///
+/// this uses the scaling and squaring algorithm of al-mohy and higham (2009), where the degree of
+/// the padé approximant ($3$, $5$, $7$, $9$ or $13$) and the number of squarings are selected
+/// from $1$-norm estimates of powers of $A$. when the fréchet derivative is requested, the
+/// parameters are instead selected from the $1$-norm of $A$, as in their algorithm for the
+/// fréchet derivative. the selection thresholds are tuned for double precision
+#[math]
+#[track_caller]
+pub fn expm<T: ComplexField>(out: MatMut<'_, T>, A: MatRef<'_, T>, frechet: Option<(MatMut<'_, T>, MatRef<'_, T>)>, par: Par, stack: &mut MemStack) {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, out.nrows() == n, out.ncols() == n));
+	if let Some((L, E)) = frechet.as_ref() {
+		assert!(all(L.nrows() == n, L.ncols() == n, E.nrows() == n, E.ncols() == n));
+	}
+
+	let mut out = out;
+	let compute_frechet = frechet.is_some();
+	let (L_out, E) = match frechet {
+		Some((L, E)) => (Some(L), Some(E)),
+		None => (None, None),
+	};
+
+	if n == 0 {
+		return;
+	}
+
+	let norm = norm1(A);
+	if !is_finite(norm) {
+		out.fill(nan());
+		if let Some(mut L) = L_out {
+			L.fill(nan());
+		}
+		return;
+	}
+
+	let nf = if compute_frechet { n } else { 0 };
+
+	let (mut As, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut A2, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut A4, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut A6, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut A8, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut W1, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut Z1, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut U, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut V, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut tmp, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+
+	let (mut Es, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+	let (mut M2, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+	let (mut M4, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+	let (mut M6, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+	let (mut M8, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+	let (mut Lu, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+	let (mut Lv, stack) = unsafe { temp_mat_uninit::<T, _, _>(nf, nf, stack) };
+
+	let (mut perm_fwd, stack) = stack.make_with(n, |_| 0usize);
+	let (mut perm_bwd, stack) = stack.make_with(n, |_| 0usize);
+
+	let mut As = As.as_mat_mut();
+	let mut A2 = A2.as_mat_mut();
+	let mut A4 = A4.as_mat_mut();
+	let mut A6 = A6.as_mat_mut();
+	let mut A8 = A8.as_mat_mut();
+	let mut W1 = W1.as_mat_mut();
+	let mut Z1 = Z1.as_mat_mut();
+	let mut W = W.as_mat_mut();
+	let mut U = U.as_mat_mut();
+	let mut V = V.as_mat_mut();
+	let mut tmp = tmp.as_mat_mut();
+
+	let mut Es = Es.as_mat_mut();
+	let mut M2 = M2.as_mat_mut();
+	let mut M4 = M4.as_mat_mut();
+	let mut M6 = M6.as_mat_mut();
+	let mut M8 = M8.as_mat_mut();
+	let mut Lu = Lu.as_mat_mut();
+	let mut Lv = Lv.as_mat_mut();
+
+	As.copy_from(A);
+	linalg::matmul::matmul(A2.rb_mut(), Accum::Replace, A, A, one(), par);
+	linalg::matmul::matmul(A4.rb_mut(), Accum::Replace, A2.rb(), A2.rb(), one(), par);
+	linalg::matmul::matmul(A6.rb_mut(), Accum::Replace, A4.rb(), A2.rb(), one(), par);
+
+	// select the degree of the padé approximant and the number of squarings
+	let (m, s) = if compute_frechet {
+		let mut selected = None;
+		for &(m, ell) in ELL_FRECHET {
+			if norm <= from_f64::<T::Real>(ell) {
+				selected = Some((m, 0usize));
+				break;
+			}
+		}
+
+		match selected {
+			Some(selected) => selected,
+			None => {
+				let mut s = 0usize;
+				let mut theta = from_f64::<T::Real>(ELL_FRECHET_13);
+				while norm > theta {
+					theta = mul_pow2(theta, from_f64::<T::Real>(2.0));
+					s += 1;
+				}
+				(13, s)
+			},
+		}
+	} else {
+		let norm4 = norm1(A4.rb());
+		let norm6 = norm1(A6.rb());
+
+		let mut selected = None;
+		for &(m, theta) in &THETA[..2] {
+			let theta = from_f64::<T::Real>(theta);
+			if norm4 <= pow::<T>(copy(theta), 4) && norm6 <= pow::<T>(copy(theta), 6) && ell(A, tmp.rb_mut(), m, stack) == 0 {
+				selected = Some((m, 0usize));
+				break;
+			}
+		}
+
+		match selected {
+			Some(selected) => selected,
+			None => {
+				let est8 = norm1_est_product(&[A4.rb(), A4.rb()], stack);
+
+				let mut selected = None;
+				for &(m, theta) in &THETA[2..] {
+					let theta = from_f64::<T::Real>(theta);
+					if norm6 <= pow::<T>(copy(theta), 6) && est8 <= pow::<T>(copy(theta), 8) && ell(A, tmp.rb_mut(), m, stack) == 0 {
+						selected = Some((m, 0usize));
+						break;
+					}
+				}
+
+				match selected {
+					Some(selected) => selected,
+					None => {
+						let est10 = norm1_est_product(&[A4.rb(), A6.rb()], stack);
+
+						let mut s = 0usize;
+						let mut theta = from_f64::<T::Real>(THETA_13);
+						loop {
+							let theta6 = pow::<T>(copy(theta), 6);
+							let theta8 = pow::<T>(copy(theta), 8);
+							let theta10 = pow::<T>(copy(theta), 10);
+							if est8 <= theta8 && (norm6 <= theta6 || est10 <= theta10) {
+								break;
+							}
+							theta = mul_pow2(theta, from_f64::<T::Real>(2.0));
+							s += 1;
+						}
+
+						let mut factor = one::<T::Real>();
+						for _ in 0..s {
+							factor = mul_pow2(factor, from_f64::<T::Real>(0.5));
+						}
+						scale(As.rb_mut(), factor);
+						s += ell(As.rb(), tmp.rb_mut(), 13, stack);
+
+						(13, s)
+					},
+				}
+			},
+		}
+	};
+
+	let mut factor = one::<T::Real>();
+	for _ in 0..s {
+		factor = mul_pow2(factor, from_f64::<T::Real>(0.5));
+	}
+	if s > 0 {
+		let factor2 = factor * factor;
+		let factor4 = factor2 * factor2;
+		As.copy_from(A);
+		scale(As.rb_mut(), copy(factor));
+		scale(A2.rb_mut(), copy(factor2));
+		scale(A4.rb_mut(), copy(factor4));
+		scale(A6.rb_mut(), factor4 * factor2);
+	}
+
+	if let Some(E) = E {
+		Es.copy_from(E);
+		if s > 0 {
+			scale(Es.rb_mut(), copy(factor));
+		}
+
+		linalg::matmul::matmul(M2.rb_mut(), Accum::Replace, As.rb(), Es.rb(), one(), par);
+		linalg::matmul::matmul(M2.rb_mut(), Accum::Add, Es.rb(), As.rb(), one(), par);
+		linalg::matmul::matmul(M4.rb_mut(), Accum::Replace, A2.rb(), M2.rb(), one(), par);
+		linalg::matmul::matmul(M4.rb_mut(), Accum::Add, M2.rb(), A2.rb(), one(), par);
+		linalg::matmul::matmul(M6.rb_mut(), Accum::Replace, A4.rb(), M2.rb(), one(), par);
+		linalg::matmul::matmul(M6.rb_mut(), Accum::Add, M4.rb(), A2.rb(), one(), par);
+		if m == 9 {
+			linalg::matmul::matmul(M8.rb_mut(), Accum::Replace, A4.rb(), M4.rb(), one(), par);
+			linalg::matmul::matmul(M8.rb_mut(), Accum::Add, M4.rb(), A4.rb(), one(), par);
+		}
+	}
+
+	let b = pade_coeffs(m);
+
+	// e^A ≈ (V - U)^{-1} (V + U), with U = A W, where W and V are polynomials in A^2
+	if m < 13 {
+		if m == 9 {
+			linalg::matmul::matmul(A8.rb_mut(), Accum::Replace, A4.rb(), A4.rb(), one(), par);
+		}
+		let k = (m - 1) / 2;
+		let odd: [f64; 4] = core::array::from_fn(|j| b.get(2 * j + 3).copied().unwrap_or(0.0));
+		let even: [f64; 4] = core::array::from_fn(|j| b.get(2 * j + 2).copied().unwrap_or(0.0));
+		let powers = [A2.rb(), A4.rb(), A6.rb(), A8.rb()];
+
+		lin_comb(W.rb_mut(), &odd[..k], &powers[..k], b[1]);
+		lin_comb(V.rb_mut(), &even[..k], &powers[..k], b[0]);
+
+		if compute_frechet {
+			let mpowers = [M2.rb(), M4.rb(), M6.rb(), M8.rb()];
+
+			// L_W
+			lin_comb(Lv.rb_mut(), &odd[..k], &mpowers[..k], 0.0);
+			linalg::matmul::matmul(Lu.rb_mut(), Accum::Replace, As.rb(), Lv.rb(), one(), par);
+			linalg::matmul::matmul(Lu.rb_mut(), Accum::Add, Es.rb(), W.rb(), one(), par);
+
+			lin_comb(Lv.rb_mut(), &even[..k], &mpowers[..k], 0.0);
+		}
+	} else {
+		let powers = [A2.rb(), A4.rb(), A6.rb()];
+
+		lin_comb(W1.rb_mut(), &[b[9], b[11], b[13]], &powers, 0.0);
+		lin_comb(W.rb_mut(), &[b[3], b[5], b[7]], &powers, b[1]);
+		linalg::matmul::matmul(W.rb_mut(), Accum::Add, A6.rb(), W1.rb(), one(), par);
+
+		lin_comb(Z1.rb_mut(), &[b[8], b[10], b[12]], &powers, 0.0);
+		lin_comb(V.rb_mut(), &[b[2], b[4], b[6]], &powers, b[0]);
+		linalg::matmul::matmul(V.rb_mut(), Accum::Add, A6.rb(), Z1.rb(), one(), par);
+
+		if compute_frechet {
+			let mpowers = [M2.rb(), M4.rb(), M6.rb()];
+
+			// L_W
+			lin_comb(tmp.rb_mut(), &[b[9], b[11], b[13]], &mpowers, 0.0);
+			lin_comb(Lv.rb_mut(), &[b[3], b[5], b[7]], &mpowers, 0.0);
+			linalg::matmul::matmul(Lv.rb_mut(), Accum::Add, A6.rb(), tmp.rb(), one(), par);
+			linalg::matmul::matmul(Lv.rb_mut(), Accum::Add, M6.rb(), W1.rb(), one(), par);
+
+			linalg::matmul::matmul(Lu.rb_mut(), Accum::Replace, As.rb(), Lv.rb(), one(), par);
+			linalg::matmul::matmul(Lu.rb_mut(), Accum::Add, Es.rb(), W.rb(), one(), par);
+
+			lin_comb(tmp.rb_mut(), &[b[8], b[10], b[12]], &mpowers, 0.0);
+			lin_comb(Lv.rb_mut(), &[b[2], b[4], b[6]], &mpowers, 0.0);
+			linalg::matmul::matmul(Lv.rb_mut(), Accum::Add, A6.rb(), tmp.rb(), one(), par);
+			linalg::matmul::matmul(Lv.rb_mut(), Accum::Add, M6.rb(), Z1.rb(), one(), par);
+		}
+	}
+	linalg::matmul::matmul(U.rb_mut(), Accum::Replace, As.rb(), W.rb(), one(), par);
+
+	// R = (V - U)^{-1} (V + U)
+	z!(tmp.rb_mut(), V.rb_mut(), U.rb()).for_each(|uz!(q, v, u)| {
+		*q = *v - *u;
+		*v = *v + *u;
+	});
+	let (_, perm) = lu_factor::lu_in_place(tmp.rb_mut(), &mut perm_fwd, &mut perm_bwd, par, stack, default());
+	lu_solve::solve_in_place_with_conj(tmp.rb(), tmp.rb(), perm, Conj::No, V.rb_mut(), par, stack);
+
+	// L = (V - U)^{-1} (L_U + L_V + (L_U - L_V) R)
+	if compute_frechet {
+		z!(W.rb_mut(), Lu.rb_mut(), Lv.rb()).for_each(|uz!(w, lu, lv)| {
+			*w = *lu - *lv;
+			*lu = *lu + *lv;
+		});
+		linalg::matmul::matmul(Lu.rb_mut(), Accum::Add, W.rb(), V.rb(), one(), par);
+		lu_solve::solve_in_place_with_conj(tmp.rb(), tmp.rb(), perm, Conj::No, Lu.rb_mut(), par, stack);
+	}
+
+	// undo the scaling by repeated squaring, using L(A^2, E) = A L(A, E) + L(A, E) A for the
+	// derivative
+	out.copy_from(V.rb());
+	for _ in 0..s {
+		if compute_frechet {
+			linalg::matmul::matmul(W.rb_mut(), Accum::Replace, out.rb(), Lu.rb(), one(), par);
+			linalg::matmul::matmul(W.rb_mut(), Accum::Add, Lu.rb(), out.rb(), one(), par);
+			Lu.copy_from(W.rb());
+		}
+		linalg::matmul::matmul(W.rb_mut(), Accum::Replace, out.rb(), out.rb(), one(), par);
+		out.copy_from(W.rb());
+	}
+
+	if let Some(mut L) = L_out {
+		L.copy_from(Lu.rb());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+	use linalg::solvers::SelfAdjointEigen;
+
+	fn expm_of(A: MatRef<'_, c64>, frechet: Option<(MatMut<'_, c64>, MatRef<'_, c64>)>) -> Mat<c64> {
+		let n = A.nrows();
+		let mut out = Mat::zeros(n, n);
+		let compute_frechet = frechet.is_some();
+		expm(
+			out.as_mut(),
+			A,
+			frechet,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(expm_scratch::<c64>(n, compute_frechet, Par::Seq))),
+		);
+		out
+	}
+
+	#[test]
+	fn test_expm_self_adjoint() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 12;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let H = &X + X.adjoint();
+		let norm = norm1(H.as_ref());
+
+		// the target norms cover every padé degree, as well as scaling and squaring
+		for target in [1e-3, 1e-1, 0.5, 1.5, 3.0, 40.0] {
+			let A = &H * Scale(c64::new(target / norm, 0.0));
+
+			let evd = SelfAdjointEigen::new(A.as_ref(), Side::Lower).unwrap();
+			let S = evd.S().column_vector();
+			let expS = Col::<c64>::from_fn(n, |i| c64::new(S[i].re.exp(), 0.0));
+			let expected = evd.U() * expS.as_diagonal() * evd.U().adjoint();
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 64.0 * (n as f64) * f64::max(target, 1.0));
+			assert!(expm_of(A.as_ref(), None) ~ expected);
+		}
+	}
+
+	#[test]
+	fn test_expm_nilpotent() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 8;
+
+		let mut N = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		z!(N.as_mut()).for_each_triangular_lower(linalg::zip::Diag::Include, |uz!(x)| *x = c64::ZERO);
+
+		let mut expected = Mat::<c64>::identity(n, n);
+		let mut term = Mat::<c64>::identity(n, n);
+		for k in 1..n {
+			term = &term * &N * Scale(c64::new(1.0 / k as f64, 0.0));
+			expected += &term;
+		}
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+		assert!(expm_of(N.as_ref(), None) ~ expected);
+	}
+
+	#[test]
+	fn test_expm_frechet() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 10;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let E = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let norm = norm1(X.as_ref());
+
+		for target in [1e-3, 1e-1, 0.5, 1.5, 3.0, 20.0] {
+			let A = &X * Scale(c64::new(target / norm, 0.0));
+
+			// the exponential of [A E; 0 A] is [e^A L(A, E); 0 e^A]
+			let mut B = Mat::<c64>::zeros(2 * n, 2 * n);
+			B.as_mut().submatrix_mut(0, 0, n, n).copy_from(&A);
+			B.as_mut().submatrix_mut(0, n, n, n).copy_from(&E);
+			B.as_mut().submatrix_mut(n, n, n, n).copy_from(&A);
+			let expB = expm_of(B.as_ref(), None);
+
+			let mut L = Mat::<c64>::zeros(n, n);
+			let expA = expm_of(A.as_ref(), Some((L.as_mut(), E.as_ref())));
+
+			let scale = expB.norm_max();
+			let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * (n as f64) * scale);
+			assert!(expA ~ expB.submatrix(0, 0, n, n));
+			assert!(L ~ expB.submatrix(0, n, n, n));
+		}
+	}
+}
@@ -0,0 +1,268 @@
+use super::*;
+use crate::assert;
+use linalg::matfun::sqrtm::sqrtm_triangular_in_place;
+
+// nodes and weights of the 7-point gauss-legendre quadrature rule on $[0, 1]$. the quadrature of
+// $\log(I + X) = \int_0^1 X (I + t X)^{-1} dt$ is the $[7/7]$ padé approximant of $\log(I + X)$
+const GAUSS_LEGENDRE_7: &[(f64, f64)] = &[
+	(0.025446043828620757, 0.06474248308443485),
+	(0.12923440720030277, 0.13985269574463832),
+	(0.2970774243113014, 0.19091502525255946),
+	(0.5, 0.2089795918367347),
+	(0.7029225756886985, 0.19091502525255946),
+	(0.8707655927996972, 0.13985269574463832),
+	(0.9745539561713792, 0.06474248308443485),
+];
+
+// the $[7/7]$ padé approximant of $\log(I + X)$ is accurate to double precision for
+// $\|X\|_1 \leq 0.264$, see higham (2001), evaluating padé approximants of the matrix logarithm
+const THETA_7: f64 = 0.25;
+
+// upper bound on the number of square roots, which is only reached for non-finite inputs
+const MAX_SQRT: usize = 64;
+
+#[math]
+fn norm1_minus_identity<T: ComplexField>(t: MatRef<'_, T>) -> T::Real {
+	let mut norm = zero::<T::Real>();
+	for j in 0..t.ncols() {
+		let mut col = zero::<T::Real>();
+		for i in 0..t.nrows() {
+			col = col + if i == j { abs(t[(i, j)] - one()) } else { abs(t[(i, j)]) };
+		}
+		norm = max(norm, col);
+	}
+	norm
+}
+
+pub(crate) fn logm_triangular_scratch<T: ComplexField>(dim: usize) -> StackReq {
+	temp_mat_scratch::<T>(dim, dim).array(2)
+}
+
+/// computes the logarithm of the upper triangular matrix $T$ with the inverse scaling and squaring
+/// method, and stores the result in `out`. $T$ is overwritten with intermediate values
+///
+/// the diagonal of $T$ must not contain elements on the closed negative real axis
+#[math]
+pub(crate) fn logm_triangular<T: ComplexField>(out: MatMut<'_, T>, t: MatMut<'_, T>, par: Par, stack: &mut MemStack) {
+	let n = t.nrows();
+	let mut t = t;
+	let mut out = out;
+
+	// T^{1/2^k} converges to the identity as k grows. the negated comparison keeps taking square
+	// roots if the norm is nan
+	let mut k = 0;
+	#[allow(clippy::neg_cmp_op_on_partial_ord)]
+	while k < MAX_SQRT && !(norm1_minus_identity(t.rb()) <= from_f64::<T::Real>(THETA_7)) {
+		sqrtm_triangular_in_place(t.rb_mut(), par);
+		k += 1;
+	}
+
+	for i in 0..n {
+		t[(i, i)] = t[(i, i)] - one();
+	}
+
+	let (mut m, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut y, _) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut m = m.as_mat_mut();
+	let mut y = y.as_mat_mut();
+
+	// log(T) = 2^k log(I + X), where X = T^{1/2^k} - I
+	out.fill(zero());
+	for &(node, weight) in GAUSS_LEGENDRE_7 {
+		let node = from_f64::<T::Real>(node);
+		let weight = from_f64::<T::Real>(weight);
+
+		z!(m.rb_mut(), t.rb()).for_each(|uz!(m, x)| *m = mul_real(*x, node));
+		for i in 0..n {
+			m[(i, i)] = m[(i, i)] + one();
+		}
+		y.copy_from(t.rb());
+		linalg::triangular_solve::solve_upper_triangular_in_place(m.rb(), y.rb_mut(), par);
+
+		z!(out.rb_mut(), y.rb()).for_each_triangular_upper(linalg::zip::Diag::Include, |uz!(out, y)| *out = *out + mul_real(*y, weight));
+	}
+
+	let mut scale = one::<T::Real>();
+	for _ in 0..k {
+		scale = mul_pow2(scale, from_f64::<T::Real>(2.0));
+	}
+	z!(out.rb_mut()).for_each(|uz!(x)| *x = mul_real(*x, scale));
+}
+
+/// computes the size and alignment of the workspace required to compute the logarithm of a
+/// matrix of dimension `dim`
+pub fn logm_scratch<T: RealField>(dim: usize, par: Par) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<Complex<T>>(dim, dim).array(3),
+		StackReq::any_of(&[
+			schur_scratch::<T>(dim, par),
+			logm_triangular_scratch::<Complex<T>>(dim),
+			from_schur_scratch::<Complex<T>>(dim),
+		]),
+	])
+}
+
+/// computes the principal logarithm $\log A$ of the square matrix $A$, i.e., the unique
+/// logarithm whose eigenvalues have imaginary parts in $(-\pi, \pi)$, and stores the result in
+/// `out`
+///
+/// the result is computed from the complex schur form of $A$, using the inverse scaling and
+/// squaring method on the triangular factor: square roots are taken until it is close to the
+/// identity, after which a padé approximant is applied
+///
+/// returns an error if $A$ has an eigenvalue on the closed negative real axis
+#[track_caller]
+pub fn logm<T: RealField>(out: MatMut<'_, Complex<T>>, A: MatRef<'_, Complex<T>>, par: Par, stack: &mut MemStack) -> Result<(), MatFunError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, out.nrows() == n, out.ncols() == n));
+
+	let (mut t, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut z, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut l, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let mut t = t.as_mat_mut();
+	let mut z = z.as_mat_mut();
+	let mut l = l.as_mat_mut();
+
+	schur(A, t.rb_mut(), z.rb_mut(), par, stack)?;
+	if has_eigenvalue_on_closed_negative_real_axis(t.rb()) {
+		return Err(MatFunError::EigenvalueOnNegativeRealAxis);
+	}
+
+	logm_triangular(l.rb_mut(), t.rb_mut(), par, stack);
+	from_schur(out, z.rb(), l.rb(), par, stack);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+	use linalg::matfun::expm::{expm, expm_scratch};
+	use linalg::solvers::DenseSolveCore;
+
+	#[test]
+	fn test_logm() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 4, 20, 50] {
+			let X = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let A = &X * X.adjoint() + Mat::<c64>::identity(n, n) + (&X - X.adjoint()) * Scale(c64::new(0.5, 0.0));
+
+			let mut L = Mat::zeros(n, n);
+			logm(
+				L.as_mut(),
+				A.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(logm_scratch::<f64>(n, Par::Seq))),
+			)
+			.unwrap();
+
+			let mut expL = Mat::zeros(n, n);
+			expm(
+				expL.as_mut(),
+				L.as_ref(),
+				None,
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(expm_scratch::<c64>(n, false, Par::Seq))),
+			);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 4096.0 * (n as f64));
+			assert!(expL ~ A);
+		}
+	}
+
+	#[test]
+	fn test_logm_of_expm() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 10;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		// eigenvalues with imaginary parts in (-pi, pi)
+		let X = &X * Scale(c64::new(0.5 / norm1(X.as_ref()), 0.0));
+
+		let mut A = Mat::zeros(n, n);
+		expm(
+			A.as_mut(),
+			X.as_ref(),
+			None,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(expm_scratch::<c64>(n, false, Par::Seq))),
+		);
+
+		let mut L = Mat::zeros(n, n);
+		logm(
+			L.as_mut(),
+			A.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(logm_scratch::<f64>(n, Par::Seq))),
+		)
+		.unwrap();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * (n as f64));
+		assert!(L ~ X);
+	}
+
+	#[test]
+	fn test_logm_negative_eigenvalue() {
+		let A = Mat::<c64>::from_fn(2, 2, |i, j| match (i, j) {
+			(0, 0) => c64::new(-1.0, 0.0),
+			(1, 1) => c64::new(2.0, 0.0),
+			(0, 1) => c64::ONE,
+			_ => c64::ZERO,
+		});
+		let mut L = Mat::zeros(2, 2);
+		assert!(
+			logm(
+				L.as_mut(),
+				A.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(logm_scratch::<f64>(2, Par::Seq))),
+			) == Err(MatFunError::EigenvalueOnNegativeRealAxis)
+		);
+	}
+
+	#[test]
+	fn test_logm_negative_eigenvalue_dense() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 20;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let D = Mat::<c64>::from_fn(n, n, |i, j| {
+			if i == j {
+				c64::new(if i == 5 { -0.5 } else { 1.0 + i as f64 }, 0.0)
+			} else {
+				c64::ZERO
+			}
+		});
+		let A = &X * &D * X.partial_piv_lu().inverse();
+
+		let mut L = Mat::zeros(n, n);
+		assert!(
+			logm(
+				L.as_mut(),
+				A.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(logm_scratch::<f64>(n, Par::Seq))),
+			) == Err(MatFunError::EigenvalueOnNegativeRealAxis)
+		);
+	}
+}
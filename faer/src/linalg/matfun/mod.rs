@@ -0,0 +1,317 @@
+//! low level implementation of functions of square matrices
+//!
+//! for a scalar function $f$ and a diagonalizable matrix $A = X D X^{-1}$, the matrix function is
+//! defined by $f(A) = X f(D) X^{-1}$, where $f$ is applied to each diagonal element of $D$. the
+//! definition extends to all square matrices through the jordan canonical form
+//!
+//! the algorithms in this module do not rely on an eigendecomposition. the exponential is built on
+//! rational approximations combined with scaling and squaring, while the square root, logarithm
+//! and fractional powers work on the complex schur form $A = Z T Z^H$, since $f(A) = Z f(T) Z^H$
+//! and $f(T)$ is upper triangular
+//!
+//! the square root, logarithm and fractional powers use the principal branch, which is defined
+//! when $A$ has no eigenvalues on the closed negative real axis
+
+use crate::internal_prelude::*;
+use linalg::norm_est::{norm1_est, norm1_est_scratch};
+
+/// matrix exponential
+pub mod expm;
+/// matrix logarithm
+pub mod logm;
+/// real powers of a matrix
+pub mod powm;
+/// matrix square root
+pub mod sqrtm;
+
+/// matrix function error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MatFunError {
+	/// the matrix has an eigenvalue on the closed negative real axis, so the principal branch of
+	/// the function is not defined
+	EigenvalueOnNegativeRealAxis,
+	/// the matrix is singular, and the function requires an inverse
+	Singular,
+	/// the schur decomposition did not converge
+	NoConvergence,
+}
+
+/// returns the $1$-norm of $A$, i.e., its maximum absolute column sum
+#[math]
+pub(crate) fn norm1<T: ComplexField>(A: MatRef<'_, T>) -> T::Real {
+	let mut norm = zero::<T::Real>();
+	for j in 0..A.ncols() {
+		let mut col = zero::<T::Real>();
+		for i in 0..A.nrows() {
+			col = col + abs(A[(i, j)]);
+		}
+		norm = max(norm, col);
+	}
+	norm
+}
+
+pub(crate) fn norm1_est_product_scratch<T: ComplexField>(dim: usize) -> StackReq {
+	StackReq::all_of(&[temp_mat_scratch::<T>(dim, 1).array(2), norm1_est_scratch::<T>(dim)])
+}
+
+/// estimates the $1$-norm of the product $M_0 M_1 \dots M_{k - 1}$ of the square matrices in
+/// `factors`, without forming it
+#[math]
+pub(crate) fn norm1_est_product<T: ComplexField>(factors: &[MatRef<'_, T>], stack: &mut MemStack) -> T::Real {
+	let n = factors[0].nrows();
+
+	let (mut tmp_fwd, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut tmp_bwd, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let mut tmp_fwd = tmp_fwd.as_mat_mut();
+	let mut tmp_bwd = tmp_bwd.as_mat_mut();
+
+	norm1_est(
+		n,
+		&mut |mut x: ColMut<'_, T>| {
+			for M in factors.iter().rev() {
+				tmp_fwd.rb_mut().col_mut(0).copy_from(x.rb());
+				linalg::matmul::matmul(x.rb_mut().as_mat_mut(), Accum::Replace, *M, tmp_fwd.rb(), one(), Par::Seq);
+			}
+		},
+		&mut |mut x: ColMut<'_, T>| {
+			for M in factors.iter() {
+				tmp_bwd.rb_mut().col_mut(0).copy_from(x.rb());
+				linalg::matmul::matmul(x.rb_mut().as_mat_mut(), Accum::Replace, M.adjoint(), tmp_bwd.rb(), one(), Par::Seq);
+			}
+		},
+		stack,
+	)
+}
+
+pub(crate) fn schur_scratch<T: RealField>(dim: usize, par: Par) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<Complex<T>>(dim, 1),
+		linalg::evd::schur_scratch::<Complex<T>>(dim, par, default()),
+	])
+}
+
+/// computes the complex schur decomposition $A = Z T Z^H$
+pub(crate) fn schur<T: RealField>(
+	A: MatRef<'_, Complex<T>>,
+	t: MatMut<'_, Complex<T>>,
+	z: MatMut<'_, Complex<T>>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatFunError> {
+	let n = A.nrows();
+	let (mut s, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, 1, stack) };
+	let s = s.as_mat_mut().col_mut(0).as_diagonal_mut();
+
+	linalg::evd::schur_cplx(A, t, Some(z), s, par, stack, default()).map_err(|_| MatFunError::NoConvergence)
+}
+
+/// returns the tolerance below which the imaginary part of a diagonal element of the upper
+/// triangular matrix $T$ is attributed to rounding errors from the schur decomposition
+#[math]
+pub(crate) fn schur_tolerance<T: RealField>(t: MatRef<'_, Complex<T>>) -> T {
+	eps::<T>() * from_f64::<T>(t.nrows() as f64) * norm1(t)
+}
+
+/// returns `true` if the upper triangular matrix $T$ has a diagonal element on the closed negative
+/// real axis, up to [`schur_tolerance`]
+#[math]
+pub(crate) fn has_eigenvalue_on_closed_negative_real_axis<T: RealField>(t: MatRef<'_, Complex<T>>) -> bool {
+	let tol = schur_tolerance(t);
+	for i in 0..t.nrows() {
+		let x = &t[(i, i)];
+		if abs(x.im) <= tol && x.re <= tol {
+			return true;
+		}
+	}
+	false
+}
+
+/// returns the integer and fractional parts of the nonnegative scalar $x$, if its integer part
+/// fits in a `u64`
+#[math]
+fn split_u64<T: RealField>(x: T) -> Option<(u64, T)> {
+	if !is_finite(x) || x < zero() || x >= from_f64::<T>(18446744073709551616.0) {
+		return None;
+	}
+
+	// each subtraction is exact, since the remainder lies in $[2^b, 2^{b + 1})$
+	let mut r = x;
+	let mut k = 0u64;
+	let mut pow = from_f64::<T>(9223372036854775808.0);
+	for b in (0..64).rev() {
+		if r >= pow {
+			r = r - pow;
+			k |= 1u64 << b;
+		}
+		pow = mul_pow2(pow, from_f64::<T>(0.5));
+	}
+	Some((k, r))
+}
+
+/// returns `x` as a `u64` if it is a nonnegative integer that fits in one
+#[math]
+pub(crate) fn to_u64<T: RealField>(x: T) -> Option<u64> {
+	match split_u64(x) {
+		Some((k, r)) if r == zero() => Some(k),
+		_ => None,
+	}
+}
+
+pub(crate) fn from_schur_scratch<T: ComplexField>(dim: usize) -> StackReq {
+	temp_mat_scratch::<T>(dim, dim)
+}
+
+/// computes $Z F Z^H$, and stores the result in `out`
+pub(crate) fn from_schur<T: ComplexField>(out: MatMut<'_, T>, z: MatRef<'_, T>, f: MatRef<'_, T>, par: Par, stack: &mut MemStack) {
+	let n = z.nrows();
+	let (mut tmp, _) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut tmp = tmp.as_mat_mut();
+
+	linalg::matmul::matmul(tmp.rb_mut(), Accum::Replace, z, f, one(), par);
+	linalg::matmul::matmul(out, Accum::Replace, tmp.rb(), z.adjoint(), one(), par);
+}
+
+// $\ln 2 = \text{LN\_2\_HI} + \text{LN\_2\_LO}$, where the high part has 16 significant bits, so
+// that its product with an exponent is exact
+const LN_2_HI: f64 = 0.693145751953125;
+const LN_2_LO: f64 = 1.4286068203094173e-6;
+
+/// returns $2^e$, computed by repeated squaring, which is exact as long as the result is in range
+#[math]
+fn pow2<T: RealField>(e: i64) -> T {
+	let mut base = if e < 0 { from_f64::<T>(0.5) } else { from_f64::<T>(2.0) };
+	let mut e = e.unsigned_abs();
+	let mut out = one::<T>();
+	while e > 0 {
+		if e % 2 == 1 {
+			out = out * base;
+		}
+		e /= 2;
+		if e > 0 {
+			base = base * base;
+		}
+	}
+	out
+}
+
+/// returns $x 2^k$, scaling in two steps so that the powers of two stay within range
+#[math]
+fn scale_pow2<T: RealField>(x: T, k: i64) -> T {
+	let k0 = k / 2;
+	let k1 = k - k0;
+	mul_pow2(mul_pow2(x, pow2::<T>(k0)), pow2::<T>(k1))
+}
+
+/// returns the natural logarithm of the real scalar $x$
+#[math]
+pub(crate) fn log_real<T: RealField>(x: T) -> T {
+	if is_nan(x) || x < zero() {
+		return nan();
+	}
+	if x == zero() {
+		return -infinity::<T>();
+	}
+	if !is_finite(x) {
+		return x;
+	}
+
+	// x = 2^k m, with m in [1/sqrt(2), sqrt(2)). the exponent is extracted with steps of decreasing
+	// powers of two, which are exact. steps whose powers of two are not finite and normal in `T`
+	// are skipped, since they would overflow `m` to infinity and never terminate
+	let sqrt2 = from_f64::<T>(core::f64::consts::SQRT_2);
+	let sqrt_half = from_f64::<T>(core::f64::consts::FRAC_1_SQRT_2);
+
+	let mut m = x;
+	let mut k = 0i64;
+	for j in (0..10).rev() {
+		let e = 1i64 << j;
+		let up = pow2::<T>(e);
+		let down = pow2::<T>(-e);
+		if !is_finite(up) || down < min_positive::<T>() {
+			continue;
+		}
+		while m >= up {
+			m = mul_pow2(m, down);
+			k += e;
+		}
+		while m < down {
+			m = mul_pow2(m, up);
+			k -= e;
+		}
+	}
+	// m is now in [1/2, 2)
+	if m >= sqrt2 {
+		m = mul_pow2(m, from_f64::<T>(0.5));
+		k += 1;
+	} else if m < sqrt_half {
+		m = mul_pow2(m, from_f64::<T>(2.0));
+		k -= 1;
+	}
+
+	// log(m) = 2 atanh(z) = 2 (z + z^3 / 3 + z^5 / 5 + ...), with z = (m - 1) / (m + 1)
+	let z = (m - one::<T>()) / (m + one::<T>());
+	// with |z| <= 3 - 2 sqrt(2), the terms of the series decrease faster than z2^i, which determines
+	// the number of terms that are needed. the series is then evaluated with horner's scheme
+	let z2 = z * z;
+	let mut pow = one::<T>();
+	let mut nterms = 0usize;
+	loop {
+		nterms += 1;
+		pow = pow * z2;
+		if pow <= eps::<T>() {
+			break;
+		}
+	}
+
+	let mut sum = zero::<T>();
+	for i in (0..nterms + 1).rev() {
+		sum = recip(from_f64::<T>((2 * i + 1) as f64)) + z2 * sum;
+	}
+	let sum = z * sum;
+
+	let k = from_f64::<T>(k as f64);
+	k * from_f64::<T>(LN_2_HI) + (k * from_f64::<T>(LN_2_LO) + mul_pow2(sum, from_f64::<T>(2.0)))
+}
+
+/// returns the exponential of the real scalar $x$
+#[math]
+pub(crate) fn exp_real<T: RealField>(x: T) -> T {
+	if is_nan(x) {
+		return nan();
+	}
+	if !is_finite(x) {
+		return if x > zero() { copy(x) } else { zero() };
+	}
+
+	// x = k ln(2) + r, with |r| <= ln(2) / 2, so that e^x = 2^k e^r
+	let y = abs(x) / from_f64::<T>(core::f64::consts::LN_2);
+	let k = match split_u64(y) {
+		Some((k, frac)) if k < 1 << 20 => {
+			let k = (if frac >= from_f64::<T>(0.5) { k + 1 } else { k }) as i64;
+			if x < zero() { -k } else { k }
+		},
+		_ => return if x > zero() { infinity() } else { zero() },
+	};
+
+	let kf = from_f64::<T>(k as f64);
+	let r = (x - kf * from_f64::<T>(LN_2_HI)) - kf * from_f64::<T>(LN_2_LO);
+
+	// the number of terms of the taylor series that are needed is determined first, then the
+	// series is evaluated from the smallest term with horner's scheme
+	let mut term = one::<T>();
+	let mut nterms = 0usize;
+	loop {
+		nterms += 1;
+		term = term * abs(r) / from_f64::<T>(nterms as f64);
+		if term <= eps::<T>() {
+			break;
+		}
+	}
+
+	let mut sum = one::<T>();
+	for i in (1..nterms + 1).rev() {
+		sum = one::<T>() + r * sum / from_f64::<T>(i as f64);
+	}
+
+	scale_pow2(sum, k)
+}
@@ -0,0 +1,213 @@
+use super::*;
+use crate::assert;
+use linalg::matfun::expm::{expm, expm_scratch};
+use linalg::matfun::logm::{logm_triangular, logm_triangular_scratch};
+
+/// computes the size and alignment of the workspace required to compute a real power of a matrix
+/// of dimension `dim`
+pub fn powm_scratch<T: RealField>(dim: usize, par: Par) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<Complex<T>>(dim, dim).array(4),
+		StackReq::any_of(&[
+			schur_scratch::<T>(dim, par),
+			logm_triangular_scratch::<Complex<T>>(dim),
+			expm_scratch::<Complex<T>>(dim, false, par),
+			from_schur_scratch::<Complex<T>>(dim),
+		]),
+	])
+}
+
+// computes M^e by repeated squaring, overwriting M and using `acc` as workspace
+fn pow_int<T: ComplexField>(out: MatMut<'_, T>, M: MatMut<'_, T>, acc: MatMut<'_, T>, e: u64, par: Par) {
+	let mut out = out;
+	let mut M = M;
+	let mut acc = acc;
+	let mut e = e;
+
+	out.fill(zero());
+	out.rb_mut().diagonal_mut().fill(one());
+	while e > 0 {
+		if e & 1 == 1 {
+			linalg::matmul::matmul(acc.rb_mut(), Accum::Replace, out.rb(), M.rb(), one(), par);
+			out.copy_from(acc.rb());
+		}
+		e >>= 1;
+		if e > 0 {
+			linalg::matmul::matmul(acc.rb_mut(), Accum::Replace, M.rb(), M.rb(), one(), par);
+			M.copy_from(acc.rb());
+		}
+	}
+}
+
+/// computes the power $A^p$ of the square matrix $A$ for a real exponent $p$, and stores the
+/// result in `out`
+///
+/// - if $p$ is a nonnegative integer, the power is computed by repeated squaring,
+/// - if $p$ is a negative integer, the power is computed by repeated squaring of the inverse of
+/// the triangular factor of the complex schur form of $A$, and an error is returned if $A$ is
+/// singular,
+/// - otherwise, the principal power $A^p = e^{p \log A}$ is computed from the complex schur form
+/// $A = Z T Z^H$ as $Z e^{p \log T} Z^H$, and an error is returned if $A$ has an eigenvalue on the
+/// closed negative real axis
+#[math]
+#[track_caller]
+pub fn powm<T: RealField>(out: MatMut<'_, Complex<T>>, A: MatRef<'_, Complex<T>>, p: T, par: Par, stack: &mut MemStack) -> Result<(), MatFunError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, out.nrows() == n, out.ncols() == n));
+
+	let mut out = out;
+
+	let (mut t, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut z, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut f, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut tmp, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let mut t = t.as_mat_mut();
+	let mut z = z.as_mat_mut();
+	let mut f = f.as_mat_mut();
+	let mut tmp = tmp.as_mat_mut();
+
+	let int = to_u64(abs(p));
+
+	if let (Some(e), true) = (int, p >= zero()) {
+		t.copy_from(A);
+		pow_int(out.rb_mut(), t.rb_mut(), tmp.rb_mut(), e, par);
+		return Ok(());
+	}
+
+	schur(A, t.rb_mut(), z.rb_mut(), par, stack)?;
+
+	if let Some(e) = int {
+		let tol = schur_tolerance(t.rb());
+		for i in 0..n {
+			if abs(t[(i, i)]) <= tol {
+				return Err(MatFunError::Singular);
+			}
+		}
+
+		linalg::triangular_inverse::invert_upper_triangular(tmp.rb_mut(), t.rb(), par);
+		z!(tmp.rb_mut()).for_each_triangular_lower(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+		pow_int(f.rb_mut(), tmp.rb_mut(), t.rb_mut(), e, par);
+	} else {
+		if has_eigenvalue_on_closed_negative_real_axis(t.rb()) {
+			return Err(MatFunError::EigenvalueOnNegativeRealAxis);
+		}
+
+		logm_triangular(tmp.rb_mut(), t.rb_mut(), par, stack);
+		z!(tmp.rb_mut()).for_each(|uz!(x)| *x = mul_real(*x, p));
+		expm(f.rb_mut(), tmp.rb(), None, par, stack);
+	}
+
+	from_schur(out, z.rb(), f.rb(), par, stack);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+	use linalg::matfun::sqrtm::{sqrtm, sqrtm_scratch};
+	use linalg::solvers::DenseSolveCore;
+
+	fn powm_of(A: MatRef<'_, c64>, p: f64) -> Result<Mat<c64>, MatFunError> {
+		let n = A.nrows();
+		let mut out = Mat::zeros(n, n);
+		powm(
+			out.as_mut(),
+			A,
+			p,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(powm_scratch::<f64>(n, Par::Seq))),
+		)?;
+		Ok(out)
+	}
+
+	#[test]
+	fn test_powm() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 12;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let A = &X * X.adjoint() + Mat::<c64>::identity(n, n) + (&X - X.adjoint()) * Scale(c64::new(0.5, 0.0));
+		let A = &A * Scale(c64::new(1.0 / norm1(A.as_ref()), 0.0));
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 4096.0 * (n as f64));
+
+		assert!(powm_of(A.as_ref(), 0.0).unwrap() ~ Mat::<c64>::identity(n, n));
+		assert!(powm_of(A.as_ref(), 1.0).unwrap() ~ A);
+		assert!(powm_of(A.as_ref(), 3.0).unwrap() ~ &A * &A * &A);
+		assert!(powm_of(A.as_ref(), -2.0).unwrap() * &A * &A ~ Mat::<c64>::identity(n, n));
+
+		let mut R = Mat::zeros(n, n);
+		sqrtm(
+			R.as_mut(),
+			A.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(sqrtm_scratch::<f64>(n, Par::Seq))),
+		)
+		.unwrap();
+		assert!(powm_of(A.as_ref(), 0.5).unwrap() ~ R);
+
+		let P = powm_of(A.as_ref(), 1.0 / 3.0).unwrap();
+		assert!(&P * &P * &P ~ A);
+
+		let P = powm_of(A.as_ref(), 2.5).unwrap();
+		assert!(P ~ &A * &A * &R);
+	}
+
+	#[test]
+	fn test_powm_errors() {
+		let A = Mat::<c64>::from_fn(2, 2, |i, j| match (i, j) {
+			(0, 0) => c64::new(-1.0, 0.0),
+			(0, 1) => c64::ONE,
+			_ => c64::ZERO,
+		});
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 16.0);
+		assert!(powm_of(A.as_ref(), 2.0).unwrap() ~ &A * &A);
+		assert!(powm_of(A.as_ref(), -1.0) == Err(MatFunError::Singular));
+		assert!(powm_of(A.as_ref(), 0.5) == Err(MatFunError::EigenvalueOnNegativeRealAxis));
+	}
+
+	#[test]
+	fn test_powm_errors_dense() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 20;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let X_inv = X.partial_piv_lu().inverse();
+
+		let D = Mat::<c64>::from_fn(n, n, |i, j| {
+			if i == j {
+				c64::new(if i == 7 { -3.0 } else { 1.0 + i as f64 }, 0.0)
+			} else {
+				c64::ZERO
+			}
+		});
+		let A = &X * &D * &X_inv;
+		assert!(powm_of(A.as_ref(), 0.5) == Err(MatFunError::EigenvalueOnNegativeRealAxis));
+		assert!(powm_of(A.as_ref(), -1.5) == Err(MatFunError::EigenvalueOnNegativeRealAxis));
+
+		let D = Mat::<c64>::from_fn(n, n, |i, j| {
+			if i == j {
+				c64::new(if i == 7 { 0.0 } else { 1.0 + i as f64 }, 0.0)
+			} else {
+				c64::ZERO
+			}
+		});
+		let A = &X * &D * &X_inv;
+		assert!(powm_of(A.as_ref(), -1.0) == Err(MatFunError::Singular));
+	}
+}
@@ -0,0 +1,179 @@
+use super::*;
+use crate::assert;
+use linalg::evd::schur::solve_sylvester_quasi_triangular_in_place;
+
+// below this dimension, the triangular square root is computed column by column
+const SQRTM_RECURSION_THRESHOLD: usize = 32;
+
+/// computes the square root of the upper triangular matrix $T$ in place, using the recursive
+/// blocked algorithm of deadman, higham and ralha (2013)
+///
+/// the diagonal of $T$ must not contain two zero elements
+#[math]
+pub(crate) fn sqrtm_triangular_in_place<T: ComplexField>(t: MatMut<'_, T>, par: Par) {
+	let n = t.nrows();
+	let mut t = t;
+
+	if n <= SQRTM_RECURSION_THRESHOLD {
+		// björck and hammarling (1983)
+		for j in 0..n {
+			t[(j, j)] = sqrt(t[(j, j)]);
+			for i in (0..j).rev() {
+				let mut acc = copy(t[(i, j)]);
+				for k in i + 1..j {
+					acc = acc - t[(i, k)] * t[(k, j)];
+				}
+				let denom = t[(i, i)] + t[(j, j)];
+				t[(i, j)] = if denom == zero() { zero() } else { acc * recip(denom) };
+			}
+		}
+		return;
+	}
+
+	let k = n / 2;
+	let (mut t11, mut t12, _, mut t22) = t.split_at_mut(k, k);
+	sqrtm_triangular_in_place(t11.rb_mut(), par);
+	sqrtm_triangular_in_place(t22.rb_mut(), par);
+
+	// R11 X + X R22 = T12
+	solve_sylvester_quasi_triangular_in_place(t11.rb(), t22.rb(), t12.rb_mut(), par);
+}
+
+/// computes the size and alignment of the workspace required to compute the square root of a
+/// matrix of dimension `dim`
+pub fn sqrtm_scratch<T: RealField>(dim: usize, par: Par) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<Complex<T>>(dim, dim).array(2),
+		StackReq::any_of(&[schur_scratch::<T>(dim, par), from_schur_scratch::<Complex<T>>(dim)]),
+	])
+}
+
+/// computes the principal square root $A^{1/2}$ of the square matrix $A$, i.e., the unique square
+/// root whose eigenvalues have positive real parts, and stores the result in `out`
+///
+/// the result is computed from the complex schur form of $A$, whose triangular factor is processed
+/// with a recursive blocked algorithm
+///
+/// returns an error if $A$ has an eigenvalue on the closed negative real axis
+#[track_caller]
+pub fn sqrtm<T: RealField>(out: MatMut<'_, Complex<T>>, A: MatRef<'_, Complex<T>>, par: Par, stack: &mut MemStack) -> Result<(), MatFunError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, out.nrows() == n, out.ncols() == n));
+
+	let (mut t, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut z, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let mut t = t.as_mat_mut();
+	let mut z = z.as_mat_mut();
+
+	schur(A, t.rb_mut(), z.rb_mut(), par, stack)?;
+	if has_eigenvalue_on_closed_negative_real_axis(t.rb()) {
+		return Err(MatFunError::EigenvalueOnNegativeRealAxis);
+	}
+
+	sqrtm_triangular_in_place(t.rb_mut(), par);
+	from_schur(out, z.rb(), t.rb(), par, stack);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+	use linalg::solvers::DenseSolveCore;
+
+	#[test]
+	fn test_sqrtm() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 4, 20, 75] {
+			let X = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			// the hermitian part is positive definite, so the eigenvalues have positive real parts
+			let A = &X * X.adjoint() + Mat::<c64>::identity(n, n) + (&X - X.adjoint()) * Scale(c64::new(0.5, 0.0));
+
+			let mut R = Mat::zeros(n, n);
+			sqrtm(
+				R.as_mut(),
+				A.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(sqrtm_scratch::<f64>(n, Par::Seq))),
+			)
+			.unwrap();
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+			assert!(&R * &R ~ A);
+
+			let mut t = Mat::<c64>::zeros(n, n);
+			let mut z = Mat::<c64>::zeros(n, n);
+			let mut s = Diag::<c64>::zeros(n);
+			linalg::evd::schur_cplx(
+				R.as_ref(),
+				t.as_mut(),
+				Some(z.as_mut()),
+				s.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(linalg::evd::schur_scratch::<c64>(n, Par::Seq, default()))),
+				default(),
+			)
+			.unwrap();
+			for i in 0..n {
+				assert!(s[i].re > 0.0);
+			}
+		}
+	}
+
+	#[test]
+	fn test_sqrtm_negative_eigenvalue() {
+		let A = Mat::<c64>::from_fn(3, 3, |i, j| if i == j { c64::new(i as f64 - 1.0, 0.0) } else { c64::ZERO });
+		let mut R = Mat::zeros(3, 3);
+		assert!(
+			sqrtm(
+				R.as_mut(),
+				A.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(sqrtm_scratch::<f64>(3, Par::Seq))),
+			) == Err(MatFunError::EigenvalueOnNegativeRealAxis)
+		);
+	}
+
+	#[test]
+	fn test_sqrtm_negative_eigenvalue_dense() {
+		// the computed schur form of a dense matrix carries rounding errors in the imaginary parts of
+		// its real eigenvalues
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 20;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let D = Mat::<c64>::from_fn(n, n, |i, j| {
+			if i == j {
+				c64::new(if i == 3 { -2.0 } else { 1.0 + i as f64 }, 0.0)
+			} else {
+				c64::ZERO
+			}
+		});
+		let A = &X * &D * X.partial_piv_lu().inverse();
+
+		let mut R = Mat::zeros(n, n);
+		assert!(
+			sqrtm(
+				R.as_mut(),
+				A.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(sqrtm_scratch::<f64>(n, Par::Seq))),
+			) == Err(MatFunError::EigenvalueOnNegativeRealAxis)
+		);
+	}
+}
@@ -0,0 +1,458 @@
+use super::*;
+use crate::assert;
+use linalg::evd::schur::solve_sylvester_quasi_triangular_in_place;
+use linalg::qr::no_pivoting::factor as qr;
+use linalg::qr::no_pivoting::update::make_rot;
+use linalg::triangular_solve::solve_upper_triangular_in_place;
+
+/// computes the size and alignment of the workspace required to solve the continuous lyapunov
+/// equation with a matrix $A$ of dimension `dim`
+pub fn solve_lyapunov_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	let n = dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n, n).array(4),
+		StackReq::any_of(&[schur_scratch::<T>(n, par), schur_basis_scratch::<T>(n, n)]),
+	])
+}
+
+/// solves the continuous lyapunov equation $A X + X A^H = Q$ and stores the result in `X`
+///
+/// if $Q$ is self-adjoint, then so is $X$
+///
+/// # panics
+/// panics if $A$, $Q$ and $X$ are not square matrices of the same dimension
+pub fn solve_lyapunov<T: ComplexField>(
+	X: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	Q: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, Q.nrows() == n, Q.ncols() == n, X.nrows() == n, X.ncols() == n));
+
+	let mut X = X;
+
+	let (mut T, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut U, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut Y, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut S, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut T = T.as_mat_mut();
+	let mut U = U.as_mat_mut();
+	let mut Y = Y.as_mat_mut();
+	let mut S = S.as_mat_mut();
+
+	schur(A, T.rb_mut(), U.rb_mut(), par, stack)?;
+	S.copy_from(T.rb().adjoint().reverse_rows_and_cols());
+	to_schur_basis(Y.rb_mut(), U.rb(), Q, U.rb(), par, stack);
+
+	// $T Y + Y T^H = F$ is equivalent to $T (Y P) + (Y P) (P T^H P) = F P$, where $P$ is the
+	// exchange matrix, and $S = P T^H P$ is upper quasi-triangular
+	solve_sylvester_quasi_triangular_in_place(T.rb(), S.rb(), Y.rb_mut().reverse_cols_mut(), par);
+
+	from_schur_basis(X.rb_mut(), U.rb(), Y.rb(), U.rb(), par, stack);
+
+	Ok(())
+}
+
+fn r_factor_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par) -> StackReq {
+	let bs = qr::recommended_blocksize::<T>(nrows, ncols);
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(bs, ncols),
+		qr::qr_in_place_scratch::<T>(nrows, ncols, bs, par, default()),
+	])
+}
+
+/// overwrites the top $n \times n$ block of the $m \times n$ matrix $M$ with the factor $R$ of its
+/// $QR$ decomposition, where $m \geq n$
+fn r_factor<T: ComplexField>(M: MatMut<'_, T>, par: Par, stack: &mut MemStack) {
+	let mut M = M;
+	let (m, n) = M.shape();
+	if n == 0 {
+		return;
+	}
+
+	let bs = qr::recommended_blocksize::<T>(m, n);
+	let (mut H, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs, n, stack) };
+	qr::qr_in_place(M.rb_mut(), H.as_mat_mut(), par, stack, default());
+
+	for j in 0..n {
+		M.rb_mut().col_mut(j).get_mut(j + 1..).fill(zero());
+	}
+}
+
+/// computes the size and alignment of the workspace required to compute the cholesky factor of
+/// the solution of the continuous lyapunov equation with a matrix $A$ of dimension `dim` and a
+/// matrix $B$ with `rank` columns
+pub fn lyapunov_cholesky_cplx_scratch<T: RealField>(dim: usize, rank: usize, par: Par) -> StackReq {
+	let n = dim;
+	let k = Ord::max(n, rank);
+	StackReq::all_of(&[
+		temp_mat_scratch::<Complex<T>>(n, n).array(4),
+		StackReq::any_of(&[
+			schur_scratch::<Complex<T>>(n, par),
+			StackReq::all_of(&[temp_mat_scratch::<Complex<T>>(k, n), r_factor_scratch::<Complex<T>>(k, n, par)]),
+			temp_mat_scratch::<Complex<T>>(n, 1),
+		]),
+	])
+}
+
+/// computes the size and alignment of the workspace required to compute the cholesky factor of
+/// the solution of the continuous lyapunov equation with a matrix $A$ of dimension `dim` and a
+/// matrix $B$ with `rank` columns
+pub fn lyapunov_cholesky_real_scratch<T: RealField>(dim: usize, rank: usize, par: Par) -> StackReq {
+	let n = dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<Complex<T>>(n, n).array(2),
+		temp_mat_scratch::<Complex<T>>(n, rank),
+		StackReq::any_of(&[
+			lyapunov_cholesky_cplx_scratch::<T>(n, rank, par),
+			StackReq::all_of(&[temp_mat_scratch::<T>(2 * n, n), r_factor_scratch::<T>(2 * n, n, par)]),
+		]),
+	])
+}
+
+/// computes the lower triangular cholesky factor $L$ of the solution $X = L L^H$ of the continuous
+/// lyapunov equation $A X + X A^H = -B B^H$, using hammarling's method
+///
+/// $A$ must be stable, i.e., all its eigenvalues must have a negative real part. the diagonal of
+/// $L$ is real and non-negative
+///
+/// # panics
+/// panics if $A$ and $L$ are not square matrices of the same dimension, or if $B$ doesn't have
+/// the same number of rows as $A$
+#[math]
+pub fn lyapunov_cholesky_cplx<T: RealField>(
+	L: MatMut<'_, Complex<T>>,
+	A: MatRef<'_, Complex<T>>,
+	B: MatRef<'_, Complex<T>>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let p = B.ncols();
+	assert!(all(A.ncols() == n, B.nrows() == n, L.nrows() == n, L.ncols() == n));
+
+	let mut L = L;
+	if n == 0 {
+		return Ok(());
+	}
+
+	let (mut T, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut Z, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut C, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut U, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let mut T = T.as_mat_mut();
+	let mut Z = Z.as_mat_mut();
+	let mut C = C.as_mat_mut();
+	let mut U = U.as_mat_mut();
+
+	schur(A, T.rb_mut(), Z.rb_mut(), par, stack)?;
+	for j in 0..n {
+		if real(T[(j, j)]) >= zero() {
+			return Err(MatrixEquationError::Unstable);
+		}
+	}
+
+	// the right-hand side $Z^H B B^H Z$ is written as $C C^H$ with $C$ upper triangular, using the
+	// $QR$ decomposition of $(P Z^H B)^H = R_0$, so that $C = P R_0^H P$
+	{
+		let k = Ord::max(n, p);
+		let (mut M, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(k, n, stack) };
+		let mut M = M.as_mat_mut();
+		M.rb_mut().get_mut(p.., ..).fill(zero());
+		matmul(
+			M.rb_mut().get_mut(..p, ..),
+			Accum::Replace,
+			B.adjoint(),
+			Z.rb().reverse_cols(),
+			one(),
+			par,
+		);
+		r_factor(M.rb_mut(), par, stack);
+		C.copy_from(M.rb().get(..n, ..).adjoint().reverse_rows_and_cols());
+	}
+
+	U.fill(zero());
+	{
+		let (mut d, _) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, 1, stack) };
+		let mut d = d.as_mat_mut().col_mut(0);
+		d.copy_from(T.rb().diagonal().column_vector());
+
+		for j in (0..n).rev() {
+			let tau = copy(T[(j, j)]);
+			let gamma = copy(C[(j, j)]);
+
+			// the last diagonal entry of $U$ satisfies $2 \operatorname{Re}(\tau) |\upsilon|^2 = -|\gamma|^2$
+			let ups = abs(gamma) / sqrt(-(real(tau) + real(tau)));
+			U[(j, j)] = from_real(ups);
+
+			let (C1, c) = C.rb_mut().split_at_col_mut(j);
+			let mut C1 = C1.get_mut(..j, ..);
+			let mut c = c.col_mut(0).get_mut(..j);
+			let mut u = U.rb_mut().col_mut(j).get_mut(..j);
+
+			if ups > zero() {
+				// $(T_1 + \bar\tau I) u = -(\bar\gamma / \upsilon) c - \upsilon t$
+				let alpha = mul_real(conj(gamma), recip(ups));
+				zip!(u.rb_mut(), c.rb(), T.rb().col(j).get(..j)).for_each(|unzip!(u, c, t)| *u = -(alpha * *c) - mul_real(*t, ups));
+
+				for i in 0..j {
+					T[(i, i)] = T[(i, i)] + conj(tau);
+				}
+				solve_upper_triangular_in_place(T.rb().get(..j, ..j), u.rb_mut().as_mat_mut(), par);
+				T.rb_mut().diagonal_mut().column_vector_mut().get_mut(..j).copy_from(d.rb().get(..j));
+
+				// the remaining right-hand side is $C_1 C_1^H + y y^H$, with $y = c - (\gamma / \upsilon) u$
+				let beta = mul_real(gamma, recip(ups));
+				zip!(c.rb_mut(), u.rb()).for_each(|unzip!(c, u)| *c = *c - beta * *u);
+			}
+
+			// restore the triangular structure of $[C_1, y]$ with rotations from the right
+			for i in (0..j).rev() {
+				let (rot, r) = make_rot(copy(C1[(i, i)]), copy(c[i]));
+				C1[(i, i)] = r;
+				c[i] = zero();
+				rot.conjugate()
+					.apply_on_the_right_in_place((C1.rb_mut().col_mut(i).get_mut(..i), c.rb_mut().get_mut(..i)));
+			}
+		}
+	}
+
+	// $X = (Z U) (Z U)^H$, and $L^H$ is the triangular factor of $(Z U)^H$
+	let mut W = C;
+	matmul(W.rb_mut(), Accum::Replace, U.rb().adjoint(), Z.rb().adjoint(), one(), par);
+	r_factor(W.rb_mut(), par, stack);
+
+	L.copy_from(W.rb().adjoint());
+	for j in 0..n {
+		let d = abs(L[(j, j)]);
+		if d > zero() {
+			let phase = mul_real(conj(L[(j, j)]), recip(d));
+			zip!(L.rb_mut().col_mut(j).get_mut(j + 1..)).for_each(|unzip!(x)| *x = *x * phase);
+			L[(j, j)] = from_real(d);
+		}
+	}
+
+	Ok(())
+}
+
+/// computes the lower triangular cholesky factor $L$ of the solution $X = L L^\top$ of the
+/// continuous lyapunov equation $A X + X A^\top = -B B^\top$, using hammarling's method
+///
+/// $A$ must be stable, i.e., all its eigenvalues must have a negative real part. the diagonal of
+/// $L$ is non-negative
+///
+/// # panics
+/// panics if $A$ and $L$ are not square matrices of the same dimension, or if $B$ doesn't have
+/// the same number of rows as $A$
+#[math]
+pub fn lyapunov_cholesky_real<T: RealField>(
+	L: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let p = B.ncols();
+	assert!(all(A.ncols() == n, B.nrows() == n, L.nrows() == n, L.ncols() == n));
+
+	let mut L = L;
+	if n == 0 {
+		return Ok(());
+	}
+
+	let (mut Ac, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut Lc, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, n, stack) };
+	let (mut Bc, stack) = unsafe { temp_mat_uninit::<Complex<T>, _, _>(n, p, stack) };
+	let mut Ac = Ac.as_mat_mut();
+	let mut Lc = Lc.as_mat_mut();
+	let mut Bc = Bc.as_mat_mut();
+
+	zip!(Ac.rb_mut(), A).for_each(|unzip!(dst, src)| *dst = Complex::new(copy(*src), zero()));
+	zip!(Bc.rb_mut(), B).for_each(|unzip!(dst, src)| *dst = Complex::new(copy(*src), zero()));
+
+	lyapunov_cholesky_cplx(Lc.rb_mut(), Ac.rb(), Bc.rb(), par, stack)?;
+
+	// $X = \operatorname{Re}(L_c) \operatorname{Re}(L_c)^\top + \operatorname{Im}(L_c)
+	// \operatorname{Im}(L_c)^\top$ since $X$ is real
+	let (mut M, stack) = unsafe { temp_mat_uninit::<T, _, _>(2 * n, n, stack) };
+	let mut M = M.as_mat_mut();
+	{
+		let (mut top, mut bot) = M.rb_mut().split_at_row_mut(n);
+		zip!(top.rb_mut(), bot.rb_mut(), Lc.rb().transpose()).for_each(|unzip!(re, im, l)| {
+			*re = copy(l.re);
+			*im = copy(l.im);
+		});
+	}
+	r_factor(M.rb_mut(), par, stack);
+
+	L.copy_from(M.rb().get(..n, ..).transpose());
+	for j in 0..n {
+		if L[(j, j)] < zero() {
+			zip!(L.rb_mut().col_mut(j).get_mut(j..)).for_each(|unzip!(x)| *x = -*x);
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn stable<T: ComplexField<Real = f64>>(A: Mat<T>) -> Mat<T> {
+		let n = A.nrows();
+		let shift = A.norm_l2() + 1.0;
+		let mut A = A;
+		for i in 0..n {
+			A[(i, i)] = sub(&A[(i, i)], &from_f64::<T>(shift));
+		}
+		A
+	}
+
+	fn test_lyapunov<T: ComplexField<Real = f64>>(n: usize, rng: &mut StdRng, sample: impl Fn(usize, usize, &mut StdRng) -> Mat<T>) {
+		let A = sample(n, n, rng);
+		let Q = sample(n, n, rng);
+
+		let mut X = Mat::zeros(n, n);
+		solve_lyapunov(
+			X.as_mut(),
+			A.as_ref(),
+			Q.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(solve_lyapunov_scratch::<T>(n, Par::Seq))),
+		)
+		.unwrap();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1e4 * (n as f64));
+		assert!(&A * &X + &X * A.adjoint() ~ Q);
+	}
+
+	#[test]
+	fn test_real() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for n in [0, 1, 2, 3, 7, 20, 50] {
+			test_lyapunov::<f64>(n, rng, |m, n, rng| {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.sample(rng)
+			});
+		}
+	}
+
+	#[test]
+	fn test_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for n in [0, 1, 2, 3, 7, 20, 50] {
+			test_lyapunov::<c64>(n, rng, |m, n, rng| {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+				}
+				.sample(rng)
+			});
+		}
+	}
+
+	#[test]
+	fn test_cholesky_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (n, p) in [(1, 1), (2, 1), (5, 2), (8, 8), (20, 3), (30, 40)] {
+			let sample = |m: usize, n: usize, rng: &mut StdRng| -> Mat<c64> {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+				}
+				.sample(rng)
+			};
+			let A = stable(sample(n, n, rng));
+			let B = sample(n, p, rng);
+
+			let mut L = Mat::zeros(n, n);
+			lyapunov_cholesky_cplx(
+				L.as_mut(),
+				A.as_ref(),
+				B.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(lyapunov_cholesky_cplx_scratch::<f64>(n, p, Par::Seq))),
+			)
+			.unwrap();
+
+			for j in 0..n {
+				assert!(L[(j, j)].im == 0.0);
+				assert!(L[(j, j)].re >= 0.0);
+				for i in 0..j {
+					assert!(L[(i, j)] == c64::ZERO);
+				}
+			}
+
+			let X = &L * L.adjoint();
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e4 * (n as f64));
+			assert!(&A * &X + &X * A.adjoint() ~ -(&B * B.adjoint()));
+		}
+	}
+
+	#[test]
+	fn test_cholesky_real() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (n, p) in [(1, 1), (2, 1), (5, 2), (8, 8), (20, 3), (30, 40)] {
+			let sample = |m: usize, n: usize, rng: &mut StdRng| -> Mat<f64> {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.sample(rng)
+			};
+			let A = stable(sample(n, n, rng));
+			let B = sample(n, p, rng);
+
+			let mut L = Mat::zeros(n, n);
+			lyapunov_cholesky_real(
+				L.as_mut(),
+				A.as_ref(),
+				B.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(lyapunov_cholesky_real_scratch::<f64>(n, p, Par::Seq))),
+			)
+			.unwrap();
+
+			for j in 0..n {
+				assert!(L[(j, j)] >= 0.0);
+				for i in 0..j {
+					assert!(L[(i, j)] == 0.0);
+				}
+			}
+
+			let X = &L * L.transpose();
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e4 * (n as f64));
+			assert!(&A * &X + &X * A.transpose() ~ -(&B * B.transpose()));
+		}
+	}
+
+	#[test]
+	fn test_unstable() {
+		let A = Mat::<f64>::identity(3, 3);
+		let B = Mat::<f64>::identity(3, 3);
+		let mut L = Mat::zeros(3, 3);
+		let result = lyapunov_cholesky_real(
+			L.as_mut(),
+			A.as_ref(),
+			B.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(lyapunov_cholesky_real_scratch::<f64>(3, 3, Par::Seq))),
+		);
+		assert!(result == Err(MatrixEquationError::Unstable));
+	}
+}
@@ -0,0 +1,129 @@
+//! low level implementation of solvers for linear matrix equations
+//!
+//! the following equations are supported, where $X$ is the unknown:
+//!
+//! - the sylvester equation $A X + X B = C$
+//! - the continuous lyapunov equation $A X + X A^H = Q$
+//! - the discrete lyapunov (stein) equation $A X A^H - X = Q$
+//!
+//! the solvers use the bartels-stewart algorithm: the coefficient matrices are reduced to real or
+//! complex schur form, the transformed equation is solved by block substitution, and the solution
+//! is transformed back
+//!
+//! the sylvester equation has a unique solution if and only if $A$ and $-B$ have no common
+//! eigenvalue. the continuous lyapunov equation has a unique solution if $\lambda_i + \bar\lambda_j
+//! \neq 0$ for all eigenvalues of $A$, and the stein equation if $\lambda_i \bar\lambda_j \neq 1$.
+//! when these conditions are close to being violated, small pivots are perturbed and the computed
+//! solution may be inaccurate
+//!
+//! when $A$ is stable and the right-hand side is negative semidefinite, the solution of the
+//! lyapunov equation is positive semidefinite, and [`lyapunov::lyapunov_cholesky_cplx`] computes
+//! its cholesky factor directly with hammarling's method
+//!
+//! the stabilizing solutions of the continuous and discrete algebraic riccati equations, along with
+//! the corresponding optimal feedback gains, are computed by [`riccati::care`] and
+//! [`riccati::dare`] from the stable invariant subspace of the hamiltonian matrix, or the stable
+//! deflating subspace of the symplectic pencil, and are then refined with newton's method, each
+//! step of which solves a lyapunov or stein equation
+
+use crate::internal_prelude::*;
+use linalg::matmul::matmul;
+
+/// continuous lyapunov equation
+pub mod lyapunov;
+/// continuous and discrete algebraic riccati equations
+pub mod riccati;
+/// discrete lyapunov equation
+pub mod stein;
+/// sylvester equation
+pub mod sylvester;
+
+/// matrix equation error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MatrixEquationError {
+	/// the schur decomposition did not converge
+	NoConvergence,
+	/// the coefficient matrix is not stable, which is required for the cholesky factor of the
+	/// solution to exist
+	Unstable,
+	/// the weight matrix of a riccati equation is not positive definite
+	NonPositiveDefiniteWeight,
+	/// the riccati equation has no stabilizing solution, or it could not be computed reliably
+	/// because the closed loop spectrum is too close to the stability boundary
+	NoStabilizingSolution,
+}
+
+pub(crate) fn schur_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(dim, 1).array(2),
+		linalg::evd::schur_scratch::<T>(dim, par, default()),
+	])
+}
+
+/// computes the schur decomposition $A = Z T Z^H$, where $T$ is upper quasi-triangular if `T` is
+/// real, and upper triangular otherwise
+pub(crate) fn schur<T: ComplexField>(
+	A: MatRef<'_, T>,
+	t: MatMut<'_, T>,
+	z: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let (mut s_re, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut s_im, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let s_re = s_re.as_mat_mut().col_mut(0).as_diagonal_mut();
+	let s_im = s_im.as_mat_mut().col_mut(0).as_diagonal_mut();
+
+	if try_const! { T::IS_REAL } {
+		linalg::evd::schur_real::<T::Real>(
+			unsafe { core::mem::transmute(A) },
+			unsafe { core::mem::transmute(t) },
+			Some(unsafe { core::mem::transmute(z) }),
+			unsafe { core::mem::transmute(s_re) },
+			unsafe { core::mem::transmute(s_im) },
+			par,
+			stack,
+			default(),
+		)
+	} else {
+		linalg::evd::schur_cplx(A, t, Some(z), s_re, par, stack, default())
+	}
+	.map_err(|_| MatrixEquationError::NoConvergence)
+}
+
+/// computes $U^H C V$ and stores the result in `out`
+pub(crate) fn to_schur_basis<T: ComplexField>(
+	out: MatMut<'_, T>,
+	U: MatRef<'_, T>,
+	C: MatRef<'_, T>,
+	V: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let (mut tmp, _) = unsafe { temp_mat_uninit::<T, _, _>(U.ncols(), C.ncols(), stack) };
+	let mut tmp = tmp.as_mat_mut();
+
+	matmul(tmp.rb_mut(), Accum::Replace, U.adjoint(), C, one(), par);
+	matmul(out, Accum::Replace, tmp.rb(), V, one(), par);
+}
+
+/// computes $U Y V^H$ and stores the result in `out`
+pub(crate) fn from_schur_basis<T: ComplexField>(
+	out: MatMut<'_, T>,
+	U: MatRef<'_, T>,
+	Y: MatRef<'_, T>,
+	V: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let (mut tmp, _) = unsafe { temp_mat_uninit::<T, _, _>(U.nrows(), Y.ncols(), stack) };
+	let mut tmp = tmp.as_mat_mut();
+
+	matmul(tmp.rb_mut(), Accum::Replace, U, Y, one(), par);
+	matmul(out, Accum::Replace, tmp.rb(), V.adjoint(), one(), par);
+}
+
+pub(crate) fn schur_basis_scratch<T: ComplexField>(m: usize, n: usize) -> StackReq {
+	temp_mat_scratch::<T>(m, n)
+}
@@ -0,0 +1,876 @@
+use super::*;
+use crate::assert;
+use linalg::evd::schur::{reorder_schur_cplx, reorder_schur_real};
+use linalg::gevd::{generalized_schur_imp, generalized_schur_scratch, reorder_generalized_schur_imp};
+use linalg::lu::partial_pivoting::{factor as lu, solve as lu_solve};
+
+/// riccati solver tuning parameters
+#[derive(Copy, Clone, Debug)]
+pub struct RiccatiParams {
+	/// maximum number of newton steps used to refine the solution obtained from the schur
+	/// decomposition
+	pub max_refinement_iterations: usize,
+
+	#[doc(hidden)]
+	pub non_exhaustive: NonExhaustive,
+}
+
+impl<T: ComplexField> Auto<T> for RiccatiParams {
+	fn auto() -> Self {
+		Self {
+			max_refinement_iterations: 8,
+			non_exhaustive: NonExhaustive(()),
+		}
+	}
+}
+
+/// replaces $X$ by $(X + X^H) / 2$
+#[math]
+fn make_self_adjoint<T: ComplexField>(X: MatMut<'_, T>) {
+	let mut X = X;
+	let n = X.nrows();
+	for j in 0..n {
+		X[(j, j)] = from_real(real(X[(j, j)]));
+		for i in j + 1..n {
+			let x = mul_real(X[(i, j)] + conj(X[(j, i)]), from_f64::<T::Real>(0.5));
+			X[(j, i)] = conj(x);
+			X[(i, j)] = x;
+		}
+	}
+}
+
+/// computes the cholesky factor of the self-adjoint matrix stored in the lower triangular half of
+/// $L$, and sets its strictly upper triangular half to zero
+fn cholesky<T: ComplexField>(L: MatMut<'_, T>, par: Par, stack: &mut MemStack) -> Result<(), MatrixEquationError> {
+	let mut L = L;
+	linalg::cholesky::llt::factor::cholesky_in_place(L.rb_mut(), default(), par, stack, default())
+		.map_err(|_| MatrixEquationError::NonPositiveDefiniteWeight)?;
+	z!(L).for_each_triangular_upper(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
+	Ok(())
+}
+
+fn cholesky_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	linalg::cholesky::llt::factor::cholesky_in_place_scratch::<T>(dim, par, default())
+}
+
+fn stabilizing_solution_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	let n = dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n, n),
+		StackReq::new::<usize>(n).array(2),
+		StackReq::any_of(&[
+			lu::lu_in_place_scratch::<usize, T>(n, n, par, default()),
+			lu_solve::solve_transpose_in_place_scratch::<usize, T>(n, n, par),
+		]),
+	])
+}
+
+/// computes $X = Z_{21} Z_{11}^{-1}$, where the first $n$ columns of the $2n \times 2n$ matrix $Z$
+/// span the stable invariant or deflating subspace
+#[math]
+fn stabilizing_solution<T: ComplexField>(X: MatMut<'_, T>, Z: MatRef<'_, T>, par: Par, stack: &mut MemStack) -> Result<(), MatrixEquationError> {
+	let n = X.nrows();
+	let mut X = X;
+
+	let (mut Z11, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut Z11 = Z11.as_mat_mut();
+	let (mut perm, stack) = stack.make_with(n, |_| 0usize);
+	let (mut perm_inv, stack) = stack.make_with(n, |_| 0usize);
+
+	Z11.copy_from(Z.get(..n, ..n));
+	let (_, perm) = lu::lu_in_place(Z11.rb_mut(), &mut perm, &mut perm_inv, par, stack, default());
+	for j in 0..n {
+		if Z11[(j, j)] == zero() {
+			return Err(MatrixEquationError::NoStabilizingSolution);
+		}
+	}
+
+	// $X Z_{11} = Z_{21}$
+	X.copy_from(Z.get(n.., ..n));
+	lu_solve::solve_transpose_in_place_with_conj(Z11.rb(), Z11.rb(), perm, Conj::No, X.rb_mut().transpose_mut(), par, stack);
+
+	for j in 0..n {
+		for i in 0..n {
+			if !is_finite(X[(i, j)]) {
+				return Err(MatrixEquationError::NoStabilizingSolution);
+			}
+		}
+	}
+	make_self_adjoint(X);
+	Ok(())
+}
+
+/// computes $L = \operatorname{chol}(R)$, $W = L^{-1} B^H$ and $G = W^H W = B R^{-1} B^H$
+fn weighted_input<T: ComplexField>(
+	L: MatMut<'_, T>,
+	W: MatMut<'_, T>,
+	G: MatMut<'_, T>,
+	B: MatRef<'_, T>,
+	R: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let mut L = L;
+	let mut W = W;
+
+	L.copy_from_triangular_lower(R);
+	cholesky(L.rb_mut(), par, stack)?;
+
+	W.copy_from(B.adjoint());
+	linalg::triangular_solve::solve_lower_triangular_in_place(L.rb(), W.rb_mut(), par);
+	matmul(G, Accum::Replace, W.rb().adjoint(), W.rb(), one(), par);
+	Ok(())
+}
+
+fn care_schur_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	let n2 = 2 * dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n2, n2).array(3),
+		StackReq::new::<bool>(n2),
+		StackReq::any_of(&[schur_scratch::<T>(n2, par), stabilizing_solution_scratch::<T>(dim, par)]),
+	])
+}
+
+/// computes the stabilizing solution of $A^H X + X A - X G X + Q = 0$ from the ordered schur
+/// decomposition of the hamiltonian matrix
+#[math]
+fn care_schur<T: ComplexField>(
+	X: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	G: MatRef<'_, T>,
+	Q: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let n2 = 2 * n;
+
+	let (mut H, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut T, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut U, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let mut H = H.as_mat_mut();
+	let mut T = T.as_mat_mut();
+	let mut U = U.as_mat_mut();
+	let (mut select, stack) = stack.make_with(n2, |_| false);
+
+	// $H = \begin{bmatrix} A & -G \\\\ -Q & -A^H \end{bmatrix}$
+	{
+		let (mut H00, mut H01, mut H10, mut H11) = H.rb_mut().split_at_mut(n, n);
+		H00.copy_from(A);
+		zip!(H01.rb_mut(), G).for_each(|unzip!(dst, src)| *dst = -*src);
+		zip!(H10.rb_mut(), Q).for_each(|unzip!(dst, src)| *dst = -*src);
+		H11.copy_from(A.adjoint());
+		zip!(H11.rb_mut()).for_each(|unzip!(x)| *x = -*x);
+	}
+
+	schur(H.rb(), T.rb_mut(), U.rb_mut(), par, stack)?;
+
+	let mut k = 0;
+	while k < n2 {
+		if try_const! { T::IS_REAL } && k + 1 < n2 && T[(k + 1, k)] != zero() {
+			let stable = real(T[(k, k)]) + real(T[(k + 1, k + 1)]) < zero();
+			select[k] = stable;
+			select[k + 1] = stable;
+			k += 2;
+		} else {
+			select[k] = real(T[(k, k)]) < zero();
+			k += 1;
+		}
+	}
+	if select.iter().filter(|&&stable| stable).count() != n {
+		return Err(MatrixEquationError::NoStabilizingSolution);
+	}
+
+	let count = if try_const! { T::IS_REAL } {
+		reorder_schur_real::<T::Real>(
+			unsafe { core::mem::transmute(T.rb_mut()) },
+			Some(unsafe { core::mem::transmute(U.rb_mut()) }),
+			&select,
+		)
+		.map_err(|_| MatrixEquationError::NoStabilizingSolution)?
+	} else {
+		reorder_schur_cplx(T.rb_mut(), Some(U.rb_mut()), &select)
+	};
+	if count != n {
+		return Err(MatrixEquationError::NoStabilizingSolution);
+	}
+
+	stabilizing_solution(X, U.rb(), par, stack)
+}
+
+fn dare_schur_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	let n2 = 2 * dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n2, n2).array(5),
+		temp_mat_scratch::<T>(n2, 1).array(2),
+		StackReq::new::<bool>(n2),
+		StackReq::any_of(&[generalized_schur_scratch::<T>(n2, par), stabilizing_solution_scratch::<T>(dim, par)]),
+	])
+}
+
+/// computes the stabilizing solution of $A^H X A - X - A^H X (I + G X)^{-1} G X A + Q = 0$ from
+/// the ordered generalized schur decomposition of the symplectic pencil. `T` must be a complex
+/// type
+#[math]
+fn dare_schur<T: ComplexField>(
+	X: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	G: MatRef<'_, T>,
+	Q: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let n2 = 2 * n;
+
+	let (mut L, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut M, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut S, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut P, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut Z, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, n2, stack) };
+	let (mut alpha, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, 1, stack) };
+	let (mut beta, stack) = unsafe { temp_mat_uninit::<T, _, _>(n2, 1, stack) };
+	let mut L = L.as_mat_mut();
+	let mut M = M.as_mat_mut();
+	let mut S = S.as_mat_mut();
+	let mut P = P.as_mat_mut();
+	let mut Z = Z.as_mat_mut();
+	let (mut select, stack) = stack.make_with(n2, |_| false);
+
+	// $L = \begin{bmatrix} A & 0 \\\\ -Q & I \end{bmatrix}$, $M = \begin{bmatrix} I & G \\\\ 0 &
+	// A^H \end{bmatrix}$
+	{
+		let (mut L00, mut L01, mut L10, mut L11) = L.rb_mut().split_at_mut(n, n);
+		L00.copy_from(A);
+		L01.fill(zero());
+		zip!(L10.rb_mut(), Q).for_each(|unzip!(dst, src)| *dst = -*src);
+		L11.fill(zero());
+		L11.diagonal_mut().fill(one());
+
+		let (mut M00, mut M01, mut M10, mut M11) = M.rb_mut().split_at_mut(n, n);
+		M00.fill(zero());
+		M00.diagonal_mut().fill(one());
+		M01.copy_from(G);
+		M10.fill(zero());
+		M11.copy_from(A.adjoint());
+	}
+
+	generalized_schur_imp(
+		L.rb(),
+		M.rb(),
+		S.rb_mut(),
+		P.rb_mut(),
+		None,
+		Some(Z.rb_mut()),
+		alpha.as_mat_mut().col_mut(0),
+		beta.as_mat_mut().col_mut(0),
+		par,
+		stack,
+	)
+	.map_err(|_| MatrixEquationError::NoConvergence)?;
+
+	for k in 0..n2 {
+		select[k] = abs(S[(k, k)]) < abs(P[(k, k)]);
+	}
+	if select.iter().filter(|&&stable| stable).count() != n {
+		return Err(MatrixEquationError::NoStabilizingSolution);
+	}
+	reorder_generalized_schur_imp(S.rb_mut(), P.rb_mut(), None, Some(Z.rb_mut()), &select);
+
+	stabilizing_solution(X, Z.rb(), par, stack)
+}
+
+/// computes the size and alignment of the workspace required to solve the continuous algebraic
+/// riccati equation with `state_dim` states and `input_dim` inputs
+pub fn care_scratch<T: ComplexField>(state_dim: usize, input_dim: usize, par: Par, params: Spec<RiccatiParams, T>) -> StackReq {
+	_ = params;
+	let n = state_dim;
+	let m = input_dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(m, m),
+		temp_mat_scratch::<T>(m, n),
+		temp_mat_scratch::<T>(n, n),
+		StackReq::any_of(&[
+			cholesky_scratch::<T>(m, par),
+			care_schur_scratch::<T>(n, par),
+			StackReq::all_of(&[
+				temp_mat_scratch::<T>(n, n).array(4),
+				temp_mat_scratch::<T>(m, n),
+				lyapunov::solve_lyapunov_scratch::<T>(n, par),
+			]),
+		]),
+	])
+}
+
+/// solves the continuous algebraic riccati equation
+///
+/// $$A^H X + X A - X B R^{-1} B^H X + Q = 0$$
+///
+/// and stores the stabilizing solution in `X`, i.e. the self-adjoint solution for which all the
+/// eigenvalues of $A - B K$ have a negative real part, where $K = R^{-1} B^H X$ is the optimal
+/// gain of the associated linear quadratic regulator, which is stored in `K`
+///
+/// $Q$ must be self-adjoint, and $R$ self-adjoint positive definite, in which case only its lower
+/// triangular half is accessed. the solution is computed from the ordered schur decomposition of
+/// the hamiltonian matrix, then refined with newton's method
+///
+/// # panics
+/// panics if $A$ is $n\times n$, $B$ is $n\times m$, and the other matrices don't have compatible
+/// dimensions
+#[math]
+#[track_caller]
+pub fn care<T: ComplexField>(
+	X: MatMut<'_, T>,
+	K: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	Q: MatRef<'_, T>,
+	R: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<RiccatiParams, T>,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let m = B.ncols();
+	assert!(all(
+		A.ncols() == n,
+		B.nrows() == n,
+		Q.nrows() == n,
+		Q.ncols() == n,
+		R.nrows() == m,
+		R.ncols() == m,
+		X.nrows() == n,
+		X.ncols() == n,
+		K.nrows() == m,
+		K.ncols() == n,
+	));
+
+	let params = params.config;
+	let mut X = X;
+	let mut K = K;
+
+	let (mut L, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, m, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, n, stack) };
+	let (mut G, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut L = L.as_mat_mut();
+	let mut W = W.as_mat_mut();
+	let mut G = G.as_mat_mut();
+
+	weighted_input(L.rb_mut(), W.rb_mut(), G.rb_mut(), B, R, par, stack)?;
+	care_schur(X.rb_mut(), A, G.rb(), Q, par, stack)?;
+
+	{
+		let (mut Res, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut Ak, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut N, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut X_prev, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut V, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, n, stack) };
+		let mut Res = Res.as_mat_mut();
+		let mut Ak = Ak.as_mat_mut();
+		let mut N = N.as_mat_mut();
+		let mut X_prev = X_prev.as_mat_mut();
+		let mut V = V.as_mat_mut();
+
+		let tol = eps::<T::Real>() * from_f64::<T::Real>(n as f64);
+		let mut prev_norm = infinity::<T::Real>();
+
+		for iter in 0..params.max_refinement_iterations + 1 {
+			// $\mathcal{R}(X) = A^H X + X A - V^H V + Q$, with $V = W X$
+			matmul(V.rb_mut(), Accum::Replace, W.rb(), X.rb(), one(), par);
+			Res.copy_from(Q);
+			matmul(Res.rb_mut(), Accum::Add, A.adjoint(), X.rb(), one(), par);
+			matmul(Res.rb_mut(), Accum::Add, X.rb(), A, one(), par);
+			matmul(Res.rb_mut(), Accum::Add, V.rb().adjoint(), V.rb(), -one::<T>(), par);
+
+			let res_norm = Res.norm_l2();
+			if res_norm >= prev_norm {
+				X.copy_from(X_prev.rb());
+				break;
+			}
+			let x_norm = X.norm_l2();
+			let v_norm = V.norm_l2();
+			let scale = Q.norm_l2() + from_f64::<T::Real>(2.0) * A.norm_l2() * x_norm + v_norm * v_norm;
+			if res_norm <= tol * scale || iter == params.max_refinement_iterations {
+				break;
+			}
+			prev_norm = res_norm;
+
+			// $A_k^H N + N A_k = -\mathcal{R}(X)$, with $A_k = A - G X = A - W^H V$
+			Ak.copy_from(A.adjoint());
+			matmul(Ak.rb_mut(), Accum::Add, V.rb().adjoint(), W.rb(), -one::<T>(), par);
+			zip!(Res.rb_mut()).for_each(|unzip!(x)| *x = -*x);
+			lyapunov::solve_lyapunov(N.rb_mut(), Ak.rb(), Res.rb(), par, stack)?;
+
+			X_prev.copy_from(X.rb());
+			zip!(X.rb_mut(), N.rb()).for_each(|unzip!(x, n)| *x = *x + *n);
+			make_self_adjoint(X.rb_mut());
+		}
+	}
+
+	// $K = R^{-1} B^H X = L^{-H} W X$
+	matmul(K.rb_mut(), Accum::Replace, W.rb(), X.rb(), one(), par);
+	linalg::triangular_solve::solve_upper_triangular_in_place(L.rb().adjoint(), K.rb_mut(), par);
+
+	Ok(())
+}
+
+/// computes the size and alignment of the workspace required to solve the discrete algebraic
+/// riccati equation with `state_dim` states and `input_dim` inputs
+pub fn dare_scratch<T: ComplexField>(state_dim: usize, input_dim: usize, par: Par, params: Spec<RiccatiParams, T>) -> StackReq {
+	_ = params;
+	let n = state_dim;
+	let m = input_dim;
+
+	let schur = if try_const! { T::IS_REAL } {
+		StackReq::all_of(&[
+			temp_mat_scratch::<Complex<T::Real>>(n, n).array(4),
+			dare_schur_scratch::<Complex<T::Real>>(n, par),
+		])
+	} else {
+		dare_schur_scratch::<T>(n, par)
+	};
+
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(m, m).array(2),
+		temp_mat_scratch::<T>(m, n).array(2),
+		temp_mat_scratch::<T>(n, n),
+		StackReq::any_of(&[
+			cholesky_scratch::<T>(m, par),
+			schur,
+			StackReq::all_of(&[
+				temp_mat_scratch::<T>(n, n).array(4),
+				StackReq::any_of(&[
+					StackReq::all_of(&[temp_mat_scratch::<T>(n, m), cholesky_scratch::<T>(m, par)]),
+					stein::solve_stein_scratch::<T>(n, par),
+				]),
+			]),
+		]),
+	])
+}
+
+/// computes $S = R + B^H X B$, its cholesky factor $L_S$, $F = B^H X A$ and $\tilde K = L_S^{-1}
+/// F$, where $R$ is given by its cholesky factor $L$
+#[math]
+fn dare_gain<T: ComplexField>(
+	S: MatMut<'_, T>,
+	F: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	L: MatRef<'_, T>,
+	X: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let m = B.ncols();
+	let mut S = S;
+	let mut F = F;
+
+	let (mut XB, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, m, stack) };
+	let mut XB = XB.as_mat_mut();
+	matmul(XB.rb_mut(), Accum::Replace, X, B, one(), par);
+
+	matmul(S.rb_mut(), Accum::Replace, L, L.adjoint(), one(), par);
+	matmul(S.rb_mut(), Accum::Add, B.adjoint(), XB.rb(), one(), par);
+	cholesky(S.rb_mut(), par, stack)?;
+
+	matmul(F.rb_mut(), Accum::Replace, XB.rb().adjoint(), A, one(), par);
+	linalg::triangular_solve::solve_lower_triangular_in_place(S.rb(), F.rb_mut(), par);
+	Ok(())
+}
+
+/// solves the discrete algebraic riccati equation
+///
+/// $$A^H X A - X - A^H X B (R + B^H X B)^{-1} B^H X A + Q = 0$$
+///
+/// and stores the stabilizing solution in `X`, i.e. the self-adjoint solution for which all the
+/// eigenvalues of $A - B K$ lie inside the unit circle, where $K = (R + B^H X B)^{-1} B^H X A$ is
+/// the optimal gain of the associated linear quadratic regulator, which is stored in `K`
+///
+/// $Q$ must be self-adjoint, and $R$ self-adjoint positive definite, in which case only its lower
+/// triangular half is accessed. the solution is computed from the ordered generalized schur
+/// decomposition of the symplectic pencil, then refined with newton's method
+///
+/// # panics
+/// panics if $A$ is $n\times n$, $B$ is $n\times m$, and the other matrices don't have compatible
+/// dimensions
+#[math]
+#[track_caller]
+pub fn dare<T: ComplexField>(
+	X: MatMut<'_, T>,
+	K: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	Q: MatRef<'_, T>,
+	R: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<RiccatiParams, T>,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	let m = B.ncols();
+	assert!(all(
+		A.ncols() == n,
+		B.nrows() == n,
+		Q.nrows() == n,
+		Q.ncols() == n,
+		R.nrows() == m,
+		R.ncols() == m,
+		X.nrows() == n,
+		X.ncols() == n,
+		K.nrows() == m,
+		K.ncols() == n,
+	));
+
+	let params = params.config;
+	let mut X = X;
+	let mut K = K;
+
+	let (mut L, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, m, stack) };
+	let (mut S, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, m, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, n, stack) };
+	let (mut F, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, n, stack) };
+	let (mut G, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut L = L.as_mat_mut();
+	let mut S = S.as_mat_mut();
+	let mut W = W.as_mat_mut();
+	let mut F = F.as_mat_mut();
+	let mut G = G.as_mat_mut();
+
+	weighted_input(L.rb_mut(), W.rb_mut(), G.rb_mut(), B, R, par, stack)?;
+
+	if try_const! { T::IS_REAL } {
+		// the generalized schur decomposition is only available in complex arithmetic
+		let (mut Ac, stack) = unsafe { temp_mat_uninit::<Complex<T::Real>, _, _>(n, n, stack) };
+		let (mut Gc, stack) = unsafe { temp_mat_uninit::<Complex<T::Real>, _, _>(n, n, stack) };
+		let (mut Qc, stack) = unsafe { temp_mat_uninit::<Complex<T::Real>, _, _>(n, n, stack) };
+		let (mut Xc, stack) = unsafe { temp_mat_uninit::<Complex<T::Real>, _, _>(n, n, stack) };
+		let mut Ac = Ac.as_mat_mut();
+		let mut Gc = Gc.as_mat_mut();
+		let mut Qc = Qc.as_mat_mut();
+		let mut Xc = Xc.as_mat_mut();
+
+		zip!(Ac.rb_mut(), A).for_each(|unzip!(dst, src)| *dst = Complex::new(real(*src), zero()));
+		zip!(Gc.rb_mut(), G.rb()).for_each(|unzip!(dst, src)| *dst = Complex::new(real(*src), zero()));
+		zip!(Qc.rb_mut(), Q).for_each(|unzip!(dst, src)| *dst = Complex::new(real(*src), zero()));
+
+		dare_schur(Xc.rb_mut(), Ac.rb(), Gc.rb(), Qc.rb(), par, stack)?;
+		zip!(X.rb_mut(), Xc.rb()).for_each(|unzip!(dst, src)| *dst = from_real(copy(src.re)));
+	} else {
+		dare_schur(X.rb_mut(), A, G.rb(), Q, par, stack)?;
+	}
+
+	{
+		let (mut Res, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut Ak, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut N, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let (mut X_prev, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+		let mut Res = Res.as_mat_mut();
+		let mut Ak = Ak.as_mat_mut();
+		let mut N = N.as_mat_mut();
+		let mut X_prev = X_prev.as_mat_mut();
+
+		let tol = eps::<T::Real>() * from_f64::<T::Real>(n as f64);
+		let mut prev_norm = infinity::<T::Real>();
+
+		for iter in 0..params.max_refinement_iterations + 1 {
+			// $\mathcal{R}(X) = A^H X A - X + Q - \tilde K^H \tilde K$
+			dare_gain(S.rb_mut(), F.rb_mut(), A, B, L.rb(), X.rb(), par, stack)?;
+			matmul(N.rb_mut(), Accum::Replace, X.rb(), A, one(), par);
+			Res.copy_from(Q);
+			zip!(Res.rb_mut(), X.rb()).for_each(|unzip!(r, x)| *r = *r - *x);
+			matmul(Res.rb_mut(), Accum::Add, A.adjoint(), N.rb(), one(), par);
+			matmul(Res.rb_mut(), Accum::Add, F.rb().adjoint(), F.rb(), -one::<T>(), par);
+
+			let res_norm = Res.norm_l2();
+			if res_norm >= prev_norm {
+				X.copy_from(X_prev.rb());
+				break;
+			}
+			let a_norm = A.norm_l2();
+			let f_norm = F.norm_l2();
+			let scale = Q.norm_l2() + (one::<T::Real>() + a_norm * a_norm) * X.norm_l2() + f_norm * f_norm;
+			if res_norm <= tol * scale || iter == params.max_refinement_iterations {
+				break;
+			}
+			prev_norm = res_norm;
+
+			// $A_k^H N A_k - N = -\mathcal{R}(X)$, with $A_k = A - B K$ and $K = L_S^{-H} \tilde K$
+			linalg::triangular_solve::solve_upper_triangular_in_place(S.rb().adjoint(), F.rb_mut(), par);
+			Ak.copy_from(A.adjoint());
+			matmul(Ak.rb_mut(), Accum::Add, F.rb().adjoint(), B.adjoint(), -one::<T>(), par);
+			zip!(Res.rb_mut()).for_each(|unzip!(x)| *x = -*x);
+			stein::solve_stein(N.rb_mut(), Ak.rb(), Res.rb(), par, stack)?;
+
+			X_prev.copy_from(X.rb());
+			zip!(X.rb_mut(), N.rb()).for_each(|unzip!(x, n)| *x = *x + *n);
+			make_self_adjoint(X.rb_mut());
+		}
+	}
+
+	// $K = (R + B^H X B)^{-1} B^H X A = L_S^{-H} \tilde K$
+	dare_gain(S.rb_mut(), F.rb_mut(), A, B, L.rb(), X.rb(), par, stack)?;
+	K.copy_from(F.rb());
+	linalg::triangular_solve::solve_upper_triangular_in_place(S.rb().adjoint(), K.rb_mut(), par);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn problem<T: ComplexField<Real = f64>>(
+		n: usize,
+		m: usize,
+		rng: &mut StdRng,
+		sample: &impl Fn(usize, usize, &mut StdRng) -> Mat<T>,
+	) -> (Mat<T>, Mat<T>, Mat<T>, Mat<T>) {
+		let A = sample(n, n, rng);
+		let B = sample(n, m, rng);
+		let C = sample(n, n, rng);
+		let D = sample(m, m, rng);
+		let Q = C.adjoint() * &C + Mat::<T>::identity(n, n);
+		let R = D.adjoint() * &D + Mat::<T>::identity(m, m);
+		(A, B, Q, R)
+	}
+
+	fn solve(
+		f: fn(
+			MatMut<'_, c64>,
+			MatMut<'_, c64>,
+			MatRef<'_, c64>,
+			MatRef<'_, c64>,
+			MatRef<'_, c64>,
+			MatRef<'_, c64>,
+			Par,
+			&mut MemStack,
+			Spec<RiccatiParams, c64>,
+		) -> Result<(), MatrixEquationError>,
+		scratch: StackReq,
+		A: &Mat<c64>,
+		B: &Mat<c64>,
+		Q: &Mat<c64>,
+		R: &Mat<c64>,
+	) -> (Mat<c64>, Mat<c64>) {
+		let n = A.nrows();
+		let m = B.ncols();
+		let mut X = Mat::zeros(n, n);
+		let mut K = Mat::zeros(m, n);
+		f(
+			X.as_mut(),
+			K.as_mut(),
+			A.as_ref(),
+			B.as_ref(),
+			Q.as_ref(),
+			R.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(scratch)),
+			default(),
+		)
+		.unwrap();
+		(X, K)
+	}
+
+	fn check_care<T: ComplexField<Real = f64>>(A: &Mat<T>, B: &Mat<T>, Q: &Mat<T>, R: &Mat<T>, X: &Mat<T>, K: &Mat<T>, eigenvalues: Vec<c64>) {
+		let scale = Q.norm_l2() + 2.0 * A.norm_l2() * X.norm_l2() + (B.adjoint() * X).norm_l2().powi(2);
+
+		let res = A.adjoint() * X + X * A - X * B * K + Q;
+		assert!(res.norm_l2() <= 1e-12 * scale);
+		assert!((R * K - B.adjoint() * X).norm_l2() <= 1e-12 * scale);
+		assert!((X - X.adjoint()).norm_l2() == 0.0);
+
+		for lambda in eigenvalues {
+			assert!(lambda.re < 0.0);
+		}
+		for lambda in X.self_adjoint_eigenvalues(Side::Lower).unwrap() {
+			assert!(lambda > -1e-12 * X.norm_l2());
+		}
+	}
+
+	fn check_dare<T: ComplexField<Real = f64>>(A: &Mat<T>, B: &Mat<T>, Q: &Mat<T>, R: &Mat<T>, X: &Mat<T>, K: &Mat<T>, eigenvalues: Vec<c64>) {
+		let S = R + B.adjoint() * X * B;
+		let scale = Q.norm_l2() + (1.0 + A.norm_l2().powi(2)) * X.norm_l2();
+
+		let res = A.adjoint() * X * A - X - A.adjoint() * X * B * K + Q;
+		assert!(res.norm_l2() <= 1e-11 * scale);
+		assert!((S * K - B.adjoint() * X * A).norm_l2() <= 1e-11 * scale);
+		assert!((X - X.adjoint()).norm_l2() == 0.0);
+
+		for lambda in eigenvalues {
+			assert!(abs(&lambda) < 1.0);
+		}
+		for lambda in X.self_adjoint_eigenvalues(Side::Lower).unwrap() {
+			assert!(lambda > -1e-12 * X.norm_l2());
+		}
+	}
+
+	#[test]
+	fn test_scalar() {
+		let one = Mat::<f64>::identity(1, 1);
+		let mut X = Mat::zeros(1, 1);
+		let mut K = Mat::zeros(1, 1);
+
+		care(
+			X.as_mut(),
+			K.as_mut(),
+			one.as_ref(),
+			one.as_ref(),
+			one.as_ref(),
+			one.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(care_scratch::<f64>(1, 1, Par::Seq, default()))),
+			default(),
+		)
+		.unwrap();
+		let approx_eq = ApproxEq::<f64>::eps() * 8.0;
+		assert!(X[(0, 0)] ~ 1.0 + f64::sqrt(2.0));
+		assert!(K[(0, 0)] ~ 1.0 + f64::sqrt(2.0));
+
+		dare(
+			X.as_mut(),
+			K.as_mut(),
+			one.as_ref(),
+			one.as_ref(),
+			one.as_ref(),
+			one.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(dare_scratch::<f64>(1, 1, Par::Seq, default()))),
+			default(),
+		)
+		.unwrap();
+		let golden = (1.0 + f64::sqrt(5.0)) / 2.0;
+		assert!(X[(0, 0)] ~ golden);
+		assert!(K[(0, 0)] ~ golden / (1.0 + golden));
+	}
+
+	#[test]
+	fn test_care() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let sample_real = |m: usize, n: usize, rng: &mut StdRng| -> Mat<f64> {
+			CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: StandardNormal,
+			}
+			.sample(rng)
+		};
+		let sample_cplx = |m: usize, n: usize, rng: &mut StdRng| -> Mat<c64> {
+			CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.sample(rng)
+		};
+
+		for (n, m) in [(0, 1), (1, 1), (2, 1), (4, 2), (10, 3), (30, 5)] {
+			let (A, B, Q, R) = problem(n, m, rng, &sample_real);
+			let mut X = Mat::zeros(n, n);
+			let mut K = Mat::zeros(m, n);
+			care(
+				X.as_mut(),
+				K.as_mut(),
+				A.as_ref(),
+				B.as_ref(),
+				Q.as_ref(),
+				R.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(care_scratch::<f64>(n, m, Par::Seq, default()))),
+				default(),
+			)
+			.unwrap();
+			check_care(&A, &B, &Q, &R, &X, &K, (&A - &B * &K).eigenvalues_from_real().unwrap());
+
+			let (A, B, Q, R) = problem(n, m, rng, &sample_cplx);
+			let (X, K) = solve(care, care_scratch::<c64>(n, m, Par::Seq, default()), &A, &B, &Q, &R);
+			check_care(&A, &B, &Q, &R, &X, &K, (&A - &B * &K).eigenvalues().unwrap());
+		}
+	}
+
+	#[test]
+	fn test_dare() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let sample_real = |m: usize, n: usize, rng: &mut StdRng| -> Mat<f64> {
+			CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: StandardNormal,
+			}
+			.sample(rng)
+		};
+		let sample_cplx = |m: usize, n: usize, rng: &mut StdRng| -> Mat<c64> {
+			CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.sample(rng)
+		};
+
+		for (n, m) in [(0, 1), (1, 1), (2, 1), (4, 2), (10, 3), (30, 5)] {
+			let (A, B, Q, R) = problem(n, m, rng, &sample_real);
+			let mut X = Mat::zeros(n, n);
+			let mut K = Mat::zeros(m, n);
+			dare(
+				X.as_mut(),
+				K.as_mut(),
+				A.as_ref(),
+				B.as_ref(),
+				Q.as_ref(),
+				R.as_ref(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(dare_scratch::<f64>(n, m, Par::Seq, default()))),
+				default(),
+			)
+			.unwrap();
+			check_dare(&A, &B, &Q, &R, &X, &K, (&A - &B * &K).eigenvalues_from_real().unwrap());
+
+			let (A, B, Q, R) = problem(n, m, rng, &sample_cplx);
+			let (X, K) = solve(dare, dare_scratch::<c64>(n, m, Par::Seq, default()), &A, &B, &Q, &R);
+			check_dare(&A, &B, &Q, &R, &X, &K, (&A - &B * &K).eigenvalues().unwrap());
+		}
+	}
+
+	#[test]
+	fn test_no_stabilizing_solution() {
+		// the unstable mode $a = 1$ is not controllable
+		let A = Mat::<f64>::identity(1, 1);
+		let B = Mat::<f64>::zeros(1, 1);
+		let Q = Mat::<f64>::identity(1, 1);
+		let R = Mat::<f64>::identity(1, 1);
+		let mut X = Mat::zeros(1, 1);
+		let mut K = Mat::zeros(1, 1);
+
+		let result = care(
+			X.as_mut(),
+			K.as_mut(),
+			A.as_ref(),
+			B.as_ref(),
+			Q.as_ref(),
+			R.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(care_scratch::<f64>(1, 1, Par::Seq, default()))),
+			default(),
+		);
+		assert!(result == Err(MatrixEquationError::NoStabilizingSolution));
+
+		let R = -Mat::<f64>::identity(1, 1);
+		let result = care(
+			X.as_mut(),
+			K.as_mut(),
+			A.as_ref(),
+			B.as_ref(),
+			Q.as_ref(),
+			R.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(care_scratch::<f64>(1, 1, Par::Seq, default()))),
+			default(),
+		);
+		assert!(result == Err(MatrixEquationError::NonPositiveDefiniteWeight));
+	}
+}
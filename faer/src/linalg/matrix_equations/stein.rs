@@ -0,0 +1,114 @@
+use super::*;
+use crate::assert;
+use linalg::evd::schur::{solve_stein_quasi_triangular_in_place, solve_stein_quasi_triangular_in_place_scratch};
+
+/// computes the size and alignment of the workspace required to solve the discrete lyapunov
+/// equation with a matrix $A$ of dimension `dim`
+pub fn solve_stein_scratch<T: ComplexField>(dim: usize, par: Par) -> StackReq {
+	let n = dim;
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(n, n).array(4),
+		StackReq::any_of(&[
+			schur_scratch::<T>(n, par),
+			schur_basis_scratch::<T>(n, n),
+			solve_stein_quasi_triangular_in_place_scratch::<T>(n, n, par),
+		]),
+	])
+}
+
+/// solves the discrete lyapunov (stein) equation $A X A^H - X = Q$ and stores the result in `X`
+///
+/// if $Q$ is self-adjoint, then so is $X$
+///
+/// # panics
+/// panics if $A$, $Q$ and $X$ are not square matrices of the same dimension
+pub fn solve_stein<T: ComplexField>(
+	X: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	Q: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let n = A.nrows();
+	assert!(all(A.ncols() == n, Q.nrows() == n, Q.ncols() == n, X.nrows() == n, X.ncols() == n));
+
+	let mut X = X;
+
+	let (mut T, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut U, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut Y, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut S, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut T = T.as_mat_mut();
+	let mut U = U.as_mat_mut();
+	let mut Y = Y.as_mat_mut();
+	let mut S = S.as_mat_mut();
+
+	schur(A, T.rb_mut(), U.rb_mut(), par, stack)?;
+	S.copy_from(T.rb().adjoint().reverse_rows_and_cols());
+	to_schur_basis(Y.rb_mut(), U.rb(), Q, U.rb(), par, stack);
+
+	// $T Y T^H - Y = F$ is equivalent to $T (Y P) (P T^H P) - (Y P) = F P$, where $P$ is the
+	// exchange matrix, and $S = P T^H P$ is upper quasi-triangular
+	solve_stein_quasi_triangular_in_place(T.rb(), S.rb(), Y.rb_mut().reverse_cols_mut(), par, stack);
+
+	from_schur_basis(X.rb_mut(), U.rb(), Y.rb(), U.rb(), par, stack);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn test_stein<T: ComplexField<Real = f64>>(n: usize, rng: &mut StdRng, sample: impl Fn(usize, usize, &mut StdRng) -> Mat<T>) {
+		let A = sample(n, n, rng);
+		let Q = sample(n, n, rng);
+
+		let mut X = Mat::zeros(n, n);
+		solve_stein(
+			X.as_mut(),
+			A.as_ref(),
+			Q.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(solve_stein_scratch::<T>(n, Par::Seq))),
+		)
+		.unwrap();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1e5 * (n as f64));
+		assert!(&A * &X * A.adjoint() - &X ~ Q);
+	}
+
+	#[test]
+	fn test_real() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for n in [0, 1, 2, 3, 7, 20, 50] {
+			test_stein::<f64>(n, rng, |m, n, rng| {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.sample(rng)
+			});
+		}
+	}
+
+	#[test]
+	fn test_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for n in [0, 1, 2, 3, 7, 20, 50] {
+			test_stein::<c64>(n, rng, |m, n, rng| {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+				}
+				.sample(rng)
+			});
+		}
+	}
+}
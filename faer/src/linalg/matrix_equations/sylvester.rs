@@ -0,0 +1,126 @@
+use super::*;
+use crate::assert;
+use linalg::evd::schur::solve_sylvester_quasi_triangular_in_place;
+
+/// computes the size and alignment of the workspace required to solve the sylvester equation
+/// with an $m\times m$ matrix $A$ and an $n\times n$ matrix $B$
+pub fn solve_sylvester_scratch<T: ComplexField>(m: usize, n: usize, par: Par) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(m, m).array(2),
+		temp_mat_scratch::<T>(n, n).array(2),
+		StackReq::any_of(&[
+			schur_scratch::<T>(m, par),
+			schur_scratch::<T>(n, par),
+			StackReq::all_of(&[temp_mat_scratch::<T>(m, n), schur_basis_scratch::<T>(m, n)]),
+		]),
+	])
+}
+
+/// solves the sylvester equation $A X + X B = C$ and stores the result in `X`
+///
+/// $A$ and $B$ are reduced to schur form, after which the transformed equation is solved by
+/// block back substitution
+///
+/// # panics
+/// panics if the dimensions of $A$, $B$, $C$ and $X$ are incompatible
+pub fn solve_sylvester<T: ComplexField>(
+	X: MatMut<'_, T>,
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	C: MatRef<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), MatrixEquationError> {
+	let m = A.nrows();
+	let n = B.nrows();
+	assert!(all(
+		A.ncols() == m,
+		B.ncols() == n,
+		C.nrows() == m,
+		C.ncols() == n,
+		X.nrows() == m,
+		X.ncols() == n,
+	));
+
+	let mut X = X;
+
+	let (mut TA, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, m, stack) };
+	let (mut UA, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, m, stack) };
+	let (mut TB, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let (mut UB, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut TA = TA.as_mat_mut();
+	let mut UA = UA.as_mat_mut();
+	let mut TB = TB.as_mat_mut();
+	let mut UB = UB.as_mat_mut();
+
+	schur(A, TA.rb_mut(), UA.rb_mut(), par, stack)?;
+	schur(B, TB.rb_mut(), UB.rb_mut(), par, stack)?;
+
+	let (mut Y, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, n, stack) };
+	let mut Y = Y.as_mat_mut();
+
+	to_schur_basis(Y.rb_mut(), UA.rb(), C, UB.rb(), par, stack);
+	solve_sylvester_quasi_triangular_in_place(TA.rb(), TB.rb(), Y.rb_mut(), par);
+	from_schur_basis(X.rb_mut(), UA.rb(), Y.rb(), UB.rb(), par, stack);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn test_sylvester<T: ComplexField<Real = f64>>(m: usize, n: usize, rng: &mut StdRng, sample: impl Fn(usize, usize, &mut StdRng) -> Mat<T>) {
+		let A = sample(m, m, rng);
+		let B = sample(n, n, rng);
+		let C = sample(m, n, rng);
+
+		let mut X = Mat::zeros(m, n);
+		solve_sylvester(
+			X.as_mut(),
+			A.as_ref(),
+			B.as_ref(),
+			C.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(solve_sylvester_scratch::<T>(m, n, Par::Seq))),
+		)
+		.unwrap();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1e4 * ((m + n) as f64));
+		assert!(&A * &X + &X * &B ~ C);
+	}
+
+	#[test]
+	fn test_real() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n) in [(0, 3), (1, 1), (2, 5), (7, 4), (20, 13), (40, 40)] {
+			test_sylvester::<f64>(m, n, rng, |m, n, rng| {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.sample(rng)
+			});
+		}
+	}
+
+	#[test]
+	fn test_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n) in [(0, 3), (1, 1), (2, 5), (7, 4), (20, 13), (40, 40)] {
+			test_sylvester::<c64>(m, n, rng, |m, n, rng| {
+				CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+				}
+				.sample(rng)
+			});
+		}
+	}
+}
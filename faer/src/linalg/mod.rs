@@ -180,6 +180,7 @@ pub mod triangular_inverse;
 /// triangular matrix solve
 pub mod triangular_solve;
 
+pub(crate) mod norm_est;
 pub(crate) mod reductions;
 /// matrix zipping implementation
 pub mod zip;
@@ -196,7 +197,13 @@ pub mod lu;
 pub mod qr;
 
 pub mod evd;
+pub mod gevd;
 pub mod svd;
+pub mod polar;
+
+pub mod matfun;
+pub mod matrix_equations;
+pub mod refinement;
 
 mod mat_ops;
 
@@ -0,0 +1,173 @@
+use crate::internal_prelude::*;
+
+/// computes the size and alignment of the workspace required to estimate the $1$-norm of a linear
+/// operator of dimension `dim`
+pub fn norm1_est_scratch<T: ComplexField>(dim: usize) -> StackReq {
+	temp_mat_scratch::<T>(dim, 1).array(2)
+}
+
+#[math]
+fn sign<T: ComplexField>(x: &T) -> T {
+	let a = abs(*x);
+	if a == zero() { one() } else { mul_real(*x, recip(a)) }
+}
+
+#[math]
+fn sum_abs<T: ComplexField>(x: ColRef<'_, T>) -> T::Real {
+	let mut acc = zero::<T::Real>();
+	for i in 0..x.nrows() {
+		acc = acc + abs(x[i]);
+	}
+	acc
+}
+
+#[math]
+fn argmax_abs<T: ComplexField>(x: ColRef<'_, T>) -> usize {
+	let mut j = 0;
+	let mut max = zero::<T::Real>();
+	for i in 0..x.nrows() {
+		let a = abs(x[i]);
+		if a > max {
+			max = a;
+			j = i;
+		}
+	}
+	j
+}
+
+/// estimates the $1$-norm of a square linear operator $A$ of dimension `dim`, using the
+/// hager-higham algorithm (lapack `lacn2`)
+///
+/// `apply` must overwrite its argument $x$ with $A x$, and `apply_adjoint` must overwrite it with
+/// $A^H x$. the estimate is a lower bound for the true norm, and typically within a factor of $3$
+/// of it
+#[math]
+pub fn norm1_est<T: ComplexField>(
+	dim: usize,
+	apply: &mut dyn FnMut(ColMut<'_, T>),
+	apply_adjoint: &mut dyn FnMut(ColMut<'_, T>),
+	stack: &mut MemStack,
+) -> T::Real {
+	let n = dim;
+	if n == 0 {
+		return zero();
+	}
+
+	let (mut x, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut xi, _) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let mut x = x.as_mat_mut().col_mut(0);
+	let mut xi = xi.as_mat_mut().col_mut(0);
+
+	let inv_n = from_f64::<T::Real>(n as f64);
+	let inv_n = recip(inv_n);
+	x.fill(from_real(inv_n));
+	apply(x.rb_mut());
+
+	if n == 1 {
+		return abs(x[0]);
+	}
+
+	let mut est = sum_abs(x.rb());
+	for i in 0..n {
+		xi[i] = sign(&x[i]);
+	}
+	x.copy_from(&xi);
+	apply_adjoint(x.rb_mut());
+	let mut j = argmax_abs(x.rb());
+
+	let max_iter = 5;
+	let mut iter = 2;
+	loop {
+		x.fill(zero());
+		x[j] = one();
+		apply(x.rb_mut());
+
+		let est_old = copy(est);
+		est = sum_abs(x.rb());
+
+		// for real operators, a repeated sign vector means we have converged
+		let mut repeated = try_const! { T::IS_REAL };
+		if repeated {
+			for i in 0..n {
+				if sign(&x[i]) != xi[i] {
+					repeated = false;
+					break;
+				}
+			}
+		}
+		if repeated || est <= est_old {
+			est = max(est, est_old);
+			break;
+		}
+
+		for i in 0..n {
+			xi[i] = sign(&x[i]);
+		}
+		x.copy_from(&xi);
+		apply_adjoint(x.rb_mut());
+
+		let j_last = j;
+		j = argmax_abs(x.rb());
+		if abs(x[j_last]) == abs(x[j]) || iter >= max_iter {
+			break;
+		}
+		iter += 1;
+	}
+
+	// alternative estimate, guards against the worst cases of the main iteration
+	let denom = from_f64::<T::Real>((n - 1) as f64);
+	let mut alt_sign = one::<T::Real>();
+	for i in 0..n {
+		x[i] = from_real(alt_sign * (one::<T::Real>() + from_f64::<T::Real>(i as f64) / denom));
+		alt_sign = -alt_sign;
+	}
+	apply(x.rb_mut());
+	let alt = from_f64::<T::Real>(2.0) * sum_abs(x.rb()) / from_f64::<T::Real>(3.0 * n as f64);
+
+	max(est, alt)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use dyn_stack::MemBuffer;
+
+	#[test]
+	fn test_norm1_est() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [1, 2, 5, 20, 50] {
+			let A = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			let mut exact = 0.0f64;
+			for j in 0..n {
+				exact = exact.max(A.col(j).iter().map(|x| x.norm_sqr().sqrt()).sum::<f64>());
+			}
+
+			let mut tmp = Col::<c64>::zeros(n);
+			let mut tmp_adj = Col::<c64>::zeros(n);
+			let est = norm1_est(
+				n,
+				&mut |mut x| {
+					tmp.copy_from(&A * &x);
+					x.copy_from(&tmp);
+				},
+				&mut |mut x| {
+					tmp_adj.copy_from(A.adjoint() * &x);
+					x.copy_from(&tmp_adj);
+				},
+				MemStack::new(&mut MemBuffer::new(norm1_est_scratch::<c64>(n))),
+			);
+
+			assert!(est <= exact * (1.0 + 1e-10));
+			assert!(est >= exact / 3.0);
+		}
+	}
+}
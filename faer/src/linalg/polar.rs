@@ -0,0 +1,495 @@
+//! low level implementation of the polar decomposition of a matrix
+//!
+//! the polar decomposition of a matrix $A$ of shape $(m, n)$ is a decomposition into two
+//! components $U$ and $H$, such that:
+//!
+//! - $U$ has shape $(m, n)$ and has orthonormal columns if $m \geq n$, or orthonormal rows
+//!   otherwise
+//! - $H$ has shape $(n, n)$ and is a self-adjoint positive semidefinite matrix
+//! - and finally:
+//!
+//! $$A = U H$$
+//!
+//! the factor $H$ is unique and equal to $(A^H A)^{1/2}$. if $A$ has full column rank, then $U$ is
+//! also unique, and is the closest matrix to $A$ with orthonormal columns, in any unitarily
+//! invariant norm
+
+use crate::assert;
+use crate::internal_prelude::*;
+use linalg::cholesky::llt::factor::{LltParams, LltRegularization};
+use linalg::matfun::{exp_real, log_real};
+use linalg::norm_est::{norm1_est, norm1_est_scratch};
+use linalg::qr::no_pivoting::factor::QrParams;
+use linalg::svd::{ComputeSvdVectors, SvdParams};
+
+/// polar decomposition error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PolarError {
+	/// reached max iterations
+	NoConvergence,
+}
+
+/// algorithm used to compute the polar decomposition
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PolarMethod {
+	/// compute the polar factors from the svd $A = W S V^H$, as $U = W V^H$ and $H = V S V^H$
+	Svd,
+	/// compute the unitary factor with the qr-based dynamically weighted halley (qdwh) iteration
+	///
+	/// the implementation falls back to the svd if $A$ has more columns than rows, is numerically
+	/// rank deficient, or if the iteration does not converge
+	Qdwh,
+}
+
+/// polar decomposition tuning parameters
+#[derive(Copy, Clone, Debug)]
+pub struct PolarParams {
+	/// algorithm used to compute the decomposition
+	pub method: PolarMethod,
+	/// maximum number of qdwh iterations before falling back to the svd
+	pub max_iterations: usize,
+
+	/// $QR$ parameters
+	pub qr: QrParams,
+	/// cholesky parameters
+	pub llt: LltParams,
+	/// svd parameters
+	pub svd: SvdParams,
+
+	#[doc(hidden)]
+	pub non_exhaustive: NonExhaustive,
+}
+
+impl<T: ComplexField> Auto<T> for PolarParams {
+	fn auto() -> Self {
+		Self {
+			method: PolarMethod::Qdwh,
+			max_iterations: 32,
+
+			qr: auto!(T),
+			llt: auto!(T),
+			svd: auto!(T),
+			non_exhaustive: NonExhaustive(()),
+		}
+	}
+}
+
+fn svd_path_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par, params: PolarParams) -> StackReq {
+	let size = Ord::min(nrows, ncols);
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(size, 1),
+		temp_mat_scratch::<T>(nrows, size),
+		temp_mat_scratch::<T>(ncols, size),
+		StackReq::any_of(&[
+			linalg::svd::svd_scratch::<T>(nrows, ncols, ComputeSvdVectors::Thin, ComputeSvdVectors::Thin, par, params.svd.into()),
+			temp_mat_scratch::<T>(ncols, size),
+		]),
+	])
+}
+
+fn qdwh_path_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par, params: PolarParams) -> StackReq {
+	let m = nrows;
+	let n = ncols;
+	let bs_a = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(m, n);
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(m + n, n);
+
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(m, n),
+		temp_mat_scratch::<T>(m + n, n).array(2),
+		temp_mat_scratch::<T>(Ord::max(bs, bs_a), n),
+		temp_mat_scratch::<T>(n, n),
+		StackReq::any_of(&[
+			linalg::qr::no_pivoting::factor::qr_in_place_scratch::<T>(m, n, bs_a, par, params.qr.into()),
+			norm1_est_scratch::<T>(n),
+			linalg::qr::no_pivoting::factor::qr_in_place_scratch::<T>(m + n, n, bs, par, params.qr.into()),
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(m + n, bs, n),
+			linalg::cholesky::llt::factor::cholesky_in_place_scratch::<T>(n, par, params.llt.into()),
+		]),
+	])
+}
+
+/// computes the size and alignment of the workspace required to compute the polar decomposition
+/// of a matrix with shape `(nrows, ncols)`
+pub fn polar_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par, params: Spec<PolarParams, T>) -> StackReq {
+	let params = params.config;
+	if Ord::min(nrows, ncols) == 0 {
+		return StackReq::EMPTY;
+	}
+
+	match params.method {
+		PolarMethod::Qdwh if nrows >= ncols => StackReq::any_of(&[
+			qdwh_path_scratch::<T>(nrows, ncols, par, params),
+			svd_path_scratch::<T>(nrows, ncols, par, params),
+		]),
+		_ => svd_path_scratch::<T>(nrows, ncols, par, params),
+	}
+}
+
+#[math]
+fn polar_svd<T: ComplexField>(
+	A: MatRef<'_, T>,
+	u: MatMut<'_, T>,
+	h: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+	params: PolarParams,
+) -> Result<(), PolarError> {
+	let (m, n) = A.shape();
+	let size = Ord::min(m, n);
+
+	let (mut s, stack) = unsafe { temp_mat_uninit::<T, _, _>(size, 1, stack) };
+	let (mut w, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, size, stack) };
+	let (mut v, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, size, stack) };
+	let mut s = s.as_mat_mut().col_mut(0);
+	let mut w = w.as_mat_mut();
+	let mut v = v.as_mat_mut();
+
+	linalg::svd::svd(
+		A,
+		s.rb_mut().as_diagonal_mut(),
+		Some(w.rb_mut()),
+		Some(v.rb_mut()),
+		par,
+		stack,
+		params.svd.into(),
+	)
+	.map_err(|_| PolarError::NoConvergence)?;
+
+	linalg::matmul::matmul(u, Accum::Replace, w.rb(), v.rb().adjoint(), one(), par);
+
+	if let Some(h) = h {
+		let (mut vs, _) = unsafe { temp_mat_uninit::<T, _, _>(n, size, stack) };
+		let mut vs = vs.as_mat_mut();
+		for j in 0..size {
+			let sj = real(s[j]);
+			z!(vs.rb_mut().col_mut(j), v.rb().col(j)).for_each(|uz!(dst, src)| *dst = mul_real(*src, sj));
+		}
+		linalg::matmul::matmul(h, Accum::Replace, vs.rb(), v.rb().adjoint(), one(), par);
+	}
+
+	Ok(())
+}
+
+// returns the weights $(a, b, c)$ of the dynamically weighted halley iteration, given a lower bound
+// $l$ on the smallest singular value of the current iterate, see nakatsukasa, bai and gygi (2010),
+// optimizing halley's iteration for computing the matrix polar decomposition
+#[math]
+fn qdwh_weights<T: RealField>(l: T) -> (T, T, T) {
+	let one = one::<T>();
+	let two = from_f64::<T>(2.0);
+	let four = from_f64::<T>(4.0);
+	let eight = from_f64::<T>(8.0);
+
+	let l2 = l * l;
+	let d = exp_real(log_real(four * (one - l2) / (l2 * l2)) / from_f64::<T>(3.0));
+	let sqrt_1_d = sqrt(one + d);
+	let a = sqrt_1_d + mul_pow2(sqrt(eight - four * d + eight * (two - l2) / (l2 * sqrt_1_d)), from_f64::<T>(0.5));
+	let b = mul_pow2((a - one) * (a - one), from_f64::<T>(0.25));
+	let c = a + b - one;
+	(a, b, c)
+}
+
+// computes the unitary polar factor of $A$ with the qdwh iteration, and stores it in `u`. returns
+// `false` if the iteration could not be used
+#[math]
+fn polar_qdwh<T: ComplexField>(A: MatRef<'_, T>, u: MatMut<'_, T>, par: Par, stack: &mut MemStack, params: PolarParams) -> bool {
+	let (m, n) = A.shape();
+	let mut x = u;
+
+	let bs_a = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(m, n);
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(m + n, n);
+
+	let (mut x_prev, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, n, stack) };
+	let (mut stacked, stack) = unsafe { temp_mat_uninit::<T, _, _>(m + n, n, stack) };
+	let (mut q, stack) = unsafe { temp_mat_uninit::<T, _, _>(m + n, n, stack) };
+	let (mut householder, stack) = unsafe { temp_mat_uninit::<T, _, _>(Ord::max(bs, bs_a), n, stack) };
+	let (mut z, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, n, stack) };
+	let mut x_prev = x_prev.as_mat_mut();
+	let mut stacked = stacked.as_mat_mut();
+	let mut q = q.as_mat_mut();
+	let mut householder = householder.as_mat_mut();
+	let mut z = z.as_mat_mut();
+
+	// ‖A‖_2 ≤ ‖A‖_F
+	let alpha = A.norm_l2();
+	if !is_finite(alpha) {
+		return false;
+	}
+
+	// σ_min(A) ≥ 1 / (√n ‖R^{-1}‖_1), where A = QR
+	let l = {
+		let mut qr = stacked.rb_mut().subrows_mut(0, m);
+		qr.copy_from(A);
+		linalg::qr::no_pivoting::factor::qr_in_place(qr.rb_mut(), householder.rb_mut().subrows_mut(0, bs_a), par, stack, params.qr.into());
+		let R = qr.rb().subrows(0, n);
+
+		for i in 0..n {
+			if R[(i, i)] == zero() {
+				return false;
+			}
+		}
+
+		let inv_norm = norm1_est(
+			n,
+			&mut |x: ColMut<'_, T>| linalg::triangular_solve::solve_upper_triangular_in_place(R, x.as_mat_mut(), Par::Seq),
+			&mut |x: ColMut<'_, T>| linalg::triangular_solve::solve_lower_triangular_in_place(R.adjoint(), x.as_mat_mut(), Par::Seq),
+			stack,
+		);
+		recip(alpha * inv_norm * sqrt(from_f64::<T::Real>(n as f64)))
+	};
+	// also falls back to the svd if the estimate is nan
+	#[allow(clippy::neg_cmp_op_on_partial_ord)]
+	if !(l > eps::<T::Real>()) {
+		return false;
+	}
+
+	let tol_l = from_f64::<T::Real>(10.0) * eps::<T::Real>();
+	let tol_x = exp_real(log_real(copy(tol_l)) / from_f64::<T::Real>(3.0));
+	let mut l = min(l, one());
+
+	let alpha_inv = recip(alpha);
+	z!(x.rb_mut(), A).for_each(|uz!(dst, src)| *dst = mul_real(*src, alpha_inv));
+
+	for _ in 0..params.max_iterations {
+		let (a, b, c) = qdwh_weights(copy(l));
+		l = min(l * (a + b * l * l) / (one::<T::Real>() + c * l * l), one());
+
+		x_prev.copy_from(x.rb());
+		let b_c = b / c;
+
+		if c > from_f64::<T::Real>(100.0) {
+			// X ← (b / c) X + (a - b / c) / √c Q_1 Q_2^H, where [√c X; I] = [Q_1; Q_2] R
+			let sqrt_c = sqrt(c);
+			z!(stacked.rb_mut().subrows_mut(0, m), x_prev.rb()).for_each(|uz!(dst, src)| *dst = mul_real(*src, sqrt_c));
+			let mut bot = stacked.rb_mut().subrows_mut(m, n);
+			bot.fill(zero());
+			bot.diagonal_mut().fill(one());
+
+			let mut householder = householder.rb_mut().subrows_mut(0, bs);
+			linalg::qr::no_pivoting::factor::qr_in_place(stacked.rb_mut(), householder.rb_mut(), par, stack, params.qr.into());
+
+			q.fill(zero());
+			q.rb_mut().subrows_mut(0, n).diagonal_mut().fill(one());
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+				stacked.rb(),
+				householder.rb(),
+				Conj::No,
+				q.rb_mut(),
+				par,
+				stack,
+			);
+
+			z!(x.rb_mut()).for_each(|uz!(x)| *x = mul_real(*x, b_c));
+			linalg::matmul::matmul(
+				x.rb_mut(),
+				Accum::Add,
+				q.rb().subrows(0, m),
+				q.rb().subrows(m, n).adjoint(),
+				from_real((a - b_c) / sqrt_c),
+				par,
+			);
+		} else {
+			// X ← (b / c) X + (a - b / c) X (I + c X^H X)^{-1}
+			z.fill(zero());
+			z.rb_mut().diagonal_mut().fill(one());
+			linalg::matmul::triangular::matmul(
+				z.rb_mut(),
+				linalg::matmul::triangular::BlockStructure::TriangularLower,
+				Accum::Add,
+				x_prev.rb().adjoint(),
+				linalg::matmul::triangular::BlockStructure::Rectangular,
+				x_prev.rb(),
+				linalg::matmul::triangular::BlockStructure::Rectangular,
+				from_real(c),
+				par,
+			);
+
+			if linalg::cholesky::llt::factor::cholesky_in_place(z.rb_mut(), LltRegularization::default(), par, stack, params.llt.into()).is_err() {
+				return false;
+			}
+
+			// Y = X Z^{-1}, i.e., Y^T = conj(Z)^{-1} X^T
+			let mut y = stacked.rb_mut().subrows_mut(0, m);
+			y.copy_from(x_prev.rb());
+			linalg::cholesky::llt::solve::solve_in_place_with_conj(z.rb(), Conj::Yes, y.rb_mut().transpose_mut(), par, stack);
+
+			z!(x.rb_mut(), y.rb()).for_each(|uz!(x, y)| *x = mul_real(*x, b_c) + mul_real(*y, a - b_c));
+		}
+
+		let mut diff = zero::<T::Real>();
+		z!(x.rb(), x_prev.rb()).for_each(|uz!(x, x_prev)| diff = diff + abs2(*x - *x_prev));
+		let diff = sqrt(diff);
+
+		if !is_finite(diff) {
+			return false;
+		}
+		if abs(one::<T::Real>() - l) <= tol_l && diff <= tol_x {
+			return true;
+		}
+	}
+
+	false
+}
+
+/// computes the polar decomposition $A = U H$ of the matrix $A$, where $U$ has orthonormal columns
+/// (or rows if $A$ is wide) and $H$ is self-adjoint positive semidefinite
+///
+/// the factor $U$ is stored in `u`, with shape $(m, n)$, and $H$ is optionally stored in `h`, with
+/// shape $(n, n)$
+#[math]
+#[track_caller]
+pub fn polar<T: ComplexField>(
+	A: MatRef<'_, T>,
+	u: MatMut<'_, T>,
+	h: Option<MatMut<'_, T>>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<PolarParams, T>,
+) -> Result<(), PolarError> {
+	let params = params.config;
+	let (m, n) = A.shape();
+	assert!(all(u.nrows() == m, u.ncols() == n));
+	if let Some(h) = h.rb() {
+		assert!(all(h.nrows() == n, h.ncols() == n));
+	}
+
+	let mut u = u;
+	let mut h = h;
+
+	if Ord::min(m, n) == 0 {
+		if let Some(mut h) = h.rb_mut() {
+			h.fill(zero());
+		}
+		return Ok(());
+	}
+
+	if params.method == PolarMethod::Qdwh && m >= n && polar_qdwh(A, u.rb_mut(), par, stack, params) {
+		if let Some(mut h) = h.rb_mut() {
+			// H = U^H A, symmetrized to remove rounding errors
+			linalg::matmul::matmul(h.rb_mut(), Accum::Replace, u.rb().adjoint(), A, one(), par);
+			for j in 0..n {
+				h[(j, j)] = from_real(real(h[(j, j)]));
+				for i in j + 1..n {
+					let x = mul_pow2(h[(i, j)] + conj(h[(j, i)]), from_f64::<T::Real>(0.5));
+					h[(j, i)] = conj(x);
+					h[(i, j)] = x;
+				}
+			}
+		}
+		return Ok(());
+	}
+
+	polar_svd(A, u, h, par, stack, params)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn test_polar_imp(A: MatRef<'_, c64>, method: PolarMethod) {
+		let (m, n) = A.shape();
+		let params = PolarParams { method, ..auto!(c64) };
+
+		let mut U = Mat::zeros(m, n);
+		let mut H = Mat::zeros(n, n);
+		polar(
+			A,
+			U.as_mut(),
+			Some(H.as_mut()),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(polar_scratch::<c64>(m, n, Par::Seq, params.into()))),
+			params.into(),
+		)
+		.unwrap();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * ((m + n) as f64));
+		assert!(&U * &H ~ A);
+		assert!(H.adjoint().to_owned() ~ H);
+		if m >= n {
+			assert!(U.adjoint() * &U ~ Mat::<c64>::identity(n, n));
+		} else {
+			assert!(&U * U.adjoint() ~ Mat::<c64>::identity(m, m));
+		}
+
+		if n > 0 {
+			let evd = H.self_adjoint_eigen(Side::Lower).unwrap();
+			for i in 0..n {
+				assert!(evd.S()[i].re >= -1e-10);
+			}
+		}
+	}
+
+	#[test]
+	fn test_polar() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (m, n) in [(0, 0), (1, 1), (4, 4), (20, 20), (50, 13), (13, 50), (100, 100)] {
+			let A = CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			test_polar_imp(A.as_ref(), PolarMethod::Svd);
+			test_polar_imp(A.as_ref(), PolarMethod::Qdwh);
+		}
+	}
+
+	#[test]
+	fn test_polar_ill_conditioned() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let Q = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng)
+		.qr()
+		.compute_Q();
+
+		// singular values from 1 down to 1e-8, then an exactly singular matrix
+		for last in [1e-8, 0.0] {
+			let S = Mat::<c64>::from_fn(n, n, |i, j| {
+				if i == j {
+					if i + 1 == n {
+						c64::new(last, 0.0)
+					} else {
+						c64::new(f64::powf(1e-8, i as f64 / (n - 1) as f64), 0.0)
+					}
+				} else {
+					c64::ZERO
+				}
+			});
+			let A = &Q * &S * Q.adjoint();
+
+			test_polar_imp(A.as_ref(), PolarMethod::Qdwh);
+
+			if last != 0.0 {
+				// the unitary factor of a self-adjoint positive definite matrix is the identity
+				let mut U = Mat::zeros(n, n);
+				polar(
+					A.as_ref(),
+					U.as_mut(),
+					None,
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(polar_scratch::<c64>(n, n, Par::Seq, default()))),
+					default(),
+				)
+				.unwrap();
+				let approx_eq = CwiseMat(ApproxEq {
+					abs_tol: 1e-6,
+					rel_tol: 1e-6,
+				});
+				assert!(U ~ Mat::<c64>::identity(n, n));
+			}
+		}
+	}
+}
@@ -0,0 +1,149 @@
+use crate::assert;
+use crate::internal_prelude::*;
+pub use linalg::qr::no_pivoting::factor::{QrParams, recommended_blocksize};
+
+/// returns the default relative tolerance used to detect the numerical rank of a matrix with the
+/// given shape, $\max(m, n) \varepsilon$
+#[math]
+pub fn default_rank_tolerance<T: ComplexField>(nrows: usize, ncols: usize) -> T::Real {
+	eps::<T::Real>() * from_f64::<T::Real>(Ord::max(Ord::max(nrows, ncols), 1) as f64)
+}
+
+/// returns the numerical rank of the factor $R$ of a $QR$ decomposition with column pivoting,
+/// i.e., the number of leading diagonal elements such that $|R_{ii}| > \text{tol} \cdot |R_{00}|$
+///
+/// only the diagonal of $R$ is accessed
+#[math]
+pub fn numerical_rank<T: ComplexField>(R: MatRef<'_, T>, tol: T::Real) -> usize {
+	let size = Ord::min(R.nrows(), R.ncols());
+	if size == 0 {
+		return 0;
+	}
+
+	let threshold = tol * abs(R[(0, 0)]);
+	let mut rank = 0;
+	while rank < size && abs(R[(rank, rank)]) > threshold {
+		rank += 1;
+	}
+	rank
+}
+
+/// computes the size and alignment of required workspace for performing an $RZ$ step on the
+/// leading `rank` rows of a factor $R$ with `ncols` columns
+pub fn rz_scratch<T: ComplexField>(rank: usize, ncols: usize, blocksize: usize, par: Par, params: Spec<QrParams, T>) -> StackReq {
+	linalg::qr::no_pivoting::factor::qr_in_place_scratch::<T>(ncols, rank, blocksize, par, params)
+}
+
+/// computes the factorization $R = \begin{bmatrix} L & 0 \end{bmatrix} Z^H$ of the upper
+/// trapezoidal matrix $R$ with full row rank, where $L$ is lower triangular and $Z$ is unitary
+///
+/// only the upper trapezoidal part of $R$ is accessed. on output, the strictly lower triangular
+/// part of `Z_basis` contains the householder basis of $Z$, and its upper triangular part contains
+/// $L^H$
+#[math]
+#[track_caller]
+pub fn rz<T: ComplexField>(
+	R: MatRef<'_, T>,
+	Z_basis: MatMut<'_, T>,
+	Z_coeff: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<QrParams, T>,
+) {
+	let r = R.nrows();
+	let n = R.ncols();
+	assert!(all(r <= n, Z_basis.nrows() == n, Z_basis.ncols() == r, Z_coeff.ncols() == r));
+
+	let mut Z_basis = Z_basis;
+	Z_basis.fill(zero());
+	for j in 0..r {
+		for i in j..n {
+			Z_basis[(i, j)] = conj(R[(j, i)]);
+		}
+	}
+
+	linalg::qr::no_pivoting::factor::qr_in_place(Z_basis, Z_coeff, par, stack, params);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	#[test]
+	fn test_rz() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n, k) in [(50, 30, 10), (30, 50, 20), (40, 40, 40), (20, 30, 0)] {
+			let dist = CwiseMatDistribution {
+				nrows: m,
+				ncols: k,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			};
+			let X = dist.rand::<Mat<c64>>(rng);
+			let Y = CwiseMatDistribution {
+				nrows: k,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let A = &X * &Y;
+			let size = Ord::min(m, n);
+
+			let mut QR = A.to_owned();
+			let mut Q_coeff = Mat::zeros(4, size);
+			let col_perm_fwd = &mut *vec![0usize; n];
+			let col_perm_bwd = &mut *vec![0usize; n];
+			linalg::qr::col_pivoting::factor::qr_in_place(
+				QR.as_mut(),
+				Q_coeff.as_mut(),
+				col_perm_fwd,
+				col_perm_bwd,
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(linalg::qr::col_pivoting::factor::qr_in_place_scratch::<usize, c64>(
+					m,
+					n,
+					4,
+					Par::Seq,
+					default(),
+				))),
+				default(),
+			);
+
+			let r = numerical_rank(QR.as_ref(), default_rank_tolerance::<c64>(m, n) * 16.0);
+			assert!(r == k);
+
+			let bs = recommended_blocksize::<c64>(n, r);
+			let mut Z_basis = Mat::zeros(n, r);
+			let mut Z_coeff = Mat::zeros(bs, r);
+			rz(
+				QR.get(..r, ..),
+				Z_basis.as_mut(),
+				Z_coeff.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(rz_scratch::<c64>(r, n, bs, Par::Seq, default()))),
+				default(),
+			);
+
+			// [L 0] Z^H = (Z [L^H; 0])^H
+			let mut W = Mat::from_fn(n, r, |i, j| if i <= j { Z_basis[(i, j)] } else { c64::ZERO });
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+				Z_basis.as_ref(),
+				Z_coeff.as_ref(),
+				Conj::No,
+				W.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(
+					linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<c64>(n, bs, r),
+				)),
+			);
+
+			let R = Mat::from_fn(r, n, |i, j| if i <= j { QR[(i, j)] } else { c64::ZERO });
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 16.0 * (n as f64));
+			assert!(W.adjoint().to_owned() ~ R);
+		}
+	}
+}
@@ -0,0 +1,12 @@
+//! the complete orthogonal decomposition of a matrix $A$ with numerical rank $r$ is given by
+//! $$AP^T = Q \begin{bmatrix} L & 0 \\ 0 & 0 \end{bmatrix} Z^H$$
+//! where $P$ is a permutation matrix, $Q$ and $Z$ are unitary matrices (represented as block
+//! householder sequences), and $L$ is an $r \times r$ lower triangular matrix.
+//!
+//! it is computed from a $QR$ decomposition with column pivoting $AP^T = QR$, by truncating $R$ to
+//! its leading $r$ rows and reducing them to lower triangular form with an $RZ$ step. it can be used
+//! to compute the minimum norm solution of rank deficient least squares problems.
+#![allow(missing_docs)]
+
+pub mod factor;
+pub mod solve;
@@ -0,0 +1,210 @@
+use crate::assert;
+use crate::internal_prelude::*;
+
+pub fn solve_lstsq_in_place_scratch<I: Index, T: ComplexField>(
+	qr_nrows: usize,
+	qr_ncols: usize,
+	qr_blocksize: usize,
+	z_blocksize: usize,
+	rhs_ncols: usize,
+	par: Par,
+) -> StackReq {
+	_ = par;
+	StackReq::any_of(&[
+		linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_scratch::<T>(qr_nrows, qr_blocksize, rhs_ncols),
+		linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(qr_ncols, z_blocksize, rhs_ncols),
+		crate::perm::permute_rows_in_place_scratch::<I, T>(qr_ncols, rhs_ncols),
+	])
+}
+
+/// computes the minimum norm solution of the least squares problem $\min_X \|AX - B\|$, where $A$
+/// is given by its complete orthogonal decomposition
+///
+/// `rhs` must have $\max(m, n)$ rows. on input, its top $m$ rows contain $B$, and on output, its
+/// top $n$ rows contain $X$
+#[track_caller]
+pub fn solve_lstsq_in_place_with_conj<I: Index, T: ComplexField>(
+	Q_basis: MatRef<'_, T>,
+	Q_coeff: MatRef<'_, T>,
+	Z_basis: MatRef<'_, T>,
+	Z_coeff: MatRef<'_, T>,
+	col_perm: PermRef<'_, I>,
+	conj_A: Conj,
+	rhs: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let m = Q_basis.nrows();
+	let n = Z_basis.nrows();
+	let r = Z_basis.ncols();
+	let size = Ord::min(m, n);
+	assert!(all(
+		Q_coeff.nrows() > 0,
+		Z_coeff.nrows() > 0,
+		Q_basis.ncols() == size,
+		Q_coeff.ncols() == size,
+		Z_coeff.ncols() == r,
+		r <= size,
+		col_perm.len() == n,
+		rhs.nrows() == Ord::max(m, n),
+	));
+
+	let mut rhs = rhs;
+
+	linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(
+		Q_basis,
+		Q_coeff,
+		conj_A.compose(Conj::Yes),
+		rhs.rb_mut().get_mut(..m, ..),
+		par,
+		stack,
+	);
+
+	// L = Z_basis[..r, ..r]^H
+	linalg::triangular_solve::solve_lower_triangular_in_place_with_conj(
+		Z_basis.get(..r, ..r).transpose(),
+		conj_A.compose(Conj::Yes),
+		rhs.rb_mut().get_mut(..r, ..),
+		par,
+	);
+	rhs.rb_mut().get_mut(r..n, ..).fill(zero());
+
+	linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+		Z_basis,
+		Z_coeff,
+		conj_A,
+		rhs.rb_mut().get_mut(..n, ..),
+		par,
+		stack,
+	);
+
+	crate::perm::permute_rows_in_place(rhs.get_mut(..n, ..), col_perm.inverse(), stack);
+}
+
+#[track_caller]
+pub fn solve_lstsq_in_place<I: Index, T: ComplexField, C: Conjugate<Canonical = T>>(
+	Q_basis: MatRef<'_, C>,
+	Q_coeff: MatRef<'_, C>,
+	Z_basis: MatRef<'_, C>,
+	Z_coeff: MatRef<'_, C>,
+	col_perm: PermRef<'_, I>,
+	rhs: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	solve_lstsq_in_place_with_conj(
+		Q_basis.canonical(),
+		Q_coeff.canonical(),
+		Z_basis.canonical(),
+		Z_coeff.canonical(),
+		col_perm,
+		Conj::get::<C>(),
+		rhs,
+		par,
+		stack,
+	);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+	use linalg::qr::complete_orthogonal::factor::*;
+
+	#[test]
+	fn test_lstsq() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n, k) in [(50, 30, 10), (30, 50, 20), (40, 40, 25), (30, 20, 20)] {
+			let X = CwiseMatDistribution {
+				nrows: m,
+				ncols: k,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let Y = CwiseMatDistribution {
+				nrows: k,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let A = &X * &Y;
+			let B = CwiseMatDistribution {
+				nrows: m,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let size = Ord::min(m, n);
+
+			let mut QR = A.to_owned();
+			let bs = recommended_blocksize::<c64>(m, n);
+			let mut Q_coeff = Mat::zeros(bs, size);
+			let col_perm_fwd = &mut *vec![0usize; n];
+			let col_perm_bwd = &mut *vec![0usize; n];
+			let (_, col_perm) = linalg::qr::col_pivoting::factor::qr_in_place(
+				QR.as_mut(),
+				Q_coeff.as_mut(),
+				col_perm_fwd,
+				col_perm_bwd,
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(linalg::qr::col_pivoting::factor::qr_in_place_scratch::<usize, c64>(
+					m,
+					n,
+					bs,
+					Par::Seq,
+					default(),
+				))),
+				default(),
+			);
+
+			let r = numerical_rank(QR.as_ref(), default_rank_tolerance::<c64>(m, n) * 16.0);
+			assert!(r == k);
+
+			let z_bs = recommended_blocksize::<c64>(n, r);
+			let mut Z_basis = Mat::zeros(n, r);
+			let mut Z_coeff = Mat::zeros(z_bs, r);
+			rz(
+				QR.get(..r, ..),
+				Z_basis.as_mut(),
+				Z_coeff.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(rz_scratch::<c64>(r, n, z_bs, Par::Seq, default()))),
+				default(),
+			);
+
+			let svd = A.thin_svd().unwrap();
+			let pinv = Mat::from_fn(n, k, |i, j| svd.V()[(i, j)] * c64::new(1.0 / svd.S()[j].re, 0.0)) * svd.U().get(.., ..k).adjoint();
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (Ord::max(m, n) as f64));
+
+			for conj in [Conj::No, Conj::Yes] {
+				let mut rhs = Mat::zeros(Ord::max(m, n), B.ncols());
+				rhs.get_mut(..m, ..).copy_from(&B);
+
+				solve_lstsq_in_place_with_conj(
+					QR.get(.., ..size),
+					Q_coeff.as_ref(),
+					Z_basis.as_ref(),
+					Z_coeff.as_ref(),
+					col_perm,
+					conj,
+					rhs.as_mut(),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(solve_lstsq_in_place_scratch::<usize, c64>(
+						m,
+						n,
+						bs,
+						z_bs,
+						B.ncols(),
+						Par::Seq,
+					))),
+				);
+
+				let expected = if conj == Conj::Yes { pinv.conjugate() * &B } else { &pinv * &B };
+				assert!(rhs.get(..n, ..) ~ expected);
+			}
+		}
+	}
+}
@@ -122,6 +122,7 @@
 //! ```
 
 pub mod col_pivoting;
+pub mod complete_orthogonal;
 pub mod no_pivoting;
 
 #[cfg(test)]
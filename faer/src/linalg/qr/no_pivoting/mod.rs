@@ -9,3 +9,4 @@ pub mod solve;
 
 pub mod inverse;
 pub mod reconstruct;
+pub mod update;
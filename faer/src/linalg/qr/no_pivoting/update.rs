@@ -0,0 +1,581 @@
+//! updating and downdating of a thin $QR$ decomposition $A = QR$ of an $m \times n$ matrix $A$,
+//! where $Q$ is stored explicitly as an $m \times \min(m, n)$ matrix with orthonormal columns, and
+//! $R$ is a $\min(m, n) \times n$ upper trapezoidal matrix
+//!
+//! each operation costs $\mathcal{O}(mn)$ and is performed with plane rotations. when $Q$ needs an
+//! additional column, it is computed with classical gram-schmidt with reorthogonalization
+//!
+//! the factor $Q$ is stored explicitly since plane rotations can not be applied to the compact
+//! householder representation of $Q$ used by [`super::factor`] without forming it
+
+use crate::assert;
+use crate::internal_prelude::*;
+use linalg::jacobi::JacobiRotation;
+use linalg::matmul::matmul;
+
+/// returns a plane rotation $G$ and $r$ such that $G \begin{bmatrix} f \\\\ g \end{bmatrix} =
+/// \begin{bmatrix} r \\\\ 0 \end{bmatrix}$
+#[math]
+pub(crate) fn make_rot<T: ComplexField>(f: T, g: T) -> (JacobiRotation<T>, T) {
+	if g == zero() {
+		(JacobiRotation { c: one(), s: zero() }, f)
+	} else {
+		JacobiRotation::rotg(f, g)
+	}
+}
+
+/// eliminates $R_{i_1, j}$ against $R_{i_0, j}$ by applying a rotation $G$ to the rows `i0` and
+/// `i1` of $R$, starting at column `j`, and multiplies the columns `i0` and `i1` of $Q$ by $G^H$
+#[math]
+fn eliminate<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, i0: usize, i1: usize, j: usize) {
+	let mut R = R;
+	let (rot, r) = make_rot(copy(R[(i0, j)]), copy(R[(i1, j)]));
+	R[(i0, j)] = r;
+	R[(i1, j)] = zero();
+
+	let n = R.ncols();
+	rot.adjoint().apply_on_the_left_in_place(R.get_mut(.., j + 1..n).two_rows_mut(i0, i1));
+	rot.apply_on_the_right_in_place(Q.two_cols_mut(i0, i1));
+}
+
+/// reduces the upper hessenberg matrix $R$ to upper trapezoidal form, starting at column `start`
+fn hessenberg_to_triangular<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, start: usize) {
+	let mut Q = Q;
+	let mut R = R;
+	let (m, n) = R.shape();
+	for k in start..Ord::min(m.saturating_sub(1), n) {
+		eliminate(Q.rb_mut(), R.rb_mut(), k, k + 1, k);
+	}
+}
+
+/// orthogonalizes `x` against the columns of $Q$, storing the coefficients in `w`, then normalizes
+/// it and returns its norm
+///
+/// the projection is repeated once if cancellation occurs. if `x` lies in the span of the columns
+/// of $Q$ up to rounding errors, it is set to zero and zero is returned
+#[math]
+fn orthogonalize<T: ComplexField>(Q: MatRef<'_, T>, x: ColMut<'_, T>, w: ColMut<'_, T>, par: Par, stack: &mut MemStack) -> T::Real {
+	let mut x = x;
+	let mut w = w;
+	let (mut tmp, _) = unsafe { temp_mat_uninit::<T, _, _>(Q.ncols(), 1, stack) };
+	let mut tmp = tmp.as_mat_mut().col_mut(0);
+
+	let eta = from_f64::<T::Real>(core::f64::consts::FRAC_1_SQRT_2);
+	let mut norm = x.norm_l2();
+	w.fill(zero());
+
+	for _ in 0..2 {
+		matmul(tmp.rb_mut(), Accum::Replace, Q.adjoint(), x.rb(), one(), par);
+		matmul(x.rb_mut(), Accum::Add, Q, tmp.rb(), -one::<T>(), par);
+		z!(w.rb_mut(), tmp.rb()).for_each(|uz!(w, tmp)| *w = *w + *tmp);
+
+		let new_norm = x.norm_l2();
+		if new_norm >= eta * norm {
+			if new_norm > zero() {
+				let inv = recip(new_norm);
+				z!(x.rb_mut()).for_each(|uz!(x)| *x = mul_real(*x, inv));
+			}
+			return new_norm;
+		}
+		norm = new_norm;
+	}
+
+	x.fill(zero());
+	zero()
+}
+
+/// stores in `x` a unit vector that is orthogonal to the columns of $Q$, which must have fewer
+/// columns than rows
+#[math]
+fn orthogonal_complement<T: ComplexField>(Q: MatRef<'_, T>, x: ColMut<'_, T>, par: Par, stack: &mut MemStack) {
+	let (m, k) = Q.shape();
+	assert!(k < m);
+	let mut x = x;
+
+	// the squared row norms of $Q$ sum to $k$, so the canonical basis vector matching the row with
+	// the smallest norm is at a distance of at least $1/\sqrt{m}$ from the span of $Q$
+	let mut idx = 0;
+	let mut min = Q.row(0).norm_l2();
+	for i in 1..m {
+		let norm = Q.row(i).norm_l2();
+		if norm < min {
+			min = norm;
+			idx = i;
+		}
+	}
+
+	let (mut w, stack) = unsafe { temp_mat_uninit::<T, _, _>(k, 1, stack) };
+	x.fill(zero());
+	x[idx] = one();
+	orthogonalize(Q, x, w.as_mat_mut().col_mut(0), par, stack);
+}
+
+/// computes the size and alignment of required workspace for performing a rank one update
+pub fn rank_one_update_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par) -> StackReq {
+	_ = par;
+	let size = Ord::min(nrows, ncols);
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(nrows, size + 1),
+		temp_mat_scratch::<T>(size + 1, ncols),
+		temp_mat_scratch::<T>(size + 1, 1),
+		temp_mat_scratch::<T>(size, 1),
+	])
+}
+
+/// computes the size and alignment of required workspace for inserting a column
+pub fn insert_col_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par) -> StackReq {
+	_ = par;
+	let size = Ord::min(nrows, ncols);
+	temp_mat_scratch::<T>(size, 1).array(2)
+}
+
+/// computes the size and alignment of required workspace for deleting a row
+pub fn delete_row_scratch<T: ComplexField>(nrows: usize, ncols: usize, par: Par) -> StackReq {
+	_ = par;
+	let size = Ord::min(nrows, ncols);
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(nrows, size + 1),
+		temp_mat_scratch::<T>(size + 1, ncols),
+		temp_mat_scratch::<T>(size, 1).array(2),
+	])
+}
+
+// stores the augmented factors $\begin{bmatrix} Q & 0 \end{bmatrix}$ and $\begin{bmatrix} R \\\\ 0
+// \end{bmatrix}$ in `Q_aug` and `R_aug`
+fn augment<T: ComplexField>(Q_aug: MatMut<'_, T>, R_aug: MatMut<'_, T>, Q: MatRef<'_, T>, R: MatRef<'_, T>) {
+	let k = Q.ncols();
+	let mut Q_aug = Q_aug;
+	let mut R_aug = R_aug;
+
+	Q_aug.rb_mut().get_mut(.., ..k).copy_from(Q);
+	Q_aug.rb_mut().col_mut(k).fill(zero());
+	R_aug.rb_mut().get_mut(..k, ..).copy_from(R);
+	R_aug.rb_mut().row_mut(k).fill(zero());
+}
+
+// updates the factors to those of $A + u v^H$, where `w` contains $Q^H u$ and $u$ lies in the span
+// of the columns of $Q$
+#[math]
+fn rank_one_update_imp<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, w: ColMut<'_, T>, v: ColRef<'_, T>) {
+	let (k, n) = R.shape();
+	let mut Q = Q;
+	let mut R = R;
+	let mut w = w;
+
+	// $Q^H u$ is reduced to a multiple of $e_0$, which makes $R$ upper hessenberg
+	for i in (1..k).rev() {
+		let (rot, r) = make_rot(copy(w[i - 1]), copy(w[i]));
+		w[i - 1] = r;
+		w[i] = zero();
+
+		let j = Ord::min(i - 1, n);
+		rot.adjoint()
+			.apply_on_the_left_in_place(R.rb_mut().get_mut(.., j..n).two_rows_mut(i - 1, i));
+		rot.apply_on_the_right_in_place(Q.rb_mut().two_cols_mut(i - 1, i));
+	}
+
+	let w0 = copy(w[0]);
+	for j in 0..n {
+		R[(0, j)] = R[(0, j)] + w0 * conj(v[j]);
+	}
+
+	hessenberg_to_triangular(Q, R, 0);
+}
+
+/// updates the decomposition of $A$ to that of $A + u v^H$
+#[math]
+#[track_caller]
+pub fn rank_one_update<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, u: ColRef<'_, T>, v: ColRef<'_, T>, par: Par, stack: &mut MemStack) {
+	let (k, n) = R.shape();
+	let m = Q.nrows();
+	assert!(all(Q.ncols() == k, k == Ord::min(m, n), u.nrows() == m, v.nrows() == n));
+
+	let mut Q = Q;
+	let mut R = R;
+	if k == 0 {
+		return;
+	}
+
+	if k == m {
+		let (mut w, _) = unsafe { temp_mat_uninit::<T, _, _>(k, 1, stack) };
+		let mut w = w.as_mat_mut().col_mut(0);
+		matmul(w.rb_mut(), Accum::Replace, Q.rb().adjoint(), u, one(), par);
+		rank_one_update_imp(Q, R, w, v);
+	} else {
+		// $u$ is split into its component in the span of $Q$ and a multiple of a unit vector $q$
+		// orthogonal to it. the factors are extended with $q$ and a zero row, and the last row of
+		// the updated factor $R$ is zero since $k = n$
+		let (mut Q_aug, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, k + 1, stack) };
+		let (mut R_aug, stack) = unsafe { temp_mat_uninit::<T, _, _>(k + 1, n, stack) };
+		let mut Q_aug = Q_aug.as_mat_mut();
+		let mut R_aug = R_aug.as_mat_mut();
+		augment(Q_aug.rb_mut(), R_aug.rb_mut(), Q.rb(), R.rb());
+		let (mut w, stack) = unsafe { temp_mat_uninit::<T, _, _>(k + 1, 1, stack) };
+		let mut w = w.as_mat_mut().col_mut(0);
+
+		let (Q0, Q1) = Q_aug.rb_mut().split_at_col_mut(k);
+		let mut q = Q1.col_mut(0);
+		q.copy_from(u);
+		let rho = orthogonalize(Q0.rb(), q, w.rb_mut().get_mut(..k), par, stack);
+		w[k] = from_real(rho);
+
+		rank_one_update_imp(Q_aug.rb_mut(), R_aug.rb_mut(), w, v);
+		Q.copy_from(Q_aug.rb().get(.., ..k));
+		R.copy_from(R_aug.rb().get(..k, ..));
+	}
+}
+
+/// updates the decomposition of $A$ to that of the matrix obtained by inserting `col` before the
+/// column `j` of $A$, or after its last column if `j` is equal to $n$
+///
+/// on input, the first $\min(m, n)$ columns of `Q` and the top left $\min(m, n) \times n$ block of
+/// `R` contain the factors of $A$. on output, `Q` and `R` contain the $m \times \min(m, n + 1)$ and
+/// $\min(m, n + 1) \times (n + 1)$ factors of the new matrix
+#[math]
+#[track_caller]
+pub fn insert_col<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, j: usize, col: ColRef<'_, T>, par: Par, stack: &mut MemStack) {
+	let m = Q.nrows();
+	let (size, n) = R.shape();
+	assert!(all(n > 0, j < n, Q.ncols() == size, size == Ord::min(m, n), col.nrows() == m));
+	let k = Ord::min(m, n - 1);
+
+	let mut Q = Q;
+	let mut R = R;
+
+	for c in (j..n - 1).rev() {
+		crate::perm::swap_cols_idx(R.rb_mut(), c, c + 1);
+	}
+
+	if size == k {
+		matmul(R.rb_mut().col_mut(j), Accum::Replace, Q.rb().adjoint(), col, one(), par);
+	} else {
+		// $Q$ is extended with the normalized component of `col` that is orthogonal to its span, or
+		// with an arbitrary orthogonal unit vector if `col` lies in the span
+		R.rb_mut().row_mut(k).fill(zero());
+
+		let (Q0, Q1) = Q.rb_mut().split_at_col_mut(k);
+		let mut q = Q1.col_mut(0);
+		q.copy_from(col);
+		let rho = orthogonalize(Q0.rb(), q.rb_mut(), R.rb_mut().col_mut(j).get_mut(..k), par, stack);
+		if rho == zero() {
+			orthogonal_complement(Q0.rb(), q, par, stack);
+		}
+		R[(k, j)] = from_real(rho);
+	}
+
+	for c in (j + 1..size).rev() {
+		eliminate(Q.rb_mut(), R.rb_mut(), c - 1, c, j);
+	}
+}
+
+/// updates the decomposition of $A$ to that of the matrix obtained by deleting the column `j` of
+/// $A$
+///
+/// on output, the first $\min(m, n - 1)$ columns of `Q` and the top left
+/// $\min(m, n - 1) \times (n - 1)$ block of `R` contain the factors of the new matrix, and the
+/// remaining entries are set to zero
+#[track_caller]
+pub fn delete_col<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, j: usize) {
+	let m = Q.nrows();
+	let (k, n) = R.shape();
+	assert!(all(j < n, Q.ncols() == k, k == Ord::min(m, n)));
+
+	let mut Q = Q;
+	let mut R = R;
+	for c in j..n - 1 {
+		crate::perm::swap_cols_idx(R.rb_mut(), c, c + 1);
+	}
+	R.rb_mut().col_mut(n - 1).fill(zero());
+
+	hessenberg_to_triangular(Q.rb_mut(), R.rb_mut().get_mut(.., ..n - 1), j);
+
+	if k > Ord::min(m, n - 1) {
+		// the last row of $R$ is now zero
+		Q.rb_mut().col_mut(k - 1).fill(zero());
+		R.rb_mut().row_mut(k - 1).fill(zero());
+	}
+}
+
+/// updates the decomposition of $A$ to that of the matrix obtained by inserting `row` before the
+/// row `i` of $A$, or after its last row if `i` is equal to $m$
+///
+/// `Q` and `R` have dimensions $(m + 1) \times (\min(m, n) + 1)$ and $(\min(m, n) + 1) \times n$.
+/// on input, the top left $m \times \min(m, n)$ block of `Q` and the first $\min(m, n)$ rows of `R`
+/// contain the factors of $A$. on output, the first $\min(m + 1, n)$ columns of `Q` and rows of
+/// `R` contain the factors of the new matrix, and the remaining entries are set to zero
+#[math]
+#[track_caller]
+pub fn insert_row<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, i: usize, row: RowRef<'_, T>) {
+	let m = Q.nrows() - 1;
+	let (size, n) = R.shape();
+	let k = size - 1;
+	assert!(all(Q.ncols() == size, k == Ord::min(m, n), i <= m, row.ncols() == n));
+
+	let mut Q = Q;
+	let mut R = R;
+
+	Q.rb_mut().row_mut(m).fill(zero());
+	Q.rb_mut().col_mut(k).fill(zero());
+	Q[(m, k)] = one();
+	R.rb_mut().row_mut(k).copy_from(row);
+
+	for c in 0..Ord::min(k, n) {
+		eliminate(Q.rb_mut(), R.rb_mut(), c, k, c);
+	}
+
+	for c in (i..m).rev() {
+		crate::perm::swap_rows_idx(Q.rb_mut(), c, c + 1);
+	}
+
+	if size > Ord::min(m + 1, n) {
+		// the last row of $R$ is now zero
+		Q.rb_mut().col_mut(k).fill(zero());
+		R.rb_mut().row_mut(k).fill(zero());
+	}
+}
+
+// deletes the row `i` of $Q$, which must have unit norm, and updates the factors accordingly
+#[math]
+fn delete_row_imp<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, i: usize) {
+	let (m, k) = Q.shape();
+	let n = R.ncols();
+
+	let mut Q = Q;
+	let mut R = R;
+
+	// the rotations reduce the row `i` of $Q$ to a multiple of $e_0^\top$, which makes $R$ upper
+	// hessenberg
+	for c in (0..k - 1).rev() {
+		let (rot, _) = make_rot(conj(Q[(i, c)]), conj(Q[(i, c + 1)]));
+
+		let j = Ord::min(c, n);
+		rot.adjoint()
+			.apply_on_the_left_in_place(R.rb_mut().get_mut(.., j..n).two_rows_mut(c, c + 1));
+		rot.apply_on_the_right_in_place(Q.rb_mut().two_cols_mut(c, c + 1));
+		Q[(i, c + 1)] = zero();
+	}
+
+	for c in i..m - 1 {
+		crate::perm::swap_rows_idx(Q.rb_mut(), c, c + 1);
+	}
+	for c in 0..k - 1 {
+		crate::perm::swap_cols_idx(Q.rb_mut(), c, c + 1);
+		crate::perm::swap_rows_idx(R.rb_mut(), c, c + 1);
+	}
+	Q.rb_mut().row_mut(m - 1).fill(zero());
+	Q.rb_mut().col_mut(k - 1).fill(zero());
+	R.rb_mut().row_mut(k - 1).fill(zero());
+}
+
+/// updates the decomposition of $A$ to that of the matrix obtained by deleting the row `i` of $A$
+///
+/// on output, the top left $(m - 1) \times \min(m - 1, n)$ block of `Q` and the first $\min(m - 1,
+/// n)$ rows of `R` contain the factors of the new matrix, and the remaining entries are set to zero
+#[track_caller]
+pub fn delete_row<T: ComplexField>(Q: MatMut<'_, T>, R: MatMut<'_, T>, i: usize, par: Par, stack: &mut MemStack) {
+	let m = Q.nrows();
+	let (k, n) = R.shape();
+	assert!(all(i < m, Q.ncols() == k, k == Ord::min(m, n)));
+
+	let mut Q = Q;
+	let mut R = R;
+	if k == m {
+		delete_row_imp(Q, R, i);
+	} else {
+		// $Q$ is extended with a unit vector $q$ such that $e_i$ lies in the span of
+		// $\begin{bmatrix} Q & q \end{bmatrix}$
+		let (mut Q_aug, stack) = unsafe { temp_mat_uninit::<T, _, _>(m, k + 1, stack) };
+		let (mut R_aug, stack) = unsafe { temp_mat_uninit::<T, _, _>(k + 1, n, stack) };
+		let mut Q_aug = Q_aug.as_mat_mut();
+		let mut R_aug = R_aug.as_mat_mut();
+		augment(Q_aug.rb_mut(), R_aug.rb_mut(), Q.rb(), R.rb());
+
+		let (Q0, Q1) = Q_aug.rb_mut().split_at_col_mut(k);
+		let mut q = Q1.col_mut(0);
+		q.fill(zero());
+		q[i] = one();
+		let rho = {
+			let (mut w, stack) = unsafe { temp_mat_uninit::<T, _, _>(k, 1, &mut *stack) };
+			orthogonalize(Q0.rb(), q.rb_mut(), w.as_mat_mut().col_mut(0), par, stack)
+		};
+		if rho == zero() {
+			orthogonal_complement(Q0.rb(), q, par, stack);
+		}
+
+		delete_row_imp(Q_aug.rb_mut(), R_aug.rb_mut(), i);
+		Q.copy_from(Q_aug.rb().get(.., ..k));
+		R.copy_from(R_aug.rb().get(..k, ..));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	fn random(m: usize, n: usize, rng: &mut StdRng) -> Mat<c64> {
+		CwiseMatDistribution {
+			nrows: m,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng)
+	}
+
+	fn factor(A: MatRef<'_, c64>) -> (Mat<c64>, Mat<c64>) {
+		let qr = A.qr();
+		(qr.compute_thin_Q(), qr.R().to_owned())
+	}
+
+	fn check(Q: MatRef<'_, c64>, R: MatRef<'_, c64>, A: MatRef<'_, c64>) {
+		let (m, n) = A.shape();
+		let k = Ord::min(m, n);
+		let approx_eq = CwiseMat(ApproxEq::eps() * 64.0 * ((m + n) as f64));
+
+		assert!(all(Q.nrows() == m, Q.ncols() == k, R.nrows() == k, R.ncols() == n));
+		assert!(Q * R ~ A);
+		assert!(Q.adjoint() * Q ~ Mat::<c64>::identity(k, k));
+		for j in 0..n {
+			for i in j + 1..k {
+				assert!(R[(i, j)] == c64::ZERO);
+			}
+		}
+	}
+
+	#[test]
+	fn test_rank_one_update() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n) in [(8, 5), (5, 8), (6, 6), (1, 3), (3, 1)] {
+			let A = random(m, n, rng);
+			let v = random(n, 1, rng);
+
+			// the second update has $u$ in the range of $A$
+			for u in [random(m, 1, rng), &A * random(n, 1, rng)] {
+				let (mut Q, mut R) = factor(A.as_ref());
+				rank_one_update(
+					Q.as_mut(),
+					R.as_mut(),
+					u.col(0),
+					v.col(0),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(rank_one_update_scratch::<c64>(m, n, Par::Seq))),
+				);
+				check(Q.as_ref(), R.as_ref(), (&A + &u * v.adjoint()).as_ref());
+			}
+		}
+	}
+
+	#[test]
+	fn test_insert_delete_col() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n) in [(8, 5), (5, 8), (6, 6), (4, 1), (6, 5)] {
+			let A = random(m, n, rng);
+
+			// the second column lies in the range of $A$
+			for col in [random(m, 1, rng), &A * random(n, 1, rng)] {
+				for j in 0..=n {
+					let (Q0, R0) = factor(A.as_ref());
+					let k = Ord::min(m, n);
+					let k_new = Ord::min(m, n + 1);
+
+					let mut Q = Mat::zeros(m, k_new);
+					let mut R = Mat::zeros(k_new, n + 1);
+					Q.get_mut(.., ..k).copy_from(&Q0);
+					R.get_mut(..k, ..n).copy_from(&R0);
+
+					insert_col(
+						Q.as_mut(),
+						R.as_mut(),
+						j,
+						col.col(0),
+						Par::Seq,
+						MemStack::new(&mut MemBuffer::new(insert_col_scratch::<c64>(m, n, Par::Seq))),
+					);
+					let A_new = Mat::from_fn(m, n + 1, |i, c| match c.cmp(&j) {
+						core::cmp::Ordering::Less => A[(i, c)],
+						core::cmp::Ordering::Equal => col[(i, 0)],
+						core::cmp::Ordering::Greater => A[(i, c - 1)],
+					});
+					check(Q.as_ref(), R.as_ref(), A_new.as_ref());
+
+					if j < n {
+						let k_new = Ord::min(m, n - 1);
+						let mut Q = Q0.clone();
+						let mut R = R0.clone();
+						delete_col(Q.as_mut(), R.as_mut(), j);
+						let A_new = Mat::from_fn(m, n - 1, |i, c| if c < j { A[(i, c)] } else { A[(i, c + 1)] });
+						check(Q.get(.., ..k_new), R.get(..k_new, ..n - 1), A_new.as_ref());
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_insert_delete_row() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		for (m, n) in [(8, 5), (5, 8), (6, 6), (1, 4), (5, 6)] {
+			let A = random(m, n, rng);
+			let row = random(1, n, rng);
+
+			for i in 0..=m {
+				let (Q0, R0) = factor(A.as_ref());
+				let k = Ord::min(m, n);
+				let k_new = Ord::min(m + 1, n);
+
+				let mut Q = Mat::zeros(m + 1, k + 1);
+				let mut R = Mat::zeros(k + 1, n);
+				Q.get_mut(..m, ..k).copy_from(&Q0);
+				R.get_mut(..k, ..).copy_from(&R0);
+
+				insert_row(Q.as_mut(), R.as_mut(), i, row.row(0));
+				let A_new = Mat::from_fn(m + 1, n, |r, j| match r.cmp(&i) {
+					core::cmp::Ordering::Less => A[(r, j)],
+					core::cmp::Ordering::Equal => row[(0, j)],
+					core::cmp::Ordering::Greater => A[(r - 1, j)],
+				});
+				check(Q.get(.., ..k_new), R.get(..k_new, ..), A_new.as_ref());
+
+				if i < m && m > 1 {
+					let k_new = Ord::min(m - 1, n);
+					let mut Q = Q0.clone();
+					let mut R = R0.clone();
+					delete_row(
+						Q.as_mut(),
+						R.as_mut(),
+						i,
+						Par::Seq,
+						MemStack::new(&mut MemBuffer::new(delete_row_scratch::<c64>(m, n, Par::Seq))),
+					);
+					let A_new = Mat::from_fn(m - 1, n, |r, j| if r < i { A[(r, j)] } else { A[(r + 1, j)] });
+					check(Q.get(..m - 1, ..k_new), R.get(..k_new, ..), A_new.as_ref());
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_delete_row_in_span() {
+		// the canonical basis vector $e_0$ lies in the span of $Q$, so the deleted row is rebuilt
+		// from an arbitrary orthogonal vector
+		let rng = &mut StdRng::seed_from_u64(0);
+		let (m, n) = (6, 3);
+		let mut A = random(m, n, rng);
+		A.row_mut(0).fill(c64::ZERO);
+		A[(0, 0)] = c64::new(1.0, 0.0);
+		A.get_mut(1.., 0).fill(c64::ZERO);
+
+		let (mut Q, mut R) = factor(A.as_ref());
+		delete_row(
+			Q.as_mut(),
+			R.as_mut(),
+			0,
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(delete_row_scratch::<c64>(m, n, Par::Seq))),
+		);
+		check(Q.get(..m - 1, ..), R.as_ref(), A.get(1.., ..));
+	}
+}
@@ -0,0 +1,426 @@
+//! mixed precision iterative refinement
+//!
+//! a linear system $A x = b$ is solved with a factorization of $A$ computed in a lower precision,
+//! and the solution is then refined in the working precision: at each step, the residual
+//! $r = b - A x$ is computed in the working precision, and the correction $d$ solving $A d = r$ is
+//! computed with the low precision factorization
+//!
+//! if $A$ is not too ill-conditioned relative to the low precision, the refinement converges to a
+//! solution that is as accurate as one computed with a working precision factorization
+//!
+//! fixed precision refinement, where the factorization is computed in the working precision, is
+//! also provided. it improves the componentwise accuracy of the solution, and computes error
+//! bounds for it
+
+use crate::assert;
+use crate::internal_prelude::*;
+use linalg::norm_est::{norm1_est, norm1_est_scratch};
+
+/// scalar type that has a lower precision counterpart, which can be used to factorize matrices
+/// during iterative refinement
+pub trait MixedPrecision: ComplexField {
+	/// low precision type
+	type Low: ComplexField;
+
+	/// converts `value` to the low precision type
+	fn to_low(value: &Self) -> Self::Low;
+	/// converts `value` from the low precision type
+	fn from_low(value: &Self::Low) -> Self;
+}
+
+impl MixedPrecision for f64 {
+	type Low = f32;
+
+	#[inline]
+	fn to_low(value: &Self) -> Self::Low {
+		*value as f32
+	}
+
+	#[inline]
+	fn from_low(value: &Self::Low) -> Self {
+		*value as f64
+	}
+}
+
+impl MixedPrecision for c64 {
+	type Low = c32;
+
+	#[inline]
+	fn to_low(value: &Self) -> Self::Low {
+		c32::new(value.re as f32, value.im as f32)
+	}
+
+	#[inline]
+	fn from_low(value: &Self::Low) -> Self {
+		c64::new(value.re as f64, value.im as f64)
+	}
+}
+
+/// iterative refinement tuning parameters
+#[derive(Copy, Clone, Debug)]
+pub struct RefinementParams {
+	/// maximum number of refinement steps
+	pub max_iterations: usize,
+	/// the refinement is considered stalled if a step does not reduce the backward error by at
+	/// least this factor
+	pub stall_factor: f64,
+
+	#[doc(hidden)]
+	pub non_exhaustive: NonExhaustive,
+}
+
+impl<T: ComplexField> Auto<T> for RefinementParams {
+	fn auto() -> Self {
+		Self {
+			max_iterations: 30,
+			stall_factor: 0.5,
+			non_exhaustive: NonExhaustive(()),
+		}
+	}
+}
+
+/// information about the result of the iterative refinement
+#[derive(Copy, Clone, Debug)]
+pub struct RefinementInfo<R> {
+	/// number of refinement steps that were performed after the initial solve
+	pub iterations: usize,
+	/// normwise backward error of the solution, $\max_j \frac{\|r_j\|_\infty}{\|A\|_\infty
+	/// \|x_j\|_\infty + \|b_j\|_\infty}$
+	pub backward_error: R,
+	/// whether the solution reached the working precision
+	pub converged: bool,
+	/// whether the solution was computed with a working precision factorization, after the
+	/// refinement failed to converge
+	pub fallback: bool,
+}
+
+/// computes the size and alignment of the workspace required to refine the solution of a linear
+/// system of dimension `dim`, with `rhs_ncols` right-hand sides
+pub fn refine_scratch<T: MixedPrecision>(dim: usize, rhs_ncols: usize) -> StackReq {
+	StackReq::all_of(&[temp_mat_scratch::<T>(dim, rhs_ncols), temp_mat_scratch::<T::Low>(dim, rhs_ncols)])
+}
+
+#[math]
+fn backward_error<T: ComplexField>(A_norm: T::Real, x: MatRef<'_, T>, rhs: MatRef<'_, T>, r: MatRef<'_, T>) -> T::Real {
+	let mut err = zero::<T::Real>();
+	for j in 0..x.ncols() {
+		let num = r.col(j).norm_max();
+		let denom = A_norm * x.col(j).norm_max() + rhs.col(j).norm_max();
+		let e = if num == zero() { zero() } else { num / denom };
+		// written so that a nan error propagates
+		#[allow(clippy::neg_cmp_op_on_partial_ord)]
+		if !(e <= err) {
+			err = e;
+		}
+	}
+	err
+}
+
+/// solves the linear system $A X = B$ with iterative refinement, and stores the result in `x`,
+/// where $B$ is given by `rhs`
+///
+/// `solve_low` must overwrite its argument $R$ with the solution of $A D = R$, computed with a low
+/// precision factorization of $A$
+///
+/// the refinement stops once the backward error reaches the working precision, if it stalls, or
+/// after the maximum number of iterations
+#[math]
+#[track_caller]
+pub fn refine<T: MixedPrecision>(
+	A: MatRef<'_, T>,
+	x: MatMut<'_, T>,
+	rhs: MatRef<'_, T>,
+	solve_low: &mut dyn FnMut(MatMut<'_, T::Low>),
+	par: Par,
+	stack: &mut MemStack,
+	params: Spec<RefinementParams, T>,
+) -> RefinementInfo<T::Real> {
+	let params = params.config;
+	let n = A.nrows();
+	let k = rhs.ncols();
+	assert!(all(A.ncols() == n, rhs.nrows() == n, x.nrows() == n, x.ncols() == k));
+
+	let mut x = x;
+	let (mut r, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, k, stack) };
+	let (mut d, _) = unsafe { temp_mat_uninit::<T::Low, _, _>(n, k, stack) };
+	let mut r = r.as_mat_mut();
+	let mut d = d.as_mat_mut();
+
+	let mut A_norm = zero::<T::Real>();
+	for i in 0..n {
+		let mut row = zero::<T::Real>();
+		for j in 0..n {
+			row = row + abs(A[(i, j)]);
+		}
+		A_norm = max(A_norm, row);
+	}
+
+	let tol = eps::<T::Real>() * sqrt(from_f64::<T::Real>(n as f64));
+	let stall_factor = from_f64::<T::Real>(params.stall_factor);
+
+	x.fill(zero());
+	r.copy_from(rhs);
+
+	let mut prev_err = infinity::<T::Real>();
+	let mut iter = 0;
+	loop {
+		z!(d.rb_mut(), r.rb()).for_each(|uz!(d, r)| *d = T::to_low(r));
+		solve_low(d.rb_mut());
+		z!(x.rb_mut(), d.rb()).for_each(|uz!(x, d)| *x = *x + T::from_low(d));
+
+		r.copy_from(rhs);
+		linalg::matmul::matmul(r.rb_mut(), Accum::Add, A, x.rb(), -one::<T>(), par);
+
+		let err = backward_error(copy(A_norm), x.rb(), rhs, r.rb());
+		if err <= tol {
+			return RefinementInfo {
+				iterations: iter,
+				backward_error: err,
+				converged: true,
+				fallback: false,
+			};
+		}
+
+		// written so that a nan error stops the iteration
+		#[allow(clippy::neg_cmp_op_on_partial_ord)]
+		if !(err <= stall_factor * prev_err) || iter == params.max_iterations {
+			return RefinementInfo {
+				iterations: iter,
+				backward_error: err,
+				converged: false,
+				fallback: false,
+			};
+		}
+
+		prev_err = err;
+		iter += 1;
+	}
+}
+
+// maximum number of fixed precision refinement steps
+const FIXED_PRECISION_MAX_ITERATIONS: usize = 5;
+
+/// computes the size and alignment of the workspace required to refine the solution of a linear
+/// system of dimension `dim` in fixed precision, and compute its error bounds
+pub fn refine_with_error_bounds_scratch<T: ComplexField>(dim: usize) -> StackReq {
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(dim, 1),
+		temp_mat_scratch::<T::Real>(dim, 1),
+		norm1_est_scratch::<T>(dim),
+	])
+}
+
+/// refines the solution $X$ of the linear system $A X = B$ in the working precision, where $X$ is
+/// given by `x` and $B$ by `rhs`, and computes error bounds for each column of the solution
+///
+/// `solve` and `solve_adjoint` must overwrite their argument $R$ with the solution of $A D = R$
+/// and $A^H D = R$ respectively
+///
+/// the componentwise relative backward error
+/// $\max_i \frac{|r_i|}{(|A| |x| + |b|)_i}$, with $r = b - A x$, is stored in
+/// `backward_error`, and an estimated bound on the relative forward error
+/// $\frac{\|x - x_{\text{true}}\|_\infty}{\|x\|_\infty}$ is stored in `forward_error`
+///
+/// this corresponds to the lapack routines `xGERFS`, `xPORFS` and `xSYRFS`
+#[math]
+#[track_caller]
+pub fn refine_with_error_bounds<T: ComplexField>(
+	A: MatRef<'_, T>,
+	x: MatMut<'_, T>,
+	rhs: MatRef<'_, T>,
+	solve: &mut dyn FnMut(MatMut<'_, T>),
+	solve_adjoint: &mut dyn FnMut(MatMut<'_, T>),
+	backward_error: ColMut<'_, T::Real>,
+	forward_error: ColMut<'_, T::Real>,
+	par: Par,
+	stack: &mut MemStack,
+) {
+	let n = A.nrows();
+	let k = rhs.ncols();
+	assert!(all(
+		A.ncols() == n,
+		rhs.nrows() == n,
+		x.nrows() == n,
+		x.ncols() == k,
+		backward_error.nrows() == k,
+		forward_error.nrows() == k,
+	));
+
+	let mut x = x;
+	let mut backward_error = backward_error;
+	let mut forward_error = forward_error;
+
+	if n == 0 {
+		backward_error.fill(zero());
+		forward_error.fill(zero());
+		return;
+	}
+
+	let (mut r, stack) = unsafe { temp_mat_uninit::<T, _, _>(n, 1, stack) };
+	let (mut w, stack) = unsafe { temp_mat_uninit::<T::Real, _, _>(n, 1, stack) };
+	let mut r = r.as_mat_mut();
+	let mut w = w.as_mat_mut().col_mut(0);
+
+	let eps = eps::<T::Real>();
+	let nz = from_f64::<T::Real>((n + 1) as f64);
+	// guards against division by zero and underflow in the componentwise error
+	let safe1 = nz * min_positive::<T::Real>();
+	let safe2 = safe1 / eps;
+
+	for j in 0..k {
+		let b = rhs.col(j);
+		let mut last_err = from_f64::<T::Real>(3.0);
+		let mut count = 1;
+
+		loop {
+			// r = b - A x, w = |A| |x| + |b|
+			r.rb_mut().col_mut(0).copy_from(b);
+			linalg::matmul::matmul(r.rb_mut(), Accum::Add, A, x.rb().col(j).as_mat(), -one::<T>(), par);
+
+			z!(w.rb_mut(), b).for_each(|uz!(w, b)| *w = abs(*b));
+			for c in 0..n {
+				let xc = abs(x[(c, j)]);
+				z!(w.rb_mut(), A.col(c)).for_each(|uz!(w, a)| *w = *w + abs(*a) * xc);
+			}
+
+			let mut err = zero::<T::Real>();
+			for i in 0..n {
+				let ri = abs(r[(i, 0)]);
+				let e = if w[i] > safe2 { ri / w[i] } else { (ri + safe1) / (w[i] + safe1) };
+				err = max(err, e);
+			}
+			backward_error[j] = copy(err);
+
+			if err > eps && mul_pow2(err, from_f64::<T::Real>(2.0)) <= last_err && count <= FIXED_PRECISION_MAX_ITERATIONS {
+				solve(r.rb_mut());
+				z!(x.rb_mut().col_mut(j), r.rb().col(0)).for_each(|uz!(x, r)| *x = *x + *r);
+				last_err = err;
+				count += 1;
+			} else {
+				break;
+			}
+		}
+
+		// ‖x - x_true‖_∞ ≤ ‖ |A^{-1}| (|r| + nz eps w) ‖_∞ = ‖ A^{-1} diag(f) ‖_∞ = ‖ diag(f) A^{-H} ‖_1
+		for i in 0..n {
+			let ri = abs(r[(i, 0)]);
+			w[i] = if w[i] > safe2 {
+				ri + nz * eps * w[i]
+			} else {
+				ri + nz * eps * w[i] + safe1
+			};
+		}
+		let f = w.rb();
+
+		let est = norm1_est(
+			n,
+			&mut |mut v: ColMut<'_, T>| {
+				solve_adjoint(v.rb_mut().as_mat_mut());
+				z!(v.rb_mut(), f).for_each(|uz!(v, f)| *v = mul_real(*v, *f));
+			},
+			&mut |mut v: ColMut<'_, T>| {
+				z!(v.rb_mut(), f).for_each(|uz!(v, f)| *v = mul_real(*v, *f));
+				solve(v.rb_mut().as_mat_mut());
+			},
+			stack,
+		);
+
+		let x_norm = x.rb().col(j).norm_max();
+		forward_error[j] = if x_norm == zero() { est } else { est / x_norm };
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+	use linalg::solvers::Solve;
+
+	#[test]
+	fn test_refine() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
+		let k = 3;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let B = CwiseMatDistribution {
+			nrows: n,
+			ncols: k,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+
+		let A_low = Mat::from_fn(n, n, |i, j| c64::to_low(&A[(i, j)]));
+		let lu = A_low.partial_piv_lu();
+
+		let mut X = Mat::zeros(n, k);
+		let info = refine(
+			A.as_ref(),
+			X.as_mut(),
+			B.as_ref(),
+			&mut |d| lu.solve_in_place(d),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(refine_scratch::<c64>(n, k))),
+			default(),
+		);
+
+		assert!(info.converged);
+		assert!(info.iterations > 0);
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+		assert!(&A * &X ~ B);
+	}
+
+	#[test]
+	fn test_refine_with_error_bounds() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
+		let k = 3;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let X_true = CwiseMatDistribution {
+			nrows: n,
+			ncols: k,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let B = &A * &X_true;
+
+		let lu = A.partial_piv_lu();
+		let mut X = lu.solve(&B);
+		let mut berr = Col::<f64>::zeros(k);
+		let mut ferr = Col::<f64>::zeros(k);
+
+		refine_with_error_bounds(
+			A.as_ref(),
+			X.as_mut(),
+			B.as_ref(),
+			&mut |x| lu.solve_in_place(x),
+			&mut |x| lu.solve_adjoint_in_place(x),
+			berr.as_mut(),
+			ferr.as_mut(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(refine_with_error_bounds_scratch::<c64>(n))),
+		);
+
+		for j in 0..k {
+			assert!(berr[j] <= 4.0 * f64::EPSILON);
+			let err = (X.col(j) - X_true.col(j)).norm_max() / X.col(j).norm_max();
+			assert!(err <= ferr[j]);
+			assert!(ferr[j] <= 1e-10);
+		}
+	}
+}
@@ -4,11 +4,15 @@ use alloc::vec;
 use alloc::vec::Vec;
 use dyn_stack::MemBuffer;
 use faer_traits::math_utils;
+use linalg::evd::ComputeEigenvectors;
+use linalg::refinement::{MixedPrecision, RefinementInfo};
 use linalg::svd::ComputeSvdVectors;
 
 pub use linalg::cholesky::ldlt::factor::LdltError;
 pub use linalg::cholesky::llt::factor::LltError;
-pub use linalg::evd::EvdError;
+pub use linalg::evd::{EigenvalueSelection, EvdError};
+pub use linalg::gevd::{GevdError, SelfAdjointGevdError, SelfAdjointGevdProblem};
+pub use linalg::polar::PolarError;
 pub use linalg::svd::SvdError;
 
 /// shape info of a linear system solver
@@ -250,6 +254,12 @@ impl<C: Conjugate> MatRef<'_, C> {
 		ColPivQr::new(self.as_mat_ref())
 	}
 
+	#[track_caller]
+	/// returns the complete orthogonal decomposition of `self`
+	pub fn complete_orthogonal(&self) -> CompleteOrthogonal<C::Canonical> {
+		CompleteOrthogonal::new(self.as_mat_ref())
+	}
+
 	#[track_caller]
 	/// returns the svd of `self`
 	///
@@ -266,6 +276,12 @@ impl<C: Conjugate> MatRef<'_, C> {
 		Svd::new_thin(self.as_mat_ref())
 	}
 
+	#[track_caller]
+	/// returns the polar decomposition of `self`
+	pub fn polar(&self) -> Result<Polar<C::Canonical>, PolarError> {
+		Polar::new(self.as_mat_ref())
+	}
+
 	#[track_caller]
 	/// returns the $L L^\top$ decomposition of `self`
 	pub fn llt(&self, side: Side) -> Result<Llt<C::Canonical>, LltError> {
@@ -292,6 +308,18 @@ impl<C: Conjugate> MatRef<'_, C> {
 		SelfAdjointEigen::new(self.as_mat_ref(), side)
 	}
 
+	#[track_caller]
+	/// returns the eigenpairs of `self` given by `selection`, assuming it is self-adjoint
+	///
+	/// eigenvalues sorted in nondecreasing order
+	pub fn partial_self_adjoint_eigen(
+		&self,
+		side: Side,
+		selection: EigenvalueSelection<Real<C>>,
+	) -> Result<PartialSelfAdjointEigen<C::Canonical>, EvdError> {
+		PartialSelfAdjointEigen::new(self.as_mat_ref(), side, selection)
+	}
+
 	#[track_caller]
 	/// returns the eigenvalues of `self`, assuming it is self-adjoint
 	///
@@ -372,7 +400,13 @@ impl<T: RealField> MatRef<'_, T> {
 	}
 
 	#[track_caller]
-	/// returns the eigenvalues of `self`
+	/// returns the real schur decomposition of `self`
+	pub fn schur_from_real(&self) -> Result<RealSchur<T>, EvdError> {
+		RealSchur::new(self.as_mat_ref())
+	}
+
+	#[track_caller]
+	/// returns the eigenvalues of `self`, computed without balancing
 	pub fn eigenvalues_from_real(&self) -> Result<Vec<Complex<T>>, EvdError> {
 		let par = get_global_parallelism();
 
@@ -417,7 +451,13 @@ impl<T: RealField> MatRef<'_, Complex<T>> {
 	}
 
 	#[track_caller]
-	/// returns the eigenvalues of `self`
+	/// returns the complex schur decomposition of `self`
+	pub fn schur(&self) -> Result<Schur<T>, EvdError> {
+		Schur::new(self.as_mat_ref())
+	}
+
+	#[track_caller]
+	/// returns the eigenvalues of `self`, computed without balancing
 	pub fn eigenvalues(&self) -> Result<Vec<Complex<T>>, EvdError> {
 		let par = get_global_parallelism();
 
@@ -472,6 +512,12 @@ impl<C: Conjugate> MatMut<'_, C> {
 		self.rb().col_piv_qr()
 	}
 
+	#[track_caller]
+	/// returns the complete orthogonal decomposition of `self`
+	pub fn complete_orthogonal(&self) -> CompleteOrthogonal<C::Canonical> {
+		self.rb().complete_orthogonal()
+	}
+
 	#[track_caller]
 	/// returns the svd of `self`
 	///
@@ -488,6 +534,12 @@ impl<C: Conjugate> MatMut<'_, C> {
 		self.rb().thin_svd()
 	}
 
+	#[track_caller]
+	/// returns the polar decomposition of `self`
+	pub fn polar(&self) -> Result<Polar<C::Canonical>, PolarError> {
+		self.rb().polar()
+	}
+
 	#[track_caller]
 	/// returns the $L L^\top$ decomposition of `self`
 	pub fn llt(&self, side: Side) -> Result<Llt<C::Canonical>, LltError> {
@@ -514,6 +566,18 @@ impl<C: Conjugate> MatMut<'_, C> {
 		self.rb().self_adjoint_eigen(side)
 	}
 
+	#[track_caller]
+	/// returns the eigenpairs of `self` given by `selection`, assuming it is self-adjoint
+	///
+	/// eigenvalues sorted in nondecreasing order
+	pub fn partial_self_adjoint_eigen(
+		&self,
+		side: Side,
+		selection: EigenvalueSelection<Real<C>>,
+	) -> Result<PartialSelfAdjointEigen<C::Canonical>, EvdError> {
+		self.rb().partial_self_adjoint_eigen(side, selection)
+	}
+
 	#[track_caller]
 	/// returns the eigenvalues of `self`, assuming it is self-adjoint
 	///
@@ -539,7 +603,13 @@ impl<T: RealField> MatMut<'_, T> {
 	}
 
 	#[track_caller]
-	/// returns the eigenvalues of `self`
+	/// returns the real schur decomposition of `self`
+	pub fn schur_from_real(&self) -> Result<RealSchur<T>, EvdError> {
+		self.rb().schur_from_real()
+	}
+
+	#[track_caller]
+	/// returns the eigenvalues of `self`, computed without balancing
 	pub fn eigenvalues_from_real(&self) -> Result<Vec<Complex<T>>, EvdError> {
 		self.rb().eigenvalues_from_real()
 	}
@@ -553,7 +623,13 @@ impl<T: RealField> MatMut<'_, Complex<T>> {
 	}
 
 	#[track_caller]
-	/// returns the eigenvalues of `self`
+	/// returns the complex schur decomposition of `self`
+	pub fn schur(&self) -> Result<Schur<T>, EvdError> {
+		self.rb().schur()
+	}
+
+	#[track_caller]
+	/// returns the eigenvalues of `self`, computed without balancing
 	pub fn eigenvalues(&self) -> Result<Vec<Complex<T>>, EvdError> {
 		self.rb().eigenvalues()
 	}
@@ -584,6 +660,12 @@ impl<C: Conjugate> Mat<C> {
 		self.rb().col_piv_qr()
 	}
 
+	#[track_caller]
+	/// returns the complete orthogonal decomposition of `self`
+	pub fn complete_orthogonal(&self) -> CompleteOrthogonal<C::Canonical> {
+		self.rb().complete_orthogonal()
+	}
+
 	#[track_caller]
 	/// returns the svd of `self`
 	///
@@ -600,6 +682,12 @@ impl<C: Conjugate> Mat<C> {
 		self.rb().thin_svd()
 	}
 
+	#[track_caller]
+	/// returns the polar decomposition of `self`
+	pub fn polar(&self) -> Result<Polar<C::Canonical>, PolarError> {
+		self.rb().polar()
+	}
+
 	#[track_caller]
 	/// returns the $L L^\top$ decomposition of `self`
 	pub fn llt(&self, side: Side) -> Result<Llt<C::Canonical>, LltError> {
@@ -626,6 +714,18 @@ impl<C: Conjugate> Mat<C> {
 		self.rb().self_adjoint_eigen(side)
 	}
 
+	#[track_caller]
+	/// returns the eigenpairs of `self` given by `selection`, assuming it is self-adjoint
+	///
+	/// eigenvalues sorted in nondecreasing order
+	pub fn partial_self_adjoint_eigen(
+		&self,
+		side: Side,
+		selection: EigenvalueSelection<Real<C>>,
+	) -> Result<PartialSelfAdjointEigen<C::Canonical>, EvdError> {
+		self.rb().partial_self_adjoint_eigen(side, selection)
+	}
+
 	#[track_caller]
 	/// returns the eigenvalues of `self`, assuming it is self-adjoint
 	///
@@ -651,7 +751,13 @@ impl<T: RealField> Mat<T> {
 	}
 
 	#[track_caller]
-	/// returns the eigenvalues of `self`
+	/// returns the real schur decomposition of `self`
+	pub fn schur_from_real(&self) -> Result<RealSchur<T>, EvdError> {
+		self.rb().schur_from_real()
+	}
+
+	#[track_caller]
+	/// returns the eigenvalues of `self`, computed without balancing
 	pub fn eigenvalues_from_real(&self) -> Result<Vec<Complex<T>>, EvdError> {
 		self.rb().eigenvalues_from_real()
 	}
@@ -665,7 +771,13 @@ impl<T: RealField> Mat<Complex<T>> {
 	}
 
 	#[track_caller]
-	/// returns the eigenvalues of `self`
+	/// returns the complex schur decomposition of `self`
+	pub fn schur(&self) -> Result<Schur<T>, EvdError> {
+		self.rb().schur()
+	}
+
+	#[track_caller]
+	/// returns the eigenvalues of `self`, computed without balancing
 	pub fn eigenvalues(&self) -> Result<Vec<Complex<T>>, EvdError> {
 		self.rb().eigenvalues()
 	}
@@ -674,7 +786,62 @@ impl<T: RealField> Mat<Complex<T>> {
 /// [`SolveLstsqCore`] extension trait
 pub trait SolveLstsq<T: ComplexField>: SolveLstsqCore<T> {}
 /// [`DenseSolveCore`] extension trait
-pub trait DenseSolve<T: ComplexField>: DenseSolveCore<T> {}
+pub trait DenseSolve<T: ComplexField>: DenseSolveCore<T> {
+	#[track_caller]
+	/// solves $A x = b$, refines the solution in the working precision, and returns it along with
+	/// error bounds for each column
+	///
+	/// `A` must be the matrix that was used to create the decomposition. see
+	/// [`linalg::refinement::refine_with_error_bounds`]
+	fn solve_with_error_bounds(
+		&self,
+		A: impl AsMatRef<T = T, Rows = usize, Cols = usize>,
+		rhs: impl AsMatRef<T = T, Rows = usize, Cols = usize>,
+	) -> (Mat<T>, ErrorBounds<T::Real>) {
+		let par = get_global_parallelism();
+		let A = A.as_mat_ref();
+		let rhs = rhs.as_mat_ref();
+		let n = A.nrows();
+		let k = rhs.ncols();
+		assert!(all(self.nrows() == n, self.ncols() == n, A.ncols() == n, rhs.nrows() == n));
+
+		let mut x = rhs.to_owned();
+		self.solve_in_place_with_conj(Conj::No, x.as_mut());
+
+		let mut backward_error = Col::<T::Real>::zeros(k);
+		let mut forward_error = Col::<T::Real>::zeros(k);
+
+		linalg::refinement::refine_with_error_bounds(
+			A,
+			x.as_mut(),
+			rhs,
+			&mut |x| self.solve_in_place_with_conj(Conj::No, x),
+			&mut |x| self.solve_transpose_in_place_with_conj(Conj::Yes, x),
+			backward_error.as_mut(),
+			forward_error.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::refinement::refine_with_error_bounds_scratch::<T>(n))),
+		);
+
+		(
+			x,
+			ErrorBounds {
+				backward_error: backward_error.iter().cloned().collect(),
+				forward_error: forward_error.iter().cloned().collect(),
+			},
+		)
+	}
+}
+
+/// error bounds of the solution of a linear system, for each column of the right-hand side
+#[derive(Clone, Debug)]
+pub struct ErrorBounds<R> {
+	/// componentwise relative backward error, $\max_i \frac{|b - A x|_i}{(|A| |x| + |b|)_i}$
+	pub backward_error: Vec<R>,
+	/// estimated bound on the relative forward error, $\frac{\|x - x_{\text{true}}\|_\infty}
+	/// {\|x\|_\infty}$
+	pub forward_error: Vec<R>,
+}
 
 impl<T: ComplexField, S: ?Sized + SolveCore<T>> Solve<T> for S {}
 impl<T: ComplexField, S: ?Sized + SolveLstsqCore<T>> SolveLstsq<T> for S {}
@@ -682,58 +849,119 @@ impl<T: ComplexField, S: ?Sized + DenseSolveCore<T>> DenseSolve<T> for S {}
 
 /// $L L^\top$ decomposition
 #[derive(Clone, Debug)]
-pub struct Llt<T> {
+pub struct Llt<T: ComplexField> {
 	L: Mat<T>,
+	// unknown after an update
+	A_norm1: Option<T::Real>,
 }
 
 /// $L D L^\top$ decomposition
 #[derive(Clone, Debug)]
-pub struct Ldlt<T> {
+pub struct Ldlt<T: ComplexField> {
 	L: Mat<T>,
 	D: Diag<T>,
+	// unknown after an update
+	A_norm1: Option<T::Real>,
 }
 
 /// bunch-kaufman decomposition
 #[derive(Clone, Debug)]
-pub struct Lblt<T> {
+pub struct Lblt<T: ComplexField> {
 	L: Mat<T>,
 	B_diag: Diag<T>,
 	B_subdiag: Diag<T>,
 	P: Perm<usize>,
+	A_norm1: T::Real,
 }
 
 /// $LU$ decomposition with partial (row) pivoting
 #[derive(Clone, Debug)]
-pub struct PartialPivLu<T> {
+pub struct PartialPivLu<T: ComplexField> {
 	L: Mat<T>,
 	U: Mat<T>,
 	P: Perm<usize>,
+	A_norm1: T::Real,
 }
 
 /// $LU$ decomposition with full pivoting
 #[derive(Clone, Debug)]
-pub struct FullPivLu<T> {
+pub struct FullPivLu<T: ComplexField> {
 	L: Mat<T>,
 	U: Mat<T>,
 	P: Perm<usize>,
 	Q: Perm<usize>,
+	A_norm1: T::Real,
 }
 
 /// $QR$ decomposition
 #[derive(Clone, Debug)]
-pub struct Qr<T> {
+pub struct Qr<T: ComplexField> {
 	Q_basis: Mat<T>,
 	Q_coeff: Mat<T>,
 	R: Mat<T>,
+	A_norm1: T::Real,
 }
 
 /// $QR$ decomposition with column pivoting
 #[derive(Clone, Debug)]
-pub struct ColPivQr<T> {
+pub struct ColPivQr<T: ComplexField> {
 	Q_basis: Mat<T>,
 	Q_coeff: Mat<T>,
 	R: Mat<T>,
 	P: Perm<usize>,
+	A_norm1: T::Real,
+}
+
+/// complete orthogonal decomposition, computed from a $QR$ decomposition with column pivoting
+///
+/// $$AP^\top = Q \begin{bmatrix} L & 0 \\ 0 & 0 \end{bmatrix} Z^H$$
+/// where $L$ is an $r \times r$ lower triangular matrix, and $r$ is the numerical rank of $A$
+#[derive(Clone, Debug)]
+pub struct CompleteOrthogonal<T: ComplexField> {
+	qr: ColPivQr<T>,
+	Z_basis: Mat<T>,
+	Z_coeff: Mat<T>,
+	L: Mat<T>,
+}
+
+/// thin $QR$ decomposition with an explicitly stored $m \times \min(m, n)$ factor $Q$, which
+/// supports inserting and deleting rows and columns, and rank one updates in $\mathcal{O}(mn)$
+/// operations
+///
+/// see [`linalg::qr::no_pivoting::update`]
+#[derive(Clone, Debug)]
+pub struct UpdatableQr<T> {
+	Q: Mat<T>,
+	R: Mat<T>,
+}
+
+/// $LU$ decomposition with partial pivoting computed in low precision, used to solve linear
+/// systems in the working precision with iterative refinement
+#[derive(Clone, Debug)]
+pub struct MixedPrecisionLu<T: MixedPrecision> {
+	A: Mat<T>,
+	lu: PartialPivLu<T::Low>,
+	// working precision decomposition, computed on the first fallback
+	fallback: Option<PartialPivLu<T>>,
+}
+
+/// $L L^\top$ decomposition computed in low precision, used to solve linear systems in the working
+/// precision with iterative refinement
+#[derive(Clone, Debug)]
+pub struct MixedPrecisionLlt<T: MixedPrecision> {
+	A: Mat<T>,
+	llt: Option<Llt<T::Low>>,
+	// working precision decomposition, computed on the first fallback or when the low precision
+	// decomposition fails
+	fallback: Option<SelfAdjointFallback<T>>,
+}
+
+// the working precision $L L^\top$ decomposition may fail if $A$ is only positive definite up to
+// the low precision, in which case the bunch-kaufman decomposition is used instead
+#[derive(Clone, Debug)]
+enum SelfAdjointFallback<T: ComplexField> {
+	Llt(Llt<T>),
+	Lblt(Lblt<T>),
 }
 
 /// svd decomposition (either full or thin)
@@ -744,6 +972,23 @@ pub struct Svd<T> {
 	S: Diag<T>,
 }
 
+/// generalized svd decomposition of a matrix pair
+#[derive(Clone, Debug)]
+pub struct Gsvd<T> {
+	U: Mat<T>,
+	V: Mat<T>,
+	X: Mat<T>,
+	C: Diag<T>,
+	S: Diag<T>,
+}
+
+/// polar decomposition
+#[derive(Clone, Debug)]
+pub struct Polar<T> {
+	U: Mat<T>,
+	H: Mat<T>,
+}
+
 /// self-adjoint eigendecomposition
 #[derive(Clone, Debug)]
 pub struct SelfAdjointEigen<T> {
@@ -751,11 +996,185 @@ pub struct SelfAdjointEigen<T> {
 	S: Diag<T>,
 }
 
+/// subset of the eigenpairs of a self-adjoint matrix
+#[derive(Clone, Debug)]
+pub struct PartialSelfAdjointEigen<T> {
+	U: Mat<T>,
+	S: Diag<T>,
+}
+
+/// generalized self-adjoint eigendecomposition
+#[derive(Clone, Debug)]
+pub struct GeneralizedSelfAdjointEigen<T> {
+	U: Mat<T>,
+	S: Diag<T>,
+}
+
 /// eigendecomposition
+///
+/// the matrix is not balanced before computing its eigendecomposition. for badly scaled matrices,
+/// [`linalg::evd::evd_real`] and [`linalg::evd::evd_cplx`] can be called directly with
+/// [`EvdParams::balance`](linalg::evd::EvdParams::balance) set to balance it first
 #[derive(Clone, Debug)]
 pub struct Eigen<T> {
 	U: Mat<Complex<T>>,
 	S: Diag<Complex<T>>,
+	U_left: Option<Mat<Complex<T>>>,
+	s_cond: Option<Col<T>>,
+	sep: Option<Col<T>>,
+}
+
+/// generalized eigendecomposition of a matrix pencil
+#[derive(Clone, Debug)]
+pub struct GeneralizedEigen<T> {
+	alpha: Diag<Complex<T>>,
+	beta: Diag<Complex<T>>,
+	U_left: Option<Mat<Complex<T>>>,
+	U_right: Option<Mat<Complex<T>>>,
+}
+
+/// complex schur decomposition
+#[derive(Clone, Debug)]
+pub struct Schur<T> {
+	T: Mat<Complex<T>>,
+	Z: Mat<Complex<T>>,
+	S: Diag<Complex<T>>,
+}
+
+/// real schur decomposition
+#[derive(Clone, Debug)]
+pub struct RealSchur<T> {
+	T: Mat<T>,
+	Z: Mat<T>,
+	S: Diag<Complex<T>>,
+}
+
+/// hessenberg decomposition
+///
+/// $$A = Q H Q^H$$
+/// where $H$ is upper hessenberg and $Q$ is unitary
+#[derive(Clone, Debug)]
+pub struct Hessenberg<T> {
+	H: Mat<T>,
+	Q_basis: Mat<T>,
+	Q_coeff: Mat<T>,
+}
+
+/// tridiagonalization of a self-adjoint matrix
+///
+/// $$A = Q T Q^H$$
+/// where $T$ is self-adjoint and tridiagonal, and $Q$ is unitary
+#[derive(Clone, Debug)]
+pub struct Tridiag<T> {
+	diag: Col<T>,
+	subdiag: Col<T>,
+	Q_basis: Mat<T>,
+	Q_coeff: Mat<T>,
+}
+
+/// bidiagonalization
+///
+/// $$A = U B V^H$$
+/// where $B$ is upper bidiagonal, and $U$ and $V$ are unitary
+#[derive(Clone, Debug)]
+pub struct Bidiag<T> {
+	diag: Col<T>,
+	superdiag: Col<T>,
+	U_basis: Mat<T>,
+	U_coeff: Mat<T>,
+	V_basis: Mat<T>,
+	V_coeff: Mat<T>,
+}
+
+// applies $Q$ (or $Q^H$ if `conj` is `Conj::Yes`) to the columns of `x`, where $Q$ is given by its
+// householder basis and coefficients
+fn apply_householder_in_place<T: ComplexField>(Q_basis: MatRef<'_, T>, Q_coeff: MatRef<'_, T>, conj: Conj, x: MatMut<'_, T>) {
+	let par = get_global_parallelism();
+	let n = Q_basis.nrows();
+	let blocksize = Q_coeff.nrows();
+	let stack = &mut MemBuffer::new(linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(
+		n,
+		blocksize,
+		x.ncols(),
+	));
+	let stack = MemStack::new(stack);
+
+	match conj {
+		Conj::No => linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(Q_basis, Q_coeff, Conj::No, x, par, stack),
+		Conj::Yes => {
+			linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(Q_basis, Q_coeff, Conj::Yes, x, par, stack)
+		},
+	}
+}
+
+// estimates the reciprocal condition number in the $1$-norm of the square matrix $A$, given
+// its exact $1$-norm and a solver for $A$. only $\|A^{-1}\|_1$ is estimated
+#[math]
+fn rcond_imp<T: ComplexField, S: SolveCore<T>>(solver: &S, norm: T::Real) -> T::Real {
+	assert!(solver.nrows() == solver.ncols());
+	let n = solver.nrows();
+	if n == 0 {
+		return one();
+	}
+	if norm == zero() {
+		return zero();
+	}
+
+	let mut mem = MemBuffer::new(linalg::norm_est::norm1_est_scratch::<T>(n));
+	let stack = MemStack::new(&mut mem);
+
+	let inv_norm = linalg::norm_est::norm1_est(
+		n,
+		&mut |x| solver.solve_in_place_with_conj(Conj::No, x.as_mat_mut()),
+		&mut |x| solver.solve_transpose_in_place_with_conj(Conj::Yes, x.as_mat_mut()),
+		stack,
+	);
+	if !is_finite(inv_norm) {
+		return zero();
+	}
+
+	recip(norm) / inv_norm
+}
+
+// returns the $1$-norm of the self-adjoint matrix whose lower triangular half is stored in `A`
+#[math]
+fn self_adjoint_norm1<T: ComplexField>(A: MatRef<'_, T>) -> T::Real {
+	let n = A.nrows();
+	let mut col = vec![zero::<T::Real>(); n];
+	for j in 0..n {
+		for i in j..n {
+			let a = abs(A[(i, j)]);
+			col[j] = col[j] + a;
+			if i > j {
+				col[i] = col[i] + a;
+			}
+		}
+	}
+	col.into_iter().fold(zero(), |norm, x| max(norm, x))
+}
+
+// returns the $1$-norm of $L D L^H$, where $L$ is lower triangular and $D$ is the identity if it
+// is not provided. the columns are computed one at a time, so no $n \times n$ workspace is needed
+#[math]
+fn factored_self_adjoint_norm1<T: ComplexField>(L: MatRef<'_, T>, D: Option<DiagRef<'_, T>>) -> T::Real {
+	let n = L.nrows();
+	let mut col = Col::<T>::zeros(n);
+	let mut norm = zero::<T::Real>();
+	for j in 0..n {
+		col.as_mut().fill(zero());
+		// the $j$-th column is $\sum_{k \le j} L_{:, k} d_k \overline{L_{j, k}}$
+		for k in 0..j + 1 {
+			let c = match D {
+				Some(D) => D[k] * conj(L[(j, k)]),
+				None => conj(L[(j, k)]),
+			};
+			for i in k..n {
+				col[i] = col[i] + L[(i, k)] * c;
+			}
+		}
+		norm = max(norm, linalg::matfun::norm1(col.as_mat()));
+	}
+	norm
 }
 
 impl<T: ComplexField> Llt<T> {
@@ -779,6 +1198,7 @@ impl<T: ComplexField> Llt<T> {
 		let par = get_global_parallelism();
 
 		let n = L.nrows();
+		let A_norm1 = self_adjoint_norm1(L.as_ref());
 
 		let mut mem = MemBuffer::new(linalg::cholesky::llt::factor::cholesky_in_place_scratch::<T>(n, par, default()));
 		let stack = MemStack::new(&mut mem);
@@ -786,13 +1206,112 @@ impl<T: ComplexField> Llt<T> {
 		linalg::cholesky::llt::factor::cholesky_in_place(L.as_mut(), Default::default(), par, stack, default())?;
 		z!(&mut L).for_each_triangular_upper(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
 
-		Ok(Self { L })
+		Ok(Self { L, A_norm1: Some(A_norm1) })
 	}
 
 	/// returns the $L$ factor
 	pub fn L(&self) -> MatRef<'_, T> {
 		self.L.as_ref()
 	}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// $\|A\|_1$ is computed exactly when the matrix is factorized, and $\|A^{-1}\|_1$ is estimated
+	/// with hager's method using only solves with the decomposition, so the cost is $O(n^2)$. a value
+	/// close to zero indicates that $A$ is close to being singular
+	///
+	/// after an update of the decomposition, $\|A\|_1$ is recomputed from the factors one column at
+	/// a time, which costs $O(n^3)$ but needs no $n \times n$ workspace
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		let A_norm1 = match &self.A_norm1 {
+			Some(norm) => copy(norm),
+			None => factored_self_adjoint_norm1(self.L(), None),
+		};
+		rcond_imp(self, A_norm1)
+	}
+
+	#[track_caller]
+	fn rank_update_imp(&mut self, W: MatRef<'_, T>, alpha: T) -> Result<(), LltError> {
+		assert!(W.nrows() == self.L.nrows());
+
+		let mut L = self.L.clone();
+		let mut W = W.to_owned();
+		let mut alpha = Diag::full(W.ncols(), alpha);
+		linalg::cholesky::llt::update::rank_r_update_clobber(L.as_mut(), W.as_mut(), alpha.as_mut())?;
+
+		self.L = L;
+		self.A_norm1 = None;
+		Ok(())
+	}
+
+	/// updates the decomposition of $A$ to that of $A + W W^H$
+	///
+	/// returns an error if $A + W W^H$ is not numerically positive definite, which can only happen
+	/// if $W$ contains non-finite values, in which case `self` is left unchanged
+	#[track_caller]
+	pub fn rank_update(&mut self, W: MatRef<'_, T>) -> Result<(), LltError> {
+		self.rank_update_imp(W, one())
+	}
+
+	/// updates the decomposition of $A$ to that of $A - W W^H$
+	///
+	/// returns an error if $A - W W^H$ is not numerically positive definite, in which case `self`
+	/// is left unchanged
+	#[track_caller]
+	pub fn downdate(&mut self, W: MatRef<'_, T>) -> Result<(), LltError> {
+		self.rank_update_imp(W, math_utils::neg(&one::<T>()))
+	}
+
+	/// updates the decomposition to that of the matrix obtained by inserting a row and a column
+	/// at `index`, where `col` is the new column of the matrix, including its diagonal element
+	///
+	/// returns an error if the new matrix is not numerically positive definite, in which case
+	/// `self` is left unchanged
+	#[track_caller]
+	pub fn insert_row_col(&mut self, index: usize, col: ColRef<'_, T>) -> Result<(), LltError> {
+		let par = get_global_parallelism();
+		let n = self.L.nrows();
+		assert!(all(index <= n, col.nrows() == n + 1));
+
+		let mut L = Mat::zeros(n + 1, n + 1);
+		L.get_mut(..n, ..n).copy_from(&self.L);
+		let mut col = col.to_owned();
+
+		linalg::cholesky::llt::update::insert_row_col_clobber(
+			L.as_mut(),
+			index,
+			col.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::cholesky::llt::update::insert_row_col_scratch::<T>(
+				n + 1,
+				par,
+			))),
+		)?;
+
+		self.L = L;
+		self.A_norm1 = None;
+		Ok(())
+	}
+
+	/// updates the decomposition to that of the matrix obtained by removing the row and the column
+	/// at `index`
+	#[track_caller]
+	pub fn remove_row_col(&mut self, index: usize) {
+		let par = get_global_parallelism();
+		let n = self.L.nrows();
+		assert!(index < n);
+
+		linalg::cholesky::llt::update::delete_row_col_clobber(
+			self.L.as_mut(),
+			index,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::cholesky::llt::update::delete_row_col_scratch::<T>(n, par))),
+		);
+		self.L.truncate(n - 1, n - 1);
+		self.A_norm1 = None;
+	}
 }
 
 impl<T: ComplexField> Ldlt<T> {
@@ -816,18 +1335,35 @@ impl<T: ComplexField> Ldlt<T> {
 		let par = get_global_parallelism();
 
 		let n = L.nrows();
-		let mut D = Diag::zeros(n);
-
+		let A_norm1 = self_adjoint_norm1(L.as_ref());
 		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::factor::cholesky_in_place_scratch::<T>(n, par, default()));
 		let stack = MemStack::new(&mut mem);
 
 		linalg::cholesky::ldlt::factor::cholesky_in_place(L.as_mut(), Default::default(), par, stack, default())?;
 
+		Ok(Self::from_packed(L, Some(A_norm1)))
+	}
+
+	// splits factors that are stored with $D$ on the diagonal of $L$
+	fn from_packed(mut L: Mat<T>, A_norm1: Option<T::Real>) -> Self {
+		let n = L.nrows();
+		let mut D = Diag::zeros(n);
+
 		D.copy_from(L.diagonal());
 		L.diagonal_mut().fill(one());
 		z!(&mut L).for_each_triangular_upper(linalg::zip::Diag::Skip, |uz!(x)| *x = zero());
 
-		Ok(Self { L, D })
+		Self { L, D, A_norm1 }
+	}
+
+	// returns the factors stored with $D$ on the diagonal of $L$, padded with zeros to dimension
+	// `dim`
+	fn to_packed(&self, dim: usize) -> Mat<T> {
+		let n = self.L.nrows();
+		let mut L = Mat::zeros(dim, dim);
+		L.get_mut(..n, ..n).copy_from(&self.L);
+		L.get_mut(..n, ..n).diagonal_mut().copy_from(self.D());
+		L
 	}
 
 	/// returns the $L$ factor
@@ -839,6 +1375,105 @@ impl<T: ComplexField> Ldlt<T> {
 	pub fn D(&self) -> DiagRef<'_, T> {
 		self.D.as_ref()
 	}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// see [`Llt::rcond`]
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		let A_norm1 = match &self.A_norm1 {
+			Some(norm) => copy(norm),
+			None => factored_self_adjoint_norm1(self.L(), Some(self.D())),
+		};
+		rcond_imp(self, A_norm1)
+	}
+
+	#[track_caller]
+	fn rank_update_imp(&mut self, W: MatRef<'_, T>, alpha: T) -> Result<(), LdltError> {
+		let n = self.L.nrows();
+		assert!(W.nrows() == n);
+
+		let mut L = self.to_packed(n);
+		let mut W = W.to_owned();
+		let mut alpha = Diag::full(W.ncols(), alpha);
+		linalg::cholesky::ldlt::update::rank_r_update_clobber(L.as_mut(), W.as_mut(), alpha.as_mut());
+		linalg::cholesky::ldlt::update::check_pivots(L.as_ref(), 0)?;
+
+		*self = Self::from_packed(L, None);
+		Ok(())
+	}
+
+	/// updates the decomposition of $A$ to that of $A + W W^H$
+	///
+	/// returns an error if a pivot of the new matrix is zero, in which case `self` is left
+	/// unchanged
+	#[track_caller]
+	pub fn rank_update(&mut self, W: MatRef<'_, T>) -> Result<(), LdltError> {
+		self.rank_update_imp(W, one())
+	}
+
+	/// updates the decomposition of $A$ to that of $A - W W^H$
+	///
+	/// returns an error if a pivot of the new matrix is zero, in which case `self` is left
+	/// unchanged
+	#[track_caller]
+	pub fn downdate(&mut self, W: MatRef<'_, T>) -> Result<(), LdltError> {
+		self.rank_update_imp(W, math_utils::neg(&one::<T>()))
+	}
+
+	/// updates the decomposition to that of the matrix obtained by inserting a row and a column
+	/// at `index`, where `col` is the new column of the matrix, including its diagonal element
+	///
+	/// returns an error if a pivot of the new matrix is zero, in which case `self` is left
+	/// unchanged
+	#[track_caller]
+	pub fn insert_row_col(&mut self, index: usize, col: ColRef<'_, T>) -> Result<(), LdltError> {
+		let par = get_global_parallelism();
+		let n = self.L.nrows();
+		assert!(all(index <= n, col.nrows() == n + 1));
+
+		let mut L = self.to_packed(n + 1);
+		let mut col = col.to_owned();
+
+		linalg::cholesky::ldlt::update::insert_row_col_clobber(
+			L.as_mut(),
+			index,
+			col.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::cholesky::ldlt::update::insert_row_col_scratch::<T>(
+				n + 1,
+				par,
+			))),
+		)?;
+
+		*self = Self::from_packed(L, None);
+		Ok(())
+	}
+
+	/// updates the decomposition to that of the matrix obtained by removing the row and the column
+	/// at `index`
+	///
+	/// returns an error if a pivot of the new matrix is zero, in which case `self` is left
+	/// unchanged
+	#[track_caller]
+	pub fn remove_row_col(&mut self, index: usize) -> Result<(), LdltError> {
+		let par = get_global_parallelism();
+		let n = self.L.nrows();
+		assert!(index < n);
+
+		let mut L = self.to_packed(n);
+		linalg::cholesky::ldlt::update::delete_row_col_clobber(
+			L.as_mut(),
+			index,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::cholesky::ldlt::update::delete_row_col_scratch::<T>(n, par))),
+		)?;
+		L.truncate(n - 1, n - 1);
+
+		*self = Self::from_packed(L, None);
+		Ok(())
+	}
 }
 
 impl<T: ComplexField> Lblt<T> {
@@ -861,6 +1496,7 @@ impl<T: ComplexField> Lblt<T> {
 		let par = get_global_parallelism();
 
 		let n = L.nrows();
+		let A_norm1 = self_adjoint_norm1(L.as_ref());
 
 		let mut diag = Diag::zeros(n);
 		let mut subdiag = Diag::zeros(n);
@@ -894,6 +1530,7 @@ impl<T: ComplexField> Lblt<T> {
 			B_diag: diag,
 			B_subdiag: subdiag,
 			P: unsafe { Perm::new_unchecked(perm_fwd.into_boxed_slice(), perm_bwd.into_boxed_slice()) },
+			A_norm1,
 		}
 	}
 
@@ -916,6 +1553,15 @@ impl<T: ComplexField> Lblt<T> {
 	pub fn P(&self) -> PermRef<'_, usize> {
 		self.P.as_ref()
 	}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// see [`Llt::rcond`]
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		rcond_imp(self, copy(&self.A_norm1))
+	}
 }
 
 fn split_LU<T: ComplexField>(LU: Mat<T>) -> (Mat<T>, Mat<T>) {
@@ -959,6 +1605,7 @@ impl<T: ComplexField> PartialPivLu<T> {
 		let par = get_global_parallelism();
 
 		let (m, n) = LU.shape();
+		let A_norm1 = linalg::matfun::norm1(LU.as_ref());
 		let mut row_perm_fwd = vec![0usize; m];
 		let mut row_perm_bwd = vec![0usize; m];
 
@@ -979,6 +1626,7 @@ impl<T: ComplexField> PartialPivLu<T> {
 			L,
 			U,
 			P: unsafe { Perm::new_unchecked(row_perm_fwd.into_boxed_slice(), row_perm_bwd.into_boxed_slice()) },
+			A_norm1,
 		}
 	}
 
@@ -996,7 +1644,16 @@ impl<T: ComplexField> PartialPivLu<T> {
 	pub fn P(&self) -> PermRef<'_, usize> {
 		self.P.as_ref()
 	}
-}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// see [`Llt::rcond`]
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		rcond_imp(self, copy(&self.A_norm1))
+	}
+}
 
 impl<T: ComplexField> FullPivLu<T> {
 	/// returns the $LU$ decomposition of $A$ with full pivoting
@@ -1011,6 +1668,7 @@ impl<T: ComplexField> FullPivLu<T> {
 		let par = get_global_parallelism();
 
 		let (m, n) = LU.shape();
+		let A_norm1 = linalg::matfun::norm1(LU.as_ref());
 		let mut row_perm_fwd = vec![0usize; m];
 		let mut row_perm_bwd = vec![0usize; m];
 		let mut col_perm_fwd = vec![0usize; n];
@@ -1039,6 +1697,7 @@ impl<T: ComplexField> FullPivLu<T> {
 			U,
 			P: unsafe { Perm::new_unchecked(row_perm_fwd.into_boxed_slice(), row_perm_bwd.into_boxed_slice()) },
 			Q: unsafe { Perm::new_unchecked(col_perm_fwd.into_boxed_slice(), col_perm_bwd.into_boxed_slice()) },
+			A_norm1,
 		}
 	}
 
@@ -1061,6 +1720,15 @@ impl<T: ComplexField> FullPivLu<T> {
 	pub fn Q(&self) -> PermRef<'_, usize> {
 		self.Q.as_ref()
 	}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// see [`Llt::rcond`]
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		rcond_imp(self, copy(&self.A_norm1))
+	}
 }
 
 impl<T: ComplexField> Qr<T> {
@@ -1077,6 +1745,7 @@ impl<T: ComplexField> Qr<T> {
 
 		let (m, n) = QR.shape();
 		let size = Ord::min(m, n);
+		let A_norm1 = linalg::matfun::norm1(QR.as_ref());
 
 		let blocksize = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(m, n);
 		let mut Q_coeff = Mat::zeros(blocksize, size);
@@ -1097,7 +1766,18 @@ impl<T: ComplexField> Qr<T> {
 
 		let (Q_basis, R) = split_LU(QR);
 
-		Self { Q_basis, Q_coeff, R }
+		Self {
+			Q_basis,
+			Q_coeff,
+			R,
+			A_norm1,
+		}
+	}
+
+	/// returns a copy of the decomposition with an explicitly stored factor $Q$, which can be
+	/// updated efficiently
+	pub fn to_updatable(&self) -> UpdatableQr<T> {
+		UpdatableQr::from_qr(self)
 	}
 
 	/// returns the householder basis of $Q$
@@ -1159,6 +1839,19 @@ impl<T: ComplexField> Qr<T> {
 		);
 		Q
 	}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// see [`Llt::rcond`]
+	///
+	/// # panics
+	///
+	/// panics if $A$ is not square
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		rcond_imp(self, copy(&self.A_norm1))
+	}
 }
 
 impl<T: ComplexField> ColPivQr<T> {
@@ -1175,6 +1868,7 @@ impl<T: ComplexField> ColPivQr<T> {
 
 		let (m, n) = QR.shape();
 		let size = Ord::min(m, n);
+		let A_norm1 = linalg::matfun::norm1(QR.as_ref());
 
 		let mut col_perm_fwd = vec![0usize; n];
 		let mut col_perm_bwd = vec![0usize; n];
@@ -1205,6 +1899,7 @@ impl<T: ComplexField> ColPivQr<T> {
 			Q_coeff,
 			R,
 			P: unsafe { Perm::new_unchecked(col_perm_fwd.into_boxed_slice(), col_perm_bwd.into_boxed_slice()) },
+			A_norm1,
 		}
 	}
 
@@ -1272,6 +1967,458 @@ impl<T: ComplexField> ColPivQr<T> {
 	pub fn P(&self) -> PermRef<'_, usize> {
 		self.P.as_ref()
 	}
+
+	/// returns an estimate of the reciprocal of the condition number of $A$ in the $1$-norm,
+	/// $(\|A\|_1 \|A^{-1}\|_1)^{-1}$
+	///
+	/// see [`Llt::rcond`]
+	///
+	/// # panics
+	///
+	/// panics if $A$ is not square
+	#[track_caller]
+	pub fn rcond(&self) -> T::Real {
+		rcond_imp(self, copy(&self.A_norm1))
+	}
+}
+
+impl<T: ComplexField> CompleteOrthogonal<T> {
+	/// returns the complete orthogonal decomposition of $A$
+	///
+	/// the numerical rank is determined with the relative tolerance $\max(m, n) \varepsilon$
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Self {
+		let (m, n) = A.shape();
+		Self::new_with_tolerance(A, linalg::qr::complete_orthogonal::factor::default_rank_tolerance::<T>(m, n))
+	}
+
+	/// returns the complete orthogonal decomposition of $A$
+	///
+	/// the numerical rank is the number of diagonal elements of the factor $R$ of the $QR$
+	/// decomposition of $A$ with column pivoting such that $|R_{ii}| > \text{tol} \cdot |R_{00}|$
+	#[track_caller]
+	pub fn new_with_tolerance<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>, tol: T::Real) -> Self {
+		let par = get_global_parallelism();
+		let qr = ColPivQr::new(A);
+
+		let n = qr.ncols();
+		let rank = linalg::qr::complete_orthogonal::factor::numerical_rank(qr.R(), tol);
+
+		let blocksize = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, rank);
+		let mut Z_basis = Mat::zeros(n, rank);
+		let mut Z_coeff = Mat::zeros(blocksize, rank);
+
+		linalg::qr::complete_orthogonal::factor::rz(
+			qr.R().get(..rank, ..),
+			Z_basis.as_mut(),
+			Z_coeff.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::qr::complete_orthogonal::factor::rz_scratch::<T>(
+				rank,
+				n,
+				blocksize,
+				par,
+				default(),
+			))),
+			default(),
+		);
+
+		let mut L = Mat::zeros(rank, rank);
+		L.copy_from_triangular_lower(Z_basis.get(..rank, ..rank).adjoint());
+
+		Self { qr, Z_basis, Z_coeff, L }
+	}
+
+	/// returns the numerical rank $r$ of $A$
+	pub fn rank(&self) -> usize {
+		self.L.nrows()
+	}
+
+	/// returns the $QR$ decomposition with column pivoting that the decomposition is built on
+	pub fn col_piv_qr(&self) -> &ColPivQr<T> {
+		&self.qr
+	}
+
+	/// returns the householder basis of $Q$
+	pub fn Q_basis(&self) -> MatRef<'_, T> {
+		self.qr.Q_basis()
+	}
+
+	/// returns the householder coefficients of $Q$
+	pub fn Q_coeff(&self) -> MatRef<'_, T> {
+		self.qr.Q_coeff()
+	}
+
+	/// returns the factor $L$
+	pub fn L(&self) -> MatRef<'_, T> {
+		self.L.as_ref()
+	}
+
+	/// returns the householder basis of $Z$
+	pub fn Z_basis(&self) -> MatRef<'_, T> {
+		self.Z_basis.as_ref()
+	}
+
+	/// returns the householder coefficients of $Z$
+	pub fn Z_coeff(&self) -> MatRef<'_, T> {
+		self.Z_coeff.as_ref()
+	}
+
+	/// returns the column pivoting permutation $P$
+	pub fn P(&self) -> PermRef<'_, usize> {
+		self.qr.P()
+	}
+
+	/// computes the factor $Q$
+	pub fn compute_Q(&self) -> Mat<T> {
+		self.qr.compute_Q()
+	}
+
+	/// computes the factor $Z$
+	pub fn compute_Z(&self) -> Mat<T> {
+		let n = self.ncols();
+		let mut Z = Mat::identity(n, n);
+		let par = get_global_parallelism();
+		linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+			self.Z_basis(),
+			self.Z_coeff(),
+			Conj::No,
+			Z.rb_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(
+				linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(n, self.Z_coeff.nrows(), n),
+			)),
+		);
+		Z
+	}
+
+	/// returns the minimum norm solution of the least squares problem $\min_X \|AX - B\|$,
+	/// where $B$ is given by `rhs`
+	///
+	/// unlike [`SolveLstsqCore::solve_lstsq_in_place_with_conj`], $A$ may have fewer rows than columns
+	#[track_caller]
+	pub fn solve_lstsq_min_norm(&self, rhs: impl AsMatRef<T = T, Rows = usize, Cols = usize>) -> Mat<T> {
+		let rhs = rhs.as_mat_ref();
+		let (m, n) = (self.nrows(), self.ncols());
+		assert!(rhs.nrows() == m);
+
+		let mut out = Mat::zeros(Ord::max(m, n), rhs.ncols());
+		out.get_mut(..m, ..).copy_from(rhs);
+		self.solve_lstsq_min_norm_in_place_with_conj(Conj::No, out.as_mut());
+		out.truncate(n, rhs.ncols());
+		out
+	}
+
+	#[track_caller]
+	fn solve_lstsq_min_norm_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let m = self.nrows();
+		let n = self.ncols();
+		let k = rhs.ncols();
+
+		linalg::qr::complete_orthogonal::solve::solve_lstsq_in_place_with_conj(
+			self.Q_basis(),
+			self.Q_coeff(),
+			self.Z_basis(),
+			self.Z_coeff(),
+			self.P(),
+			conj,
+			rhs,
+			par,
+			MemStack::new(&mut MemBuffer::new(
+				linalg::qr::complete_orthogonal::solve::solve_lstsq_in_place_scratch::<usize, T>(
+					m,
+					n,
+					self.Q_coeff().nrows(),
+					self.Z_coeff.nrows(),
+					k,
+					par,
+				),
+			)),
+		);
+	}
+}
+
+impl<T: ComplexField> UpdatableQr<T> {
+	/// returns the thin $QR$ decomposition of $A$ with an explicitly stored factor $Q$
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Self {
+		Self::from_qr(&Qr::new(A))
+	}
+
+	/// returns the thin $QR$ decomposition `qr` with an explicitly stored factor $Q$
+	pub fn from_qr(qr: &Qr<T>) -> Self {
+		Self {
+			Q: qr.compute_thin_Q(),
+			R: qr.R().to_owned(),
+		}
+	}
+
+	/// returns the $m \times \min(m, n)$ factor $Q$
+	pub fn Q(&self) -> MatRef<'_, T> {
+		self.Q.as_ref()
+	}
+
+	/// returns the $\min(m, n) \times n$ factor $R$
+	pub fn R(&self) -> MatRef<'_, T> {
+		self.R.as_ref()
+	}
+
+	/// updates the decomposition of $A$ to that of $A + u v^H$
+	#[track_caller]
+	pub fn rank_one_update(&mut self, u: ColRef<'_, T>, v: ColRef<'_, T>) {
+		let par = get_global_parallelism();
+		let (m, n) = (self.nrows(), self.ncols());
+		linalg::qr::no_pivoting::update::rank_one_update(
+			self.Q.as_mut(),
+			self.R.as_mut(),
+			u,
+			v,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::qr::no_pivoting::update::rank_one_update_scratch::<T>(
+				m, n, par,
+			))),
+		);
+	}
+
+	/// updates the decomposition to that of the matrix obtained by inserting `col` before the
+	/// column `j` of $A$, or after its last column if `j` is equal to $n$
+	#[track_caller]
+	pub fn insert_col(&mut self, j: usize, col: ColRef<'_, T>) {
+		let par = get_global_parallelism();
+		let (m, n) = (self.nrows(), self.ncols());
+		assert!(all(j <= n, col.nrows() == m));
+
+		let k = Ord::min(m, n);
+		let size = Ord::min(m, n + 1);
+		let mut Q = Mat::zeros(m, size);
+		let mut R = Mat::zeros(size, n + 1);
+		Q.get_mut(.., ..k).copy_from(&self.Q);
+		R.get_mut(..k, ..n).copy_from(&self.R);
+
+		linalg::qr::no_pivoting::update::insert_col(
+			Q.as_mut(),
+			R.as_mut(),
+			j,
+			col,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::qr::no_pivoting::update::insert_col_scratch::<T>(m, n, par))),
+		);
+		(self.Q, self.R) = (Q, R);
+	}
+
+	/// updates the decomposition to that of the matrix obtained by deleting the column `j` of $A$
+	#[track_caller]
+	pub fn delete_col(&mut self, j: usize) {
+		let (m, n) = (self.nrows(), self.ncols());
+		assert!(j < n);
+
+		linalg::qr::no_pivoting::update::delete_col(self.Q.as_mut(), self.R.as_mut(), j);
+
+		let size = Ord::min(m, n - 1);
+		self.Q.truncate(m, size);
+		self.R.truncate(size, n - 1);
+	}
+
+	/// updates the decomposition to that of the matrix obtained by inserting `row` before the row
+	/// `i` of $A$, or after its last row if `i` is equal to $m$
+	#[track_caller]
+	pub fn insert_row(&mut self, i: usize, row: RowRef<'_, T>) {
+		let (m, n) = (self.nrows(), self.ncols());
+		assert!(all(i <= m, row.ncols() == n));
+
+		let k = Ord::min(m, n);
+		let mut Q = Mat::zeros(m + 1, k + 1);
+		let mut R = Mat::zeros(k + 1, n);
+		Q.get_mut(..m, ..k).copy_from(&self.Q);
+		R.get_mut(..k, ..).copy_from(&self.R);
+
+		linalg::qr::no_pivoting::update::insert_row(Q.as_mut(), R.as_mut(), i, row);
+
+		let size = Ord::min(m + 1, n);
+		Q.truncate(m + 1, size);
+		R.truncate(size, n);
+		(self.Q, self.R) = (Q, R);
+	}
+
+	/// updates the decomposition to that of the matrix obtained by deleting the row `i` of $A$
+	#[track_caller]
+	pub fn delete_row(&mut self, i: usize) {
+		let par = get_global_parallelism();
+		let (m, n) = (self.nrows(), self.ncols());
+		assert!(i < m);
+
+		linalg::qr::no_pivoting::update::delete_row(
+			self.Q.as_mut(),
+			self.R.as_mut(),
+			i,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::qr::no_pivoting::update::delete_row_scratch::<T>(m, n, par))),
+		);
+
+		let size = Ord::min(m - 1, n);
+		self.Q.truncate(m - 1, size);
+		self.R.truncate(size, n);
+	}
+}
+
+impl<T: MixedPrecision> MixedPrecisionLu<T> {
+	/// returns the low precision $LU$ decomposition of $A$ with partial pivoting
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Self {
+		assert!(A.nrows() == A.ncols());
+		let A = A.to_owned();
+		let lu = Mat::from_fn(A.nrows(), A.ncols(), |i, j| T::to_low(&A[(i, j)])).partial_piv_lu();
+		Self { A, lu, fallback: None }
+	}
+
+	/// returns the matrix $A$
+	pub fn A(&self) -> MatRef<'_, T> {
+		self.A.as_ref()
+	}
+
+	/// returns the low precision decomposition
+	pub fn low(&self) -> &PartialPivLu<T::Low> {
+		&self.lu
+	}
+
+	/// solves $A X = B$ in place, where $B$ is given by `rhs`, and returns information about the
+	/// refinement
+	///
+	/// if the refinement does not converge, the system is solved with a working precision $LU$
+	/// decomposition of $A$ instead, which is computed on the first fallback and kept for later
+	/// solves
+	#[track_caller]
+	pub fn solve_in_place(&mut self, rhs: MatMut<'_, T>) -> RefinementInfo<T::Real> {
+		let Self { A, lu, fallback } = self;
+		refine_or_fallback(A.as_ref(), rhs, &mut |d| lu.solve_in_place(d), &mut |rhs| {
+			fallback.get_or_insert_with(|| A.partial_piv_lu()).solve_in_place(rhs)
+		})
+	}
+
+	/// solves $A X = B$, and returns the solution along with information about the refinement
+	///
+	/// see [`Self::solve_in_place`]
+	#[track_caller]
+	pub fn solve(&mut self, rhs: impl AsMatRef<T = T, Rows = usize, Cols = usize>) -> (Mat<T>, RefinementInfo<T::Real>) {
+		let mut out = rhs.as_mat_ref().to_owned();
+		let info = self.solve_in_place(out.as_mut());
+		(out, info)
+	}
+}
+
+impl<T: MixedPrecision> MixedPrecisionLlt<T> {
+	/// returns the low precision $L L^\top$ decomposition of $A$, assuming it is self-adjoint
+	/// positive definite
+	///
+	/// if the low precision decomposition fails, solves are performed with a working precision
+	/// decomposition instead. an error is returned if that decomposition also fails
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>, side: Side) -> Result<Self, LltError> {
+		assert!(A.nrows() == A.ncols());
+		let n = A.nrows();
+
+		let mut L = Mat::zeros(n, n);
+		match side {
+			Side::Lower => L.copy_from_triangular_lower(A),
+			Side::Upper => L.copy_from_triangular_lower(A.adjoint()),
+		}
+		let A = Mat::from_fn(n, n, |i, j| if i >= j { L[(i, j)].clone() } else { math_utils::conj(&L[(j, i)]) });
+
+		let (llt, fallback) = match Mat::from_fn(n, n, |i, j| T::to_low(&A[(i, j)])).llt(Side::Lower) {
+			Ok(llt) => (Some(llt), None),
+			Err(_) => (None, Some(SelfAdjointFallback::Llt(A.llt(Side::Lower)?))),
+		};
+
+		Ok(Self { A, llt, fallback })
+	}
+
+	/// returns the matrix $A$
+	pub fn A(&self) -> MatRef<'_, T> {
+		self.A.as_ref()
+	}
+
+	/// returns the low precision decomposition, if it succeeded
+	pub fn low(&self) -> Option<&Llt<T::Low>> {
+		self.llt.as_ref()
+	}
+
+	/// solves $A X = B$ in place, where $B$ is given by `rhs`, and returns information about the
+	/// refinement
+	///
+	/// if the refinement does not converge, the system is solved with a working precision
+	/// $L L^\top$ decomposition of $A$ instead, which is computed on the first fallback and kept
+	/// for later solves
+	#[track_caller]
+	pub fn solve_in_place(&mut self, rhs: MatMut<'_, T>) -> RefinementInfo<T::Real> {
+		let Self { A, llt, fallback } = self;
+		let mut fallback = |rhs: MatMut<'_, T>| {
+			let fallback = fallback.get_or_insert_with(|| match A.llt(Side::Lower) {
+				Ok(llt) => SelfAdjointFallback::Llt(llt),
+				Err(_) => SelfAdjointFallback::Lblt(A.lblt(Side::Lower)),
+			});
+			match fallback {
+				SelfAdjointFallback::Llt(llt) => llt.solve_in_place(rhs),
+				SelfAdjointFallback::Lblt(lblt) => lblt.solve_in_place(rhs),
+			}
+		};
+
+		match llt {
+			Some(llt) => refine_or_fallback(A.as_ref(), rhs, &mut |d| llt.solve_in_place(d), &mut fallback),
+			None => {
+				fallback(rhs);
+				RefinementInfo {
+					iterations: 0,
+					backward_error: zero(),
+					converged: false,
+					fallback: true,
+				}
+			},
+		}
+	}
+
+	/// solves $A X = B$, and returns the solution along with information about the refinement
+	///
+	/// see [`Self::solve_in_place`]
+	#[track_caller]
+	pub fn solve(&mut self, rhs: impl AsMatRef<T = T, Rows = usize, Cols = usize>) -> (Mat<T>, RefinementInfo<T::Real>) {
+		let mut out = rhs.as_mat_ref().to_owned();
+		let info = self.solve_in_place(out.as_mut());
+		(out, info)
+	}
+}
+
+#[track_caller]
+fn refine_or_fallback<T: MixedPrecision>(
+	A: MatRef<'_, T>,
+	rhs: MatMut<'_, T>,
+	solve_low: &mut dyn FnMut(MatMut<'_, T::Low>),
+	fallback: &mut dyn FnMut(MatMut<'_, T>),
+) -> RefinementInfo<T::Real> {
+	let par = get_global_parallelism();
+	let n = A.nrows();
+	let k = rhs.ncols();
+	let mut rhs = rhs;
+
+	let mut x = Mat::zeros(n, k);
+	let mut info = linalg::refinement::refine(
+		A,
+		x.as_mut(),
+		rhs.rb(),
+		solve_low,
+		par,
+		MemStack::new(&mut MemBuffer::new(linalg::refinement::refine_scratch::<T>(n, k))),
+		default(),
+	);
+
+	if info.converged {
+		rhs.copy_from(&x);
+	} else {
+		fallback(rhs);
+		info.fallback = true;
+	}
+	info
 }
 
 impl<T: ComplexField> Svd<T> {
@@ -1342,39 +2489,36 @@ impl<T: ComplexField> Svd<T> {
 	}
 }
 
-impl<T: ComplexField> SelfAdjointEigen<T> {
-	/// returns the eigendecomposition of $A$, assuming it is self-adjoint
+impl<T: ComplexField> Polar<T> {
+	/// returns the polar decomposition of $A$, computed with the qdwh iteration
 	#[track_caller]
-	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>, side: Side) -> Result<Self, EvdError> {
-		assert!(A.nrows() == A.ncols());
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Result<Self, PolarError> {
+		Self::new_imp(A.canonical(), Conj::get::<C>(), linalg::polar::PolarMethod::Qdwh)
+	}
 
-		match side {
-			Side::Lower => Self::new_imp(A.canonical(), Conj::get::<C>()),
-			Side::Upper => Self::new_imp(A.adjoint().canonical(), Conj::get::<C::Conj>()),
-		}
+	/// returns the polar decomposition of $A$, computed from its svd
+	#[track_caller]
+	pub fn new_from_svd<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Result<Self, PolarError> {
+		Self::new_imp(A.canonical(), Conj::get::<C>(), linalg::polar::PolarMethod::Svd)
 	}
 
 	#[track_caller]
-	fn new_imp(A: MatRef<'_, T>, conj: Conj) -> Result<Self, EvdError> {
+	fn new_imp(A: MatRef<'_, T>, conj: Conj, method: linalg::polar::PolarMethod) -> Result<Self, PolarError> {
 		let par = get_global_parallelism();
+		let (m, n) = A.shape();
 
-		let n = A.nrows();
+		let mut U = Mat::zeros(m, n);
+		let mut H = Mat::zeros(n, n);
 
-		let mut U = Mat::zeros(n, n);
-		let mut S = Diag::zeros(n);
+		let params = linalg::polar::PolarParams { method, ..auto!(T) };
 
-		linalg::evd::self_adjoint_evd(
+		linalg::polar::polar(
 			A,
-			S.as_mut(),
-			Some(U.as_mut()),
+			U.as_mut(),
+			Some(H.as_mut()),
 			par,
-			MemStack::new(&mut MemBuffer::new(linalg::evd::self_adjoint_evd_scratch::<T>(
-				n,
-				linalg::evd::ComputeEigenvectors::Yes,
-				par,
-				default(),
-			))),
-			default(),
+			MemStack::new(&mut MemBuffer::new(linalg::polar::polar_scratch::<T>(m, n, par, params.into()))),
+			params.into(),
 		)?;
 
 		if conj == Conj::Yes {
@@ -1383,9 +2527,14 @@ impl<T: ComplexField> SelfAdjointEigen<T> {
 					*x = math_utils::conj(x);
 				}
 			}
+			for c in H.col_iter_mut() {
+				for x in c.iter_mut() {
+					*x = math_utils::conj(x);
+				}
+			}
 		}
 
-		Ok(Self { U, S })
+		Ok(Self { U, H })
 	}
 
 	/// returns the factor $U$
@@ -1393,43 +2542,40 @@ impl<T: ComplexField> SelfAdjointEigen<T> {
 		self.U.as_ref()
 	}
 
-	/// returns the factor $S$
-	pub fn S(&self) -> DiagRef<'_, T> {
-		self.S.as_ref()
+	/// returns the factor $H$
+	pub fn H(&self) -> MatRef<'_, T> {
+		self.H.as_ref()
 	}
 }
 
-impl<T: RealField> Eigen<T> {
-	/// returns the eigendecomposition of $A$
+impl<T: ComplexField> SelfAdjointEigen<T> {
+	/// returns the eigendecomposition of $A$, assuming it is self-adjoint
 	#[track_caller]
-	pub fn new<C: Conjugate<Canonical = Complex<T>>>(A: MatRef<'_, C>) -> Result<Self, EvdError> {
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>, side: Side) -> Result<Self, EvdError> {
 		assert!(A.nrows() == A.ncols());
-		Self::new_imp(A.canonical(), Conj::get::<C>())
+
+		match side {
+			Side::Lower => Self::new_imp(A.canonical(), Conj::get::<C>()),
+			Side::Upper => Self::new_imp(A.adjoint().canonical(), Conj::get::<C::Conj>()),
+		}
 	}
 
-	/// returns the eigendecomposition of $A$
 	#[track_caller]
-	pub fn new_from_real(A: MatRef<'_, T>) -> Result<Self, EvdError> {
-		assert!(A.nrows() == A.ncols());
-
+	fn new_imp(A: MatRef<'_, T>, conj: Conj) -> Result<Self, EvdError> {
 		let par = get_global_parallelism();
 
 		let n = A.nrows();
 
-		let mut U_real = Mat::zeros(n, n);
-		let mut S_re = Diag::zeros(n);
-		let mut S_im = Diag::zeros(n);
+		let mut U = Mat::zeros(n, n);
+		let mut S = Diag::zeros(n);
 
-		linalg::evd::evd_real(
+		linalg::evd::self_adjoint_evd(
 			A,
-			S_re.as_mut(),
-			S_im.as_mut(),
-			None,
-			Some(U_real.as_mut()),
+			S.as_mut(),
+			Some(U.as_mut()),
 			par,
-			MemStack::new(&mut MemBuffer::new(linalg::evd::evd_scratch::<T>(
+			MemStack::new(&mut MemBuffer::new(linalg::evd::self_adjoint_evd_scratch::<T>(
 				n,
-				linalg::evd::ComputeEigenvectors::No,
 				linalg::evd::ComputeEigenvectors::Yes,
 				par,
 				default(),
@@ -1437,36 +2583,223 @@ impl<T: RealField> Eigen<T> {
 			default(),
 		)?;
 
-		let mut U = Mat::zeros(n, n);
-		let mut S = Diag::zeros(n);
+		if conj == Conj::Yes {
+			for c in U.col_iter_mut() {
+				for x in c.iter_mut() {
+					*x = math_utils::conj(x);
+				}
+			}
+		}
 
-		let mut j = 0;
-		while j < n {
-			if S_im[j] == zero() {
-				S[j] = Complex::new(S_re[j].clone(), zero());
+		Ok(Self { U, S })
+	}
 
-				for i in 0..n {
-					U[(i, j)] = Complex::new(U_real[(i, j)].clone(), zero());
-				}
+	/// returns the factor $U$
+	pub fn U(&self) -> MatRef<'_, T> {
+		self.U.as_ref()
+	}
 
-				j += 1;
-			} else {
-				S[j] = Complex::new(S_re[j].clone(), S_im[j].clone());
-				S[j + 1] = Complex::new(S_re[j].clone(), neg(&S_im[j]));
+	/// returns the factor $S$
+	pub fn S(&self) -> DiagRef<'_, T> {
+		self.S.as_ref()
+	}
 
-				for i in 0..n {
-					U[(i, j)] = Complex::new(U_real[(i, j)].clone(), U_real[(i, j + 1)].clone());
-					U[(i, j + 1)] = Complex::new(U_real[(i, j)].clone(), neg(&U_real[(i, j + 1)]));
-				}
+	/// returns $f(A) = U f(S) U^H$, where the real scalar function $f$ is applied to each
+	/// eigenvalue
+	#[track_caller]
+	pub fn apply_fn(&self, f: impl FnMut(T::Real) -> T::Real) -> Mat<T> {
+		let par = get_global_parallelism();
+		let n = self.U.nrows();
 
-				j += 2;
+		let mut out = Mat::zeros(n, n);
+		linalg::evd::self_adjoint_fn_from_evd(
+			out.as_mut(),
+			self.S.column_vector(),
+			self.U.as_ref(),
+			f,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::evd::self_adjoint_fn_from_evd_scratch::<T>(n, par))),
+		);
+		out
+	}
+
+	/// returns the principal square root $A^{1/2}$, assuming $A$ is positive semidefinite
+	pub fn sqrt(&self) -> Mat<T> {
+		self.apply_fn(|x| math_utils::sqrt(&x))
+	}
+
+	/// returns the inverse square root $A^{-1/2}$, assuming $A$ is positive definite
+	pub fn inv_sqrt(&self) -> Mat<T> {
+		self.apply_fn(|x| math_utils::recip(&math_utils::sqrt(&x)))
+	}
+
+	/// returns the principal logarithm $\log A$, assuming $A$ is positive definite
+	pub fn log(&self) -> Mat<T> {
+		self.apply_fn(linalg::matfun::log_real)
+	}
+
+	/// returns the exponential $e^A$
+	pub fn exp(&self) -> Mat<T> {
+		self.apply_fn(linalg::matfun::exp_real)
+	}
+}
+
+impl<T: ComplexField> PartialSelfAdjointEigen<T> {
+	/// returns the eigenpairs of $A$ given by `selection`, assuming it is self-adjoint
+	///
+	/// when `selection` is an interval, the eigenvalues it contains are counted after the reduction
+	/// to tridiagonal form, so that only storage for the selected eigenpairs is allocated
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>, side: Side, selection: EigenvalueSelection<T::Real>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+
+		match side {
+			Side::Lower => Self::new_imp(A.canonical(), Conj::get::<C>(), selection),
+			Side::Upper => Self::new_imp(A.adjoint().canonical(), Conj::get::<C::Conj>(), selection),
+		}
+	}
+
+	#[track_caller]
+	fn new_imp(A: MatRef<'_, T>, conj: Conj, selection: EigenvalueSelection<T::Real>) -> Result<Self, EvdError> {
+		let par = get_global_parallelism();
+
+		let n = A.nrows();
+		let params: Spec<linalg::evd::SelfAdjointEvdParams, T> = default();
+
+		let (mut U, S) = match selection {
+			EigenvalueSelection::Interval(a, b) if n > 0 => {
+				// the number of eigenvalues in the interval is only known after the reduction to
+				// tridiagonal form, so the eigenpairs are allocated in between
+				let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+				let mut trid = Mat::zeros(n, n);
+				let mut householder = Mat::zeros(bs, n - 1);
+				let mut diag = Col::<T::Real>::zeros(n);
+				let mut offdiag = Col::<T::Real>::zeros(n);
+				let mut diag = diag.as_mut().try_as_col_major_mut().unwrap();
+				let mut offdiag = offdiag.as_mut().try_as_col_major_mut().unwrap();
+
+				linalg::evd::self_adjoint_to_real_tridiag(
+					A,
+					trid.as_mut(),
+					householder.as_mut(),
+					diag.rb_mut(),
+					offdiag.rb_mut(),
+					par,
+					MemStack::new(&mut MemBuffer::new(linalg::evd::tridiag::tridiag_in_place_scratch::<T>(
+						n,
+						par,
+						params.tridiag.into(),
+					))),
+					params.config,
+				);
+
+				let (lo, hi) = linalg::evd::interval_index_range(diag.rb(), offdiag.rb(), a, b);
+				let count = hi - lo;
+
+				let mut U = Mat::zeros(n, count);
+				let mut S = Col::zeros(count);
+				linalg::evd::evd_select_from_real_tridiag(
+					trid.as_ref(),
+					householder.as_ref(),
+					diag.rb(),
+					offdiag.rb(),
+					lo,
+					S.as_mut(),
+					Some(U.as_mut()),
+					par,
+					MemStack::new(&mut MemBuffer::new(linalg::evd::evd_select_from_real_tridiag_scratch::<T>(
+						n,
+						count,
+						ComputeEigenvectors::Yes,
+					))),
+				)?;
+				(U, S)
+			},
+			selection => {
+				let count = match &selection {
+					EigenvalueSelection::All | EigenvalueSelection::Interval(..) => n,
+					EigenvalueSelection::Indices(range) => range.end.saturating_sub(range.start),
+				};
+
+				let mut U = Mat::zeros(n, count);
+				let mut S = Col::zeros(count);
+				linalg::evd::self_adjoint_evd_select(
+					A,
+					selection,
+					S.as_mut().as_diagonal_mut(),
+					Some(U.as_mut()),
+					par,
+					MemStack::new(&mut MemBuffer::new(linalg::evd::self_adjoint_evd_select_scratch::<T>(
+						n,
+						count,
+						ComputeEigenvectors::Yes,
+						par,
+						params,
+					))),
+					params,
+				)?;
+				(U, S)
+			},
+		};
+
+		if conj == Conj::Yes {
+			for c in U.col_iter_mut() {
+				for x in c.iter_mut() {
+					*x = math_utils::conj(x);
+				}
 			}
 		}
 
-		Ok(Self { U, S })
+		Ok(Self { U, S: S.into_diagonal() })
 	}
 
-	fn new_imp(A: MatRef<'_, Complex<T>>, conj: Conj) -> Result<Self, EvdError> {
+	/// returns the eigenvectors $U$, of shape $(n, k)$
+	pub fn U(&self) -> MatRef<'_, T> {
+		self.U.as_ref()
+	}
+
+	/// returns the eigenvalues $S$, sorted in nondecreasing order
+	pub fn S(&self) -> DiagRef<'_, T> {
+		self.S.as_ref()
+	}
+}
+
+impl<T: ComplexField> GeneralizedSelfAdjointEigen<T> {
+	/// returns the eigendecomposition of the generalized eigenproblem given by `problem`, assuming
+	/// $A$ is self-adjoint and $B$ is self-adjoint positive definite
+	#[track_caller]
+	pub fn new<CA: Conjugate<Canonical = T>, CB: Conjugate<Canonical = T>>(
+		A: MatRef<'_, CA>,
+		B: MatRef<'_, CB>,
+		side: Side,
+		problem: SelfAdjointGevdProblem,
+	) -> Result<Self, SelfAdjointGevdError> {
+		assert!(all(A.nrows() == A.ncols(), B.nrows() == A.nrows(), B.ncols() == A.ncols()));
+
+		match side {
+			Side::Lower => Self::new_conj(A, B, problem),
+			Side::Upper => Self::new_conj(A.adjoint(), B.adjoint(), problem),
+		}
+	}
+
+	#[track_caller]
+	fn new_conj<CA: Conjugate<Canonical = T>, CB: Conjugate<Canonical = T>>(
+		A: MatRef<'_, CA>,
+		B: MatRef<'_, CB>,
+		problem: SelfAdjointGevdProblem,
+	) -> Result<Self, SelfAdjointGevdError> {
+		let conj_A = Conj::get::<CA>();
+		let conj_B = Conj::get::<CB>();
+
+		if conj_A == conj_B {
+			Self::new_imp(A.canonical(), B.canonical(), problem, conj_A)
+		} else {
+			Self::new_imp(A.to_owned().as_ref(), B.to_owned().as_ref(), problem, Conj::No)
+		}
+	}
+
+	#[track_caller]
+	fn new_imp(A: MatRef<'_, T>, B: MatRef<'_, T>, problem: SelfAdjointGevdProblem, conj: Conj) -> Result<Self, SelfAdjointGevdError> {
 		let par = get_global_parallelism();
 
 		let n = A.nrows();
@@ -1474,16 +2807,16 @@ impl<T: RealField> Eigen<T> {
 		let mut U = Mat::zeros(n, n);
 		let mut S = Diag::zeros(n);
 
-		linalg::evd::evd_cplx(
+		linalg::gevd::self_adjoint_gevd(
 			A,
+			B,
+			problem,
 			S.as_mut(),
-			None,
 			Some(U.as_mut()),
 			par,
-			MemStack::new(&mut MemBuffer::new(linalg::evd::evd_scratch::<Complex<T>>(
+			MemStack::new(&mut MemBuffer::new(linalg::gevd::self_adjoint_gevd_scratch::<T>(
 				n,
-				linalg::evd::ComputeEigenvectors::No,
-				linalg::evd::ComputeEigenvectors::Yes,
+				ComputeEigenvectors::Yes,
 				par,
 				default(),
 			))),
@@ -1501,479 +2834,1518 @@ impl<T: RealField> Eigen<T> {
 		Ok(Self { U, S })
 	}
 
-	/// returns the factor $U$
-	pub fn U(&self) -> MatRef<'_, Complex<T>> {
+	/// returns the eigenvectors $U$, normalized so that $U^H B U = I$ (or $U^H B^{-1} U = I$ for
+	/// [`SelfAdjointGevdProblem::BAxLambdaX`])
+	pub fn U(&self) -> MatRef<'_, T> {
 		self.U.as_ref()
 	}
 
-	/// returns the factor $S$
-	pub fn S(&self) -> DiagRef<'_, Complex<T>> {
+	/// returns the eigenvalues $S$, sorted in nondecreasing order
+	pub fn S(&self) -> DiagRef<'_, T> {
 		self.S.as_ref()
 	}
 }
 
-impl<T: ComplexField> ShapeCore for Llt<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.L().nrows()
-	}
+impl<T: ComplexField> Gsvd<T> {
+	/// returns the generalized svd of the pair $(A, B)$, such that $A = U C X^H$ and $B = V S X^H$
+	///
+	/// see [`linalg::svd::gsvd::gsvd`] for the layout of $C$ and $S$
+	#[track_caller]
+	pub fn new<CA: Conjugate<Canonical = T>, CB: Conjugate<Canonical = T>>(A: MatRef<'_, CA>, B: MatRef<'_, CB>) -> Result<Self, SvdError> {
+		assert!(A.ncols() == B.ncols());
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.L().ncols()
-	}
-}
-impl<T: ComplexField> ShapeCore for Ldlt<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.L().nrows()
-	}
+		let conj_A = Conj::get::<CA>();
+		let conj_B = Conj::get::<CB>();
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.L().ncols()
-	}
-}
-impl<T: ComplexField> ShapeCore for Lblt<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.L().nrows()
+		if conj_A == conj_B {
+			Self::new_imp(A.canonical(), B.canonical(), conj_A)
+		} else {
+			Self::new_imp(A.to_owned().as_ref(), B.to_owned().as_ref(), Conj::No)
+		}
 	}
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.L().ncols()
-	}
-}
-impl<T: ComplexField> ShapeCore for PartialPivLu<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.L().nrows()
-	}
+	#[track_caller]
+	fn new_imp(A: MatRef<'_, T>, B: MatRef<'_, T>, conj: Conj) -> Result<Self, SvdError> {
+		let par = get_global_parallelism();
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.U().ncols()
-	}
-}
-impl<T: ComplexField> ShapeCore for FullPivLu<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.L().nrows()
+		let m = A.nrows();
+		let p = B.nrows();
+		let n = A.ncols();
+		let q = Ord::min(m + p, n);
+
+		let mut U = Mat::zeros(m, m);
+		let mut V = Mat::zeros(p, p);
+		let mut X = Mat::zeros(n, q);
+		let mut C = Diag::zeros(q);
+		let mut S = Diag::zeros(q);
+
+		linalg::svd::gsvd::gsvd(
+			A,
+			B,
+			C.as_mut(),
+			S.as_mut(),
+			Some(U.as_mut()),
+			Some(V.as_mut()),
+			X.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::svd::gsvd::gsvd_scratch::<T>(m, p, n, par))),
+		)?;
+
+		if conj == Conj::Yes {
+			for M in [&mut U, &mut V, &mut X] {
+				for c in M.col_iter_mut() {
+					for x in c.iter_mut() {
+						*x = math_utils::conj(x);
+					}
+				}
+			}
+		}
+
+		Ok(Self { U, V, X, C, S })
 	}
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.U().ncols()
+	/// returns the factor $U$
+	pub fn U(&self) -> MatRef<'_, T> {
+		self.U.as_ref()
 	}
-}
-impl<T: ComplexField> ShapeCore for Qr<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.Q_basis().nrows()
+
+	/// returns the factor $V$
+	pub fn V(&self) -> MatRef<'_, T> {
+		self.V.as_ref()
 	}
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.R().ncols()
+	/// returns the factor $X$
+	pub fn X(&self) -> MatRef<'_, T> {
+		self.X.as_ref()
 	}
-}
-impl<T: ComplexField> ShapeCore for ColPivQr<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.Q_basis().nrows()
+
+	/// returns the nonzero values of the factor $C$, sorted in nonincreasing order
+	pub fn C(&self) -> DiagRef<'_, T> {
+		self.C.as_ref()
 	}
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.R().ncols()
+	/// returns the nonzero values of the factor $S$, sorted in nondecreasing order
+	pub fn S(&self) -> DiagRef<'_, T> {
+		self.S.as_ref()
 	}
 }
-impl<T: ComplexField> ShapeCore for Svd<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.U().nrows()
+
+impl<T: RealField> Eigen<T> {
+	/// returns the eigendecomposition of $A$
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = Complex<T>>>(A: MatRef<'_, C>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+		Self::new_imp(A.canonical(), Conj::get::<C>(), false)
 	}
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.V().nrows()
+	/// returns the eigendecomposition of $A$
+	#[track_caller]
+	pub fn new_from_real(A: MatRef<'_, T>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+		Self::new_from_real_imp(A, false)
 	}
-}
-impl<T: ComplexField> ShapeCore for SelfAdjointEigen<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.U().nrows()
+
+	/// returns the eigendecomposition of $A$, along with its left eigenvectors and the reciprocal
+	/// condition numbers of its eigenvalues and eigenvectors
+	///
+	/// the left and right eigenvectors are normalized to have unit norm
+	#[track_caller]
+	pub fn new_with_condition<C: Conjugate<Canonical = Complex<T>>>(A: MatRef<'_, C>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+		Self::new_imp(A.canonical(), Conj::get::<C>(), true)
 	}
 
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.U().nrows()
-	}
-}
-impl<T: RealField> ShapeCore for Eigen<T> {
-	#[inline]
-	fn nrows(&self) -> usize {
-		self.U().nrows()
-	}
-
-	#[inline]
-	fn ncols(&self) -> usize {
-		self.U().nrows()
+	/// returns the eigendecomposition of $A$, along with its left eigenvectors and the reciprocal
+	/// condition numbers of its eigenvalues and eigenvectors
+	///
+	/// the left and right eigenvectors are normalized to have unit norm
+	#[track_caller]
+	pub fn new_from_real_with_condition(A: MatRef<'_, T>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+		Self::new_from_real_imp(A, true)
 	}
-}
 
-impl<T: ComplexField> SolveCore<T> for Llt<T> {
-	#[track_caller]
-	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+	fn new_from_real_imp(A: MatRef<'_, T>, condition: bool) -> Result<Self, EvdError> {
 		let par = get_global_parallelism();
 
-		let mut mem = MemBuffer::new(linalg::cholesky::llt::solve::solve_in_place_scratch::<T>(
-			self.L.nrows(),
-			rhs.ncols(),
-			par,
-		));
-		let stack = MemStack::new(&mut mem);
+		let n = A.nrows();
 
-		linalg::cholesky::llt::solve::solve_in_place_with_conj(self.L.as_ref(), conj, rhs, par, stack);
-	}
+		let mut U_real = Mat::zeros(n, n);
+		let mut U_left_real = if condition { Some(Mat::zeros(n, n)) } else { None };
+		let mut S_re = Diag::zeros(n);
+		let mut S_im = Diag::zeros(n);
+		let mut s_cond = Col::zeros(if condition { n } else { 0 });
+		let mut sep = Col::zeros(if condition { n } else { 0 });
 
-	#[track_caller]
-	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+		let eigen_left = if condition {
+			linalg::evd::ComputeEigenvectors::Yes
+		} else {
+			linalg::evd::ComputeEigenvectors::No
+		};
 
-		let mut mem = MemBuffer::new(linalg::cholesky::llt::solve::solve_in_place_scratch::<T>(
-			self.L.nrows(),
-			rhs.ncols(),
-			par,
-		));
-		let stack = MemStack::new(&mut mem);
+		if condition {
+			linalg::evd::evd_real_with_condition(
+				A,
+				S_re.as_mut(),
+				S_im.as_mut(),
+				U_left_real.as_mut().map(|u| u.as_mut()),
+				Some(U_real.as_mut()),
+				s_cond.as_mut(),
+				sep.as_mut(),
+				par,
+				MemStack::new(&mut MemBuffer::new(linalg::evd::evd_with_condition_scratch::<T>(
+					n,
+					eigen_left,
+					linalg::evd::ComputeEigenvectors::Yes,
+					par,
+					default(),
+				))),
+				default(),
+			)?;
+		} else {
+			linalg::evd::evd_real(
+				A,
+				S_re.as_mut(),
+				S_im.as_mut(),
+				None,
+				Some(U_real.as_mut()),
+				par,
+				MemStack::new(&mut MemBuffer::new(linalg::evd::evd_scratch::<T>(
+					n,
+					eigen_left,
+					linalg::evd::ComputeEigenvectors::Yes,
+					par,
+					default(),
+				))),
+				default(),
+			)?;
+		}
 
-		linalg::cholesky::llt::solve::solve_in_place_with_conj(self.L.as_ref(), conj.compose(Conj::Yes), rhs, par, stack);
-	}
-}
+		let mut S = Diag::zeros(n);
+		let mut j = 0;
+		while j < n {
+			if S_im[j] == zero() {
+				S[j] = Complex::new(S_re[j].clone(), zero());
+				j += 1;
+			} else {
+				S[j] = Complex::new(S_re[j].clone(), S_im[j].clone());
+				S[j + 1] = Complex::new(S_re[j].clone(), neg(&S_im[j]));
+				j += 2;
+			}
+		}
 
-#[math]
-fn make_self_adjoint<T: ComplexField>(mut A: MatMut<'_, T>) {
-	assert!(A.nrows() == A.ncols());
-	let n = A.nrows();
-	for j in 0..n {
-		A[(j, j)] = from_real(real(A[(j, j)]));
-		for i in 0..j {
-			A[(i, j)] = conj(A[(j, i)]);
+		// complex conjugate pairs are stored as the real and imaginary parts of the eigenvector
+		// associated with the first eigenvalue
+		let to_cplx = |U_real: &Mat<T>| {
+			let mut U = Mat::zeros(n, n);
+			let mut j = 0;
+			while j < n {
+				if S_im[j] == zero() {
+					for i in 0..n {
+						U[(i, j)] = Complex::new(U_real[(i, j)].clone(), zero());
+					}
+					j += 1;
+				} else {
+					for i in 0..n {
+						U[(i, j)] = Complex::new(U_real[(i, j)].clone(), U_real[(i, j + 1)].clone());
+						U[(i, j + 1)] = Complex::new(U_real[(i, j)].clone(), neg(&U_real[(i, j + 1)]));
+					}
+					j += 2;
+				}
+			}
+			U
+		};
+
+		let mut U = to_cplx(&U_real);
+		let mut U_left = U_left_real.as_ref().map(to_cplx);
+
+		if condition {
+			normalize_columns(U.as_mut());
+			if let Some(U_left) = U_left.as_mut() {
+				normalize_columns(U_left.as_mut());
+			}
 		}
+
+		Ok(Self {
+			U,
+			S,
+			U_left,
+			s_cond: if condition { Some(s_cond) } else { None },
+			sep: if condition { Some(sep) } else { None },
+		})
 	}
-}
 
-impl<T: ComplexField> DenseSolveCore<T> for Llt<T> {
-	#[track_caller]
-	fn reconstruct(&self) -> Mat<T> {
+	fn new_imp(A: MatRef<'_, Complex<T>>, conj: Conj, condition: bool) -> Result<Self, EvdError> {
 		let par = get_global_parallelism();
 
-		let n = self.L.nrows();
-		let mut out = Mat::zeros(n, n);
+		let n = A.nrows();
 
-		let mut mem = MemBuffer::new(linalg::cholesky::llt::reconstruct::reconstruct_scratch::<T>(n, par));
-		let stack = MemStack::new(&mut mem);
+		let mut U = Mat::zeros(n, n);
+		let mut U_left = if condition { Some(Mat::zeros(n, n)) } else { None };
+		let mut S = Diag::zeros(n);
+		let mut s_cond = Col::zeros(if condition { n } else { 0 });
+		let mut sep = Col::zeros(if condition { n } else { 0 });
 
-		linalg::cholesky::llt::reconstruct::reconstruct(out.as_mut(), self.L(), par, stack);
+		if condition {
+			linalg::evd::evd_cplx_with_condition(
+				A,
+				S.as_mut(),
+				U_left.as_mut().map(|u| u.as_mut()),
+				Some(U.as_mut()),
+				s_cond.as_mut(),
+				sep.as_mut(),
+				par,
+				MemStack::new(&mut MemBuffer::new(linalg::evd::evd_with_condition_scratch::<Complex<T>>(
+					n,
+					linalg::evd::ComputeEigenvectors::Yes,
+					linalg::evd::ComputeEigenvectors::Yes,
+					par,
+					default(),
+				))),
+				default(),
+			)?;
 
-		make_self_adjoint(out.as_mut());
-		out
+			normalize_columns(U.as_mut());
+			if let Some(U_left) = U_left.as_mut() {
+				normalize_columns(U_left.as_mut());
+			}
+		} else {
+			linalg::evd::evd_cplx(
+				A,
+				S.as_mut(),
+				None,
+				Some(U.as_mut()),
+				par,
+				MemStack::new(&mut MemBuffer::new(linalg::evd::evd_scratch::<Complex<T>>(
+					n,
+					linalg::evd::ComputeEigenvectors::No,
+					linalg::evd::ComputeEigenvectors::Yes,
+					par,
+					default(),
+				))),
+				default(),
+			)?;
+		}
+
+		if conj == Conj::Yes {
+			for M in core::iter::once(&mut U).chain(U_left.as_mut()) {
+				for c in M.col_iter_mut() {
+					for x in c.iter_mut() {
+						*x = math_utils::conj(x);
+					}
+				}
+			}
+			for x in S.column_vector_mut().iter_mut() {
+				*x = math_utils::conj(x);
+			}
+		}
+
+		Ok(Self {
+			U,
+			S,
+			U_left,
+			s_cond: if condition { Some(s_cond) } else { None },
+			sep: if condition { Some(sep) } else { None },
+		})
 	}
 
-	#[track_caller]
-	fn inverse(&self) -> Mat<T> {
-		let par = get_global_parallelism();
+	/// returns the factor $U$
+	pub fn U(&self) -> MatRef<'_, Complex<T>> {
+		self.U.as_ref()
+	}
 
-		let n = self.L.nrows();
-		let mut out = Mat::zeros(n, n);
+	/// returns the factor $S$
+	pub fn S(&self) -> DiagRef<'_, Complex<T>> {
+		self.S.as_ref()
+	}
 
-		let mut mem = MemBuffer::new(linalg::cholesky::llt::inverse::inverse_scratch::<T>(n, par));
-		let stack = MemStack::new(&mut mem);
+	/// returns the left eigenvectors $Y$, satisfying $Y^H A = S Y^H$, if they were computed
+	pub fn U_left(&self) -> Option<MatRef<'_, Complex<T>>> {
+		self.U_left.as_ref().map(|u| u.as_ref())
+	}
 
-		linalg::cholesky::llt::inverse::inverse(out.as_mut(), self.L(), par, stack);
+	/// returns the reciprocal condition numbers $s_i = |y_i^H x_i|$ of the eigenvalues, if they
+	/// were computed
+	///
+	/// the error in the $i$-th computed eigenvalue is approximately $\varepsilon \|A\| / s_i$
+	pub fn eigenvalue_rcond(&self) -> Option<ColRef<'_, T>> {
+		self.s_cond.as_ref().map(|s| s.as_ref())
+	}
 
-		make_self_adjoint(out.as_mut());
-		out
+	/// returns estimates of the separation of each eigenvalue from the rest of the spectrum, which
+	/// are the reciprocal condition numbers of the eigenvectors, if they were computed
+	///
+	/// the angle between the $i$-th computed and exact eigenvectors is approximately
+	/// $\varepsilon \|A\| / \text{sep}_i$
+	pub fn eigenvector_sep(&self) -> Option<ColRef<'_, T>> {
+		self.sep.as_ref().map(|s| s.as_ref())
 	}
 }
 
-impl<T: ComplexField> SolveCore<T> for Ldlt<T> {
+fn normalize_columns<T: RealField>(U: MatMut<'_, Complex<T>>) {
+	let mut U = U;
+	for j in 0..U.ncols() {
+		let norm = U.rb().col(j).norm_l2();
+		if norm > zero() {
+			let inv = math_utils::recip(&norm);
+			for x in U.rb_mut().col_mut(j).iter_mut() {
+				*x = math_utils::mul_real(x, &inv);
+			}
+		}
+	}
+}
+
+impl<T: RealField> GeneralizedEigen<T> {
+	/// returns the generalized eigendecomposition of the pencil $(A, B)$
+	///
+	/// the left and right eigenvectors are only computed if requested
 	#[track_caller]
-	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+	pub fn new<CA: Conjugate<Canonical = Complex<T>>, CB: Conjugate<Canonical = Complex<T>>>(
+		A: MatRef<'_, CA>,
+		B: MatRef<'_, CB>,
+		eigen_left: ComputeEigenvectors,
+		eigen_right: ComputeEigenvectors,
+	) -> Result<Self, GevdError> {
+		assert!(all(A.nrows() == A.ncols(), B.nrows() == A.nrows(), B.ncols() == A.ncols()));
+
+		if Conj::get::<CA>() == Conj::No && Conj::get::<CB>() == Conj::No {
+			Self::new_imp(A.canonical(), B.canonical(), eigen_left, eigen_right)
+		} else {
+			Self::new_imp(A.to_owned().as_ref(), B.to_owned().as_ref(), eigen_left, eigen_right)
+		}
+	}
 
-		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::solve::solve_in_place_scratch::<T>(
-			self.L.nrows(),
-			rhs.ncols(),
-			par,
-		));
-		let stack = MemStack::new(&mut mem);
+	/// returns the generalized eigendecomposition of the real pencil $(A, B)$
+	///
+	/// the left and right eigenvectors are only computed if requested
+	#[track_caller]
+	pub fn new_from_real(
+		A: MatRef<'_, T>,
+		B: MatRef<'_, T>,
+		eigen_left: ComputeEigenvectors,
+		eigen_right: ComputeEigenvectors,
+	) -> Result<Self, GevdError> {
+		assert!(all(A.nrows() == A.ncols(), B.nrows() == A.nrows(), B.ncols() == A.ncols()));
 
-		linalg::cholesky::ldlt::solve::solve_in_place_with_conj(self.L.as_ref(), self.D.as_ref(), conj, rhs, par, stack);
+		let n = A.nrows();
+		let A = Mat::from_fn(n, n, |i, j| Complex::new(A[(i, j)].clone(), zero()));
+		let B = Mat::from_fn(n, n, |i, j| Complex::new(B[(i, j)].clone(), zero()));
+
+		Self::new_imp(A.as_ref(), B.as_ref(), eigen_left, eigen_right)
 	}
 
 	#[track_caller]
-	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+	fn new_imp(
+		A: MatRef<'_, Complex<T>>,
+		B: MatRef<'_, Complex<T>>,
+		eigen_left: ComputeEigenvectors,
+		eigen_right: ComputeEigenvectors,
+	) -> Result<Self, GevdError> {
 		let par = get_global_parallelism();
 
-		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::solve::solve_in_place_scratch::<T>(
-			self.L.nrows(),
-			rhs.ncols(),
+		let n = A.nrows();
+
+		let mut alpha = Diag::zeros(n);
+		let mut beta = Diag::zeros(n);
+		let mut U_left = if eigen_left == ComputeEigenvectors::Yes {
+			Some(Mat::zeros(n, n))
+		} else {
+			None
+		};
+		let mut U_right = if eigen_right == ComputeEigenvectors::Yes {
+			Some(Mat::zeros(n, n))
+		} else {
+			None
+		};
+
+		linalg::gevd::gevd_cplx(
+			A,
+			B,
+			alpha.as_mut(),
+			beta.as_mut(),
+			U_left.as_mut().map(|u| u.as_mut()),
+			U_right.as_mut().map(|u| u.as_mut()),
 			par,
-		));
-		let stack = MemStack::new(&mut mem);
+			MemStack::new(&mut MemBuffer::new(linalg::gevd::gevd_scratch::<Complex<T>>(
+				n,
+				eigen_left,
+				eigen_right,
+				par,
+			))),
+		)?;
 
-		linalg::cholesky::ldlt::solve::solve_in_place_with_conj(self.L(), self.D(), conj.compose(Conj::Yes), rhs, par, stack);
+		Ok(Self {
+			alpha,
+			beta,
+			U_left,
+			U_right,
+		})
+	}
+
+	/// returns the numerators $\alpha$ of the generalized eigenvalues $\lambda = \alpha / \beta$
+	pub fn alpha(&self) -> DiagRef<'_, Complex<T>> {
+		self.alpha.as_ref()
+	}
+
+	/// returns the denominators $\beta$ of the generalized eigenvalues $\lambda = \alpha / \beta$
+	///
+	/// the denominators are real and non-negative, and are zero for infinite eigenvalues
+	pub fn beta(&self) -> DiagRef<'_, Complex<T>> {
+		self.beta.as_ref()
+	}
+
+	/// returns the left eigenvectors, normalized to have unit norm, if they were computed
+	pub fn U_left(&self) -> Option<MatRef<'_, Complex<T>>> {
+		self.U_left.as_ref().map(|u| u.as_ref())
+	}
+
+	/// returns the right eigenvectors, normalized to have unit norm, if they were computed
+	pub fn U_right(&self) -> Option<MatRef<'_, Complex<T>>> {
+		self.U_right.as_ref().map(|u| u.as_ref())
 	}
 }
 
-impl<T: ComplexField> DenseSolveCore<T> for Ldlt<T> {
+impl<T: RealField> Schur<T> {
+	/// returns the complex schur decomposition of $A$
 	#[track_caller]
-	fn reconstruct(&self) -> Mat<T> {
+	pub fn new<C: Conjugate<Canonical = Complex<T>>>(A: MatRef<'_, C>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+		Self::new_imp(A.canonical(), Conj::get::<C>())
+	}
+
+	#[track_caller]
+	fn new_imp(A: MatRef<'_, Complex<T>>, conj: Conj) -> Result<Self, EvdError> {
 		let par = get_global_parallelism();
 
-		let n = self.L.nrows();
-		let mut out = Mat::zeros(n, n);
+		let n = A.nrows();
 
-		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::reconstruct::reconstruct_scratch::<T>(n, par));
-		let stack = MemStack::new(&mut mem);
+		let mut T = Mat::zeros(n, n);
+		let mut Z = Mat::zeros(n, n);
+		let mut S = Diag::zeros(n);
 
-		linalg::cholesky::ldlt::reconstruct::reconstruct(out.as_mut(), self.L(), self.D(), par, stack);
+		linalg::evd::schur_cplx(
+			A,
+			T.as_mut(),
+			Some(Z.as_mut()),
+			S.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::evd::schur_scratch::<Complex<T>>(n, par, default()))),
+			default(),
+		)?;
 
-		make_self_adjoint(out.as_mut());
-		out
+		if conj == Conj::Yes {
+			for M in [&mut T, &mut Z] {
+				for c in M.col_iter_mut() {
+					for x in c.iter_mut() {
+						*x = math_utils::conj(x);
+					}
+				}
+			}
+			for x in S.column_vector_mut().iter_mut() {
+				*x = math_utils::conj(x);
+			}
+		}
+
+		Ok(Self { T, Z, S })
 	}
 
-	#[track_caller]
-	fn inverse(&self) -> Mat<T> {
-		let par = get_global_parallelism();
+	/// returns the upper triangular factor $T$
+	pub fn T(&self) -> MatRef<'_, Complex<T>> {
+		self.T.as_ref()
+	}
 
-		let n = self.L.nrows();
-		let mut out = Mat::zeros(n, n);
+	/// returns the unitary factor $Z$
+	pub fn Z(&self) -> MatRef<'_, Complex<T>> {
+		self.Z.as_ref()
+	}
 
-		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::inverse::inverse_scratch::<T>(n, par));
-		let stack = MemStack::new(&mut mem);
+	/// returns the eigenvalues, in the same order as they appear on the diagonal of $T$
+	pub fn S(&self) -> DiagRef<'_, Complex<T>> {
+		self.S.as_ref()
+	}
 
-		linalg::cholesky::ldlt::inverse::inverse(out.as_mut(), self.L(), self.D(), par, stack);
+	/// reorders the decomposition so that the eigenvalues for which `select` returns `true` appear
+	/// in the leading diagonal block of $T$, and returns the dimension of that block
+	///
+	/// the leading columns of $Z$ then span the invariant subspace associated with the selected
+	/// eigenvalues
+	pub fn reorder(&mut self, select: impl FnMut(&Complex<T>) -> bool) -> usize {
+		let k = linalg::evd::schur::reorder_schur_cplx_by(self.T.as_mut(), Some(self.Z.as_mut()), select);
 
-		make_self_adjoint(out.as_mut());
-		out
+		for i in 0..self.T.nrows() {
+			self.S[i] = self.T[(i, i)].clone();
+		}
+		k
 	}
-}
 
-impl<T: ComplexField> SolveCore<T> for Lblt<T> {
+	/// returns condition estimates for the cluster of eigenvalues in the leading `k`-dimensional
+	/// diagonal block of $T$
 	#[track_caller]
-	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+	pub fn cluster_condition(&self, k: usize) -> linalg::evd::schur::SchurClusterCondition<T> {
 		let par = get_global_parallelism();
+		let n = self.T.nrows();
 
-		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::solve::solve_in_place_scratch::<usize, T>(
-			self.L.nrows(),
-			rhs.ncols(),
+		linalg::evd::schur::schur_cluster_condition(
+			self.T.as_ref(),
+			k,
 			par,
-		));
-		let stack = MemStack::new(&mut mem);
+			MemStack::new(&mut MemBuffer::new(linalg::evd::schur::schur_cluster_condition_scratch::<Complex<T>>(
+				n, k,
+			))),
+		)
+	}
+}
 
-		linalg::cholesky::bunch_kaufman::solve::solve_in_place_with_conj(
-			self.L.as_ref(),
-			self.B_diag(),
-			self.B_subdiag(),
-			conj,
-			self.P(),
-			rhs,
+impl<T: RealField> RealSchur<T> {
+	/// returns the real schur decomposition of $A$
+	#[track_caller]
+	pub fn new(A: MatRef<'_, T>) -> Result<Self, EvdError> {
+		assert!(A.nrows() == A.ncols());
+
+		let par = get_global_parallelism();
+
+		let n = A.nrows();
+
+		let mut T = Mat::zeros(n, n);
+		let mut Z = Mat::zeros(n, n);
+		let mut S_re = Diag::zeros(n);
+		let mut S_im = Diag::zeros(n);
+
+		linalg::evd::schur_real(
+			A,
+			T.as_mut(),
+			Some(Z.as_mut()),
+			S_re.as_mut(),
+			S_im.as_mut(),
 			par,
-			stack,
-		);
+			MemStack::new(&mut MemBuffer::new(linalg::evd::schur_scratch::<T>(n, par, default()))),
+			default(),
+		)?;
+
+		let mut S = Diag::zeros(n);
+		for i in 0..n {
+			S[i] = Complex::new(S_re[i].clone(), S_im[i].clone());
+		}
+
+		Ok(Self { T, Z, S })
+	}
+
+	/// returns the upper quasi-triangular factor $T$
+	///
+	/// complex conjugate eigenvalue pairs correspond to $2\times 2$ diagonal blocks
+	pub fn T(&self) -> MatRef<'_, T> {
+		self.T.as_ref()
+	}
+
+	/// returns the orthogonal factor $Z$
+	pub fn Z(&self) -> MatRef<'_, T> {
+		self.Z.as_ref()
 	}
 
+	/// returns the eigenvalues, in the same order as they appear on the diagonal of $T$
+	pub fn S(&self) -> DiagRef<'_, Complex<T>> {
+		self.S.as_ref()
+	}
+
+	/// reorders the decomposition so that the eigenvalues for which `select` returns `true` appear
+	/// in the leading diagonal block of $T$, and returns the dimension of that block
+	///
+	/// a pair of complex conjugate eigenvalues is moved if either of them is selected. if two
+	/// adjacent blocks are too close to be swapped stably, the decomposition is left partially
+	/// reordered, but remains valid
+	pub fn reorder(&mut self, select: impl FnMut(&Complex<T>) -> bool) -> Result<usize, linalg::evd::schur::SchurReorderError> {
+		let k = linalg::evd::schur::reorder_schur_real_by(self.T.as_mut(), Some(self.Z.as_mut()), select);
+
+		let n = self.T.nrows();
+		let mut i = 0;
+		while i < n {
+			if i + 1 < n && self.T[(i + 1, i)] != zero() {
+				// 2x2 blocks are kept in standardized form, with equal diagonal entries
+				let re = self.T[(i, i)].clone();
+				let im = math_utils::sqrt(&math_utils::abs(&self.T[(i, i + 1)])) * math_utils::sqrt(&math_utils::abs(&self.T[(i + 1, i)]));
+				self.S[i] = Complex::new(re.clone(), im.clone());
+				self.S[i + 1] = Complex::new(re, -im);
+				i += 2;
+			} else {
+				self.S[i] = Complex::new(self.T[(i, i)].clone(), zero());
+				i += 1;
+			}
+		}
+		k
+	}
+
+	/// returns condition estimates for the cluster of eigenvalues in the leading `k`-dimensional
+	/// diagonal block of $T$
+	///
+	/// `k` must not split a $2\times 2$ diagonal block
 	#[track_caller]
-	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+	pub fn cluster_condition(&self, k: usize) -> linalg::evd::schur::SchurClusterCondition<T> {
 		let par = get_global_parallelism();
+		let n = self.T.nrows();
 
-		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::solve::solve_in_place_scratch::<usize, T>(
-			self.L.nrows(),
-			rhs.ncols(),
-			par,
-		));
-		let stack = MemStack::new(&mut mem);
-
-		linalg::cholesky::bunch_kaufman::solve::solve_in_place_with_conj(
-			self.L(),
-			self.B_diag(),
-			self.B_subdiag(),
-			conj.compose(Conj::Yes),
-			self.P(),
-			rhs,
+		linalg::evd::schur::schur_cluster_condition(
+			self.T.as_ref(),
+			k,
 			par,
-			stack,
-		);
+			MemStack::new(&mut MemBuffer::new(linalg::evd::schur::schur_cluster_condition_scratch::<T>(n, k))),
+		)
 	}
 }
 
-impl<T: ComplexField> DenseSolveCore<T> for Lblt<T> {
+impl<T: ComplexField> Hessenberg<T> {
+	/// returns the hessenberg decomposition of $A$
 	#[track_caller]
-	fn reconstruct(&self) -> Mat<T> {
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Self {
+		assert!(A.nrows() == A.ncols());
+		Self::new_imp(A.to_owned())
+	}
+
+	fn new_imp(mut H: Mat<T>) -> Self {
 		let par = get_global_parallelism();
 
-		let n = self.L.nrows();
-		let mut out = Mat::zeros(n, n);
+		let n = H.nrows();
+		let k = n.saturating_sub(1);
 
-		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::reconstruct::reconstruct_scratch::<usize, T>(n, par));
-		let stack = MemStack::new(&mut mem);
+		let blocksize = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(k, k);
+		let mut Q_coeff = Mat::zeros(blocksize, k);
 
-		linalg::cholesky::bunch_kaufman::reconstruct::reconstruct(out.as_mut(), self.L(), self.B_diag(), self.B_subdiag(), self.P(), par, stack);
+		linalg::evd::hessenberg::hessenberg_in_place(
+			H.as_mut(),
+			Q_coeff.as_mut(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::evd::hessenberg::hessenberg_in_place_scratch::<T>(
+				n,
+				blocksize,
+				par,
+				default(),
+			))),
+			default(),
+		);
 
-		make_self_adjoint(out.as_mut());
-		out
+		let Q_basis = householder_basis(H.as_ref().submatrix(Ord::min(1, n), 0, k, k));
+		for j in 0..n {
+			for i in j + 2..n {
+				H[(i, j)] = zero();
+			}
+		}
+
+		Self { H, Q_basis, Q_coeff }
 	}
 
-	#[track_caller]
-	fn inverse(&self) -> Mat<T> {
-		let par = get_global_parallelism();
+	/// returns the upper hessenberg factor $H$
+	pub fn H(&self) -> MatRef<'_, T> {
+		self.H.as_ref()
+	}
 
-		let n = self.L.nrows();
-		let mut out = Mat::zeros(n, n);
+	/// returns the householder basis of $Q$, which acts on all the rows but the first
+	pub fn Q_basis(&self) -> MatRef<'_, T> {
+		self.Q_basis.as_ref()
+	}
 
-		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::inverse::inverse_scratch::<usize, T>(n, par));
-		let stack = MemStack::new(&mut mem);
+	/// returns the householder coefficients of $Q$
+	pub fn Q_coeff(&self) -> MatRef<'_, T> {
+		self.Q_coeff.as_ref()
+	}
 
-		linalg::cholesky::bunch_kaufman::inverse::inverse(out.as_mut(), self.L(), self.B_diag(), self.B_subdiag(), self.P(), par, stack);
+	/// computes the factor $Q$
+	pub fn compute_Q(&self) -> Mat<T> {
+		let n = self.H.nrows();
+		let mut Q = Mat::identity(n, n);
+		self.apply_Q_in_place(Q.as_mut());
+		Q
+	}
 
-		make_self_adjoint(out.as_mut());
-		out
+	/// computes $Q M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_Q_in_place(&self, M: MatMut<'_, T>) {
+		let n = self.H.nrows();
+		assert!(M.nrows() == n);
+		if n > 1 {
+			apply_householder_in_place(self.Q_basis(), self.Q_coeff(), Conj::No, M.subrows_mut(1, n - 1));
+		}
 	}
-}
 
-impl<T: ComplexField> SolveCore<T> for PartialPivLu<T> {
+	/// computes $Q^H M$, and stores the result in $M$
 	#[track_caller]
-	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+	pub fn apply_Q_adjoint_in_place(&self, M: MatMut<'_, T>) {
+		let n = self.H.nrows();
+		assert!(M.nrows() == n);
+		if n > 1 {
+			apply_householder_in_place(self.Q_basis(), self.Q_coeff(), Conj::Yes, M.subrows_mut(1, n - 1));
+		}
+	}
+}
 
-		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+impl<T: ComplexField> Tridiag<T> {
+	/// returns the tridiagonalization of $A$, assuming it is self-adjoint
+	///
+	/// only the triangular half of $A$ given by `side` is accessed
+	#[track_caller]
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>, side: Side) -> Self {
+		assert!(A.nrows() == A.ncols());
 
-		let k = rhs.ncols();
+		let n = A.nrows();
+		let mut trid = Mat::zeros(n, n);
+		match side {
+			Side::Lower => trid.copy_from_triangular_lower(A),
+			Side::Upper => trid.copy_from_triangular_lower(A.adjoint()),
+		}
 
-		linalg::lu::partial_pivoting::solve::solve_in_place_with_conj(
-			self.L(),
-			self.U(),
-			self.P(),
-			conj,
-			rhs,
-			par,
-			MemStack::new(&mut MemBuffer::new(
-				linalg::lu::partial_pivoting::solve::solve_in_place_scratch::<usize, T>(self.nrows(), k, par),
-			)),
-		);
+		Self::new_imp(trid)
 	}
 
-	#[track_caller]
-	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+	fn new_imp(mut trid: Mat<T>) -> Self {
 		let par = get_global_parallelism();
 
-		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+		let n = trid.nrows();
+		let k = n.saturating_sub(1);
 
-		let k = rhs.ncols();
+		let blocksize = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(n, n);
+		let mut Q_coeff = Mat::zeros(blocksize, k);
 
-		linalg::lu::partial_pivoting::solve::solve_transpose_in_place_with_conj(
-			self.L(),
-			self.U(),
-			self.P(),
-			conj,
-			rhs,
+		linalg::evd::tridiag::tridiag_in_place(
+			trid.as_mut(),
+			Q_coeff.as_mut(),
 			par,
-			MemStack::new(&mut MemBuffer::new(
-				linalg::lu::partial_pivoting::solve::solve_transpose_in_place_scratch::<usize, T>(self.nrows(), k, par),
-			)),
+			MemStack::new(&mut MemBuffer::new(linalg::evd::tridiag::tridiag_in_place_scratch::<T>(
+				n,
+				par,
+				default(),
+			))),
+			default(),
 		);
-	}
-}
 
-impl<T: ComplexField> DenseSolveCore<T> for PartialPivLu<T> {
-	fn reconstruct(&self) -> Mat<T> {
-		let par = get_global_parallelism();
-		let m = self.nrows();
-		let n = self.ncols();
+		let diag = Col::from_fn(n, |i| from_real(&real(&trid[(i, i)])));
+		let subdiag = Col::from_fn(k, |i| trid[(i + 1, i)].clone());
+		let Q_basis = householder_basis(trid.as_ref().submatrix(Ord::min(1, n), 0, k, k));
 
-		let mut out = Mat::zeros(m, n);
+		Self {
+			diag,
+			subdiag,
+			Q_basis,
+			Q_coeff,
+		}
+	}
 
-		linalg::lu::partial_pivoting::reconstruct::reconstruct(
-			out.as_mut(),
-			self.L(),
-			self.U(),
-			self.P(),
-			par,
-			MemStack::new(&mut MemBuffer::new(linalg::lu::partial_pivoting::reconstruct::reconstruct_scratch::<
-				usize,
-				T,
-			>(m, n, par))),
-		);
+	/// returns the diagonal of $T$, which is real valued
+	pub fn diag(&self) -> ColRef<'_, T> {
+		self.diag.as_ref()
+	}
 
-		out
+	/// returns the subdiagonal of $T$. the superdiagonal is its conjugate
+	pub fn subdiag(&self) -> ColRef<'_, T> {
+		self.subdiag.as_ref()
 	}
 
-	#[track_caller]
-	fn inverse(&self) -> Mat<T> {
-		let par = get_global_parallelism();
+	/// computes the tridiagonal factor $T$
+	pub fn compute_T(&self) -> Mat<T> {
+		let n = self.diag.nrows();
+		let mut T = Mat::zeros(n, n);
+		for i in 0..n {
+			T[(i, i)] = self.diag[i].clone();
+			if i + 1 < n {
+				T[(i + 1, i)] = self.subdiag[i].clone();
+				T[(i, i + 1)] = conj(&self.subdiag[i]);
+			}
+		}
+		T
+	}
 
-		assert!(self.nrows() == self.ncols());
+	/// returns the householder basis of $Q$, which acts on all the rows but the first
+	pub fn Q_basis(&self) -> MatRef<'_, T> {
+		self.Q_basis.as_ref()
+	}
 
-		let n = self.ncols();
+	/// returns the householder coefficients of $Q$
+	pub fn Q_coeff(&self) -> MatRef<'_, T> {
+		self.Q_coeff.as_ref()
+	}
 
-		let mut out = Mat::zeros(n, n);
+	/// computes the factor $Q$
+	pub fn compute_Q(&self) -> Mat<T> {
+		let n = self.diag.nrows();
+		let mut Q = Mat::identity(n, n);
+		self.apply_Q_in_place(Q.as_mut());
+		Q
+	}
 
-		linalg::lu::partial_pivoting::inverse::inverse(
-			out.as_mut(),
-			self.L(),
-			self.U(),
-			self.P(),
-			par,
-			MemStack::new(&mut MemBuffer::new(linalg::lu::partial_pivoting::inverse::inverse_scratch::<usize, T>(
-				n, par,
-			))),
-		);
+	/// computes $Q M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_Q_in_place(&self, M: MatMut<'_, T>) {
+		let n = self.diag.nrows();
+		assert!(M.nrows() == n);
+		if n > 1 {
+			apply_householder_in_place(self.Q_basis(), self.Q_coeff(), Conj::No, M.subrows_mut(1, n - 1));
+		}
+	}
 
-		out
+	/// computes $Q^H M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_Q_adjoint_in_place(&self, M: MatMut<'_, T>) {
+		let n = self.diag.nrows();
+		assert!(M.nrows() == n);
+		if n > 1 {
+			apply_householder_in_place(self.Q_basis(), self.Q_coeff(), Conj::Yes, M.subrows_mut(1, n - 1));
+		}
 	}
 }
 
-impl<T: ComplexField> SolveCore<T> for FullPivLu<T> {
+impl<T: ComplexField> Bidiag<T> {
+	/// returns the bidiagonalization of $A$
+	///
+	/// # panics
+	///
+	/// panics if $A$ has fewer rows than columns
 	#[track_caller]
-	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+	pub fn new<C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Self {
+		assert!(A.nrows() >= A.ncols());
+		Self::new_imp(A.to_owned())
+	}
+
+	fn new_imp(mut bid: Mat<T>) -> Self {
 		let par = get_global_parallelism();
 
-		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+		let (m, n) = bid.shape();
+		let k = n.saturating_sub(1);
 
-		let k = rhs.ncols();
+		let blocksize = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(m, n);
+		let mut U_coeff = Mat::zeros(blocksize, n);
+		let mut V_coeff = Mat::zeros(blocksize, k);
 
-		linalg::lu::full_pivoting::solve::solve_in_place_with_conj(
-			self.L(),
-			self.U(),
-			self.P(),
-			self.Q(),
-			conj,
-			rhs,
+		linalg::svd::bidiag::bidiag_in_place(
+			bid.as_mut(),
+			U_coeff.as_mut(),
+			V_coeff.as_mut(),
 			par,
-			MemStack::new(&mut MemBuffer::new(linalg::lu::full_pivoting::solve::solve_in_place_scratch::<usize, T>(
-				self.nrows(),
-				k,
+			MemStack::new(&mut MemBuffer::new(linalg::svd::bidiag::bidiag_in_place_scratch::<T>(
+				m,
+				n,
 				par,
+				default(),
 			))),
+			default(),
 		);
-	}
 
-	#[track_caller]
-	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+		let diag = Col::from_fn(n, |i| bid[(i, i)].clone());
+		let superdiag = Col::from_fn(k, |i| bid[(i, i + 1)].clone());
+		let U_basis = householder_basis(bid.as_ref());
 
-		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+		// the right householder reflectors are stored in the rows of the upper triangular half,
+		// so that $V$ is the conjugate of the sequence formed by their transposes
+		let V_basis = householder_basis(bid.as_ref().submatrix(0, Ord::min(1, n), k, k).adjoint());
+		for x in V_coeff.col_iter_mut().flat_map(|c| c.iter_mut()) {
+			*x = conj(x);
+		}
 
-		let k = rhs.ncols();
+		Self {
+			diag,
+			superdiag,
+			U_basis,
+			U_coeff,
+			V_basis,
+			V_coeff,
+		}
+	}
 
-		linalg::lu::full_pivoting::solve::solve_transpose_in_place_with_conj(
-			self.L(),
-			self.U(),
-			self.P(),
+	/// returns the diagonal of $B$
+	pub fn diag(&self) -> ColRef<'_, T> {
+		self.diag.as_ref()
+	}
+
+	/// returns the superdiagonal of $B$
+	pub fn superdiag(&self) -> ColRef<'_, T> {
+		self.superdiag.as_ref()
+	}
+
+	/// computes the bidiagonal factor $B$
+	pub fn compute_B(&self) -> Mat<T> {
+		let (m, n) = (self.U_basis.nrows(), self.diag.nrows());
+		let mut B = Mat::zeros(m, n);
+		for i in 0..n {
+			B[(i, i)] = self.diag[i].clone();
+			if i + 1 < n {
+				B[(i, i + 1)] = self.superdiag[i].clone();
+			}
+		}
+		B
+	}
+
+	/// returns the householder basis of $U$
+	pub fn U_basis(&self) -> MatRef<'_, T> {
+		self.U_basis.as_ref()
+	}
+
+	/// returns the householder coefficients of $U$
+	pub fn U_coeff(&self) -> MatRef<'_, T> {
+		self.U_coeff.as_ref()
+	}
+
+	/// returns the householder basis of $V$, which acts on all the rows but the first
+	pub fn V_basis(&self) -> MatRef<'_, T> {
+		self.V_basis.as_ref()
+	}
+
+	/// returns the householder coefficients of $V$
+	pub fn V_coeff(&self) -> MatRef<'_, T> {
+		self.V_coeff.as_ref()
+	}
+
+	/// computes the factor $U$
+	pub fn compute_U(&self) -> Mat<T> {
+		let m = self.U_basis.nrows();
+		let mut U = Mat::identity(m, m);
+		self.apply_U_in_place(U.as_mut());
+		U
+	}
+
+	/// computes the first $\text{ncols}$ columns of the factor $U$
+	pub fn compute_thin_U(&self) -> Mat<T> {
+		let (m, n) = (self.U_basis.nrows(), self.diag.nrows());
+		let mut U = Mat::identity(m, n);
+		self.apply_U_in_place(U.as_mut());
+		U
+	}
+
+	/// computes the factor $V$
+	pub fn compute_V(&self) -> Mat<T> {
+		let n = self.diag.nrows();
+		let mut V = Mat::identity(n, n);
+		self.apply_V_in_place(V.as_mut());
+		V
+	}
+
+	/// computes $U M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_U_in_place(&self, M: MatMut<'_, T>) {
+		assert!(M.nrows() == self.U_basis.nrows());
+		apply_householder_in_place(self.U_basis(), self.U_coeff(), Conj::No, M);
+	}
+
+	/// computes $U^H M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_U_adjoint_in_place(&self, M: MatMut<'_, T>) {
+		assert!(M.nrows() == self.U_basis.nrows());
+		apply_householder_in_place(self.U_basis(), self.U_coeff(), Conj::Yes, M);
+	}
+
+	/// computes $V M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_V_in_place(&self, M: MatMut<'_, T>) {
+		let n = self.diag.nrows();
+		assert!(M.nrows() == n);
+		if n > 1 {
+			apply_householder_in_place(self.V_basis(), self.V_coeff(), Conj::No, M.subrows_mut(1, n - 1));
+		}
+	}
+
+	/// computes $V^H M$, and stores the result in $M$
+	#[track_caller]
+	pub fn apply_V_adjoint_in_place(&self, M: MatMut<'_, T>) {
+		let n = self.diag.nrows();
+		assert!(M.nrows() == n);
+		if n > 1 {
+			apply_householder_in_place(self.V_basis(), self.V_coeff(), Conj::Yes, M.subrows_mut(1, n - 1));
+		}
+	}
+}
+
+// returns the unit lower trapezoidal householder basis stored in the strictly lower part of `A`
+fn householder_basis<T: ComplexField, C: Conjugate<Canonical = T>>(A: MatRef<'_, C>) -> Mat<T> {
+	Mat::from_fn(A.nrows(), A.ncols(), |i, j| {
+		if i > j {
+			Conj::apply::<C>(&A[(i, j)])
+		} else if i == j {
+			one()
+		} else {
+			zero()
+		}
+	})
+}
+
+impl<T: ComplexField> ShapeCore for Llt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.L().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for Ldlt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.L().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for Lblt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.L().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for PartialPivLu<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.U().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for FullPivLu<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.U().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for Qr<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.Q_basis().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.R().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for ColPivQr<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.Q_basis().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.R().ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for CompleteOrthogonal<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.qr.nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.qr.ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for UpdatableQr<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.Q.nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.R.ncols()
+	}
+}
+impl<T: ComplexField> ShapeCore for Svd<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.U().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.V().nrows()
+	}
+}
+impl<T: ComplexField> ShapeCore for SelfAdjointEigen<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.U().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.U().nrows()
+	}
+}
+impl<T: RealField> ShapeCore for Eigen<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.U().nrows()
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.U().nrows()
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Llt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let mut mem = MemBuffer::new(linalg::cholesky::llt::solve::solve_in_place_scratch::<T>(
+			self.L.nrows(),
+			rhs.ncols(),
+			par,
+		));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::llt::solve::solve_in_place_with_conj(self.L.as_ref(), conj, rhs, par, stack);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let mut mem = MemBuffer::new(linalg::cholesky::llt::solve::solve_in_place_scratch::<T>(
+			self.L.nrows(),
+			rhs.ncols(),
+			par,
+		));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::llt::solve::solve_in_place_with_conj(self.L.as_ref(), conj.compose(Conj::Yes), rhs, par, stack);
+	}
+}
+
+#[math]
+fn make_self_adjoint<T: ComplexField>(mut A: MatMut<'_, T>) {
+	assert!(A.nrows() == A.ncols());
+	let n = A.nrows();
+	for j in 0..n {
+		A[(j, j)] = from_real(real(A[(j, j)]));
+		for i in 0..j {
+			A[(i, j)] = conj(A[(j, i)]);
+		}
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for Llt<T> {
+	#[track_caller]
+	fn reconstruct(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		let n = self.L.nrows();
+		let mut out = Mat::zeros(n, n);
+
+		let mut mem = MemBuffer::new(linalg::cholesky::llt::reconstruct::reconstruct_scratch::<T>(n, par));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::llt::reconstruct::reconstruct(out.as_mut(), self.L(), par, stack);
+
+		make_self_adjoint(out.as_mut());
+		out
+	}
+
+	#[track_caller]
+	fn inverse(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		let n = self.L.nrows();
+		let mut out = Mat::zeros(n, n);
+
+		let mut mem = MemBuffer::new(linalg::cholesky::llt::inverse::inverse_scratch::<T>(n, par));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::llt::inverse::inverse(out.as_mut(), self.L(), par, stack);
+
+		make_self_adjoint(out.as_mut());
+		out
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Ldlt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::solve::solve_in_place_scratch::<T>(
+			self.L.nrows(),
+			rhs.ncols(),
+			par,
+		));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::ldlt::solve::solve_in_place_with_conj(self.L.as_ref(), self.D.as_ref(), conj, rhs, par, stack);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::solve::solve_in_place_scratch::<T>(
+			self.L.nrows(),
+			rhs.ncols(),
+			par,
+		));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::ldlt::solve::solve_in_place_with_conj(self.L(), self.D(), conj.compose(Conj::Yes), rhs, par, stack);
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for Ldlt<T> {
+	#[track_caller]
+	fn reconstruct(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		let n = self.L.nrows();
+		let mut out = Mat::zeros(n, n);
+
+		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::reconstruct::reconstruct_scratch::<T>(n, par));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::ldlt::reconstruct::reconstruct(out.as_mut(), self.L(), self.D(), par, stack);
+
+		make_self_adjoint(out.as_mut());
+		out
+	}
+
+	#[track_caller]
+	fn inverse(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		let n = self.L.nrows();
+		let mut out = Mat::zeros(n, n);
+
+		let mut mem = MemBuffer::new(linalg::cholesky::ldlt::inverse::inverse_scratch::<T>(n, par));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::ldlt::inverse::inverse(out.as_mut(), self.L(), self.D(), par, stack);
+
+		make_self_adjoint(out.as_mut());
+		out
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Lblt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::solve::solve_in_place_scratch::<usize, T>(
+			self.L.nrows(),
+			rhs.ncols(),
+			par,
+		));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::bunch_kaufman::solve::solve_in_place_with_conj(
+			self.L.as_ref(),
+			self.B_diag(),
+			self.B_subdiag(),
+			conj,
+			self.P(),
+			rhs,
+			par,
+			stack,
+		);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::solve::solve_in_place_scratch::<usize, T>(
+			self.L.nrows(),
+			rhs.ncols(),
+			par,
+		));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::bunch_kaufman::solve::solve_in_place_with_conj(
+			self.L(),
+			self.B_diag(),
+			self.B_subdiag(),
+			conj.compose(Conj::Yes),
+			self.P(),
+			rhs,
+			par,
+			stack,
+		);
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for Lblt<T> {
+	#[track_caller]
+	fn reconstruct(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		let n = self.L.nrows();
+		let mut out = Mat::zeros(n, n);
+
+		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::reconstruct::reconstruct_scratch::<usize, T>(n, par));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::bunch_kaufman::reconstruct::reconstruct(out.as_mut(), self.L(), self.B_diag(), self.B_subdiag(), self.P(), par, stack);
+
+		make_self_adjoint(out.as_mut());
+		out
+	}
+
+	#[track_caller]
+	fn inverse(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		let n = self.L.nrows();
+		let mut out = Mat::zeros(n, n);
+
+		let mut mem = MemBuffer::new(linalg::cholesky::bunch_kaufman::inverse::inverse_scratch::<usize, T>(n, par));
+		let stack = MemStack::new(&mut mem);
+
+		linalg::cholesky::bunch_kaufman::inverse::inverse(out.as_mut(), self.L(), self.B_diag(), self.B_subdiag(), self.P(), par, stack);
+
+		make_self_adjoint(out.as_mut());
+		out
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for PartialPivLu<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+
+		let k = rhs.ncols();
+
+		linalg::lu::partial_pivoting::solve::solve_in_place_with_conj(
+			self.L(),
+			self.U(),
+			self.P(),
+			conj,
+			rhs,
+			par,
+			MemStack::new(&mut MemBuffer::new(
+				linalg::lu::partial_pivoting::solve::solve_in_place_scratch::<usize, T>(self.nrows(), k, par),
+			)),
+		);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+
+		let k = rhs.ncols();
+
+		linalg::lu::partial_pivoting::solve::solve_transpose_in_place_with_conj(
+			self.L(),
+			self.U(),
+			self.P(),
+			conj,
+			rhs,
+			par,
+			MemStack::new(&mut MemBuffer::new(
+				linalg::lu::partial_pivoting::solve::solve_transpose_in_place_scratch::<usize, T>(self.nrows(), k, par),
+			)),
+		);
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for PartialPivLu<T> {
+	fn reconstruct(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+		let m = self.nrows();
+		let n = self.ncols();
+
+		let mut out = Mat::zeros(m, n);
+
+		linalg::lu::partial_pivoting::reconstruct::reconstruct(
+			out.as_mut(),
+			self.L(),
+			self.U(),
+			self.P(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::lu::partial_pivoting::reconstruct::reconstruct_scratch::<
+				usize,
+				T,
+			>(m, n, par))),
+		);
+
+		out
+	}
+
+	#[track_caller]
+	fn inverse(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		assert!(self.nrows() == self.ncols());
+
+		let n = self.ncols();
+
+		let mut out = Mat::zeros(n, n);
+
+		linalg::lu::partial_pivoting::inverse::inverse(
+			out.as_mut(),
+			self.L(),
+			self.U(),
+			self.P(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::lu::partial_pivoting::inverse::inverse_scratch::<usize, T>(
+				n, par,
+			))),
+		);
+
+		out
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for FullPivLu<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+
+		let k = rhs.ncols();
+
+		linalg::lu::full_pivoting::solve::solve_in_place_with_conj(
+			self.L(),
+			self.U(),
+			self.P(),
+			self.Q(),
+			conj,
+			rhs,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::lu::full_pivoting::solve::solve_in_place_scratch::<usize, T>(
+				self.nrows(),
+				k,
+				par,
+			))),
+		);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+
+		let k = rhs.ncols();
+
+		linalg::lu::full_pivoting::solve::solve_transpose_in_place_with_conj(
+			self.L(),
+			self.U(),
+			self.P(),
 			self.Q(),
 			conj,
 			rhs,
@@ -2219,72 +4591,312 @@ impl<T: ComplexField> SolveLstsqCore<T> for ColPivQr<T> {
 		let blocksize = self.Q_coeff().nrows();
 		let k = rhs.ncols();
 
-		linalg::qr::col_pivoting::solve::solve_lstsq_in_place_with_conj(
-			self.Q_basis(),
-			self.Q_coeff(),
-			self.R(),
-			self.P(),
-			conj,
-			rhs,
+		linalg::qr::col_pivoting::solve::solve_lstsq_in_place_with_conj(
+			self.Q_basis(),
+			self.Q_coeff(),
+			self.R(),
+			self.P(),
+			conj,
+			rhs,
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::qr::col_pivoting::solve::solve_lstsq_in_place_scratch::<
+				usize,
+				T,
+			>(m, n, blocksize, k, par))),
+		);
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for ColPivQr<T> {
+	fn reconstruct(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+		let m = self.nrows();
+		let n = self.ncols();
+		let blocksize = self.Q_coeff().nrows();
+
+		let mut out = Mat::zeros(m, n);
+
+		linalg::qr::col_pivoting::reconstruct::reconstruct(
+			out.as_mut(),
+			self.Q_basis(),
+			self.Q_coeff(),
+			self.R(),
+			self.P(),
+			par,
+			MemStack::new(&mut MemBuffer::new(
+				linalg::qr::col_pivoting::reconstruct::reconstruct_scratch::<usize, T>(m, n, blocksize, par),
+			)),
+		);
+
+		out
+	}
+
+	fn inverse(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+		assert!(self.nrows() == self.ncols());
+
+		let n = self.ncols();
+		let blocksize = self.Q_coeff().nrows();
+
+		let mut out = Mat::zeros(n, n);
+
+		linalg::qr::col_pivoting::inverse::inverse(
+			out.as_mut(),
+			self.Q_basis(),
+			self.Q_coeff(),
+			self.R(),
+			self.P(),
+			par,
+			MemStack::new(&mut MemBuffer::new(linalg::qr::col_pivoting::inverse::inverse_scratch::<usize, T>(
+				n, blocksize, par,
+			))),
+		);
+
+		out
+	}
+}
+
+impl<T: ComplexField> SolveLstsqCore<T> for CompleteOrthogonal<T> {
+	#[track_caller]
+	fn solve_lstsq_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		assert!(all(self.nrows() == rhs.nrows(), self.nrows() >= self.ncols(),));
+		self.solve_lstsq_min_norm_in_place_with_conj(conj, rhs);
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for UpdatableQr<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+		self.solve_lstsq_in_place_with_conj(conj, rhs);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+
+		let mut rhs = rhs;
+		linalg::triangular_solve::solve_lower_triangular_in_place_with_conj(self.R().transpose(), conj, rhs.rb_mut(), par);
+
+		let tmp = rhs.to_owned();
+		linalg::matmul::matmul_with_conj(rhs, Accum::Replace, self.Q(), conj.compose(Conj::Yes), tmp.as_ref(), Conj::No, one(), par);
+	}
+}
+
+impl<T: ComplexField> SolveLstsqCore<T> for UpdatableQr<T> {
+	#[track_caller]
+	fn solve_lstsq_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == rhs.nrows(), self.nrows() >= self.ncols(),));
+
+		let n = self.ncols();
+		let mut rhs = rhs;
+
+		let tmp = rhs.to_owned();
+		linalg::matmul::matmul_with_conj(
+			rhs.rb_mut().get_mut(..n, ..),
+			Accum::Replace,
+			self.Q().transpose(),
+			conj.compose(Conj::Yes),
+			tmp.as_ref(),
+			Conj::No,
+			one(),
+			par,
+		);
+		linalg::triangular_solve::solve_upper_triangular_in_place_with_conj(self.R(), conj, rhs.get_mut(..n, ..), par);
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for UpdatableQr<T> {
+	fn reconstruct(&self) -> Mat<T> {
+		self.Q() * self.R()
+	}
+
+	fn inverse(&self) -> Mat<T> {
+		assert!(self.nrows() == self.ncols());
+
+		let mut out = Mat::identity(self.nrows(), self.ncols());
+		self.solve_in_place_with_conj(Conj::No, out.as_mut());
+		out
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Svd<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+
+		let mut rhs = rhs;
+		let n = self.nrows();
+		let k = rhs.ncols();
+		let mut tmp = Mat::zeros(n, k);
+
+		linalg::matmul::matmul_with_conj(
+			tmp.as_mut(),
+			Accum::Replace,
+			self.U().transpose(),
+			conj.compose(Conj::Yes),
+			rhs.as_ref(),
+			Conj::No,
+			one(),
+			par,
+		);
+
+		for j in 0..k {
+			for i in 0..n {
+				let s = recip(&real(&self.S()[i]));
+				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
+			}
+		}
+
+		linalg::matmul::matmul_with_conj(rhs.as_mut(), Accum::Replace, self.V(), conj, tmp.as_ref(), Conj::No, one(), par);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+
+		let mut rhs = rhs;
+		let n = self.nrows();
+		let k = rhs.ncols();
+		let mut tmp = Mat::zeros(n, k);
+
+		linalg::matmul::matmul_with_conj(
+			tmp.as_mut(),
+			Accum::Replace,
+			self.V().transpose(),
+			conj,
+			rhs.as_ref(),
+			Conj::No,
+			one(),
+			par,
+		);
+
+		for j in 0..k {
+			for i in 0..n {
+				let s = recip(&real(&self.S()[i]));
+				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
+			}
+		}
+
+		linalg::matmul::matmul_with_conj(
+			rhs.as_mut(),
+			Accum::Replace,
+			self.U(),
+			conj.compose(Conj::Yes),
+			tmp.as_ref(),
+			Conj::No,
+			one(),
+			par,
+		);
+	}
+}
+
+impl<T: ComplexField> SolveLstsqCore<T> for Svd<T> {
+	#[track_caller]
+	fn solve_lstsq_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let par = get_global_parallelism();
+
+		assert!(all(self.nrows() == rhs.nrows(), self.nrows() >= self.ncols(),));
+
+		let m = self.nrows();
+		let n = self.ncols();
+
+		let size = Ord::min(m, n);
+
+		let U = self.U().get(.., ..size);
+		let V = self.V().get(.., ..size);
+
+		let k = rhs.ncols();
+
+		let mut rhs = rhs;
+		let mut tmp = Mat::zeros(size, k);
+
+		linalg::matmul::matmul_with_conj(
+			tmp.as_mut(),
+			Accum::Replace,
+			U.transpose(),
+			conj.compose(Conj::Yes),
+			rhs.as_ref(),
+			Conj::No,
+			one(),
 			par,
-			MemStack::new(&mut MemBuffer::new(linalg::qr::col_pivoting::solve::solve_lstsq_in_place_scratch::<
-				usize,
-				T,
-			>(m, n, blocksize, k, par))),
 		);
+
+		for j in 0..k {
+			for i in 0..size {
+				let s = recip(&real(&self.S()[i]));
+				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
+			}
+		}
+
+		linalg::matmul::matmul_with_conj(rhs.as_mut(), Accum::Replace, V, conj, tmp.as_ref(), Conj::No, one(), par);
 	}
 }
 
-impl<T: ComplexField> DenseSolveCore<T> for ColPivQr<T> {
+impl<T: ComplexField> DenseSolveCore<T> for Svd<T> {
 	fn reconstruct(&self) -> Mat<T> {
 		let par = get_global_parallelism();
 		let m = self.nrows();
 		let n = self.ncols();
-		let blocksize = self.Q_coeff().nrows();
+
+		let size = Ord::min(m, n);
+
+		let U = self.U().get(.., ..size);
+		let V = self.V().get(.., ..size);
+		let S = self.S();
+
+		let mut UxS = Mat::zeros(m, size);
+		for j in 0..size {
+			let s = real(&S[j]);
+			for i in 0..m {
+				UxS[(i, j)] = mul_real(&U[(i, j)], &s);
+			}
+		}
 
 		let mut out = Mat::zeros(m, n);
 
-		linalg::qr::col_pivoting::reconstruct::reconstruct(
-			out.as_mut(),
-			self.Q_basis(),
-			self.Q_coeff(),
-			self.R(),
-			self.P(),
-			par,
-			MemStack::new(&mut MemBuffer::new(
-				linalg::qr::col_pivoting::reconstruct::reconstruct_scratch::<usize, T>(m, n, blocksize, par),
-			)),
-		);
+		linalg::matmul::matmul(out.as_mut(), Accum::Replace, UxS.as_ref(), V.adjoint(), one(), par);
 
 		out
 	}
 
+	#[track_caller]
 	fn inverse(&self) -> Mat<T> {
 		let par = get_global_parallelism();
+
 		assert!(self.nrows() == self.ncols());
+		let n = self.nrows();
 
-		let n = self.ncols();
-		let blocksize = self.Q_coeff().nrows();
+		let U = self.U();
+		let V = self.V();
+		let S = self.S();
+
+		let mut VxS = Mat::zeros(n, n);
+		for j in 0..n {
+			let s = recip(&real(&S[j]));
+
+			for i in 0..n {
+				VxS[(i, j)] = mul_real(&V[(i, j)], &s);
+			}
+		}
 
 		let mut out = Mat::zeros(n, n);
 
-		linalg::qr::col_pivoting::inverse::inverse(
-			out.as_mut(),
-			self.Q_basis(),
-			self.Q_coeff(),
-			self.R(),
-			self.P(),
-			par,
-			MemStack::new(&mut MemBuffer::new(linalg::qr::col_pivoting::inverse::inverse_scratch::<usize, T>(
-				n, blocksize, par,
-			))),
-		);
+		linalg::matmul::matmul(out.as_mut(), Accum::Replace, VxS.as_ref(), U.adjoint(), one(), par);
 
 		out
 	}
 }
 
-impl<T: ComplexField> SolveCore<T> for Svd<T> {
+impl<T: ComplexField> SolveCore<T> for SelfAdjointEigen<T> {
 	#[track_caller]
 	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
 		let par = get_global_parallelism();
@@ -2314,7 +4926,7 @@ impl<T: ComplexField> SolveCore<T> for Svd<T> {
 			}
 		}
 
-		linalg::matmul::matmul_with_conj(rhs.as_mut(), Accum::Replace, self.V(), conj, tmp.as_ref(), Conj::No, one(), par);
+		linalg::matmul::matmul_with_conj(rhs.as_mut(), Accum::Replace, self.U(), conj, tmp.as_ref(), Conj::No, one(), par);
 	}
 
 	#[track_caller]
@@ -2331,7 +4943,7 @@ impl<T: ComplexField> SolveCore<T> for Svd<T> {
 		linalg::matmul::matmul_with_conj(
 			tmp.as_mut(),
 			Accum::Replace,
-			self.V().transpose(),
+			self.U().transpose(),
 			conj,
 			rhs.as_ref(),
 			Conj::No,
@@ -2339,351 +4951,894 @@ impl<T: ComplexField> SolveCore<T> for Svd<T> {
 			par,
 		);
 
-		for j in 0..k {
-			for i in 0..n {
-				let s = recip(&real(&self.S()[i]));
-				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
-			}
+		for j in 0..k {
+			for i in 0..n {
+				let s = recip(&real(&self.S()[i]));
+				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
+			}
+		}
+
+		linalg::matmul::matmul_with_conj(
+			rhs.as_mut(),
+			Accum::Replace,
+			self.U(),
+			conj.compose(Conj::Yes),
+			tmp.as_ref(),
+			Conj::No,
+			one(),
+			par,
+		);
+	}
+}
+
+impl<T: ComplexField> DenseSolveCore<T> for SelfAdjointEigen<T> {
+	fn reconstruct(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+		let m = self.nrows();
+		let n = self.ncols();
+
+		let size = Ord::min(m, n);
+
+		let U = self.U().get(.., ..size);
+		let V = self.U().get(.., ..size);
+		let S = self.S();
+
+		let mut UxS = Mat::zeros(m, size);
+		for j in 0..size {
+			let s = real(&S[j]);
+			for i in 0..m {
+				UxS[(i, j)] = mul_real(&U[(i, j)], &s);
+			}
+		}
+
+		let mut out = Mat::zeros(m, n);
+
+		linalg::matmul::matmul(out.as_mut(), Accum::Replace, UxS.as_ref(), V.adjoint(), one(), par);
+
+		out
+	}
+
+	fn inverse(&self) -> Mat<T> {
+		let par = get_global_parallelism();
+
+		assert!(self.nrows() == self.ncols());
+		let n = self.nrows();
+
+		let U = self.U();
+		let V = self.U();
+		let S = self.S();
+
+		let mut VxS = Mat::zeros(n, n);
+		for j in 0..n {
+			let s = recip(&real(&S[j]));
+
+			for i in 0..n {
+				VxS[(i, j)] = mul_real(&V[(i, j)], &s);
+			}
+		}
+
+		let mut out = Mat::zeros(n, n);
+
+		linalg::matmul::matmul(out.as_mut(), Accum::Replace, VxS.as_ref(), U.adjoint(), one(), par);
+
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+
+	#[track_caller]
+	fn test_solver(A: MatRef<'_, c64>, A_dec: impl SolveCore<c64>) {
+		#[track_caller]
+		fn test_solver_imp(A: MatRef<'_, c64>, A_dec: &dyn SolveCore<c64>) {
+			let rng = &mut StdRng::seed_from_u64(0xC0FFEE);
+
+			let n = A.nrows();
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+
+			let k = 3;
+
+			let ref R = CwiseMatDistribution {
+				nrows: n,
+				ncols: k,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			let ref L = CwiseMatDistribution {
+				nrows: k,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			assert!(A * A_dec.solve(R) ~ R);
+			assert!(A.conjugate() * A_dec.solve_conjugate(R) ~ R);
+			assert!(A.transpose() * A_dec.solve_transpose(R) ~ R);
+			assert!(A.adjoint() * A_dec.solve_adjoint(R) ~ R);
+
+			assert!(A_dec.rsolve(L) * A ~ L);
+			assert!(A_dec.rsolve_conjugate(L) * A.conjugate() ~ L);
+			assert!(A_dec.rsolve_transpose(L) * A.transpose() ~ L);
+			assert!(A_dec.rsolve_adjoint(L) * A.adjoint() ~ L);
+		}
+
+		test_solver_imp(A, &A_dec)
+	}
+
+	#[test]
+	fn test_all_solvers() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
+
+		let ref A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let A = A.rb();
+
+		test_solver(A, A.partial_piv_lu());
+		test_solver(A, A.full_piv_lu());
+		test_solver(A, A.qr());
+		test_solver(A, A.col_piv_qr());
+		test_solver(A, A.qr().to_updatable());
+		test_solver(A, A.svd().unwrap());
+
+		{
+			let ref A = A * A.adjoint();
+			let A = A.rb();
+			test_solver(A, A.llt(Side::Lower).unwrap());
+			test_solver(A, A.ldlt(Side::Lower).unwrap());
+		}
+
+		{
+			let ref A = A + A.adjoint();
+			let A = A.rb();
+			test_solver(A, A.lblt(Side::Lower));
+			test_solver(A, A.self_adjoint_eigen(Side::Lower).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_rcond() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
+
+		fn norm1(A: MatRef<'_, c64>) -> f64 {
+			A.col_iter()
+				.map(|col| col.iter().map(|x| f64::hypot(x.re, x.im)).sum::<f64>())
+				.fold(0.0, f64::max)
+		}
+
+		#[track_caller]
+		fn check(A: MatRef<'_, c64>, rcond: f64) {
+			let exact = 1.0 / (norm1(A) * norm1(A.partial_piv_lu().inverse().as_ref()));
+			// the norm estimates are lower bounds, and are usually within a small factor of the exact
+			// value
+			assert!(rcond >= exact * (1.0 - 1e-10));
+			assert!(rcond <= exact * 10.0);
+		}
+
+		let ref A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
 		}
+		.rand::<Mat<c64>>(rng);
+		let A = A.rb();
 
-		linalg::matmul::matmul_with_conj(
-			rhs.as_mut(),
-			Accum::Replace,
-			self.U(),
-			conj.compose(Conj::Yes),
-			tmp.as_ref(),
-			Conj::No,
-			one(),
-			par,
-		);
-	}
-}
+		check(A, A.partial_piv_lu().rcond());
+		check(A, A.full_piv_lu().rcond());
+		check(A, A.qr().rcond());
+		check(A, A.col_piv_qr().rcond());
 
-impl<T: ComplexField> SolveLstsqCore<T> for Svd<T> {
-	#[track_caller]
-	fn solve_lstsq_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+		{
+			let ref A = A * A.adjoint();
+			let A = A.rb();
+			check(A, A.llt(Side::Lower).unwrap().rcond());
+			check(A, A.ldlt(Side::Lower).unwrap().rcond());
+
+			// the norm of the updated matrix is recomputed from the factors
+			let W = A.get(.., ..2);
+			let ref B = A + W * W.adjoint();
+			let B = B.rb();
+			let mut llt = A.llt(Side::Lower).unwrap();
+			llt.rank_update(W).unwrap();
+			check(B, llt.rcond());
+			let mut ldlt = A.ldlt(Side::Lower).unwrap();
+			ldlt.rank_update(W).unwrap();
+			check(B, ldlt.rcond());
+		}
 
-		assert!(all(self.nrows() == rhs.nrows(), self.nrows() >= self.ncols(),));
+		{
+			let ref A = A + A.adjoint();
+			let A = A.rb();
+			check(A, A.lblt(Side::Lower).rcond());
+		}
 
-		let m = self.nrows();
-		let n = self.ncols();
+		{
+			let mut A = A.to_owned();
+			A.col_mut(3).fill(c64::ZERO);
+			assert!(A.partial_piv_lu().rcond() <= f64::EPSILON);
+			assert!(A.full_piv_lu().rcond() <= f64::EPSILON);
+			assert!(A.qr().rcond() <= f64::EPSILON);
+			assert!(A.col_piv_qr().rcond() <= f64::EPSILON);
+		}
 
-		let size = Ord::min(m, n);
+		assert!(Mat::<c64>::zeros(0, 0).partial_piv_lu().rcond() == 1.0);
+	}
 
-		let U = self.U().get(.., ..size);
-		let V = self.V().get(.., ..size);
+	#[test]
+	fn test_mixed_precision() {
+		fn assert_sync<T: Sync>() {}
+		assert_sync::<MixedPrecisionLu<c64>>();
+		assert_sync::<MixedPrecisionLlt<c64>>();
 
-		let k = rhs.ncols();
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
+		let k = 3;
 
-		let mut rhs = rhs;
-		let mut tmp = Mat::zeros(size, k);
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let B = CwiseMatDistribution {
+			nrows: n,
+			ncols: k,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
 
-		linalg::matmul::matmul_with_conj(
-			tmp.as_mut(),
-			Accum::Replace,
-			U.transpose(),
-			conj.compose(Conj::Yes),
-			rhs.as_ref(),
-			Conj::No,
-			one(),
-			par,
-		);
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
 
-		for j in 0..k {
-			for i in 0..size {
-				let s = recip(&real(&self.S()[i]));
-				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
-			}
+		{
+			let (X, info) = MixedPrecisionLu::new(A.as_ref()).solve(&B);
+			assert!(all(info.converged, !info.fallback));
+			assert!(&A * &X ~ B);
+
+			let H = &A * A.adjoint() + Mat::<c64>::identity(n, n);
+			let (X, info) = MixedPrecisionLlt::new(H.as_ref(), Side::Lower).unwrap().solve(&B);
+			assert!(all(info.converged, !info.fallback));
+			assert!(&H * &X ~ B);
 		}
 
-		linalg::matmul::matmul_with_conj(rhs.as_mut(), Accum::Replace, V, conj, tmp.as_ref(), Conj::No, one(), par);
+		{
+			// condition number ~1e12, too large for single precision
+			let Q = A.qr().compute_Q();
+			let S = Mat::<c64>::from_fn(n, n, |i, j| {
+				if i == j {
+					c64::new(f64::powf(1e-12, i as f64 / (n - 1) as f64), 0.0)
+				} else {
+					c64::ZERO
+				}
+			});
+			let H = &Q * &S * Q.adjoint();
+
+			// the residual is small relative to the solution
+			let mut lu = MixedPrecisionLu::new(H.as_ref());
+			assert!(lu.fallback.as_ref().is_none());
+			let (X, info) = lu.solve(&B);
+			assert!(all(!info.converged, info.fallback));
+			assert!((&H * &X - &B).norm_max() <= 1e-12 * X.norm_max());
+
+			// the working precision decomposition is reused by later solves
+			let factor = lu.fallback.as_ref().unwrap() as *const _;
+			let (X, info) = lu.solve(&B);
+			assert!(all(info.fallback, core::ptr::eq(factor, lu.fallback.as_ref().unwrap())));
+			assert!((&H * &X - &B).norm_max() <= 1e-12 * X.norm_max());
+
+			let mut llt = MixedPrecisionLlt::new(H.as_ref(), Side::Lower).unwrap();
+			let (X, info) = llt.solve(&B);
+			assert!(all(!info.converged, info.fallback));
+			assert!((&H * &X - &B).norm_max() <= 1e-12 * X.norm_max());
+
+			let factor = llt.fallback.as_ref().unwrap() as *const _;
+			let (X, info) = llt.solve(&B);
+			assert!(all(info.fallback, core::ptr::eq(factor, llt.fallback.as_ref().unwrap())));
+			assert!((&H * &X - &B).norm_max() <= 1e-12 * X.norm_max());
+		}
 	}
-}
-
-impl<T: ComplexField> DenseSolveCore<T> for Svd<T> {
-	fn reconstruct(&self) -> Mat<T> {
-		let par = get_global_parallelism();
-		let m = self.nrows();
-		let n = self.ncols();
 
-		let size = Ord::min(m, n);
+	#[test]
+	fn test_solve_with_error_bounds() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
+		let k = 3;
 
-		let U = self.U().get(.., ..size);
-		let V = self.V().get(.., ..size);
-		let S = self.S();
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let X_true = CwiseMatDistribution {
+			nrows: n,
+			ncols: k,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
 
-		let mut UxS = Mat::zeros(m, size);
-		for j in 0..size {
-			let s = real(&S[j]);
-			for i in 0..m {
-				UxS[(i, j)] = mul_real(&U[(i, j)], &s);
+		#[track_caller]
+		fn check(A: MatRef<'_, c64>, X_true: MatRef<'_, c64>, A_dec: impl DenseSolve<c64>) {
+			let B = A * X_true;
+			let (X, bounds) = A_dec.solve_with_error_bounds(A, &B);
+			for j in 0..X.ncols() {
+				assert!(bounds.backward_error[j] <= 4.0 * f64::EPSILON);
+				let err = (X.col(j) - X_true.col(j)).norm_max() / X.col(j).norm_max();
+				assert!(err <= bounds.forward_error[j]);
+				assert!(bounds.forward_error[j] <= 1e-8);
 			}
 		}
 
-		let mut out = Mat::zeros(m, n);
+		let A = A.as_ref();
+		check(A, X_true.as_ref(), A.partial_piv_lu());
+		check(A, X_true.as_ref(), A.full_piv_lu());
+		check(A, X_true.as_ref(), A.qr());
+		check(A, X_true.as_ref(), A.col_piv_qr());
 
-		linalg::matmul::matmul(out.as_mut(), Accum::Replace, UxS.as_ref(), V.adjoint(), one(), par);
+		{
+			let ref A = A * A.adjoint();
+			let A = A.as_ref();
+			check(A, X_true.as_ref(), A.llt(Side::Lower).unwrap());
+			check(A, X_true.as_ref(), A.ldlt(Side::Lower).unwrap());
+		}
 
-		out
+		{
+			let ref A = A + A.adjoint();
+			let A = A.as_ref();
+			check(A, X_true.as_ref(), A.lblt(Side::Lower));
+		}
 	}
 
-	#[track_caller]
-	fn inverse(&self) -> Mat<T> {
-		let par = get_global_parallelism();
-
-		assert!(self.nrows() == self.ncols());
-		let n = self.nrows();
-
-		let U = self.U();
-		let V = self.V();
-		let S = self.S();
-
-		let mut VxS = Mat::zeros(n, n);
-		for j in 0..n {
-			let s = recip(&real(&S[j]));
+	#[test]
+	fn test_eigen_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
 
-			for i in 0..n {
-				VxS[(i, j)] = mul_real(&V[(i, j)], &s);
-			}
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
 		}
+		.rand::<Mat<c64>>(rng);
 
-		let mut out = Mat::zeros(n, n);
+		let n = A.nrows();
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
 
-		linalg::matmul::matmul(out.as_mut(), Accum::Replace, VxS.as_ref(), U.adjoint(), one(), par);
+		let evd = A.eigen().unwrap();
+		let e = A.eigenvalues().unwrap();
+		assert!(&A * evd.U() ~ evd.U() * evd.S());
+		assert!(evd.S().column_vector() ~ ColRef::from_slice(&e));
 
-		out
+		let evd_conj = Eigen::new(A.conjugate()).unwrap();
+		assert!(A.conjugate() * evd_conj.U() ~ evd_conj.U() * evd_conj.S());
+		assert!(evd_conj.S().column_vector() ~ evd.S().column_vector().conjugate().to_owned());
 	}
-}
 
-impl<T: ComplexField> SolveCore<T> for SelfAdjointEigen<T> {
-	#[track_caller]
-	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+	#[test]
+	fn test_schur_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
 
-		assert!(all(self.nrows() == self.ncols(), self.nrows() == rhs.nrows(),));
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
 
-		let mut rhs = rhs;
-		let n = self.nrows();
-		let k = rhs.ncols();
-		let mut tmp = Mat::zeros(n, k);
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
 
-		linalg::matmul::matmul_with_conj(
-			tmp.as_mut(),
-			Accum::Replace,
-			self.U().transpose(),
-			conj.compose(Conj::Yes),
-			rhs.as_ref(),
-			Conj::No,
-			one(),
-			par,
-		);
+		let schur = A.schur().unwrap();
+		let T = schur.T();
+		let Z = schur.Z();
 
-		for j in 0..k {
-			for i in 0..n {
-				let s = recip(&real(&self.S()[i]));
-				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
+		assert!(Z * T * Z.adjoint() ~ A);
+		assert!(Z.adjoint() * Z ~ Mat::<c64>::identity(n, n));
+		for j in 0..n {
+			assert!(T[(j, j)] == schur.S()[j]);
+			for i in j + 1..n {
+				assert!(T[(i, j)] == c64::ZERO);
 			}
 		}
 
-		linalg::matmul::matmul_with_conj(rhs.as_mut(), Accum::Replace, self.U(), conj, tmp.as_ref(), Conj::No, one(), par);
+		let schur = Schur::new(A.conjugate()).unwrap();
+		assert!(schur.Z() * schur.T() * schur.Z().adjoint() ~ A.conjugate().to_owned());
 	}
 
-	#[track_caller]
-	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
-		let par = get_global_parallelism();
+	#[test]
+	fn test_schur_real() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 50;
 
-		assert!(all(self.nrows() == self.ncols(), self.ncols() == rhs.nrows(),));
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
 
-		let mut rhs = rhs;
-		let n = self.nrows();
-		let k = rhs.ncols();
-		let mut tmp = Mat::zeros(n, k);
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
 
-		linalg::matmul::matmul_with_conj(
-			tmp.as_mut(),
-			Accum::Replace,
-			self.U().transpose(),
-			conj,
-			rhs.as_ref(),
-			Conj::No,
-			one(),
-			par,
-		);
+		let schur = A.schur_from_real().unwrap();
+		let T = schur.T();
+		let Z = schur.Z();
 
-		for j in 0..k {
-			for i in 0..n {
-				let s = recip(&real(&self.S()[i]));
-				tmp[(i, j)] = mul_real(&tmp[(i, j)], &s);
+		assert!(Z * T * Z.transpose() ~ A);
+		assert!(Z.transpose() * Z ~ Mat::<f64>::identity(n, n));
+		for j in 0..n {
+			for i in j + 2..n {
+				assert!(T[(i, j)] == 0.0);
 			}
 		}
 
-		linalg::matmul::matmul_with_conj(
-			rhs.as_mut(),
-			Accum::Replace,
-			self.U(),
-			conj.compose(Conj::Yes),
-			tmp.as_ref(),
-			Conj::No,
-			one(),
-			par,
-		);
+		let mut e = A.eigenvalues_from_real().unwrap();
+		let mut s = schur.S().column_vector().iter().copied().collect::<Vec<_>>();
+		let cmp = |a: &c64, b: &c64| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im));
+		e.sort_by(cmp);
+		s.sort_by(cmp);
+		assert!(ColRef::from_slice(&s) ~ ColRef::from_slice(&e));
 	}
-}
 
-impl<T: ComplexField> DenseSolveCore<T> for SelfAdjointEigen<T> {
-	fn reconstruct(&self) -> Mat<T> {
-		let par = get_global_parallelism();
-		let m = self.nrows();
-		let n = self.ncols();
+	#[test]
+	fn test_generalized_eigen() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
+		let B = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+
+		let gevd = GeneralizedEigen::new_from_real(A.as_ref(), B.as_ref(), ComputeEigenvectors::Yes, ComputeEigenvectors::Yes).unwrap();
+		let A = Mat::from_fn(n, n, |i, j| c64::new(A[(i, j)], 0.0));
+		let B = Mat::from_fn(n, n, |i, j| c64::new(B[(i, j)], 0.0));
 
-		let size = Ord::min(m, n);
+		let UL = gevd.U_left().unwrap();
+		let UR = gevd.U_right().unwrap();
+		assert!(&A * UR * gevd.beta() ~ &B * UR * gevd.alpha());
+		assert!(gevd.beta() * UL.adjoint() * &A ~ gevd.alpha() * UL.adjoint() * &B);
 
-		let U = self.U().get(.., ..size);
-		let V = self.U().get(.., ..size);
-		let S = self.S();
+		let gevd = GeneralizedEigen::new(A.conjugate(), B.as_ref(), ComputeEigenvectors::No, ComputeEigenvectors::Yes).unwrap();
+		let A = A.conjugate().to_owned();
 
-		let mut UxS = Mat::zeros(m, size);
-		for j in 0..size {
-			let s = real(&S[j]);
-			for i in 0..m {
-				UxS[(i, j)] = mul_real(&U[(i, j)], &s);
-			}
+		assert!(gevd.U_left().is_none());
+		let UR = gevd.U_right().unwrap();
+		assert!(&A * UR * gevd.beta() ~ &B * UR * gevd.alpha());
+	}
+
+	#[test]
+	fn test_generalized_self_adjoint_eigen() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let Y = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
 		}
+		.rand::<Mat<c64>>(rng);
 
-		let mut out = Mat::zeros(m, n);
+		let A = &X + X.adjoint();
+		let B = &Y * Y.adjoint() + Mat::<c64>::identity(n, n);
 
-		linalg::matmul::matmul(out.as_mut(), Accum::Replace, UxS.as_ref(), V.adjoint(), one(), par);
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
 
-		out
-	}
+		for side in [Side::Lower, Side::Upper] {
+			let gevd = GeneralizedSelfAdjointEigen::new(A.as_ref(), B.as_ref(), side, SelfAdjointGevdProblem::AxLambdaBx).unwrap();
+			let U = gevd.U();
+			assert!(&A * U ~ &B * U * gevd.S());
+			assert!(U.adjoint() * &B * U ~ Mat::<c64>::identity(n, n));
+		}
 
-	fn inverse(&self) -> Mat<T> {
-		let par = get_global_parallelism();
+		let gevd = GeneralizedSelfAdjointEigen::new(A.conjugate(), B.as_ref(), Side::Lower, SelfAdjointGevdProblem::ABxLambdaX).unwrap();
+		let A = A.conjugate().to_owned();
+		let U = gevd.U();
+		assert!(&A * &B * U ~ U * gevd.S());
+		assert!(U.adjoint() * &B * U ~ Mat::<c64>::identity(n, n));
 
-		assert!(self.nrows() == self.ncols());
-		let n = self.nrows();
+		let gevd = GeneralizedSelfAdjointEigen::new(A.conjugate(), B.conjugate(), Side::Lower, SelfAdjointGevdProblem::BAxLambdaX).unwrap();
+		let A = A.conjugate().to_owned();
+		let B = B.conjugate().to_owned();
+		let U = gevd.U();
+		assert!(&B * &A * U ~ U * gevd.S());
+	}
 
-		let U = self.U();
-		let V = self.U();
-		let S = self.S();
+	#[test]
+	fn test_self_adjoint_eigen_fn() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 20;
 
-		let mut VxS = Mat::zeros(n, n);
-		for j in 0..n {
-			let s = recip(&real(&S[j]));
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let A = &X * X.adjoint() * Scale(c64::new(0.1, 0.0)) + Mat::<c64>::identity(n, n);
+
+		let evd = SelfAdjointEigen::new(A.as_ref(), Side::Lower).unwrap();
+		let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * (n as f64));
+
+		let sqrt_A = evd.sqrt();
+		let inv_sqrt_A = evd.inv_sqrt();
+		assert!(&sqrt_A * &sqrt_A ~ A);
+		assert!(&inv_sqrt_A * &A * &inv_sqrt_A ~ Mat::<c64>::identity(n, n));
+		assert!(evd.apply_fn(|x| x * x) ~ &A * &A);
+
+		let log_A = evd.log();
+		let exp_log_A = SelfAdjointEigen::new(log_A.as_ref(), Side::Lower).unwrap().exp();
+		assert!(exp_log_A ~ A);
+
+		let mut expected = Mat::zeros(n, n);
+		linalg::matfun::logm::logm(
+			expected.as_mut(),
+			A.as_ref(),
+			Par::Seq,
+			MemStack::new(&mut MemBuffer::new(linalg::matfun::logm::logm_scratch::<f64>(n, Par::Seq))),
+		)
+		.unwrap();
+		assert!(log_A ~ expected);
+
+		for x in [1e-310, 1e-300, 1e-5, 0.3, 1.0, 2.0, 1e10, 1e300] {
+			assert!((linalg::matfun::log_real(x) - f64::ln(x)).abs() <= 2.0 * f64::EPSILON * f64::ln(x).abs());
+		}
+		for x in [-700.0, -20.0, -1.0, -1e-3, 0.0, 1e-3, 1.0, 20.0, 700.0] {
+			assert!((linalg::matfun::exp_real(x) - f64::exp(x)).abs() <= 2.0 * f64::EPSILON * f64::exp(x));
+		}
 
-			for i in 0..n {
-				VxS[(i, j)] = mul_real(&V[(i, j)], &s);
-			}
+		// the range reduction must stay within the exponent range of `f32`, including subnormals
+		for x in [1e-45f32, 1e-40, 1e-30, 0.3, 2.0, 1e30, f32::MAX] {
+			assert!((linalg::matfun::log_real(x) - f32::ln(x)).abs() <= 2.0 * f32::EPSILON * f32::ln(x).abs());
 		}
+		for x in [-100.0f32, -20.0, -1e-3, 1.0, 20.0, 88.0] {
+			assert!((linalg::matfun::exp_real(x) - f32::exp(x)).abs() <= 2.0 * f32::EPSILON * f32::exp(x));
+		}
+	}
 
-		let mut out = Mat::zeros(n, n);
+	#[test]
+	fn test_partial_self_adjoint_eigen() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 60;
 
-		linalg::matmul::matmul(out.as_mut(), Accum::Replace, VxS.as_ref(), U.adjoint(), one(), par);
+		let X = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let A = &X + X.adjoint();
+
+		let evd = A.self_adjoint_eigen(Side::Lower).unwrap();
+		let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * (n as f64));
+
+		for side in [Side::Lower, Side::Upper] {
+			let partial = A.partial_self_adjoint_eigen(side, EigenvalueSelection::Indices(0..10)).unwrap();
+			let U = partial.U();
+			assert!(U.ncols() == 10);
+			assert!(partial.S().column_vector() ~ evd.S().column_vector().get(..10));
+			assert!(&A * U ~ U * partial.S());
+			assert!(U.adjoint() * U ~ Mat::<c64>::identity(10, 10));
+
+			let partial = A
+				.conjugate()
+				.partial_self_adjoint_eigen(side, EigenvalueSelection::Indices(0..10))
+				.unwrap();
+			let U = partial.U();
+			assert!(partial.S().column_vector() ~ evd.S().column_vector().get(..10));
+			assert!(A.conjugate() * U ~ U * partial.S());
+		}
 
-		out
+		let S = evd.S().column_vector();
+		let (a, b) = ((S[19].re + S[20].re) / 2.0, (S[34].re + S[35].re) / 2.0);
+		let partial = A.partial_self_adjoint_eigen(Side::Lower, EigenvalueSelection::Interval(a, b)).unwrap();
+		assert!(partial.U().ncols() == 15);
+		assert!(partial.S().column_vector() ~ S.get(20..35));
+		assert!(&A * partial.U() ~ partial.U() * partial.S());
+
+		let partial = A.partial_self_adjoint_eigen(Side::Lower, EigenvalueSelection::All).unwrap();
+		assert!(partial.S().column_vector() ~ S);
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use crate::assert;
-	use crate::stats::prelude::*;
-	use crate::utils::approx::*;
+	#[test]
+	fn test_polar() {
+		let rng = &mut StdRng::seed_from_u64(0);
 
-	#[track_caller]
-	fn test_solver(A: MatRef<'_, c64>, A_dec: impl SolveCore<c64>) {
-		#[track_caller]
-		fn test_solver_imp(A: MatRef<'_, c64>, A_dec: &dyn SolveCore<c64>) {
-			let rng = &mut StdRng::seed_from_u64(0xC0FFEE);
+		for (m, n) in [(10, 10), (30, 7), (7, 30)] {
+			let A = CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
 
-			let n = A.nrows();
-			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+			let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * ((m + n) as f64));
 
-			let k = 3;
+			for polar in [Polar::new(A.as_ref()).unwrap(), Polar::new_from_svd(A.as_ref()).unwrap()] {
+				assert!(polar.U() * polar.H() ~ A);
+				assert!(polar.H().adjoint().to_owned() ~ polar.H());
+			}
 
-			let ref R = CwiseMatDistribution {
-				nrows: n,
+			let polar = A.conjugate().polar().unwrap();
+			assert!(polar.U() * polar.H() ~ A.conjugate().to_owned());
+
+			if m >= n {
+				let polar = A.polar().unwrap();
+				assert!(polar.U().adjoint() * polar.U() ~ Mat::<c64>::identity(n, n));
+			}
+		}
+	}
+
+	#[test]
+	fn test_complete_orthogonal() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (m, n, k) in [(60, 40, 15), (40, 60, 15), (40, 40, 40), (20, 30, 0)] {
+			let X = CwiseMatDistribution {
+				nrows: m,
 				ncols: k,
 				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
 			}
 			.rand::<Mat<c64>>(rng);
-
-			let ref L = CwiseMatDistribution {
+			let Y = CwiseMatDistribution {
 				nrows: k,
 				ncols: n,
 				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
 			}
 			.rand::<Mat<c64>>(rng);
+			let B = CwiseMatDistribution {
+				nrows: m,
+				ncols: 4,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let A = &X * &Y;
 
-			assert!(A * A_dec.solve(R) ~ R);
-			assert!(A.conjugate() * A_dec.solve_conjugate(R) ~ R);
-			assert!(A.transpose() * A_dec.solve_transpose(R) ~ R);
-			assert!(A.adjoint() * A_dec.solve_adjoint(R) ~ R);
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (Ord::max(m, n) as f64));
 
-			assert!(A_dec.rsolve(L) * A ~ L);
-			assert!(A_dec.rsolve_conjugate(L) * A.conjugate() ~ L);
-			assert!(A_dec.rsolve_transpose(L) * A.transpose() ~ L);
-			assert!(A_dec.rsolve_adjoint(L) * A.adjoint() ~ L);
+			let cod = A.complete_orthogonal();
+			assert!(cod.rank() == k);
+
+			let mut T = Mat::<c64>::zeros(m, n);
+			T.get_mut(..k, ..k).copy_from(cod.L());
+			assert!(cod.compute_Q() * &T * cod.compute_Z().adjoint() * cod.P() ~ A);
+
+			let svd = A.thin_svd().unwrap();
+			let pinv = Mat::from_fn(n, k, |i, j| svd.V()[(i, j)] * c64::new(1.0 / svd.S()[j].re, 0.0)) * svd.U().get(.., ..k).adjoint();
+
+			let X = cod.solve_lstsq_min_norm(&B);
+			assert!(X ~ &pinv * &B);
+
+			let X = A.conjugate().complete_orthogonal().solve_lstsq_min_norm(&B);
+			assert!(X ~ pinv.conjugate() * &B);
+
+			if m >= n {
+				let mut X = B.to_owned();
+				cod.solve_lstsq_in_place_with_conj(Conj::No, X.as_mut());
+				assert!(X.get(..n, ..) ~ &pinv * &B);
+			}
+
+			// a tolerance close to one only keeps the leading diagonal element of R
+			let cod = CompleteOrthogonal::new_with_tolerance(A.as_ref(), 1.0 - f64::EPSILON);
+			assert!(cod.rank() == Ord::min(k, 1));
 		}
+	}
 
-		test_solver_imp(A, &A_dec)
+	#[test]
+	fn test_updatable_qr() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let rand = |m: usize, n: usize, rng: &mut StdRng| {
+			CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng)
+		};
+
+		let (m, n) = (20, 8);
+		let mut A = rand(m, n, rng);
+		let mut qr = A.qr().to_updatable();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 256.0 * (m as f64));
+
+		let col = rand(m, 1, rng);
+		qr.insert_col(3, col.col(0));
+		A = Mat::from_fn(m, n + 1, |i, j| match j.cmp(&3) {
+			core::cmp::Ordering::Less => A[(i, j)],
+			core::cmp::Ordering::Equal => col[(i, 0)],
+			core::cmp::Ordering::Greater => A[(i, j - 1)],
+		});
+		assert!(qr.reconstruct() ~ A);
+
+		let row = rand(1, n + 1, rng);
+		qr.insert_row(m, row.row(0));
+		A = Mat::from_fn(m + 1, n + 1, |i, j| if i < m { A[(i, j)] } else { row[(0, j)] });
+		assert!(qr.reconstruct() ~ A);
+
+		qr.delete_col(0);
+		A = A.get(.., 1..).to_owned();
+		assert!(qr.reconstruct() ~ A);
+
+		qr.delete_row(5);
+		A = Mat::from_fn(m, n, |i, j| if i < 5 { A[(i, j)] } else { A[(i + 1, j)] });
+		assert!(qr.reconstruct() ~ A);
+
+		let u = rand(m, 1, rng);
+		let v = rand(n, 1, rng);
+		qr.rank_one_update(u.col(0), v.col(0));
+		A = &A + &u * v.adjoint();
+		assert!(qr.reconstruct() ~ A);
+		assert!(all(qr.Q().shape() == (m, n), qr.R().shape() == (n, n)));
+		assert!(qr.Q().adjoint() * qr.Q() ~ Mat::<c64>::identity(n, n));
+
+		let B = rand(m, 3, rng);
+		let mut X = B.to_owned();
+		qr.solve_lstsq_in_place_with_conj(Conj::No, X.as_mut());
+		let mut X_ref = B.to_owned();
+		A.col_piv_qr().solve_lstsq_in_place_with_conj(Conj::No, X_ref.as_mut());
+		assert!(X.get(..n, ..) ~ X_ref.get(..n, ..));
 	}
 
 	#[test]
-	fn test_all_solvers() {
+	fn test_cholesky_update() {
 		let rng = &mut StdRng::seed_from_u64(0);
-		let n = 50;
+		let rand = |m: usize, n: usize, rng: &mut StdRng| {
+			CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng)
+		};
+
+		let n = 12;
+		let X = rand(n + 1, n + 1, rng);
+		let B = &X * X.adjoint() + Mat::<c64>::identity(n + 1, n + 1);
+		let k = 4;
+		let A = Mat::from_fn(n, n, |i, j| B[(i + (i >= k) as usize, j + (j >= k) as usize)]);
+		let W = rand(n, 3, rng);
+		let A_up = &A + &W * W.adjoint();
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 1024.0 * (n as f64));
+
+		let mut llt = A.llt(Side::Lower).unwrap();
+		let mut W_nan = W.clone();
+		W_nan[(1, 1)] = c64::new(f64::NAN, 0.0);
+		assert!(llt.rank_update(W_nan.as_ref()).is_err());
+		assert!(llt.reconstruct() ~ A);
+		llt.rank_update(W.as_ref()).unwrap();
+		assert!(llt.reconstruct() ~ A_up);
+		llt.downdate(W.as_ref()).unwrap();
+		assert!(llt.reconstruct() ~ A);
+		let W_big = &W * Scale(c64::new(100.0, 0.0));
+		assert!(llt.downdate(W_big.as_ref()).is_err());
+		assert!(llt.reconstruct() ~ A);
+
+		llt.insert_row_col(k, B.col(k)).unwrap();
+		assert!(llt.reconstruct() ~ B);
+		llt.remove_row_col(k);
+		assert!(llt.reconstruct() ~ A);
+		let mut col = B.col(k).to_owned();
+		col[k] = c64::ZERO;
+		assert!(llt.insert_row_col(k, col.as_ref()).is_err());
+		assert!(llt.reconstruct() ~ A);
+
+		let mut ldlt = A.ldlt(Side::Lower).unwrap();
+		ldlt.rank_update(W.as_ref()).unwrap();
+		assert!(ldlt.reconstruct() ~ A_up);
+		ldlt.downdate(W.as_ref()).unwrap();
+		assert!(ldlt.reconstruct() ~ A);
+		ldlt.insert_row_col(k, B.col(k)).unwrap();
+		assert!(ldlt.reconstruct() ~ B);
+		ldlt.remove_row_col(k).unwrap();
+		assert!(ldlt.reconstruct() ~ A);
+
+		// the ldlt decomposition can represent indefinite matrices
+		ldlt.downdate(W_big.as_ref()).unwrap();
+		assert!(ldlt.reconstruct() ~ &A - &W_big * W_big.adjoint());
+	}
 
-		let ref A = CwiseMatDistribution {
-			nrows: n,
+	#[test]
+	fn test_gsvd() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let (m, p, n) = (12, 9, 7);
+
+		let A = CwiseMatDistribution {
+			nrows: m,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let B = CwiseMatDistribution {
+			nrows: p,
 			ncols: n,
 			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
 		}
 		.rand::<Mat<c64>>(rng);
-		let A = A.rb();
 
-		test_solver(A, A.partial_piv_lu());
-		test_solver(A, A.full_piv_lu());
-		test_solver(A, A.qr());
-		test_solver(A, A.col_piv_qr());
-		test_solver(A, A.svd().unwrap());
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * ((m + p + n) as f64));
 
-		{
-			let ref A = A * A.adjoint();
-			let A = A.rb();
-			test_solver(A, A.llt(Side::Lower).unwrap());
-			test_solver(A, A.ldlt(Side::Lower).unwrap());
-		}
+		let check = |A: MatRef<'_, c64>, B: MatRef<'_, c64>, gsvd: &Gsvd<c64>| {
+			let mut C = Mat::<c64>::zeros(m, n);
+			let mut S = Mat::<c64>::zeros(p, n);
+			for i in 0..n {
+				C[(i, i)] = gsvd.C()[i];
+				S[(i + p - n, i)] = gsvd.S()[i];
+			}
 
-		{
-			let ref A = A + A.adjoint();
-			let A = A.rb();
-			test_solver(A, A.lblt(Side::Lower));
-			test_solver(A, A.self_adjoint_eigen(Side::Lower).unwrap());
-		}
+			assert!(gsvd.U() * &C * gsvd.X().adjoint() ~ A);
+			assert!(gsvd.V() * &S * gsvd.X().adjoint() ~ B);
+		};
+
+		let gsvd = Gsvd::new(A.as_ref(), B.as_ref()).unwrap();
+		check(A.as_ref(), B.as_ref(), &gsvd);
+
+		let gsvd = Gsvd::new(A.conjugate(), B.conjugate()).unwrap();
+		check(A.conjugate().to_owned().as_ref(), B.conjugate().to_owned().as_ref(), &gsvd);
+
+		let gsvd = Gsvd::new(A.as_ref(), B.conjugate()).unwrap();
+		check(A.as_ref(), B.conjugate().to_owned().as_ref(), &gsvd);
 	}
 
 	#[test]
-	fn test_eigen_cplx() {
+	fn test_schur_real_reorder() {
 		let rng = &mut StdRng::seed_from_u64(0);
 		let n = 50;
 
 		let A = CwiseMatDistribution {
 			nrows: n,
 			ncols: n,
-			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			dist: StandardNormal,
 		}
-		.rand::<Mat<c64>>(rng);
+		.rand::<Mat<f64>>(rng);
 
-		let n = A.nrows();
 		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
 
-		let evd = A.eigen().unwrap();
-		let e = A.eigenvalues().unwrap();
-		assert!(&A * evd.U() ~ evd.U() * evd.S());
-		assert!(evd.S().column_vector() ~ ColRef::from_slice(&e));
+		let mut schur = A.schur_from_real().unwrap();
+		let mut before = schur.S().column_vector().iter().copied().collect::<Vec<_>>();
+		let k = schur.reorder(|e| e.im != 0.0).unwrap();
+
+		let T = schur.T();
+		let Z = schur.Z();
+		assert!(Z * T * Z.transpose() ~ A);
+		for i in 0..n {
+			assert!((schur.S()[i].im != 0.0) == (i < k));
+		}
+
+		let mut after = schur.S().column_vector().iter().copied().collect::<Vec<_>>();
+		let cmp = |a: &c64, b: &c64| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im));
+		before.sort_by(cmp);
+		after.sort_by(cmp);
+		assert!(ColRef::from_slice(&after) ~ ColRef::from_slice(&before));
+
+		let cond = schur.cluster_condition(k);
+		assert!(all(cond.s > 0.0, cond.s <= 1.0, cond.sep > 0.0));
 	}
 
 	#[test]
@@ -2709,4 +5864,175 @@ mod tests {
 		assert!(&A * evd.U() ~ evd.U() * evd.S());
 		assert!(evd.S().column_vector() ~ ColRef::from_slice(&e));
 	}
+
+	#[test]
+	fn test_eigen_condition() {
+		use faer_traits::math_utils::abs;
+
+		let rng = &mut StdRng::seed_from_u64(0);
+		let n = 30;
+
+		let A = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand::<Mat<c64>>(rng);
+		let A_real = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: StandardNormal,
+		}
+		.rand::<Mat<f64>>(rng);
+
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+
+		let check = |A: MatRef<'_, c64>, evd: &Eigen<f64>| {
+			let U = evd.U();
+			let Y = evd.U_left().unwrap();
+			let S = evd.S();
+			let s_cond = evd.eigenvalue_rcond().unwrap();
+			let sep = evd.eigenvector_sep().unwrap();
+
+			assert!(A * U ~ U * S);
+			assert!(Y.adjoint() * A ~ S * Y.adjoint());
+
+			for i in 0..n {
+				assert!((U.col(i).norm_l2() - 1.0).abs() < 1e-10);
+				assert!((Y.col(i).norm_l2() - 1.0).abs() < 1e-10);
+				assert!((abs(&(Y.col(i).adjoint() * U.col(i))) - s_cond[i]).abs() < 1e-8);
+				assert!(all(s_cond[i] > 0.0, s_cond[i] <= 1.0 + 1e-10, sep[i] > 0.0));
+			}
+		};
+
+		check(A.as_ref(), &Eigen::new_with_condition(A.as_ref()).unwrap());
+		check(A.conjugate().to_owned().as_ref(), &Eigen::new_with_condition(A.conjugate()).unwrap());
+		check(
+			Mat::from_fn(n, n, |i, j| c64::from(A_real[(i, j)])).as_ref(),
+			&Eigen::new_from_real_with_condition(A_real.as_ref()).unwrap(),
+		);
+
+		let evd = A.eigen().unwrap();
+		assert!(all(
+			evd.U_left().is_none(),
+			evd.eigenvalue_rcond().is_none(),
+			evd.eigenvector_sep().is_none()
+		));
+	}
+
+	#[test]
+	fn test_hessenberg() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 2, 5, 50] {
+			let A = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+
+			let hess = Hessenberg::new(A.as_ref());
+			let H = hess.H();
+			let Q = hess.compute_Q();
+
+			assert!(&Q * H * Q.adjoint() ~ &A);
+			assert!(Q.adjoint() * &Q ~ Mat::<c64>::identity(n, n));
+			for j in 0..n {
+				for i in j + 2..n {
+					assert!(H[(i, j)] == c64::ZERO);
+				}
+			}
+
+			let mut M = A.clone();
+			hess.apply_Q_adjoint_in_place(M.as_mut());
+			assert!(M ~ Q.adjoint() * &A);
+			hess.apply_Q_in_place(M.as_mut());
+			assert!(M ~ A);
+
+			let hess = Hessenberg::new(A.conjugate());
+			let Q = hess.compute_Q();
+			assert!(&Q * hess.H() * Q.adjoint() ~ A.conjugate().to_owned());
+		}
+	}
+
+	#[test]
+	fn test_tridiag() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 2, 5, 50] {
+			let A = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let A = &A + A.adjoint();
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (n as f64));
+
+			for side in [Side::Lower, Side::Upper] {
+				let trid = Tridiag::new(A.as_ref(), side);
+				let T = trid.compute_T();
+				let Q = trid.compute_Q();
+
+				assert!(&Q * &T * Q.adjoint() ~ &A);
+				assert!(Q.adjoint() * &Q ~ Mat::<c64>::identity(n, n));
+				for i in 0..n {
+					assert!(trid.diag()[i].im == 0.0);
+				}
+
+				let mut M = A.clone();
+				trid.apply_Q_adjoint_in_place(M.as_mut());
+				assert!(M ~ Q.adjoint() * &A);
+				trid.apply_Q_in_place(M.as_mut());
+				assert!(M ~ A);
+			}
+		}
+	}
+
+	#[test]
+	fn test_bidiag() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (m, n) in [(0, 0), (1, 1), (2, 1), (5, 5), (50, 20), (20, 20)] {
+			let A = CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * (m as f64));
+
+			let bid = Bidiag::new(A.as_ref());
+			let B = bid.compute_B();
+			let U = bid.compute_U();
+			let V = bid.compute_V();
+
+			assert!(&U * &B * V.adjoint() ~ &A);
+			assert!(U.adjoint() * &U ~ Mat::<c64>::identity(m, m));
+			assert!(V.adjoint() * &V ~ Mat::<c64>::identity(n, n));
+			assert!(bid.compute_thin_U() ~ U.get(.., ..n));
+
+			let mut M = A.clone();
+			bid.apply_U_adjoint_in_place(M.as_mut());
+			assert!(M ~ U.adjoint() * &A);
+			bid.apply_U_in_place(M.as_mut());
+			assert!(M ~ A);
+
+			let mut M = A.adjoint().to_owned();
+			bid.apply_V_adjoint_in_place(M.as_mut());
+			assert!(M ~ V.adjoint() * A.adjoint());
+			bid.apply_V_in_place(M.as_mut());
+			assert!(M ~ A.adjoint().to_owned());
+
+			let bid = Bidiag::new(A.conjugate());
+			let U = bid.compute_U();
+			let V = bid.compute_V();
+			assert!(&U * bid.compute_B() * V.adjoint() ~ A.conjugate().to_owned());
+		}
+	}
 }
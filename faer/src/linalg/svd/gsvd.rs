@@ -0,0 +1,469 @@
+use crate::assert;
+use crate::internal_prelude::*;
+use linalg::svd::{ComputeSvdVectors, SvdError};
+
+/// computes the size and alignment of the workspace required to compute the generalized svd of a
+/// matrix pair $(A, B)$, where $A$ has shape `(a_nrows, ncols)` and $B$ has shape
+/// `(b_nrows, ncols)`
+pub fn gsvd_scratch<T: ComplexField>(a_nrows: usize, b_nrows: usize, ncols: usize, par: Par) -> StackReq {
+	let m = a_nrows;
+	let p = b_nrows;
+	let n = ncols;
+	let mp = m + p;
+	let q = Ord::min(mp, n);
+
+	if q == 0 {
+		return StackReq::EMPTY;
+	}
+
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(mp, n);
+	let bs_t = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(p, q);
+	let r = Ord::min(p, q);
+
+	StackReq::all_of(&[
+		temp_mat_scratch::<T>(mp, n),
+		temp_mat_scratch::<T>(bs, q),
+		StackReq::new::<usize>(n).array(2),
+		temp_mat_scratch::<T>(mp, q),
+		temp_mat_scratch::<T>(q, n),
+		temp_mat_scratch::<T>(q, q),
+		temp_mat_scratch::<T>(Ord::min(m, q), 1),
+		temp_mat_scratch::<T>(p, q),
+		temp_mat_scratch::<T>(bs_t, r),
+		temp_mat_scratch::<T>(q, n),
+		StackReq::any_of(&[
+			linalg::qr::col_pivoting::factor::qr_in_place_scratch::<usize, T>(mp, n, bs, par, default()),
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(mp, bs, q),
+			linalg::svd::svd_scratch::<T>(m, q, ComputeSvdVectors::Full, ComputeSvdVectors::Full, par, default()),
+			linalg::qr::no_pivoting::factor::qr_in_place_scratch::<T>(p, q, bs_t, par, default()),
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(p, bs_t, p),
+			crate::perm::permute_cols_in_place_scratch::<usize, T>(q, n),
+			StackReq::all_of(&[
+				temp_mat_scratch::<T>(r, q),
+				temp_mat_scratch::<T>(r, r),
+				temp_mat_scratch::<T>(q, q),
+				temp_mat_scratch::<T>(q, 1),
+				temp_mat_scratch::<T>(q, q),
+				temp_mat_scratch::<T>(Ord::max(Ord::max(m, p), q), q),
+				linalg::svd::svd_scratch::<T>(r, q, ComputeSvdVectors::Full, ComputeSvdVectors::Full, par, default()),
+			]),
+		]),
+	])
+}
+
+/// computes the generalized svd of the matrix pair $(A, B)$, where $A$ has shape $(m, n)$ and $B$
+/// has shape $(p, n)$
+///
+/// $$A = U C X^H, \quad B = V S X^H$$
+///
+/// with $q = \min(m + p, n)$:
+///
+/// - $U$ has shape $(m, m)$ and $V$ has shape $(p, p)$, and both are unitary,
+/// - $X$ has shape $(n, q)$, and is invertible if $\begin{bmatrix} A \\\\ B \end{bmatrix}$ is square
+/// and nonsingular,
+/// - $C$ has shape $(m, q)$, and is zero except for the entries $C_{i, i} = c_i$,
+/// - $S$ has shape $(p, q)$, and is zero except for the entries $S_{i + p - q, i} = s_i$,
+/// - $c_i^2 + s_i^2 = 1$, with $c$ sorted in nonincreasing order, and $s$ in nondecreasing order.
+///
+/// the values $c_i$ and $s_i$ are stored in `c` and `s`. the generalized singular values are given
+/// by the ratios $c_i / s_i$
+///
+/// the decomposition is computed from the column pivoted qr decomposition of the stacked matrix
+/// $\begin{bmatrix} A \\\\ B \end{bmatrix}$, followed by a cs decomposition of its orthonormal
+/// factor
+#[math]
+#[track_caller]
+pub fn gsvd<T: ComplexField>(
+	A: MatRef<'_, T>,
+	B: MatRef<'_, T>,
+	c: DiagMut<'_, T>,
+	s: DiagMut<'_, T>,
+	u: Option<MatMut<'_, T>>,
+	v: Option<MatMut<'_, T>>,
+	x: MatMut<'_, T>,
+	par: Par,
+	stack: &mut MemStack,
+) -> Result<(), SvdError> {
+	let m = A.nrows();
+	let p = B.nrows();
+	let n = A.ncols();
+	let mp = m + p;
+	let q = Ord::min(mp, n);
+
+	assert!(all(B.ncols() == n, c.dim() == q, s.dim() == q, x.nrows() == n, x.ncols() == q));
+	if let Some(u) = u.rb() {
+		assert!(all(u.nrows() == m, u.ncols() == m));
+	}
+	if let Some(v) = v.rb() {
+		assert!(all(v.nrows() == p, v.ncols() == p));
+	}
+
+	let mut c = c.column_vector_mut();
+	let mut s = s.column_vector_mut();
+	let mut u = u;
+	let mut v = v;
+	let mut x = x;
+
+	if q == 0 {
+		if let Some(mut u) = u.rb_mut() {
+			u.fill(zero());
+			u.diagonal_mut().fill(one());
+		}
+		if let Some(mut v) = v.rb_mut() {
+			v.fill(zero());
+			v.diagonal_mut().fill(one());
+		}
+		return Ok(());
+	}
+
+	for j in 0..n {
+		for i in 0..m {
+			if !is_finite(A[(i, j)]) {
+				return Err(SvdError::NoConvergence);
+			}
+		}
+		for i in 0..p {
+			if !is_finite(B[(i, j)]) {
+				return Err(SvdError::NoConvergence);
+			}
+		}
+	}
+
+	let bs = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(mp, n);
+	let bs_t = linalg::qr::no_pivoting::factor::recommended_blocksize::<T>(p, q);
+	let r = Ord::min(p, q);
+
+	let (mut QR, stack) = unsafe { temp_mat_uninit::<T, _, _>(mp, n, stack) };
+	let (mut Q_coeff, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs, q, stack) };
+	let (mut perm_fwd, stack) = stack.make_with(n, |_| 0usize);
+	let (mut perm_bwd, stack) = stack.make_with(n, |_| 0usize);
+	let (mut Q, stack) = unsafe { temp_mat_uninit::<T, _, _>(mp, q, stack) };
+	let (mut R, stack) = unsafe { temp_mat_uninit::<T, _, _>(q, n, stack) };
+	let (mut W, stack) = unsafe { temp_mat_uninit::<T, _, _>(q, q, stack) };
+	let (mut sc, stack) = unsafe { temp_mat_uninit::<T, _, _>(Ord::min(m, q), 1, stack) };
+	let (mut T, stack) = unsafe { temp_mat_uninit::<T, _, _>(p, q, stack) };
+	let (mut T_coeff, stack) = unsafe { temp_mat_uninit::<T, _, _>(bs_t, r, stack) };
+
+	let mut QR = QR.as_mat_mut();
+	let mut Q_coeff = Q_coeff.as_mat_mut();
+	let mut Q = Q.as_mat_mut();
+	let mut R = R.as_mat_mut();
+	let mut W = W.as_mat_mut();
+	let mut sc = sc.as_mat_mut().col_mut(0);
+	let mut T = T.as_mat_mut();
+	let mut T_coeff = T_coeff.as_mat_mut();
+
+	// [A; B] P = Q R
+	QR.rb_mut().subrows_mut(0, m).copy_from(A);
+	QR.rb_mut().subrows_mut(m, p).copy_from(B);
+
+	let (_, perm) = linalg::qr::col_pivoting::factor::qr_in_place(QR.rb_mut(), Q_coeff.rb_mut(), &mut perm_fwd, &mut perm_bwd, par, stack, default());
+
+	Q.fill(zero());
+	Q.rb_mut().subrows_mut(0, q).diagonal_mut().fill(one());
+	linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+		QR.rb().subcols(0, q),
+		Q_coeff.rb(),
+		Conj::No,
+		Q.rb_mut(),
+		par,
+		stack,
+	);
+
+	R.fill(zero());
+	R.copy_from_triangular_upper(QR.rb().subrows(0, q));
+
+	let (Q1, Q2) = Q.rb().split_at_row(m);
+
+	// Q1 = U C W^H
+	if m == 0 {
+		W.fill(zero());
+		W.rb_mut().diagonal_mut().fill(one());
+		if let Some(mut u) = u.rb_mut() {
+			u.fill(zero());
+		}
+	} else {
+		linalg::svd::svd(Q1, sc.rb_mut().as_diagonal_mut(), u.rb_mut(), Some(W.rb_mut()), par, stack, default())?;
+	}
+	for i in 0..q {
+		c[i] = if i < m { from_real(real(sc[i])) } else { zero() };
+	}
+
+	// Q2 W = V S, computed from the qr decomposition of Q2 W with its columns in reverse order
+	linalg::matmul::matmul(T.rb_mut().reverse_cols_mut(), Accum::Replace, Q2, W.rb(), one(), par);
+
+	// the columns of W associated with the c_i that are close to one are poorly determined by the
+	// svd of Q1, so the trailing block of the triangular factor of Q2 W is not diagonal to working
+	// precision. it is diagonalized by an svd, and the columns of U, V and W are updated
+	// accordingly, as in the cs decomposition algorithm of van loan
+	let threshold = sqrt(from_f64::<T::Real>(0.5));
+	let mut k = 0;
+	while k < Ord::min(m, q) && real(c[k]) > threshold {
+		k += 1;
+	}
+	let j0 = q - k;
+	let kr = r.saturating_sub(j0);
+
+	s.fill(zero());
+	if p > 0 {
+		linalg::qr::no_pivoting::factor::qr_in_place(T.rb_mut(), T_coeff.rb_mut(), par, stack, default());
+
+		for j in 0..r {
+			s[q - 1 - j] = from_real(abs(T[(j, j)]));
+		}
+
+		if let Some(mut v) = v.rb_mut() {
+			let mut v = v.rb_mut().reverse_cols_mut();
+			v.fill(zero());
+			v.rb_mut().diagonal_mut().fill(one());
+			linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+				T.rb().subcols(0, r),
+				T_coeff.rb(),
+				Conj::No,
+				v.rb_mut(),
+				par,
+				stack,
+			);
+
+			for j in 0..Ord::min(r, j0) {
+				let d = copy(T[(j, j)]);
+				let norm = abs(d);
+				if norm > zero() {
+					let phase = mul_real(d, recip(norm));
+					z!(v.rb_mut().col_mut(j)).for_each(|uz!(x)| *x = *x * phase);
+				}
+			}
+		}
+	}
+
+	if k > 0 && kr > 0 {
+		let kmin = Ord::min(kr, k);
+
+		let (mut Rb, stack) = unsafe { temp_mat_uninit::<T, _, _>(kr, k, stack) };
+		let (mut Vb, stack) = unsafe { temp_mat_uninit::<T, _, _>(kr, kr, stack) };
+		let (mut Zb, stack) = unsafe { temp_mat_uninit::<T, _, _>(k, k, stack) };
+		let (mut sigma, stack) = unsafe { temp_mat_uninit::<T, _, _>(kmin, 1, stack) };
+		let (mut M, stack) = unsafe { temp_mat_uninit::<T, _, _>(k, k, stack) };
+		let (mut tmp, stack) = unsafe { temp_mat_uninit::<T, _, _>(Ord::max(Ord::max(m, p), q), k, stack) };
+
+		let mut Rb = Rb.as_mat_mut();
+		let mut Vb = Vb.as_mat_mut();
+		let mut Zb = Zb.as_mat_mut();
+		let mut sigma = sigma.as_mat_mut().col_mut(0);
+		let mut M = M.as_mat_mut();
+		let mut tmp = tmp.as_mat_mut();
+
+		// R22 = Vb sigma Zb^H
+		Rb.fill(zero());
+		Rb.copy_from_triangular_upper(T.rb().submatrix(j0, j0, kr, k));
+		linalg::svd::svd(
+			Rb.rb(),
+			sigma.rb_mut().as_diagonal_mut(),
+			if v.is_some() { Some(Vb.rb_mut()) } else { None },
+			Some(Zb.rb_mut()),
+			par,
+			stack,
+			default(),
+		)?;
+
+		// the local indices of the cluster run in reverse order with respect to c and s
+		for jj in 0..k {
+			s[k - 1 - jj] = if jj < kmin { from_real(real(sigma[jj])) } else { zero() };
+		}
+
+		{
+			let mut Wc = W.rb_mut().reverse_cols_mut().subcols_mut(j0, k);
+			let mut tmp = tmp.rb_mut().subrows_mut(0, q);
+			linalg::matmul::matmul(tmp.rb_mut(), Accum::Replace, Wc.rb(), Zb.rb(), one(), par);
+			Wc.copy_from(tmp.rb());
+		}
+
+		if let Some(mut v) = v.rb_mut() {
+			let mut Vc = v.rb_mut().reverse_cols_mut().subcols_mut(j0, kr);
+			let mut tmp = tmp.rb_mut().submatrix_mut(0, 0, p, kr);
+			linalg::matmul::matmul(tmp.rb_mut(), Accum::Replace, Vc.rb(), Vb.rb(), one(), par);
+			Vc.copy_from(tmp.rb());
+		}
+
+		// Q1 W Zb = U C Zb, whose columns are orthogonal to working precision since the c_i are
+		// bounded away from zero, so U and C are recovered by normalizing them
+		for b in 0..k {
+			for a in 0..k {
+				M[(a, b)] = mul_real(Zb[(a, b)], real(c[k - 1 - a]));
+			}
+		}
+		for b in 0..k {
+			let norm = M.rb().col(b).norm_l2();
+			c[k - 1 - b] = from_real(norm);
+			let inv = recip(norm);
+			z!(M.rb_mut().col_mut(b)).for_each(|uz!(x)| *x = mul_real(*x, inv));
+		}
+
+		if let Some(mut u) = u.rb_mut() {
+			let mut Uc = u.rb_mut().subcols_mut(0, k).reverse_cols_mut();
+			let mut tmp = tmp.rb_mut().subrows_mut(0, m);
+			linalg::matmul::matmul(tmp.rb_mut(), Accum::Replace, Uc.rb(), M.rb(), one(), par);
+			Uc.copy_from(tmp.rb());
+		}
+	}
+
+	// the smaller of c_i and s_i is computed accurately, so the larger one is recomputed from it,
+	// which keeps c_i^2 + s_i^2 = 1 to working precision even when one of them is close to one
+	for i in 0..q {
+		let ci = real(c[i]);
+		let si = real(s[i]);
+		if ci <= si {
+			s[i] = from_real(sqrt((one::<T::Real>() - ci) * (one::<T::Real>() + ci)));
+		} else {
+			c[i] = from_real(sqrt((one::<T::Real>() - si) * (one::<T::Real>() + si)));
+		}
+	}
+
+	// X^H = W^H R P^T
+	let (mut XH, stack) = unsafe { temp_mat_uninit::<T, _, _>(q, n, stack) };
+	let mut XH = XH.as_mat_mut();
+	linalg::matmul::matmul(XH.rb_mut(), Accum::Replace, W.rb().adjoint(), R.rb(), one(), par);
+	crate::perm::permute_cols_in_place(XH.rb_mut(), perm.inverse(), stack);
+	x.copy_from(XH.rb().adjoint());
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+	use dyn_stack::MemBuffer;
+
+	#[test]
+	fn test_gsvd() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (m, p, n) in [(10, 8, 6), (3, 7, 6), (8, 3, 6), (2, 3, 8), (6, 0, 4), (0, 6, 4), (5, 5, 0), (1, 1, 1)] {
+			let A = CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+			let B = CwiseMatDistribution {
+				nrows: p,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand::<Mat<c64>>(rng);
+
+			let q = Ord::min(m + p, n);
+
+			let mut c = Diag::zeros(q);
+			let mut s = Diag::zeros(q);
+			let mut U = Mat::zeros(m, m);
+			let mut V = Mat::zeros(p, p);
+			let mut X = Mat::zeros(n, q);
+
+			gsvd(
+				A.as_ref(),
+				B.as_ref(),
+				c.as_mut(),
+				s.as_mut(),
+				Some(U.as_mut()),
+				Some(V.as_mut()),
+				X.as_mut(),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(gsvd_scratch::<c64>(m, p, n, Par::Seq))),
+			)
+			.unwrap();
+
+			let mut C = Mat::<c64>::zeros(m, q);
+			let mut S = Mat::<c64>::zeros(p, q);
+			for i in 0..q {
+				if i < m {
+					C[(i, i)] = c[i];
+				}
+				if i + p >= q {
+					S[(i + p - q, i)] = s[i];
+				}
+			}
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 128.0 * ((m + p + n) as f64));
+			assert!(&U * &C * X.adjoint() ~ A);
+			assert!(&V * &S * X.adjoint() ~ B);
+			assert!(U.adjoint() * &U ~ Mat::<c64>::identity(m, m));
+			assert!(V.adjoint() * &V ~ Mat::<c64>::identity(p, p));
+
+			for i in 0..q {
+				assert!((c[i].re * c[i].re + s[i].re * s[i].re - 1.0).abs() <= 4.0 * f64::EPSILON);
+				if i > 0 {
+					assert!(c[i - 1].re >= c[i].re);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_gsvd_graded() {
+		// when B is much smaller or much larger than A, the c_i or the s_i are all close to one
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for (m, p, n) in [(30, 30, 30), (50, 20, 40), (20, 50, 40)] {
+			for scale in [1e-8, 1e8] {
+				let A = CwiseMatDistribution {
+					nrows: m,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.rand::<Mat<f64>>(rng);
+				let B = CwiseMatDistribution {
+					nrows: p,
+					ncols: n,
+					dist: StandardNormal,
+				}
+				.rand::<Mat<f64>>(rng)
+					* Scale(scale);
+
+				let q = Ord::min(m + p, n);
+
+				let mut c = Diag::zeros(q);
+				let mut s = Diag::zeros(q);
+				let mut U = Mat::zeros(m, m);
+				let mut V = Mat::zeros(p, p);
+				let mut X = Mat::zeros(n, q);
+
+				gsvd(
+					A.as_ref(),
+					B.as_ref(),
+					c.as_mut(),
+					s.as_mut(),
+					Some(U.as_mut()),
+					Some(V.as_mut()),
+					X.as_mut(),
+					Par::Seq,
+					MemStack::new(&mut MemBuffer::new(gsvd_scratch::<f64>(m, p, n, Par::Seq))),
+				)
+				.unwrap();
+
+				for i in 0..q {
+					assert!((c[i] * c[i] + s[i] * s[i] - 1.0).abs() <= 2.0 * f64::EPSILON);
+				}
+
+				let mut C = Mat::<f64>::zeros(m, q);
+				let mut S = Mat::<f64>::zeros(p, q);
+				for i in 0..q {
+					if i < m {
+						C[(i, i)] = c[i];
+					}
+					if i + p >= q {
+						S[(i + p - q, i)] = s[i];
+					}
+				}
+
+				// the backward error is relative to the stacked matrix [A; B]
+				let tol = 128.0 * ((m + p + n) as f64) * f64::EPSILON * f64::max(A.norm_max(), B.norm_max());
+				assert!((&U * &C * X.transpose() - &A).norm_max() <= tol);
+				assert!((&V * &S * X.transpose() - &B).norm_max() <= tol);
+			}
+		}
+	}
+}
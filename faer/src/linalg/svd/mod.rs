@@ -19,6 +19,11 @@ use crate::internal_prelude::*;
 /// bidiagonalization
 pub mod bidiag;
 pub(crate) mod bidiag_svd;
+/// generalized svd of a matrix pair
+pub mod gsvd;
+/// randomized low rank svd
+#[cfg(feature = "rand")]
+pub mod randomized;
 
 /// whether the singular vectors should be computed
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -555,6 +560,7 @@ pub fn svd<T: ComplexField>(
 		if let Some(mut u) = u.rb_mut() {
 			u.rb_mut().subrows_mut(n, m - n).fill(zero());
 			if u.ncols() == m {
+				u.rb_mut().submatrix_mut(0, n, n, m - n).fill(zero());
 				u.rb_mut().submatrix_mut(n, n, m - n, m - n).diagonal_mut().fill(one());
 			}
 
@@ -876,6 +882,54 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_full_uninit_output() {
+		// the full singular vector outputs must be completely overwritten, including the blocks
+		// that are not touched by the qr-based path for tall and wide matrices
+		let rng = &mut StdRng::seed_from_u64(0);
+		let params = Spec::new(SvdParams {
+			qr_ratio_threshold: 1.0,
+			..auto!(f64)
+		});
+
+		for (m, n) in [(40, 10), (10, 40)] {
+			let mat = CwiseMatDistribution {
+				nrows: m,
+				ncols: n,
+				dist: StandardNormal,
+			}
+			.rand::<Mat<f64>>(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::<f64>::eps() * 64.0);
+
+			let mut s = Mat::zeros(m, n);
+			let mut u = Mat::full(m, m, 1.0);
+			let mut v = Mat::full(n, n, 1.0);
+
+			svd(
+				mat.as_ref(),
+				s.as_mut().diagonal_mut(),
+				Some(u.as_mut()),
+				Some(v.as_mut()),
+				Par::Seq,
+				MemStack::new(&mut MemBuffer::new(svd_scratch::<f64>(
+					m,
+					n,
+					ComputeSvdVectors::Full,
+					ComputeSvdVectors::Full,
+					Par::Seq,
+					params,
+				))),
+				params,
+			)
+			.unwrap();
+
+			assert!(&u * &s * v.transpose() ~ mat);
+			assert!(u.transpose() * &u ~ Mat::<f64>::identity(m, m));
+			assert!(v.transpose() * &v ~ Mat::<f64>::identity(n, n));
+		}
+	}
+
 	#[test]
 	fn test_special() {
 		for (m, n) in [
@@ -0,0 +1,575 @@
+//! randomized low rank svd
+//!
+//! given a linear operator $A$ of shape $(m, n)$ and a target rank $k \ll \min(m, n)$, the
+//! algorithms in this module compute a truncated decomposition $A \approx U S V^H$, where $U$ has
+//! shape $(m, k)$, $V$ has shape $(n, k)$, both have orthonormal columns, and $S$ is a nonnegative
+//! diagonal matrix sorted in nonincreasing order
+//!
+//! they follow the randomized range finder of halko, martinsson and tropp. the range of $A$ is
+//! sampled by applying it to a gaussian test matrix $\Omega$, with a few extra columns for
+//! oversampling, and optionally refined with power iterations $(A A^H)^q A \Omega$. an orthonormal
+//! basis $Q$ of the samples is then used to form the small matrix $Q^H A$, whose svd is computed
+//! with a dense algorithm
+//!
+//! the operator is only accessed through products with $A$ and $A^H$, so the algorithms work for
+//! dense matrices, sparse matrices, and any other type implementing the matrix free operator trait
+#![cfg_attr(feature = "unstable", doc = "[`BiLinOp`](crate::matrix_free::BiLinOp)")]
+
+use crate::assert;
+use crate::internal_prelude::*;
+#[cfg(feature = "unstable")]
+use crate::matrix_free::BiLinOp;
+use crate::stats::CwiseMatDistribution;
+use dyn_stack::MemBuffer;
+use linalg::householder;
+use linalg::matmul::matmul;
+use linalg::qr::no_pivoting::factor as qr;
+use linalg::svd::{ComputeSvdVectors, SvdError, SvdParams};
+use rand::distributions::Distribution;
+
+/// randomized svd tuning parameters
+#[derive(Debug, Copy, Clone)]
+pub struct RandomizedSvdParams {
+	/// number of random samples drawn in addition to the target rank
+	pub oversampling: usize,
+	/// number of power iterations, each of which applies $A A^H$ to the samples
+	pub power_iterations: usize,
+	/// number of random samples drawn at each step of the adaptive algorithm
+	pub block_size: usize,
+	/// parameters of the svd of the projected matrix
+	pub svd: SvdParams,
+
+	#[doc(hidden)]
+	pub non_exhaustive: NonExhaustive,
+}
+
+impl<T: ComplexField> Auto<T> for RandomizedSvdParams {
+	fn auto() -> Self {
+		Self {
+			oversampling: 10,
+			power_iterations: 2,
+			block_size: 16,
+			svd: auto!(T),
+			non_exhaustive: NonExhaustive(()),
+		}
+	}
+}
+
+/// truncated svd $A \approx U S V^H$ computed by a randomized algorithm
+#[derive(Clone, Debug)]
+pub struct RandomizedSvd<T> {
+	U: Mat<T>,
+	S: Diag<T>,
+	V: Mat<T>,
+}
+
+impl<T: ComplexField> RandomizedSvd<T> {
+	/// returns the rank $k$ of the approximation
+	#[inline]
+	pub fn rank(&self) -> usize {
+		self.S.dim()
+	}
+
+	/// returns the factor $U$, of shape $(m, k)$
+	#[inline]
+	pub fn U(&self) -> MatRef<'_, T> {
+		self.U.as_ref()
+	}
+
+	/// returns the singular values $S$, sorted in nonincreasing order
+	#[inline]
+	pub fn S(&self) -> DiagRef<'_, T> {
+		self.S.as_ref()
+	}
+
+	/// returns the factor $V$, of shape $(n, k)$
+	#[inline]
+	pub fn V(&self) -> MatRef<'_, T> {
+		self.V.as_ref()
+	}
+
+	/// returns the low rank approximation $U S V^H$
+	#[math]
+	pub fn reconstruct(&self) -> Mat<T> {
+		let S = self.S.column_vector();
+		let US = Mat::from_fn(self.U.nrows(), self.rank(), |i, j| mul_real(self.U[(i, j)], real(S[j])));
+		US * self.V.adjoint()
+	}
+}
+
+/// linear operator, accessed through its products with $A$ and $A^H$
+struct Operator<'a, T> {
+	nrows: usize,
+	ncols: usize,
+	apply: &'a dyn Fn(MatMut<'_, T>, MatRef<'_, T>, Par),
+	adjoint_apply: &'a dyn Fn(MatMut<'_, T>, MatRef<'_, T>, Par),
+}
+
+fn sample<T>(nrows: usize, ncols: usize, rng: &mut (impl ?Sized + rand::Rng), dist: &impl Distribution<T>) -> Mat<T> {
+	CwiseMatDistribution { nrows, ncols, dist }.sample(rng)
+}
+
+/// computes an orthonormal basis $Q$ of the column space of $Y$, which is clobbered
+fn orthonormalize<T: ComplexField>(Y: MatMut<'_, T>, Q: MatMut<'_, T>, par: Par) {
+	let mut Y = Y;
+	let mut Q = Q;
+	let (m, k) = Y.shape();
+	if k == 0 {
+		return;
+	}
+
+	let bs = qr::recommended_blocksize::<T>(m, k);
+	let mut H = Mat::<T>::zeros(bs, k);
+	let mut mem = MemBuffer::new(StackReq::any_of(&[
+		qr::qr_in_place_scratch::<T>(m, k, bs, par, default()),
+		householder::apply_block_householder_sequence_on_the_left_in_place_scratch::<T>(m, bs, k),
+	]));
+	let stack = MemStack::new(&mut mem);
+
+	qr::qr_in_place(Y.rb_mut(), H.as_mut(), par, stack, default());
+
+	Q.fill(zero());
+	Q.rb_mut().diagonal_mut().column_vector_mut().fill(one());
+	householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(Y.rb(), H.as_ref(), Conj::No, Q.rb_mut(), par, stack);
+}
+
+/// removes the components of the columns of $Y$ along the orthonormal columns of $Q$
+#[math]
+fn project_out<T: ComplexField>(Y: MatMut<'_, T>, Q: MatRef<'_, T>, par: Par) {
+	let mut Y = Y;
+	if Q.ncols() == 0 {
+		return;
+	}
+	let mut tmp = Mat::<T>::zeros(Q.ncols(), Y.ncols());
+	// two passes, to make the result orthogonal to working precision
+	for _ in 0..2 {
+		matmul(tmp.as_mut(), Accum::Replace, Q.adjoint(), Y.rb(), one(), par);
+		matmul(Y.rb_mut(), Accum::Add, Q, tmp.as_ref(), -one::<T>(), par);
+	}
+}
+
+/// applies $(A A^H)^q$ to the samples $Y$, re-orthonormalizing between each product
+///
+/// the components along the orthonormal columns of `basis` are removed after each product, since
+/// they would otherwise dominate the samples
+fn power_iterations<T: ComplexField>(A: &Operator<'_, T>, Y: MatMut<'_, T>, basis: MatRef<'_, T>, q: usize, par: Par) {
+	let mut Y = Y;
+	let (m, k) = Y.shape();
+	let n = A.ncols;
+	if q == 0 {
+		return;
+	}
+
+	let mut Q = Mat::<T>::zeros(m, k);
+	let mut Z = Mat::<T>::zeros(n, k);
+	let mut W = Mat::<T>::zeros(n, k);
+	for _ in 0..q {
+		orthonormalize(Y.rb_mut(), Q.as_mut(), par);
+		(A.adjoint_apply)(Z.as_mut(), Q.as_ref(), par);
+		orthonormalize(Z.as_mut(), W.as_mut(), par);
+		(A.apply)(Y.rb_mut(), W.as_ref(), par);
+		project_out(Y.rb_mut(), basis, par);
+	}
+}
+
+/// computes the svd of $Q Q^H A$ and truncates it to the given rank
+fn svd_of_projection<T: ComplexField>(
+	A: &Operator<'_, T>,
+	Q: MatRef<'_, T>,
+	rank: usize,
+	par: Par,
+	params: SvdParams,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	let m = A.nrows;
+	let n = A.ncols;
+	let l = Q.ncols();
+
+	if l == 0 {
+		return Ok(RandomizedSvd {
+			U: Mat::zeros(m, 0),
+			S: Diag::zeros(0),
+			V: Mat::zeros(n, 0),
+		});
+	}
+
+	// $B = A^H Q = U_B S V_B^H$, so that $Q Q^H A = (Q V_B) S U_B^H$
+	let mut B = Mat::<T>::zeros(n, l);
+	(A.adjoint_apply)(B.as_mut(), Q, par);
+
+	let mut s = Diag::<T>::zeros(l);
+	let mut u = Mat::<T>::zeros(n, l);
+	let mut v = Mat::<T>::zeros(l, l);
+	linalg::svd::svd(
+		B.as_ref(),
+		s.as_mut(),
+		Some(u.as_mut()),
+		Some(v.as_mut()),
+		par,
+		MemStack::new(&mut MemBuffer::new(linalg::svd::svd_scratch::<T>(
+			n,
+			l,
+			ComputeSvdVectors::Thin,
+			ComputeSvdVectors::Thin,
+			par,
+			params.into(),
+		))),
+		params.into(),
+	)?;
+
+	let mut U = Mat::<T>::zeros(m, rank);
+	matmul(U.as_mut(), Accum::Replace, Q, v.get(.., ..rank), one(), par);
+
+	Ok(RandomizedSvd {
+		U,
+		S: s.column_vector().get(..rank).to_owned().into_diagonal(),
+		V: u.get(.., ..rank).to_owned(),
+	})
+}
+
+#[track_caller]
+fn randomized_svd_imp<T: ComplexField>(
+	A: &Operator<'_, T>,
+	rank: usize,
+	sample: &mut dyn FnMut(usize, usize) -> Mat<T>,
+	par: Par,
+	params: RandomizedSvdParams,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	let m = A.nrows;
+	let n = A.ncols;
+	let size = Ord::min(m, n);
+	assert!(rank <= size);
+
+	let l = Ord::min(rank.saturating_add(params.oversampling), size);
+	let Omega = sample(n, l);
+
+	let mut Y = Mat::<T>::zeros(m, l);
+	(A.apply)(Y.as_mut(), Omega.as_ref(), par);
+	power_iterations(A, Y.as_mut(), Mat::zeros(m, 0).as_ref(), params.power_iterations, par);
+
+	let mut Q = Mat::<T>::zeros(m, l);
+	orthonormalize(Y.as_mut(), Q.as_mut(), par);
+
+	svd_of_projection(A, Q.as_ref(), rank, par, params.svd)
+}
+
+#[math]
+#[track_caller]
+fn randomized_svd_adaptive_imp<T: ComplexField>(
+	A: &Operator<'_, T>,
+	tolerance: T::Real,
+	max_rank: usize,
+	sample: &mut dyn FnMut(usize, usize) -> Mat<T>,
+	par: Par,
+	params: RandomizedSvdParams,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	let m = A.nrows;
+	let n = A.ncols;
+	assert!(max_rank <= Ord::min(m, n));
+
+	let b = Ord::max(params.block_size, 1);
+
+	// $\|(I - Q Q^H) A\| \le 10 \sqrt{2 / \pi} \max_i \|(I - Q Q^H) A \omega_i\|$
+	let factor = from_f64::<T::Real>(10.0 * core::f64::consts::FRAC_2_SQRT_PI * core::f64::consts::FRAC_1_SQRT_2);
+
+	let mut Q = Mat::<T>::zeros(m, max_rank);
+	let mut Y = Mat::<T>::zeros(m, b);
+	let mut W = Mat::<T>::zeros(m, b);
+	let mut rank = 0;
+
+	while rank < max_rank {
+		let Omega = sample(n, b);
+		(A.apply)(Y.as_mut(), Omega.as_ref(), par);
+		project_out(Y.as_mut(), Q.get(.., ..rank), par);
+
+		let mut err = zero::<T::Real>();
+		for j in 0..b {
+			err = max(err, Y.col(j).norm_l2());
+		}
+		if factor * err <= tolerance {
+			break;
+		}
+
+		power_iterations(A, Y.as_mut(), Q.get(.., ..rank), params.power_iterations, par);
+
+		let c = Ord::min(b, max_rank - rank);
+		let mut W = W.get_mut(.., ..c);
+		orthonormalize(Y.get_mut(.., ..c), W.rb_mut(), par);
+
+		// directions that were numerically zero in the samples are filled arbitrarily by the
+		// orthonormalization, so they are projected out again
+		project_out(W.rb_mut(), Q.get(.., ..rank), par);
+		orthonormalize(W, Q.get_mut(.., rank..rank + c), par);
+
+		rank += c;
+	}
+
+	svd_of_projection(A, Q.get(.., ..rank), rank, par, params.svd)
+}
+
+/// computes a rank $k$ approximation $A \approx U S V^H$ of the matrix $A$
+///
+/// `standard_normal` is used to draw the entries of the gaussian test matrix, and should sample
+/// from the standard normal distribution, e.g., [`rand_distr::StandardNormal`] for real types, or
+/// [`num_complex::ComplexDistribution`] for complex types
+///
+/// the error of the approximation is close to the $(k + 1)$-th singular value of $A$ when the
+/// singular values decay quickly. otherwise, increasing the number of power iterations improves
+/// the accuracy
+#[track_caller]
+pub fn randomized_svd<T: ComplexField>(
+	A: MatRef<'_, T>,
+	rank: usize,
+	rng: &mut (impl ?Sized + rand::Rng),
+	standard_normal: impl Distribution<T>,
+	par: Par,
+	params: Spec<RandomizedSvdParams, T>,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	randomized_svd_imp(
+		&Operator {
+			nrows: A.nrows(),
+			ncols: A.ncols(),
+			apply: &|out, rhs, par| matmul(out, Accum::Replace, A, rhs, one(), par),
+			adjoint_apply: &|out, rhs, par| matmul(out, Accum::Replace, A.adjoint(), rhs, one(), par),
+		},
+		rank,
+		&mut |nrows, ncols| sample(nrows, ncols, rng, &standard_normal),
+		par,
+		params.config,
+	)
+}
+
+/// computes a low rank approximation $A \approx U S V^H$ of the matrix $A$, whose spectral norm
+/// error is estimated to be at most `tolerance`, and whose rank is at most `max_rank`
+///
+/// the basis of the range of $A$ is grown by blocks of `params.block_size` samples, until the
+/// error of the approximation, estimated from the next block of samples, falls below the tolerance.
+/// the estimate holds with probability at least $1 - 10^{-b}$, where $b$ is the block size
+///
+/// `standard_normal` is used to draw the entries of the gaussian test matrices, and should sample
+/// from the standard normal distribution
+#[track_caller]
+pub fn randomized_svd_adaptive<T: ComplexField>(
+	A: MatRef<'_, T>,
+	tolerance: T::Real,
+	max_rank: usize,
+	rng: &mut (impl ?Sized + rand::Rng),
+	standard_normal: impl Distribution<T>,
+	par: Par,
+	params: Spec<RandomizedSvdParams, T>,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	randomized_svd_adaptive_imp(
+		&Operator {
+			nrows: A.nrows(),
+			ncols: A.ncols(),
+			apply: &|out, rhs, par| matmul(out, Accum::Replace, A, rhs, one(), par),
+			adjoint_apply: &|out, rhs, par| matmul(out, Accum::Replace, A.adjoint(), rhs, one(), par),
+		},
+		tolerance,
+		max_rank,
+		&mut |nrows, ncols| sample(nrows, ncols, rng, &standard_normal),
+		par,
+		params.config,
+	)
+}
+
+/// computes a rank $k$ approximation $A \approx U S V^H$ of the operator $A$
+///
+/// see [`randomized_svd`] for more details
+#[cfg(feature = "unstable")]
+#[track_caller]
+pub fn randomized_svd_matrix_free<T: ComplexField>(
+	A: impl BiLinOp<T>,
+	rank: usize,
+	rng: &mut (impl ?Sized + rand::Rng),
+	standard_normal: impl Distribution<T>,
+	par: Par,
+	params: Spec<RandomizedSvdParams, T>,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	randomized_svd_imp(
+		&Operator {
+			nrows: A.nrows(),
+			ncols: A.ncols(),
+			apply: &|out, rhs, par| A.apply(out, rhs, par, MemStack::new(&mut MemBuffer::new(A.apply_scratch(rhs.ncols(), par)))),
+			adjoint_apply: &|out, rhs, par| {
+				A.adjoint_apply(
+					out,
+					rhs,
+					par,
+					MemStack::new(&mut MemBuffer::new(A.transpose_apply_scratch(rhs.ncols(), par))),
+				)
+			},
+		},
+		rank,
+		&mut |nrows, ncols| sample(nrows, ncols, rng, &standard_normal),
+		par,
+		params.config,
+	)
+}
+
+/// computes a low rank approximation $A \approx U S V^H$ of the operator $A$, whose spectral norm
+/// error is estimated to be at most `tolerance`, and whose rank is at most `max_rank`
+///
+/// see [`randomized_svd_adaptive`] for more details
+#[cfg(feature = "unstable")]
+#[track_caller]
+pub fn randomized_svd_adaptive_matrix_free<T: ComplexField>(
+	A: impl BiLinOp<T>,
+	tolerance: T::Real,
+	max_rank: usize,
+	rng: &mut (impl ?Sized + rand::Rng),
+	standard_normal: impl Distribution<T>,
+	par: Par,
+	params: Spec<RandomizedSvdParams, T>,
+) -> Result<RandomizedSvd<T>, SvdError> {
+	randomized_svd_adaptive_imp(
+		&Operator {
+			nrows: A.nrows(),
+			ncols: A.ncols(),
+			apply: &|out, rhs, par| A.apply(out, rhs, par, MemStack::new(&mut MemBuffer::new(A.apply_scratch(rhs.ncols(), par)))),
+			adjoint_apply: &|out, rhs, par| {
+				A.adjoint_apply(
+					out,
+					rhs,
+					par,
+					MemStack::new(&mut MemBuffer::new(A.transpose_apply_scratch(rhs.ncols(), par))),
+				)
+			},
+		},
+		tolerance,
+		max_rank,
+		&mut |nrows, ncols| sample(nrows, ncols, rng, &standard_normal),
+		par,
+		params.config,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+
+	#[math]
+	fn low_rank<T: ComplexField>(m: usize, n: usize, s: &[f64], rng: &mut StdRng, standard_normal: impl Distribution<T> + Copy) -> Mat<T> {
+		let k = s.len();
+		let U = UnitaryMat { dim: m, standard_normal }.sample(rng);
+		let V = UnitaryMat { dim: n, standard_normal }.sample(rng);
+		let US = Mat::from_fn(m, k, |i, j| mul_real(U[(i, j)], from_f64::<T::Real>(s[j])));
+		US * V.get(.., ..k).adjoint()
+	}
+
+	#[test]
+	fn test_randomized_svd() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let (m, n) = (120, 80);
+		let s = (0..40).map(|i| 0.5f64.powi(i)).collect::<Vec<_>>();
+		let A = low_rank::<f64>(m, n, &s, rng, StandardNormal);
+
+		for k in [0, 1, 5, 10] {
+			let svd = randomized_svd(A.as_ref(), k, rng, StandardNormal, Par::Seq, default()).unwrap();
+			assert!(svd.rank() == k);
+
+			let approx_eq = CwiseMat(ApproxEq {
+				abs_tol: 1e-12,
+				rel_tol: 1e-10,
+			});
+			let S = Col::from_fn(k, |j| s[j]);
+			assert!(svd.S().column_vector().as_mat() ~ S.as_mat());
+			assert!(svd.U().adjoint() * svd.U() ~ Mat::<f64>::identity(k, k));
+			assert!(svd.V().adjoint() * svd.V() ~ Mat::<f64>::identity(k, k));
+
+			let err = (&A - svd.reconstruct()).norm_l2();
+			let tail = s[k..].iter().map(|x| x * x).sum::<f64>().sqrt();
+			assert!(err <= 1.0001 * tail);
+		}
+	}
+
+	#[test]
+	fn test_randomized_svd_cplx() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let (m, n) = (60, 90);
+		let s = (0..20).map(|i| 0.25f64.powi(i)).collect::<Vec<_>>();
+		let dist = ComplexDistribution::new(StandardNormal, StandardNormal);
+		let A = low_rank::<c64>(m, n, &s, rng, dist);
+
+		let k = 8;
+		let svd = randomized_svd(A.as_ref(), k, rng, dist, Par::Seq, default()).unwrap();
+
+		let approx_eq = CwiseMat(ApproxEq {
+			abs_tol: 1e-12,
+			rel_tol: 1e-10,
+		});
+		let S = Col::from_fn(k, |j| c64::new(s[j], 0.0));
+		assert!(svd.S().column_vector().as_mat() ~ S.as_mat());
+		assert!(svd.U().adjoint() * svd.U() ~ Mat::<c64>::identity(k, k));
+		assert!(svd.V().adjoint() * svd.V() ~ Mat::<c64>::identity(k, k));
+
+		let err = (&A - svd.reconstruct()).norm_l2();
+		assert!(err <= 2.0 * s[k]);
+	}
+
+	#[cfg(feature = "unstable")]
+	#[test]
+	fn test_randomized_svd_sparse() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let (m, n) = (200, 150);
+
+		let mut triplets = vec![];
+		for j in 0..n {
+			for _ in 0..3 {
+				let i = rng.gen_range(0..m);
+				let v: f64 = StandardNormal.sample(rng);
+				triplets.push(crate::sparse::Triplet::new(i, j, v));
+			}
+		}
+		let A = crate::sparse::SparseColMat::<usize, f64>::try_new_from_triplets(m, n, &triplets).unwrap();
+		let dense = A.to_dense();
+		let exact = dense.singular_values().unwrap();
+
+		let k = 5;
+		let mut params: RandomizedSvdParams = auto!(f64);
+		params.oversampling = 40;
+		params.power_iterations = 8;
+		let svd = randomized_svd_matrix_free(A.as_ref(), k, rng, StandardNormal, Par::Seq, params.into()).unwrap();
+
+		for j in 0..k {
+			assert!((svd.S().column_vector()[j] - exact[j]).abs() <= 1e-6 * exact[0]);
+		}
+		let err = (&dense - svd.reconstruct()).norm_l2();
+		let tail = exact[k..].iter().map(|x| x * x).sum::<f64>().sqrt();
+		assert!(err <= 1.01 * tail);
+	}
+
+	#[test]
+	fn test_randomized_svd_adaptive() {
+		let rng = &mut StdRng::seed_from_u64(0);
+		let (m, n) = (150, 100);
+		let s = (0..60).map(|i| 0.7f64.powi(i)).collect::<Vec<_>>();
+		let A = low_rank::<f64>(m, n, &s, rng, StandardNormal);
+
+		for tol in [1e-2, 1e-5, 1e-8] {
+			let svd = randomized_svd_adaptive(A.as_ref(), tol, 100, rng, StandardNormal, Par::Seq, default()).unwrap();
+			let k = svd.rank();
+			// the basis grows by whole blocks
+			assert!(k <= s.len() + 16);
+			if k < s.len() {
+				assert!(s[k] <= tol);
+			}
+
+			let err = (&A - svd.reconstruct()).singular_values().unwrap()[0];
+			assert!(err <= tol);
+		}
+
+		// the rank is capped
+		let svd = randomized_svd_adaptive(A.as_ref(), 0.0, 20, rng, StandardNormal, Par::Seq, default()).unwrap();
+		assert!(svd.rank() == 20);
+
+		// exact low rank operator
+		let A = low_rank::<f64>(m, n, &s[..10], rng, StandardNormal);
+		let svd = randomized_svd_adaptive(A.as_ref(), 1e-10, 100, rng, StandardNormal, Par::Seq, default()).unwrap();
+		assert!(svd.rank() >= 10);
+		let approx_eq = CwiseMat(ApproxEq::eps() * 128.0);
+		assert!(svd.reconstruct() ~ A);
+	}
+}
@@ -0,0 +1,246 @@
+use super::*;
+use crate::assert;
+use crate::linalg::cholesky::bunch_kaufman::factor::BunchKaufmanInfo;
+
+/// computes the bunch-kaufman factorization $P A P^\top = L B L^H$ of the hermitian matrix $A$,
+/// in place, with partial pivoting
+///
+/// `A` holds the lower triangle of $A$ in packed storage (see [`PackedTri`]). on exit, the
+/// strictly lower triangle of `A` holds the strictly lower triangle of the unit lower triangular
+/// factor $L$, and its diagonal holds the diagonal of the block diagonal factor $B$, while the
+/// subdiagonal elements of its $2 \times 2$ blocks are stored in `subdiag`
+#[track_caller]
+#[math]
+pub fn cholesky_in_place<'out, T: ComplexField>(
+	A: &mut [T],
+	dim: usize,
+	subdiag: DiagMut<'_, T>,
+	perm: &'out mut [usize],
+	perm_inv: &'out mut [usize],
+) -> (BunchKaufmanInfo, PermRef<'out, usize>) {
+	let n = dim;
+	assert!(all(A.len() == packed_len(n), subdiag.dim() == n, perm.len() == n, perm_inv.len() == n));
+
+	let at = |i: usize, j: usize| packed_idx(n, Side::Lower, i, j);
+	let alpha = mul_pow2(one::<T::Real>() + sqrt(from_f64::<T::Real>(17.0)), from_f64::<T::Real>(0.125));
+
+	let mut subdiag = subdiag;
+	subdiag.fill(zero());
+	for (i, p) in perm.iter_mut().enumerate() {
+		*p = i;
+	}
+
+	let mut transposition_count = 0usize;
+	let mut k = 0usize;
+	while k < n {
+		let absakk = abs(real(A[at(k, k)]));
+		let mut imax = k;
+		let mut colmax = zero::<T::Real>();
+		for i in k + 1..n {
+			let val = abs(A[at(i, k)]);
+			if val > colmax {
+				colmax = val;
+				imax = i;
+			}
+		}
+
+		if max(absakk, colmax) == zero::<T::Real>() {
+			// the column is already zero, so no elimination is needed
+			A[at(k, k)] = zero();
+			k += 1;
+			continue;
+		}
+
+		let (kstep, kp) = if absakk >= alpha * colmax {
+			(1, k)
+		} else {
+			let mut rowmax = zero::<T::Real>();
+			for j in k..n {
+				if j != imax {
+					let val = if j < imax { abs(A[at(imax, j)]) } else { abs(A[at(j, imax)]) };
+					rowmax = max(rowmax, val);
+				}
+			}
+
+			if absakk >= alpha * colmax * (colmax / rowmax) {
+				(1, k)
+			} else if abs(real(A[at(imax, imax)])) >= alpha * rowmax {
+				(1, imax)
+			} else {
+				(2, imax)
+			}
+		};
+
+		let kk = k + kstep - 1;
+		if kp != kk {
+			transposition_count += 1;
+			perm.swap(kk, kp);
+
+			for i in kp + 1..n {
+				A.swap(at(i, kk), at(i, kp));
+			}
+			for j in kk + 1..kp {
+				let tmp = conj(A[at(j, kk)]);
+				A[at(j, kk)] = conj(A[at(kp, j)]);
+				A[at(kp, j)] = tmp;
+			}
+			A[at(kp, kk)] = conj(A[at(kp, kk)]);
+			A.swap(at(kk, kk), at(kp, kp));
+			// rows of the previous columns of L, and of the first column of a 2x2 pivot
+			for c in 0..kk {
+				A.swap(at(kk, c), at(kp, c));
+			}
+		}
+
+		if kstep == 1 {
+			let d = real(A[at(k, k)]);
+			A[at(k, k)] = from_real(d);
+			let inv = recip(d);
+
+			for j in k + 1..n {
+				let w = mul_real(conj(A[at(j, k)]), inv);
+				for i in j..n {
+					A[at(i, j)] = A[at(i, j)] - A[at(i, k)] * w;
+				}
+			}
+			for i in k + 1..n {
+				A[at(i, k)] = mul_real(A[at(i, k)], inv);
+			}
+		} else {
+			let d11 = real(A[at(k, k)]);
+			let d22 = real(A[at(k + 1, k + 1)]);
+			let s = copy(A[at(k + 1, k)]);
+			let inv = recip(d11 * d22 - abs2(s));
+
+			// the inverse of the pivot block [d11, conj(s); s, d22] is
+			// [d22, -conj(s); -s, d11] / (d11 * d22 - |s|^2)
+			for j in k + 2..n {
+				let wj1 = conj(A[at(j, k)]);
+				let wj2 = conj(A[at(j, k + 1)]);
+				for i in j..n {
+					let (wi1, wi2) = (copy(A[at(i, k)]), copy(A[at(i, k + 1)]));
+					let li1 = mul_real(mul_real(wi1, d22) - wi2 * s, inv);
+					let li2 = mul_real(mul_real(wi2, d11) - wi1 * conj(s), inv);
+					A[at(i, j)] = A[at(i, j)] - li1 * wj1 - li2 * wj2;
+				}
+
+				let (w1, w2) = (conj(wj1), conj(wj2));
+				A[at(j, k)] = mul_real(mul_real(w1, d22) - w2 * s, inv);
+				A[at(j, k + 1)] = mul_real(mul_real(w2, d11) - w1 * conj(s), inv);
+			}
+
+			A[at(k, k)] = from_real(d11);
+			A[at(k + 1, k + 1)] = from_real(d22);
+			A[at(k + 1, k)] = zero();
+			subdiag[k] = s;
+		}
+
+		k += kstep;
+	}
+
+	for i in 0..n {
+		perm_inv[perm[i]] = i;
+	}
+
+	(
+		BunchKaufmanInfo {
+			dynamic_regularization_count: 0,
+			transposition_count,
+		},
+		unsafe { PermRef::new_unchecked(perm, perm_inv, n) },
+	)
+}
+
+/// computes the size and alignment of required workspace for solving a linear system with
+/// [`solve_in_place_with_conj`]
+pub fn solve_in_place_scratch<T: ComplexField>(dim: usize, rhs_ncols: usize) -> StackReq {
+	temp_mat_scratch::<T>(dim, rhs_ncols)
+}
+
+/// given the bunch-kaufman factors of a matrix $A$ in packed storage, computed by
+/// [`cholesky_in_place`], solves the equation $A x = \text{rhs}$, implicitly conjugating $A$ if
+/// needed, and stores the result in `rhs`
+///
+/// the diagonal of `L` is not accessed, and the diagonal of $B$ is read from `diagonal` instead
+#[track_caller]
+#[math]
+pub fn solve_in_place_with_conj<T: ComplexField>(
+	L: &[T],
+	dim: usize,
+	diagonal: DiagRef<'_, T>,
+	subdiagonal: DiagRef<'_, T>,
+	conj_A: Conj,
+	perm: PermRef<'_, usize>,
+	rhs: MatMut<'_, T>,
+	stack: &mut MemStack,
+) {
+	let n = dim;
+	let k = rhs.ncols();
+	assert!(all(
+		L.len() == packed_len(n),
+		diagonal.dim() == n,
+		subdiagonal.dim() == n,
+		perm.len() == n,
+		rhs.nrows() == n,
+	));
+
+	let at = |i: usize, j: usize| packed_idx(n, Side::Lower, i, j);
+	let c = |x: &T| conj_A.apply_rt(x);
+	let mut rhs = rhs;
+
+	let (mut x, _) = unsafe { temp_mat_uninit::<T, _, _>(n, k, stack) };
+	let mut x = x.as_mat_mut();
+	let fwd = perm.arrays().0;
+	for col in 0..k {
+		for i in 0..n {
+			x[(i, col)] = copy(rhs[(fwd[i], col)]);
+		}
+	}
+
+	for j in 0..n {
+		for col in 0..k {
+			let xj = copy(x[(j, col)]);
+			for i in j + 1..n {
+				x[(i, col)] = x[(i, col)] - c(&L[at(i, j)]) * xj;
+			}
+		}
+	}
+
+	let mut i = 0;
+	while i < n {
+		if subdiagonal[i] == zero() {
+			let inv = recip(real(diagonal[i]));
+			for col in 0..k {
+				x[(i, col)] = mul_real(x[(i, col)], inv);
+			}
+			i += 1;
+		} else {
+			let d11 = real(diagonal[i]);
+			let d22 = real(diagonal[i + 1]);
+			let s = c(&subdiagonal[i]);
+			let inv = recip(d11 * d22 - abs2(s));
+			for col in 0..k {
+				let (x1, x2) = (copy(x[(i, col)]), copy(x[(i + 1, col)]));
+				x[(i, col)] = mul_real(mul_real(x1, d22) - conj(s) * x2, inv);
+				x[(i + 1, col)] = mul_real(mul_real(x2, d11) - s * x1, inv);
+			}
+			i += 2;
+		}
+	}
+
+	for j in (0..n).rev() {
+		for col in 0..k {
+			let mut acc = copy(x[(j, col)]);
+			for i in j + 1..n {
+				acc = acc - conj(c(&L[at(i, j)])) * x[(i, col)];
+			}
+			x[(j, col)] = acc;
+		}
+	}
+
+	for col in 0..k {
+		for i in 0..n {
+			rhs[(fwd[i], col)] = copy(x[(i, col)]);
+		}
+	}
+}
@@ -0,0 +1,79 @@
+use super::*;
+use crate::assert;
+
+/// computes the cholesky factor $L$ of the hermitian positive definite matrix $A$, in place
+///
+/// `L` holds the lower triangle of $A$ in packed storage (see [`PackedTri`]). on exit, it holds
+/// the lower triangle of $L$
+///
+/// returns an error if $A$ is not numerically positive definite
+#[track_caller]
+#[math]
+pub fn cholesky_in_place<T: ComplexField>(L: &mut [T], dim: usize) -> Result<(), LltError> {
+	let n = dim;
+	assert!(L.len() == packed_len(n));
+
+	for j in 0..n {
+		let jj = packed_idx(n, Side::Lower, j, j);
+		let d = real(L[jj]);
+		// written so that a nan pivot is rejected
+		#[allow(clippy::neg_cmp_op_on_partial_ord)]
+		if !(d > zero()) {
+			return Err(LltError::NonPositivePivot { index: j });
+		}
+		let d = sqrt(d);
+		L[jj] = from_real(d);
+
+		let inv = recip(d);
+		for t in 1..n - j {
+			L[jj + t] = mul_real(L[jj + t], inv);
+		}
+
+		for c in j + 1..n {
+			let l = conj(L[jj + (c - j)]);
+			let cc = packed_idx(n, Side::Lower, c, c);
+			for i in c..n {
+				L[cc + (i - c)] = L[cc + (i - c)] - L[jj + (i - j)] * l;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// given the cholesky factor $L$ of a matrix $A$ in lower packed storage, computed by
+/// [`cholesky_in_place`], solves the equation $A x = \text{rhs}$, implicitly conjugating $A$ if
+/// needed, and stores the result in `rhs`
+#[track_caller]
+#[math]
+pub fn solve_in_place_with_conj<T: ComplexField>(L: &[T], dim: usize, conj_lhs: Conj, rhs: MatMut<'_, T>) {
+	let n = dim;
+	assert!(all(L.len() == packed_len(n), rhs.nrows() == n));
+
+	let mut rhs = rhs;
+	let k = rhs.ncols();
+
+	for j in 0..n {
+		let jj = packed_idx(n, Side::Lower, j, j);
+		let inv = recip(real(L[jj]));
+		for col in 0..k {
+			let x = mul_real(rhs[(j, col)], inv);
+			for i in j + 1..n {
+				rhs[(i, col)] = rhs[(i, col)] - conj_lhs.apply_rt(&L[jj + (i - j)]) * x;
+			}
+			rhs[(j, col)] = x;
+		}
+	}
+
+	for j in (0..n).rev() {
+		let jj = packed_idx(n, Side::Lower, j, j);
+		let inv = recip(real(L[jj]));
+		for col in 0..k {
+			let mut acc = copy(rhs[(j, col)]);
+			for i in j + 1..n {
+				acc = acc - conj(conj_lhs.apply_rt(&L[jj + (i - j)])) * rhs[(i, col)];
+			}
+			rhs[(j, col)] = mul_real(acc, inv);
+		}
+	}
+}
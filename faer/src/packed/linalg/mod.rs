@@ -0,0 +1,71 @@
+use super::*;
+use crate::assert;
+
+/// bunch-kaufman decomposition in packed storage
+pub mod bunch_kaufman;
+/// cholesky decomposition in packed storage
+pub mod cholesky;
+
+/// computes the matrix product `[beta * dst] + alpha * lhs * rhs` and stores the result in
+/// `dst`, where `lhs` is a packed triangular matrix
+#[track_caller]
+#[math]
+pub fn matmul_triangular<T: ComplexField>(dst: MatMut<'_, T>, beta: Accum, lhs: &PackedTri<T>, rhs: MatRef<'_, T>, alpha: T) {
+	let n = lhs.dim;
+	assert!(all(dst.nrows() == n, dst.ncols() == rhs.ncols(), rhs.nrows() == n));
+
+	let mut dst = dst;
+	if beta == Accum::Replace {
+		dst.fill(zero());
+	}
+
+	let side = lhs.side;
+	let data = &*lhs.data;
+	for k in 0..rhs.ncols() {
+		for j in 0..n {
+			let x = alpha * rhs[(j, k)];
+			let start = packed_col_start(n, side, j);
+			let rows = packed_col_rows(n, side, j);
+			let row_start = rows.start;
+			for i in rows {
+				dst[(i, k)] = dst[(i, k)] + data[start + (i - row_start)] * x;
+			}
+		}
+	}
+}
+
+/// computes the matrix product `[beta * dst] + alpha * lhs * rhs` and stores the result in
+/// `dst`, where `lhs` is a packed hermitian matrix
+#[track_caller]
+#[math]
+pub fn matmul_hermitian<T: ComplexField>(dst: MatMut<'_, T>, beta: Accum, lhs: &PackedSym<T>, rhs: MatRef<'_, T>, alpha: T) {
+	let n = lhs.dim;
+	assert!(all(dst.nrows() == n, dst.ncols() == rhs.ncols(), rhs.nrows() == n));
+
+	let mut dst = dst;
+	if beta == Accum::Replace {
+		dst.fill(zero());
+	}
+
+	let side = lhs.side;
+	let data = &*lhs.data;
+	for k in 0..rhs.ncols() {
+		for j in 0..n {
+			let x = alpha * rhs[(j, k)];
+			let mut acc = zero::<T>();
+			let start = packed_col_start(n, side, j);
+			let rows = packed_col_rows(n, side, j);
+			let row_start = rows.start;
+			for i in rows {
+				let a = copy(data[start + (i - row_start)]);
+				if i == j {
+					dst[(j, k)] = dst[(j, k)] + mul_real(x, real(a));
+				} else {
+					dst[(i, k)] = dst[(i, k)] + a * x;
+					acc = acc + conj(a) * rhs[(i, k)];
+				}
+			}
+			dst[(j, k)] = dst[(j, k)] + alpha * acc;
+		}
+	}
+}
@@ -0,0 +1,334 @@
+use crate::assert;
+use crate::internal_prelude::*;
+use crate::linalg::cholesky::llt::factor::LltError;
+
+/// low level packed matrix products and decompositions
+pub mod linalg;
+/// high level packed matrix decompositions
+pub mod solvers;
+
+/// returns the number of stored elements of an $n \times n$ packed triangular matrix
+#[inline]
+pub(crate) fn packed_len(dim: usize) -> usize {
+	dim * (dim + 1) / 2
+}
+
+/// returns the index of the first stored element of column `j`
+#[inline]
+pub(crate) fn packed_col_start(dim: usize, side: Side, j: usize) -> usize {
+	match side {
+		Side::Lower => j * (2 * dim - j + 1) / 2,
+		Side::Upper => j * (j + 1) / 2,
+	}
+}
+
+/// returns the range of rows that are stored in column `j`
+#[inline]
+pub(crate) fn packed_col_rows(dim: usize, side: Side, j: usize) -> core::ops::Range<usize> {
+	match side {
+		Side::Lower => j..dim,
+		Side::Upper => 0..j + 1,
+	}
+}
+
+/// returns the index of the element at position $(i, j)$, which must lie in the stored triangle
+#[inline]
+pub(crate) fn packed_idx(dim: usize, side: Side, i: usize, j: usize) -> usize {
+	match side {
+		Side::Lower => packed_col_start(dim, side, j) + (i - j),
+		Side::Upper => packed_col_start(dim, side, j) + i,
+	}
+}
+
+#[inline]
+fn in_triangle(side: Side, i: usize, j: usize) -> bool {
+	match side {
+		Side::Lower => i >= j,
+		Side::Upper => i <= j,
+	}
+}
+
+/// triangular matrix in packed storage
+///
+/// only the lower or upper triangle is stored, column by column, following lapack's `UPLO`
+/// convention. with [`Side::Lower`], the element at position $(i, j)$ with $i \geq j$ is stored at
+/// index $i + j (2n - j - 1) / 2$, and with [`Side::Upper`], the element at position $(i, j)$
+/// with $i \leq j$ is stored at index $i + j (j + 1) / 2$. the elements outside the triangle
+/// are zero
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedTri<T> {
+	pub(crate) data: alloc::vec::Vec<T>,
+	pub(crate) dim: usize,
+	pub(crate) side: Side,
+}
+
+/// hermitian matrix in packed storage
+///
+/// only the lower or upper triangle is stored, with the same layout as [`PackedTri`]. the
+/// elements of the other triangle are the conjugates of their mirrored counterparts, and the
+/// imaginary part of the diagonal is ignored
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedSym<T> {
+	pub(crate) data: alloc::vec::Vec<T>,
+	pub(crate) dim: usize,
+	pub(crate) side: Side,
+}
+
+macro_rules! impl_packed_common {
+	($ty: ident) => {
+		impl<T> $ty<T> {
+			/// creates a packed matrix of dimension `dim` from its packed storage, which must have
+			/// $n (n + 1) / 2$ elements laid out following the given `side`
+			#[track_caller]
+			pub fn from_packed(data: alloc::vec::Vec<T>, dim: usize, side: Side) -> Self {
+				assert!(data.len() == packed_len(dim));
+				Self { data, dim, side }
+			}
+
+			/// returns the dimension of the matrix
+			#[inline]
+			pub fn dim(&self) -> usize {
+				self.dim
+			}
+
+			/// returns the side of the diagonal that is stored
+			#[inline]
+			pub fn side(&self) -> Side {
+				self.side
+			}
+
+			/// returns the packed storage of the matrix
+			#[inline]
+			pub fn as_slice(&self) -> &[T] {
+				&self.data
+			}
+
+			/// returns the packed storage of the matrix
+			#[inline]
+			pub fn as_slice_mut(&mut self) -> &mut [T] {
+				&mut self.data
+			}
+
+			/// returns the packed storage of the matrix
+			#[inline]
+			pub fn into_packed(self) -> alloc::vec::Vec<T> {
+				self.data
+			}
+
+			/// returns a reference to the element at position $(i, j)$, or `None` if it lies
+			/// outside the stored triangle
+			#[inline]
+			#[track_caller]
+			pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+				assert!(all(row < self.dim, col < self.dim));
+				if in_triangle(self.side, row, col) {
+					Some(&self.data[packed_idx(self.dim, self.side, row, col)])
+				} else {
+					None
+				}
+			}
+
+			/// returns a mutable reference to the element at position $(i, j)$, or `None` if it
+			/// lies outside the stored triangle
+			#[inline]
+			#[track_caller]
+			pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+				assert!(all(row < self.dim, col < self.dim));
+				if in_triangle(self.side, row, col) {
+					Some(&mut self.data[packed_idx(self.dim, self.side, row, col)])
+				} else {
+					None
+				}
+			}
+		}
+
+		impl<T: ComplexField> $ty<T> {
+			/// returns a new matrix of dimension `dim` filled with zeros, storing the triangle on
+			/// the given `side`
+			pub fn zeros(dim: usize, side: Side) -> Self {
+				Self {
+					data: alloc::vec![zero(); packed_len(dim)],
+					dim,
+					side,
+				}
+			}
+
+			/// returns a new packed matrix holding the triangle of `A` on the given `side`. the
+			/// other triangle is not accessed
+			#[track_caller]
+			pub fn from_mat(A: MatRef<'_, T>, side: Side) -> Self {
+				assert!(A.nrows() == A.ncols());
+				let n = A.nrows();
+				let mut data = alloc::vec::Vec::with_capacity(packed_len(n));
+				for j in 0..n {
+					for i in packed_col_rows(n, side, j) {
+						data.push(A[(i, j)].clone());
+					}
+				}
+				Self { data, dim: n, side }
+			}
+		}
+	};
+}
+
+impl_packed_common!(PackedTri);
+impl_packed_common!(PackedSym);
+
+impl<T: ComplexField> PackedTri<T> {
+	/// returns a newly allocated dense matrix holding the values of `self`
+	pub fn to_dense(&self) -> Mat<T> {
+		let n = self.dim;
+		let mut out = Mat::zeros(n, n);
+		for j in 0..n {
+			for i in packed_col_rows(n, self.side, j) {
+				out[(i, j)] = self.data[packed_idx(n, self.side, i, j)].clone();
+			}
+		}
+		out
+	}
+}
+
+impl<T: ComplexField> PackedSym<T> {
+	/// returns a newly allocated dense matrix holding the values of `self`, including both
+	/// triangles
+	pub fn to_dense(&self) -> Mat<T> {
+		let n = self.dim;
+		let mut out = Mat::zeros(n, n);
+		for j in 0..n {
+			for i in packed_col_rows(n, self.side, j) {
+				let val = &self.data[packed_idx(n, self.side, i, j)];
+				if i == j {
+					out[(i, i)] = from_real(&real(val));
+				} else {
+					out[(i, j)] = val.clone();
+					out[(j, i)] = conj(val);
+				}
+			}
+		}
+		out
+	}
+
+	/// returns the $L L^H$ decomposition of `self`
+	#[track_caller]
+	pub fn llt(&self) -> Result<solvers::Llt<T>, LltError> {
+		solvers::Llt::new(self)
+	}
+
+	/// returns the bunch-kaufman decomposition of `self`
+	#[track_caller]
+	pub fn lblt(&self) -> solvers::Lblt<T> {
+		solvers::Lblt::new(self)
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<&'a Mat<T>> for &'a PackedTri<T> {
+	type Output = Mat<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: &'a Mat<T>) -> Self::Output {
+		let mut out = Mat::zeros(self.dim, rhs.ncols());
+		linalg::matmul_triangular(out.as_mut(), Accum::Replace, self, rhs.as_ref(), one());
+		out
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<&'a Col<T>> for &'a PackedTri<T> {
+	type Output = Col<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: &'a Col<T>) -> Self::Output {
+		let mut out = Col::zeros(self.dim);
+		linalg::matmul_triangular(out.as_mat_mut(), Accum::Replace, self, rhs.as_mat(), one());
+		out
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<&'a Mat<T>> for &'a PackedSym<T> {
+	type Output = Mat<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: &'a Mat<T>) -> Self::Output {
+		let mut out = Mat::zeros(self.dim, rhs.ncols());
+		linalg::matmul_hermitian(out.as_mut(), Accum::Replace, self, rhs.as_ref(), one());
+		out
+	}
+}
+
+impl<'a, T: ComplexField> core::ops::Mul<&'a Col<T>> for &'a PackedSym<T> {
+	type Output = Col<T>;
+
+	#[track_caller]
+	fn mul(self, rhs: &'a Col<T>) -> Self::Output {
+		let mut out = Col::zeros(self.dim);
+		linalg::matmul_hermitian(out.as_mat_mut(), Accum::Replace, self, rhs.as_mat(), one());
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+
+	#[test]
+	fn test_packed() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 2, 7] {
+			let A: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: n,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+			let approx_eq = CwiseMat(ApproxEq::eps() * 8.0 * (n as f64 + 1.0));
+
+			for side in [Side::Lower, Side::Upper] {
+				let mut tri = Mat::zeros(n, n);
+				let mut herm = Mat::zeros(n, n);
+				match side {
+					Side::Lower => {
+						tri.copy_from_triangular_lower(&A);
+						herm.copy_from_triangular_lower(&A);
+						herm.copy_from_strict_triangular_upper(A.adjoint());
+					},
+					Side::Upper => {
+						tri.copy_from_triangular_upper(&A);
+						herm.copy_from_triangular_upper(&A);
+						herm.copy_from_strict_triangular_lower(A.adjoint());
+					},
+				}
+				for i in 0..n {
+					herm[(i, i)] = c64::new(herm[(i, i)].re, 0.0);
+				}
+
+				let T = PackedTri::from_mat(A.as_ref(), side);
+				let S = PackedSym::from_mat(A.as_ref(), side);
+				assert!(all(T.as_slice().len() == n * (n + 1) / 2, T.dim() == n, T.side() == side));
+				assert!(T.to_dense() == tri);
+				assert!(S.to_dense() == herm);
+
+				for j in 0..n {
+					for i in 0..n {
+						assert!(T.get(i, j).is_some() == in_triangle(side, i, j));
+						if let Some(val) = T.get(i, j) {
+							assert!(*val == A[(i, j)]);
+						}
+					}
+				}
+
+				assert!(&T * &rhs ~ &tri * &rhs);
+				assert!(&S * &rhs ~ &herm * &rhs);
+				assert!((&S * &rhs.col(0).to_owned()).as_mat() ~ (&herm * rhs.col(0)).as_mat());
+			}
+		}
+	}
+}
@@ -0,0 +1,269 @@
+use super::*;
+use crate::linalg::solvers::{ShapeCore, SolveCore};
+use dyn_stack::MemBuffer;
+
+/// $L L^H$ decomposition in packed storage
+#[derive(Clone, Debug)]
+pub struct Llt<T> {
+	L: PackedTri<T>,
+}
+
+/// bunch-kaufman decomposition in packed storage
+#[derive(Clone, Debug)]
+pub struct Lblt<T> {
+	L: PackedTri<T>,
+	B_diag: Diag<T>,
+	B_subdiag: Diag<T>,
+	P: Perm<usize>,
+}
+
+/// returns the lower triangle of `A` in packed storage
+fn to_packed_lower<T: ComplexField>(A: &PackedSym<T>) -> alloc::vec::Vec<T> {
+	match A.side {
+		Side::Lower => A.data.clone(),
+		Side::Upper => {
+			let n = A.dim;
+			let mut data = alloc::vec::Vec::with_capacity(packed_len(n));
+			for j in 0..n {
+				for i in j..n {
+					data.push(conj(&A.data[packed_idx(n, Side::Upper, j, i)]));
+				}
+			}
+			data
+		},
+	}
+}
+
+impl<T: ComplexField> Llt<T> {
+	/// returns the $L L^H$ decomposition of $A$
+	#[track_caller]
+	pub fn new(A: &PackedSym<T>) -> Result<Self, LltError> {
+		let n = A.dim;
+		let mut data = to_packed_lower(A);
+		linalg::cholesky::cholesky_in_place(&mut data, n)?;
+
+		Ok(Self {
+			L: PackedTri {
+				data,
+				dim: n,
+				side: Side::Lower,
+			},
+		})
+	}
+
+	/// returns the $L$ factor
+	pub fn L(&self) -> &PackedTri<T> {
+		&self.L
+	}
+}
+
+impl<T: ComplexField> Lblt<T> {
+	/// returns the bunch-kaufman decomposition of $A$
+	#[track_caller]
+	pub fn new(A: &PackedSym<T>) -> Self {
+		let n = A.dim;
+		let mut data = to_packed_lower(A);
+
+		let mut B_diag = Diag::zeros(n);
+		let mut B_subdiag = Diag::zeros(n);
+		let mut perm_fwd = alloc::vec![0usize; n];
+		let mut perm_bwd = alloc::vec![0usize; n];
+
+		linalg::bunch_kaufman::cholesky_in_place(&mut data, n, B_subdiag.as_mut(), &mut perm_fwd, &mut perm_bwd);
+
+		for j in 0..n {
+			let jj = packed_idx(n, Side::Lower, j, j);
+			B_diag[j] = core::mem::replace(&mut data[jj], one());
+		}
+
+		Self {
+			L: PackedTri {
+				data,
+				dim: n,
+				side: Side::Lower,
+			},
+			B_diag,
+			B_subdiag,
+			P: unsafe { Perm::new_unchecked(perm_fwd.into_boxed_slice(), perm_bwd.into_boxed_slice()) },
+		}
+	}
+
+	/// returns the $L$ factor
+	pub fn L(&self) -> &PackedTri<T> {
+		&self.L
+	}
+
+	/// returns the diagonal of the $B$ factor
+	pub fn B_diag(&self) -> DiagRef<'_, T> {
+		self.B_diag.as_ref()
+	}
+
+	/// returns the subdiagonal of the $B$ factor
+	pub fn B_subdiag(&self) -> DiagRef<'_, T> {
+		self.B_subdiag.as_ref()
+	}
+
+	/// returns the pivoting permutation $P$
+	pub fn P(&self) -> PermRef<'_, usize> {
+		self.P.as_ref()
+	}
+}
+
+impl<T: ComplexField> ShapeCore for Llt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L.dim
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.L.dim
+	}
+}
+
+impl<T: ComplexField> ShapeCore for Lblt<T> {
+	#[inline]
+	fn nrows(&self) -> usize {
+		self.L.dim
+	}
+
+	#[inline]
+	fn ncols(&self) -> usize {
+		self.L.dim
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Llt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::cholesky::solve_in_place_with_conj(&self.L.data, self.L.dim, conj, rhs);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		linalg::cholesky::solve_in_place_with_conj(&self.L.data, self.L.dim, conj.compose(Conj::Yes), rhs);
+	}
+}
+
+impl<T: ComplexField> SolveCore<T> for Lblt<T> {
+	#[track_caller]
+	fn solve_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		let n = self.L.dim;
+		let k = rhs.ncols();
+		linalg::bunch_kaufman::solve_in_place_with_conj(
+			&self.L.data,
+			n,
+			self.B_diag(),
+			self.B_subdiag(),
+			conj,
+			self.P(),
+			rhs,
+			MemStack::new(&mut MemBuffer::new(linalg::bunch_kaufman::solve_in_place_scratch::<T>(n, k))),
+		);
+	}
+
+	#[track_caller]
+	fn solve_transpose_in_place_with_conj(&self, conj: Conj, rhs: MatMut<'_, T>) {
+		self.solve_in_place_with_conj(conj.compose(Conj::Yes), rhs);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert;
+	use crate::linalg::solvers::Solve;
+	use crate::stats::prelude::*;
+	use crate::utils::approx::*;
+
+	fn random_hermitian(rng: &mut StdRng, n: usize, shift: f64) -> Mat<c64> {
+		let A: Mat<c64> = CwiseMatDistribution {
+			nrows: n,
+			ncols: n,
+			dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+		}
+		.rand(rng);
+		let mut A = &A + A.adjoint();
+		for i in 0..n {
+			A[(i, i)] = A[(i, i)] + c64::new(shift, 0.0);
+		}
+		A
+	}
+
+	#[test]
+	fn test_llt() {
+		let rng = &mut StdRng::seed_from_u64(0);
+
+		for n in [0, 1, 2, 5, 20] {
+			let A = random_hermitian(rng, n, 4.0 * n as f64 + 1.0);
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e3 * (n as f64 + 1.0));
+			for side in [Side::Lower, Side::Upper] {
+				let llt = PackedSym::from_mat(A.as_ref(), side).llt().unwrap();
+				let L = llt.L().to_dense();
+				assert!(&L * L.adjoint() ~ A);
+				assert!(&A * llt.solve(&rhs) ~ rhs);
+				assert!(A.conjugate() * llt.solve_conjugate(&rhs) ~ rhs);
+				assert!(A.transpose() * llt.solve_transpose(&rhs) ~ rhs);
+			}
+		}
+
+		let A = Mat::from_fn(2, 2, |i, j| if i == j { -1.0 } else { 0.0 });
+		assert!(matches!(
+			PackedSym::from_mat(A.as_ref(), Side::Lower).llt(),
+			Err(LltError::NonPositivePivot { index: 0 })
+		));
+	}
+
+	#[test]
+	fn test_lblt() {
+		let rng = &mut StdRng::seed_from_u64(1);
+
+		for n in [0, 1, 2, 3, 8, 20] {
+			// indefinite
+			let A = random_hermitian(rng, n, 0.0);
+			let rhs: Mat<c64> = CwiseMatDistribution {
+				nrows: n,
+				ncols: 3,
+				dist: ComplexDistribution::new(StandardNormal, StandardNormal),
+			}
+			.rand(rng);
+
+			let approx_eq = CwiseMat(ApproxEq::eps() * 1e4 * (n as f64 + 1.0));
+			for side in [Side::Lower, Side::Upper] {
+				let lblt = PackedSym::from_mat(A.as_ref(), side).lblt();
+
+				let L = lblt.L().to_dense();
+				let mut B = Mat::<c64>::zeros(n, n);
+				for i in 0..n {
+					B[(i, i)] = lblt.B_diag()[i];
+					if i + 1 < n {
+						B[(i + 1, i)] = lblt.B_subdiag()[i];
+						B[(i, i + 1)] = lblt.B_subdiag()[i].conj();
+					}
+				}
+				let P = lblt.P();
+				assert!(P.inverse() * (&L * &B * L.adjoint()) * P ~ A);
+
+				assert!(&A * lblt.solve(&rhs) ~ rhs);
+				assert!(A.conjugate() * lblt.solve_conjugate(&rhs) ~ rhs);
+				assert!(A.transpose() * lblt.solve_transpose(&rhs) ~ rhs);
+				assert!(A.adjoint() * lblt.solve_adjoint(&rhs) ~ rhs);
+			}
+		}
+
+		// requires 2x2 pivots
+		let A = Mat::from_fn(4, 4, |i, j| if i + j == 3 { 1.0 } else { 0.0 });
+		let lblt = PackedSym::from_mat(A.as_ref(), Side::Lower).lblt();
+		assert!(lblt.B_subdiag().column_vector().iter().any(|x| *x != 0.0));
+		let rhs = Mat::from_fn(4, 1, |i, _| i as f64);
+		let approx_eq = CwiseMat(ApproxEq::<f64>::eps());
+		assert!(&A * lblt.solve(&rhs) ~ rhs);
+	}
+}
@@ -72,6 +72,7 @@ impl<'a, T: 'a + Deserialize<'a>> Deserialize<'a> for Mat<T> {
 	{
 		#[derive(Deserialize)]
 		#[allow(non_camel_case_types)]
+		#[serde(field_identifier)]
 		enum Field {
 			nrows,
 			ncols,
@@ -243,7 +244,7 @@ impl<'a, T: 'a + Deserialize<'a>> Deserialize<'a> for Mat<T> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+	use serde_test::{Token, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens};
 	#[test]
 	fn matrix_serialization_normal() {
 		let value = Mat::from_fn(3, 4, |i, j| (i + (j * 10)) as f64);
@@ -400,4 +401,48 @@ mod tests {
 			"invalid length 14, expected 12 elements",
 		)
 	}
+
+	#[test]
+	fn matrix_serialization_roundtrip() {
+		let value = Mat::from_fn(2, 3, |i, j| (i + (j * 10)) as f64);
+		let tokens = [
+			Token::Struct { name: "Mat", len: 3 },
+			Token::Str("nrows"),
+			Token::U64(3),
+			Token::Str("ncols"),
+			Token::U64(2),
+			Token::Str("data"),
+			Token::Seq { len: Some(6) },
+			Token::F64(0.0),
+			Token::F64(1.0),
+			Token::F64(10.0),
+			Token::F64(11.0),
+			Token::F64(20.0),
+			Token::F64(21.0),
+			Token::SeqEnd,
+			Token::StructEnd,
+		];
+		// a strided view serializes the same as the owned matrix it is deserialized into
+		assert_ser_tokens(&value.transpose(), &tokens);
+		assert_tokens(&value.transpose().to_owned(), &tokens);
+
+		// the fields may come in any order, even when the data is read before the dimensions
+		assert_de_tokens(&value.transpose().to_owned(), &[
+			Token::Struct { name: "Mat", len: 3 },
+			Token::Str("data"),
+			Token::Seq { len: Some(6) },
+			Token::F64(0.0),
+			Token::F64(1.0),
+			Token::F64(10.0),
+			Token::F64(11.0),
+			Token::F64(20.0),
+			Token::F64(21.0),
+			Token::SeqEnd,
+			Token::Str("ncols"),
+			Token::U64(2),
+			Token::Str("nrows"),
+			Token::U64(3),
+			Token::StructEnd,
+		]);
+	}
 }
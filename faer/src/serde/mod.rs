@@ -1 +1,2 @@
 mod mat;
+mod packed;
@@ -0,0 +1,129 @@
+use crate::internal_prelude::*;
+use crate::packed::{PackedSym, PackedTri};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+enum SideRepr {
+	Lower,
+	Upper,
+}
+
+impl From<Side> for SideRepr {
+	fn from(side: Side) -> Self {
+		match side {
+			Side::Lower => SideRepr::Lower,
+			Side::Upper => SideRepr::Upper,
+		}
+	}
+}
+
+impl From<SideRepr> for Side {
+	fn from(side: SideRepr) -> Self {
+		match side {
+			SideRepr::Lower => Side::Lower,
+			SideRepr::Upper => Side::Upper,
+		}
+	}
+}
+
+macro_rules! impl_serde {
+	($ty: ident, $name: literal) => {
+		impl<T> Serialize for $ty<T>
+		where
+			T: Serialize,
+		{
+			fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+			where
+				S: Serializer,
+			{
+				let mut structure = s.serialize_struct($name, 3)?;
+				structure.serialize_field("dim", &self.dim())?;
+				structure.serialize_field("side", &SideRepr::from(self.side()))?;
+				structure.serialize_field("data", self.as_slice())?;
+				structure.end()
+			}
+		}
+
+		impl<'a, T: 'a + Deserialize<'a>> Deserialize<'a> for $ty<T> {
+			fn deserialize<D>(d: D) -> Result<Self, <D as Deserializer<'a>>::Error>
+			where
+				D: Deserializer<'a>,
+			{
+				#[derive(Deserialize)]
+				#[serde(rename = $name)]
+				struct Repr<U> {
+					dim: usize,
+					side: SideRepr,
+					data: alloc::vec::Vec<U>,
+				}
+
+				let Repr { dim, side, data } = Repr::<T>::deserialize(d)?;
+				let expected_length = dim.checked_add(1).and_then(|n| n.checked_mul(dim)).map(|n| n / 2);
+				if expected_length != Some(data.len()) {
+					return Err(serde::de::Error::invalid_length(data.len(), &"dim * (dim + 1) / 2 elements"));
+				}
+				Ok(Self::from_packed(data, dim, side.into()))
+			}
+		}
+	};
+}
+
+impl_serde!(PackedTri, "PackedTri");
+impl_serde!(PackedSym, "PackedSym");
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+	#[test]
+	fn packed_serialization() {
+		let value = PackedSym::from_packed(alloc::vec![1.0, 2.0, 3.0], 2, Side::Upper);
+		let tokens = [
+			Token::Struct { name: "PackedSym", len: 3 },
+			Token::Str("dim"),
+			Token::U64(2),
+			Token::Str("side"),
+			Token::UnitVariant {
+				name: "SideRepr",
+				variant: "Upper",
+			},
+			Token::Str("data"),
+			Token::Seq { len: Some(3) },
+			Token::F64(1.0),
+			Token::F64(2.0),
+			Token::F64(3.0),
+			Token::SeqEnd,
+			Token::StructEnd,
+		];
+		assert_tokens(&value, &tokens);
+		let value = PackedTri::from_packed(value.into_packed(), 2, Side::Upper);
+		let mut tokens = tokens;
+		tokens[0] = Token::Struct { name: "PackedTri", len: 3 };
+		assert_tokens(&value, &tokens);
+	}
+
+	#[test]
+	fn packed_deserialization_wrong_length() {
+		assert_de_tokens_error::<PackedTri<f64>>(
+			&[
+				Token::Struct { name: "PackedTri", len: 3 },
+				Token::Str("dim"),
+				Token::U64(2),
+				Token::Str("side"),
+				Token::UnitVariant {
+					name: "SideRepr",
+					variant: "Lower",
+				},
+				Token::Str("data"),
+				Token::Seq { len: Some(2) },
+				Token::F64(1.0),
+				Token::F64(2.0),
+				Token::SeqEnd,
+				Token::StructEnd,
+			],
+			"invalid length 2, expected dim * (dim + 1) / 2 elements",
+		);
+	}
+}